@@ -71,10 +71,10 @@ fn main() {
     println!("\n=== {map}.EVT EVENTS ===");
     match openmm_data::evt::EvtFile::parse(&assets, &map) {
         Ok(evt) => {
-            let mut ids: Vec<_> = evt.events.keys().collect();
+            let mut ids: Vec<_> = evt.events().keys().collect();
             ids.sort();
             for id in &ids {
-                let steps = &evt.events[id];
+                let steps = &evt.events()[id];
                 println!("  Event {id}:");
                 for s in steps {
                     println!("    step {}: {}", s.step, s.event);
@@ -89,10 +89,10 @@ fn main() {
     println!("\n=== GLOBAL.EVT EVENTS ===");
     match openmm_data::evt::EvtFile::parse(&assets, "global") {
         Ok(evt) => {
-            let mut ids: Vec<_> = evt.events.keys().collect();
+            let mut ids: Vec<_> = evt.events().keys().collect();
             ids.sort();
             for id in &ids {
-                let steps = &evt.events[id];
+                let steps = &evt.events()[id];
                 println!("  Event {id}:");
                 for s in steps {
                     println!("    step {}: {}", s.step, s.event);
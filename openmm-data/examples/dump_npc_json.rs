@@ -10,7 +10,7 @@ fn main() {
 
     let mut entries = Vec::new();
 
-    for (i, a) in ddm.actors.iter().enumerate() {
+    for (i, a) in ddm.delta.actors.iter().enumerate() {
         let monster = monlist.get(a.monlist_id as usize);
         let internal_name = monster.map(|m| m.internal_name.as_str()).unwrap_or("?");
         let is_peasant = monlist.is_peasant(a.monlist_id);
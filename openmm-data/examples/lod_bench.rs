@@ -0,0 +1,186 @@
+//! Time the per-map loading sequence with and without the `mmap` feature.
+//!
+//! Each round loads the named maps the way the game's loading screen does:
+//! the ODM (tile table, terrain atlas, water texture, model textures,
+//! billboards and the DDM's actors) or the BLV (face textures, DLV doors,
+//! decorations and the spawn points' monsters), then every actor's sprite
+//! frames. Static tables (`Assets::data`) are loaded once before timing, as
+//! the game does at startup. The LOD backend is chosen at compile time, so
+//! run the example once per build and compare the lines:
+//!
+//!   OPENMM_PATH_MM6=/path/to/mm6/data cargo run --release -p openmm-data --example lod_bench -- oute3 d01
+//!   OPENMM_PATH_MM6=/path/to/mm6/data cargo run --release -p openmm-data --features mmap --example lod_bench -- oute3 d01
+//!
+//! Maps default to `oute3` and `d01`. The first round is reported apart
+//! from the rest: it is the one that pays for the page cache and the
+//! archives' first reads. Peak RSS is the process's VmHWM.
+
+use openmm_data::assets::provider::{SpritePalette, SpriteRequest};
+use openmm_data::assets::{Actors, Decorations, Dlv, Dtile, Monsters};
+use openmm_data::{Assets, Blv, Odm, get_data_path};
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 5;
+
+fn main() {
+    let mut maps: Vec<String> = std::env::args().skip(1).collect();
+    if maps.is_empty() {
+        maps = vec!["oute3".into(), "d01".into()];
+    }
+    let backend = if cfg!(feature = "mmap") { "mmap" } else { "owned" };
+
+    let start = Instant::now();
+    let assets = Assets::new(get_data_path()).expect("failed to open game data");
+    assets.data();
+    let startup = start.elapsed();
+
+    let mut first = Duration::ZERO;
+    let mut rest = Duration::ZERO;
+    let mut reads = 0;
+    for round in 0..ROUNDS {
+        let start = Instant::now();
+        reads = maps.iter().map(|map| load_map(&assets, map)).sum::<usize>();
+        if round == 0 {
+            first = start.elapsed();
+        } else {
+            rest += start.elapsed();
+        }
+    }
+    println!(
+        "{:<6} {:>10} {:>8} {:>12} {:>12} {:>12}",
+        "mode", "startup", "reads", "first round", "later avg", "peak RSS"
+    );
+    println!(
+        "{:<6} {:>10.1?} {:>8} {:>12.1?} {:>12.1?} {:>9} kB",
+        backend,
+        startup,
+        reads,
+        first,
+        rest / (ROUNDS - 1),
+        peak_rss_kb().map_or("?".into(), |kb| kb.to_string())
+    );
+}
+
+/// Load `map` and everything its scene needs; returns the number of assets
+/// read. Failures are reported and skipped, like the game's loading steps.
+fn load_map(assets: &Assets, map: &str) -> usize {
+    let data = assets.data();
+    let lod = assets.lod();
+    let mut reads = 0;
+    let mut count = |found: bool| reads += found as usize;
+    let (textures, sprite_roots): (Vec<String>, Vec<(String, u8, u16)>) =
+        if map.ends_with(".blv") || !map.starts_with("out") {
+            let name = if map.ends_with(".blv") {
+                map.to_string()
+            } else {
+                format!("{map}.blv")
+            };
+            let blv = match Blv::load(assets, &name) {
+                Ok(blv) => blv,
+                Err(e) => {
+                    eprintln!("{name}: {e}");
+                    return 0;
+                }
+            };
+            count(Dlv::new(assets, &name, blv.door_count, blv.doors_data_size).is_ok());
+            billboards(assets, Decorations::from_blv(assets, &blv.decorations), &mut count);
+            let monsters = Monsters::load_for_blv(&blv.spawn_points, &name, data, assets);
+            let roots = monsters
+                .map(|m| sprite_roots(&Actors::from_monsters(&m)))
+                .unwrap_or_default();
+            (blv.texture_names, roots)
+        } else {
+            let name = if map.ends_with(".odm") {
+                map.to_string()
+            } else {
+                format!("{map}.odm")
+            };
+            let odm = match Odm::load(assets, &name) {
+                Ok(odm) => odm,
+                Err(e) => {
+                    eprintln!("{name}: {e}");
+                    return 0;
+                }
+            };
+            count(Dtile::load(assets).is_ok());
+            count(odm.tile_table(assets).and_then(|t| t.atlas_image(assets)).is_ok());
+            count(lod.bitmap("wtrtyl").is_some());
+            billboards(assets, Decorations::load(assets, &odm.billboards), &mut count);
+            let actors = Actors::new(assets, &name, None, data);
+            let roots = actors.map(|a| sprite_roots(&a)).unwrap_or_default();
+            let textures = odm.bsp_models.into_iter().flat_map(|b| b.texture_names).collect();
+            (textures, roots)
+        };
+
+    let mut textures = textures;
+    textures.sort();
+    textures.dedup();
+    for texture in textures.iter().filter(|t| !t.is_empty()) {
+        count(lod.bitmap(texture).is_some());
+    }
+    let requests = sprite_requests(assets, &sprite_roots);
+    for sprite in lod.decode_sprite_requests(&requests) {
+        count(sprite.map(std::hint::black_box).is_ok());
+    }
+    reads
+}
+
+/// Static billboards, as preloaded before the map is shown.
+fn billboards(assets: &Assets, decorations: Result<Decorations, openmm_data::Error>, count: &mut impl FnMut(bool)) {
+    let Ok(decorations) = decorations else {
+        return;
+    };
+    for dec in decorations.entries() {
+        if !dec.is_directional && dec.num_frames <= 1 {
+            count(assets.lod().billboard(&dec.sprite_name, dec.declist_id).is_some());
+        }
+    }
+}
+
+/// Unique (root, variant, palette) of every actor animation.
+fn sprite_roots(actors: &Actors) -> Vec<(String, u8, u16)> {
+    let mut roots: Vec<(String, u8, u16)> = actors
+        .get_actors()
+        .iter()
+        .flat_map(|a| {
+            [
+                &a.standing_sprite,
+                &a.walking_sprite,
+                &a.attacking_sprite,
+                &a.dying_sprite,
+            ]
+            .map(|root| (root.clone(), a.variant, a.palette_id))
+        })
+        .collect();
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// The frames `{root}{a..f}{0..4}` each root has in sprites.lod.
+fn sprite_requests(assets: &Assets, roots: &[(String, u8, u16)]) -> Vec<SpriteRequest> {
+    let mut requests = Vec::new();
+    for (root, variant, palette_id) in roots {
+        let root = root.trim_end_matches(|c: char| c.is_ascii_digit()).to_lowercase();
+        let palette = match (*variant, *palette_id) {
+            (v, p) if v > 1 && p > 0 => SpritePalette::Id(p),
+            (v, _) if v > 1 => SpritePalette::Offset((v - 1) as u16),
+            _ => SpritePalette::Header,
+        };
+        for frame in 'a'..='f' {
+            for dir in 0..5 {
+                let name = format!("{root}{frame}{dir}");
+                if assets.lod_contains("sprites", &name) {
+                    requests.push(SpriteRequest::new(name, palette));
+                }
+            }
+        }
+    }
+    requests
+}
+
+fn peak_rss_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
@@ -11,7 +11,7 @@ fn main() {
         let Ok(ddm) = openmm_data::ddm::Ddm::load(&assets, map) else {
             continue;
         };
-        for (i, a) in ddm.actors.iter().enumerate() {
+        for (i, a) in ddm.delta.actors.iter().enumerate() {
             if a.npc_id <= 0 {
                 continue;
             }
@@ -4,9 +4,9 @@
 //! Columns: Note bit, Autonote Text, Category
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One autonote entry from `autonotes.txt`.
@@ -26,7 +26,7 @@ pub struct AutonotesTable {
 }
 
 impl AutonotesTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/autonotes.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -67,7 +67,7 @@ impl AutonotesTable {
 }
 
 impl TryFrom<&[u8]> for AutonotesTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -6,10 +6,10 @@
 
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::Cursor;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One award entry from `awards.txt`.
@@ -30,12 +30,12 @@ pub struct AwardsTable {
 }
 
 impl AwardsTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/awards.txt")?;
         Self::try_from(raw.as_slice())
     }
 
-    fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+    fn parse(text: &str) -> Result<Self, Error> {
         let body: String = text.lines().skip(1).collect::<Vec<_>>().join("\n");
         let mut rdr = ReaderBuilder::new()
             .delimiter(b'\t')
@@ -45,7 +45,7 @@ impl AwardsTable {
 
         let mut awards = Vec::new();
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("awards.txt", e))?;
 
             let id: u16 = match rec.get(0).unwrap_or("").trim().parse() {
                 Ok(v) => v,
@@ -75,7 +75,7 @@ impl AwardsTable {
 }
 
 impl TryFrom<&[u8]> for AwardsTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -1,15 +1,16 @@
-use std::{
-    error::Error,
-    io::{Cursor, Read},
-};
+use std::io::{Cursor, Read};
 
 use serde::{Deserialize, Serialize};
 
+use crate::Error;
+use crate::Version;
 use crate::utils::try_read_string_block;
 
 // BillboardSprite is a compositor — it combines ddeclist + dsft + GameLod.
 pub use crate::assets::provider::lod_decoder::BillboardSprite;
 
+/// Placed decoration record (MMExtension `MapSprite`), 28 bytes in MM6.
+/// MM7+ records are 32 bytes; the trailing 4 are runtime-only and skipped.
 #[repr(C)]
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct BillboardData {
@@ -90,13 +91,19 @@ impl Billboard {
     }
 }
 
-pub(super) fn read_billboards(cursor: &mut Cursor<&[u8]>, count: usize) -> Result<Vec<Billboard>, Box<dyn Error>> {
+pub(super) fn read_billboards(
+    cursor: &mut Cursor<&[u8]>,
+    count: usize,
+    version: Version,
+) -> Result<Vec<Billboard>, Error> {
     let mut billboards_data = Vec::new();
+    let extra = if version >= Version::MM7 { 4 } else { 0 };
 
     for _i in 0..count {
         let size = std::mem::size_of::<BillboardData>();
         let mut entity_data = BillboardData::default();
         cursor.read_exact(unsafe { std::slice::from_raw_parts_mut(&mut entity_data as *mut _ as *mut u8, size) })?;
+        cursor.set_position(cursor.position() + extra);
         billboards_data.push(entity_data);
     }
 
@@ -1,11 +1,12 @@
 //! BLV indoor map parser — faces, rooms, doors, lights, decorations, spawn points.
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::{Cursor, Seek};
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
+use crate::Version;
 use crate::assets::lod_data::LodData;
 
 use super::blv_types::read_string_lossy;
@@ -25,8 +26,8 @@ pub struct Blv {
     /// Parallel to `face_extras`. May be empty strings.
     pub face_extra_texture_names: Vec<String>,
     pub sectors: Vec<BlvSector>,
-    /// Sector light data blob (section 10). Raw u16 values — structure unknown,
-    /// preserved for future analysis and round-trip saving.
+    /// Sector light data blob (section 10). Raw u16 light indices, split into
+    /// `BlvSector::light_ids` and kept whole for round-trip saving.
     pub sector_light_data: Vec<u16>,
     pub decorations: Vec<BlvDecoration>,
     pub lights: Vec<BlvLight>,
@@ -40,16 +41,69 @@ pub struct Blv {
 
 impl Blv {
     /// Parse a BLV file from a LOD archive.
-    pub fn load(assets: &Assets, name: &str) -> Result<Self, Box<dyn Error>> {
-        let raw = assets.get_bytes(format!("games/{}", name))?;
-        Self::try_from(raw.as_slice())
+    pub fn load(assets: &Assets, name: &str) -> Result<Self, Error> {
+        let data = assets.get_entry(format!("games/{}", name))?;
+        Self::parse_version(&data, assets.version()).map_err(|e| e.in_file(name))
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    /// Parse an MM6 indoor map.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        Self::parse_version(data, Version::MM6)
+    }
+
+    /// Parse an indoor map laid out for `version`.
+    pub fn parse_version(data: &[u8], version: Version) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
+        Self::read(&mut cursor, version).map_err(|e| e.at("blv", cursor.position() as usize))
+    }
+
+    /// Indices of the faces in cog group `cog`. EVT `SetFacesBit` and
+    /// `SetTexture` address faces by cog number, not by face index.
+    pub fn cog_faces(&self, cog: i32) -> Vec<usize> {
+        self.faces
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.cog_number as i32 == cog && cog != 0)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Set (`on`) or clear attribute `bits` on `face`. Returns whether the
+    /// face's attributes changed.
+    pub fn set_face_bits(&mut self, face: usize, bits: u32, on: bool) -> bool {
+        let Some(f) = self.faces.get_mut(face) else {
+            return false;
+        };
+        let old = f.attributes;
+        if on {
+            f.attributes |= bits;
+        } else {
+            f.attributes &= !bits;
+        }
+        f.attributes != old
+    }
+
+    /// Texture name of `face`, or `""` if it has none.
+    pub fn face_texture(&self, face: usize) -> &str {
+        self.texture_names.get(face).map(String::as_str).unwrap_or("")
+    }
+
+    /// Replace the texture of `face`. Returns whether the name changed.
+    pub fn set_face_texture(&mut self, face: usize, name: &str) -> bool {
+        match self.texture_names.get_mut(face) {
+            Some(tex) if !tex.eq_ignore_ascii_case(name) => {
+                *tex = name.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn read(cursor: &mut Cursor<&[u8]>, version: Version) -> Result<Self, Error> {
+        let mm7 = version >= Version::MM7;
 
         // 1. Header (136 bytes)
-        let header = Self::read_header(&mut cursor)?;
+        let header = Self::read_header(cursor)?;
 
         // 2. Vertices: u32 count, then count x 6 bytes (i16 x, y, z)
         let vertex_count = cursor.read_u32::<LittleEndian>()? as usize;
@@ -62,11 +116,11 @@ impl Blv {
             });
         }
 
-        // 3. Faces: u32 count, then count x 80 bytes each (MM6)
+        // 3. Faces: u32 count, then count x 80 bytes each (96 in MM7+)
         let face_count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut faces = Vec::with_capacity(face_count);
         for _ in 0..face_count {
-            faces.push(Self::read_face(&mut cursor)?);
+            faces.push(Self::read_face(cursor, mm7)?);
         }
 
         // 4. Face data blob: (header.face_data_size / 2) x i16
@@ -80,7 +134,7 @@ impl Blv {
         // 5. Face texture names: faces.len x 10-byte null-terminated strings
         let mut texture_names = Vec::with_capacity(face_count);
         for _ in 0..face_count {
-            texture_names.push(read_string_lossy(&mut cursor, 10)?);
+            texture_names.push(read_string_lossy(cursor, 10)?);
         }
 
         // 6. Face extras: u32 count, then count x 36 bytes each.
@@ -89,13 +143,13 @@ impl Blv {
         let mut face_extras: Vec<BlvFaceExtra> = Vec::with_capacity(face_extras_count);
         for _ in 0..face_extras_count {
             let mut unknown_head = [0u8; 20];
-            std::io::Read::read_exact(&mut cursor, &mut unknown_head)?;
+            std::io::Read::read_exact(cursor, &mut unknown_head)?;
             let texture_delta_u = cursor.read_i16::<LittleEndian>()?;
             let texture_delta_v = cursor.read_i16::<LittleEndian>()?;
             let cog_number = cursor.read_i16::<LittleEndian>()?;
             let event_id = cursor.read_u16::<LittleEndian>()?;
             let mut unknown_tail = [0u8; 8];
-            std::io::Read::read_exact(&mut cursor, &mut unknown_tail)?;
+            std::io::Read::read_exact(cursor, &mut unknown_tail)?;
             face_extras.push(BlvFaceExtra {
                 unknown_head,
                 texture_delta_u,
@@ -118,14 +172,14 @@ impl Blv {
         // 7. Face extra texture names: face_extras.len x 10 bytes
         let mut face_extra_texture_names = Vec::with_capacity(face_extras_count);
         for _ in 0..face_extras_count {
-            face_extra_texture_names.push(read_string_lossy(&mut cursor, 10)?);
+            face_extra_texture_names.push(read_string_lossy(cursor, 10)?);
         }
 
         // 8. Sectors: u32 count, then count x 116 bytes each
         let sector_count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut sectors = Vec::with_capacity(sector_count);
         for _ in 0..sector_count {
-            sectors.push(Self::read_sector(&mut cursor)?);
+            sectors.push(Self::read_sector(cursor)?);
         }
 
         // 9. Sector data blob: (header.sector_data_size / 2) x u16
@@ -137,40 +191,54 @@ impl Blv {
         Self::unpack_sector_data(&mut sectors, &sector_data_blob);
 
         // 10. Sector light data blob: (header.sector_light_data_size / 2) x u16.
-        //     Structure unknown — preserved for future analysis and round-trip saving.
+        //     Each sector's light_count light indices, in sector order.
         let sector_light_count = (header.sector_light_data_size / 2) as usize;
         let mut sector_light_data = Vec::with_capacity(sector_light_count);
         for _ in 0..sector_light_count {
             sector_light_data.push(cursor.read_u16::<LittleEndian>()?);
         }
+        Self::unpack_sector_lights(&mut sectors, &sector_light_data);
 
         // 11. Door count (actual doors stored in DLV)
         let door_count = cursor.read_u32::<LittleEndian>()?;
 
-        // 12. Decorations: u32 count, then count x 32 bytes
+        // 12. Decorations: u32 count, then count x 28 bytes (32 in MM7+)
         let decoration_count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut decorations = Vec::with_capacity(decoration_count);
         for _ in 0..decoration_count {
-            decorations.push(Self::read_decoration(&mut cursor)?);
+            decorations.push(Self::read_decoration(cursor)?);
+            if mm7 {
+                cursor.seek(std::io::SeekFrom::Current(4))?;
+            }
         }
 
         // 13. Decoration names: 32-byte null-terminated name buffers
         //     (MM6 BLV files store 32-byte name fields, verified by binary trace)
         for dec in &mut decorations {
-            dec.name = read_string_lossy(&mut cursor, 32)?;
+            dec.name = read_string_lossy(cursor, 32)?;
         }
 
-        // 14. Lights: u32 count, then count x 12 bytes (MM6 format)
+        // 14. Lights: u32 count, then count x 12 bytes (16 in MM7+, with colour and type)
         let light_count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut lights = Vec::with_capacity(light_count);
         for _ in 0..light_count {
+            let position = [
+                cursor.read_i16::<LittleEndian>()?,
+                cursor.read_i16::<LittleEndian>()?,
+                cursor.read_i16::<LittleEndian>()?,
+            ];
+            let radius = cursor.read_i16::<LittleEndian>()?;
+            let mut color = [0u8; 3];
+            let mut light_type = 0;
+            if mm7 {
+                std::io::Read::read_exact(cursor, &mut color)?;
+                light_type = cursor.read_u8()?;
+            }
             lights.push(BlvLight {
-                position: [
-                    cursor.read_i16::<LittleEndian>()?,
-                    cursor.read_i16::<LittleEndian>()?,
-                    cursor.read_i16::<LittleEndian>()?,
-                ],
-                radius: cursor.read_i16::<LittleEndian>()?,
+                position,
+                radius,
+                color,
+                light_type,
                 attributes: cursor.read_i16::<LittleEndian>()?,
                 brightness: cursor.read_u16::<LittleEndian>()?,
             });
@@ -188,7 +256,7 @@ impl Blv {
             });
         }
 
-        // 16. Spawn points: u32 count, then count x 20 bytes (MM6, no group field)
+        // 16. Spawn points: u32 count, then count x 20 bytes (24 in MM7+, with group)
         let spawn_count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut spawn_points = Vec::with_capacity(spawn_count);
         for _ in 0..spawn_count {
@@ -202,6 +270,7 @@ impl Blv {
                 spawn_type: cursor.read_u16::<LittleEndian>()?,
                 monster_index: cursor.read_u16::<LittleEndian>()?,
                 attributes: cursor.read_u16::<LittleEndian>()?,
+                group: if mm7 { cursor.read_u32::<LittleEndian>()? } else { 0 },
             });
         }
 
@@ -239,7 +308,7 @@ impl Blv {
         })
     }
 
-    fn read_header(cursor: &mut Cursor<&[u8]>) -> Result<BlvHeader, Box<dyn Error>> {
+    fn read_header(cursor: &mut Cursor<&[u8]>) -> Result<BlvHeader, Error> {
         // 0x00: unknown[4]
         let mut unknown_head = [0u8; 4];
         std::io::Read::read_exact(cursor, &mut unknown_head)?;
@@ -268,8 +337,12 @@ impl Blv {
         })
     }
 
-    /// Read a single face (80 bytes, MM6 format — no float normals).
-    fn read_face(cursor: &mut Cursor<&[u8]>) -> Result<BlvFace, Box<dyn Error>> {
+    /// Read a single face (80 bytes; MM7+ prefixes a 16-byte float plane).
+    fn read_face(cursor: &mut Cursor<&[u8]>, mm7: bool) -> Result<BlvFace, Error> {
+        let mut plane = [0f32; 4];
+        if mm7 {
+            cursor.read_f32_into::<LittleEndian>(&mut plane)?;
+        }
         let mut normal_fixed = [0i32; 4];
         for v in &mut normal_fixed {
             *v = cursor.read_i32::<LittleEndian>()?;
@@ -300,6 +373,7 @@ impl Blv {
         let _padding = cursor.read_i16::<LittleEndian>()?;
 
         Ok(BlvFace {
+            plane,
             normal_fixed,
             z_calc,
             attributes,
@@ -345,7 +419,7 @@ impl Blv {
     }
 
     /// Read a sector (116 bytes).
-    fn read_sector(cursor: &mut Cursor<&[u8]>) -> Result<BlvSector, Box<dyn Error>> {
+    fn read_sector(cursor: &mut Cursor<&[u8]>) -> Result<BlvSector, Error> {
         let flags = cursor.read_i32::<LittleEndian>()?;
 
         // 5 groups of: u16 count, u16 pad, u32 pointer
@@ -422,6 +496,7 @@ impl Blv {
             bbox_min: [x1, y1, z1],
             bbox_max: [x2, y2, z2],
             face_ids: Vec::new(),
+            light_ids: Vec::new(),
         })
     }
 
@@ -442,8 +517,23 @@ impl Blv {
         }
     }
 
+    /// Split the sector light blob by each sector's `light_count`. Left
+    /// unassigned unless the counts add up to the blob exactly.
+    fn unpack_sector_lights(sectors: &mut [BlvSector], blob: &[u16]) {
+        let total: usize = sectors.iter().map(|s| s.light_count as usize).sum();
+        if total != blob.len() {
+            return;
+        }
+        let mut offset = 0;
+        for sector in sectors.iter_mut() {
+            let count = sector.light_count as usize;
+            sector.light_ids = blob[offset..offset + count].to_vec();
+            offset += count;
+        }
+    }
+
     /// Read a decoration (28 bytes, MM6 MapSprite format).
-    fn read_decoration(cursor: &mut Cursor<&[u8]>) -> Result<BlvDecoration, Box<dyn Error>> {
+    fn read_decoration(cursor: &mut Cursor<&[u8]>) -> Result<BlvDecoration, Error> {
         let decoration_desc_id = cursor.read_u16::<LittleEndian>()?;
         let flags = cursor.read_u16::<LittleEndian>()?;
         let position = [
@@ -471,7 +561,7 @@ impl Blv {
 }
 
 impl TryFrom<&[u8]> for Blv {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -898,4 +988,82 @@ mod tests {
             bad
         );
     }
+
+    #[test]
+    fn sector_lights_split_by_count() {
+        let sector = |light_count| BlvSector {
+            light_count,
+            ..Default::default()
+        };
+        let mut sectors = vec![sector(0), sector(2), sector(1)];
+        Blv::unpack_sector_lights(&mut sectors, &[4, 7, 9]);
+        assert!(sectors[0].light_ids.is_empty());
+        assert_eq!(sectors[1].light_ids, vec![4, 7]);
+        assert_eq!(sectors[2].light_ids, vec![9]);
+
+        // A blob that doesn't match the counts is left unassigned.
+        let mut sectors = vec![sector(2)];
+        Blv::unpack_sector_lights(&mut sectors, &[1]);
+        assert!(sectors[0].light_ids.is_empty());
+    }
+
+    #[test]
+    fn parses_mm7_layout() {
+        let mut data = vec![0u8; 136];
+        data.extend(0u32.to_le_bytes()); // vertices
+        data.extend(1u32.to_le_bytes()); // faces
+        for v in [0.0f32, 0.0, 1.0, -64.0] {
+            data.extend(v.to_le_bytes());
+        }
+        let mut face = [0u8; 80];
+        face[0x1C..0x20].copy_from_slice(&0x10u32.to_le_bytes());
+        face[0x3A..0x3C].copy_from_slice(&4u16.to_le_bytes()); // bitmap_id
+        face[0x4C] = 3;
+        data.extend(face);
+        data.extend(*b"floor1\0\0\0\0");
+        data.extend(0u32.to_le_bytes()); // face extras
+        data.extend(0u32.to_le_bytes()); // sectors
+        data.extend(0u32.to_le_bytes()); // doors
+        data.extend(1u32.to_le_bytes()); // decorations
+        let mut decoration = [0u8; 32];
+        decoration[..2].copy_from_slice(&6u16.to_le_bytes());
+        decoration[0x16..0x18].copy_from_slice(&12i16.to_le_bytes());
+        data.extend(decoration);
+        let mut name = [0u8; 32];
+        name[..4].copy_from_slice(b"torc");
+        data.extend(name);
+        data.extend(1u32.to_le_bytes()); // lights
+        for v in [10i16, 20, 30, 512] {
+            data.extend(v.to_le_bytes());
+        }
+        data.extend([255, 128, 0, 5]);
+        data.extend(1i16.to_le_bytes());
+        data.extend(24u16.to_le_bytes());
+        data.extend(0u32.to_le_bytes()); // bsp nodes
+        data.extend(1u32.to_le_bytes()); // spawn points
+        for v in [1i32, 2, 3] {
+            data.extend(v.to_le_bytes());
+        }
+        data.extend([64, 0, 3, 0, 2, 0, 0, 0]);
+        data.extend(7u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes()); // outlines
+
+        let blv = Blv::parse_version(&data, Version::MM7).unwrap();
+        assert_eq!(blv.faces[0].plane, [0.0, 0.0, 1.0, -64.0]);
+        assert_eq!(blv.faces[0].attributes, 0x10);
+        assert_eq!(blv.faces[0].bitmap_id, 4);
+        assert_eq!(blv.faces[0].polygon_type, 3);
+        assert_eq!(blv.texture_names, ["floor1"]);
+        assert_eq!(blv.decorations[0].decoration_desc_id, 6);
+        assert_eq!(blv.decorations[0].event, 12);
+        assert_eq!(blv.decorations[0].name, "torc");
+        let light = &blv.lights[0];
+        assert_eq!((light.position, light.radius), ([10, 20, 30], 512));
+        assert_eq!((light.color, light.light_type), ([255, 128, 0], 5));
+        assert_eq!((light.attributes, light.brightness), (1, 24));
+        assert_eq!(blv.spawn_points[0].position, [1, 2, 3]);
+        assert_eq!(blv.spawn_points[0].monster_index, 2);
+        assert_eq!(blv.spawn_points[0].group, 7);
+        assert!(Blv::parse(&data).is_err());
+    }
 }
@@ -8,7 +8,7 @@ pub(crate) fn default_blv_unknown_mid() -> [u8; 40] {
 }
 
 /// Read a fixed-size string block, using lossy UTF-8 conversion for non-ASCII bytes.
-pub(crate) fn read_string_lossy(cursor: &mut Cursor<&[u8]>, size: usize) -> Result<String, Box<dyn std::error::Error>> {
+pub(crate) fn read_string_lossy(cursor: &mut Cursor<&[u8]>, size: usize) -> Result<String, crate::Error> {
     let mut buf = vec![0u8; size];
     std::io::Read::read_exact(cursor, &mut buf)?;
     let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
@@ -50,8 +50,9 @@ pub struct BlvVertex {
 
 /// A face in a BLV indoor map.
 ///
-/// MM6 BLV face struct is 80 bytes (no float normals — those are MM7 only).
-/// Layout:
+/// MM6 BLV face struct is 80 bytes. MM7+ faces are 96: a float plane
+/// (`plane`) comes first and the MM6 layout follows from 0x10.
+/// Layout (MM6 offsets):
 ///   0x00: fixed-point normal[4] (i32x4) -- 16 bytes
 ///   0x10: z_calc[3] (i32x3) -- 12 bytes
 ///   0x1C: attributes (u32) -- 4 bytes
@@ -61,6 +62,9 @@ pub struct BlvVertex {
 ///   0x4C: polygon_type(u8), num_vertices(u8), padding(i16)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlvFace {
+    /// Float normal (x, y, z) and distance. MM7+ only, zero for MM6.
+    #[serde(default)]
+    pub plane: [f32; 4],
     /// Fixed-point normal (i32x3, 16.16 format) and distance.
     pub normal_fixed: [i32; 4],
     /// Z calculation coefficients.
@@ -164,6 +168,11 @@ impl BlvFace {
         self.face_attributes().contains(FaceAttributes::SKY)
     }
 
+    /// The party and monsters pass through the face (`DONT_BLOCK`).
+    pub fn is_untouchable(&self) -> bool {
+        self.face_attributes().contains(FaceAttributes::DONT_BLOCK)
+    }
+
     /// Get float normal in MM6 coordinates, converted from fixed-point 16.16.
     pub fn normal_f32(&self) -> [f32; 3] {
         [
@@ -177,7 +186,7 @@ impl BlvFace {
 /// A sector (room) in a BLV indoor map.
 ///
 /// Contains face lists, lighting parameters, and spatial bounds.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct BlvSector {
     /// Sector attribute flags (e.g. has sky, underwater, no magic zone).
     pub flags: i32,
@@ -225,9 +234,13 @@ pub struct BlvSector {
     // Assigned from sector data blob:
     /// Face indices (into blv.faces) belonging to this sector.
     pub face_ids: Vec<u16>,
+    /// Light indices (into blv.lights) belonging to this sector, from the
+    /// sector light data blob. Empty if the blob does not match the counts.
+    #[serde(default)]
+    pub light_ids: Vec<u16>,
 }
 
-/// A decoration/sprite in a BLV indoor map (28 bytes on disk + 28-byte name in MM6).
+/// A decoration/sprite in a BLV indoor map (28 bytes on disk in MM6, 32 in MM7+, plus a name).
 /// Field layout from MMExtension MapSprite struct.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlvDecoration {
@@ -247,21 +260,45 @@ pub struct BlvDecoration {
     pub name: String,
 }
 
-/// A point light source in a BLV indoor map. 12 bytes, MM6 format.
+/// A point light source in a BLV indoor map. 12 bytes in MM6, 16 in MM7+.
 ///
 /// Layout: 0x00: pos[3](i16), 0x06: radius(i16), 0x08: attributes(i16), 0x0A: brightness(u16)
+/// MM7+ inserts red, green, blue and type bytes at 0x08, moving the last two fields by 4.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlvLight {
     /// Light position in MM6 coordinates (x, y, z). Offset 0x00.
     pub position: [i16; 3],
     /// Light falloff radius in MM6 units. Offset 0x06.
     pub radius: i16,
+    /// Light colour (r, g, b). MM7+ only, zero for MM6.
+    #[serde(default)]
+    pub color: [u8; 3],
+    /// Light type. MM7+ only, zero for MM6.
+    #[serde(default)]
+    pub light_type: u8,
     /// Light attribute flags (type, dynamic, etc.). Offset 0x08.
     pub attributes: i16,
     /// Light brightness/intensity (higher = brighter). Offset 0x0A.
     pub brightness: u16,
 }
 
+impl BlvLight {
+    /// Attribute bit set while the light is switched off (EVT `ToggleIndoorLight`).
+    pub const DISABLED: i16 = 0x08;
+
+    pub fn is_enabled(&self) -> bool {
+        self.attributes & Self::DISABLED == 0
+    }
+
+    pub fn set_enabled(&mut self, on: bool) {
+        if on {
+            self.attributes &= !Self::DISABLED;
+        } else {
+            self.attributes |= Self::DISABLED;
+        }
+    }
+}
+
 /// A BSP tree node in a BLV indoor map. 8 bytes.
 ///
 /// Layout: 0x00: front(i16), 0x02: back(i16), 0x04: face_id_offset(i16), 0x06: num_faces(i16)
@@ -279,7 +316,7 @@ pub struct BlvBspNode {
 
 /// A monster or item spawn point in a BLV indoor map.
 ///
-/// Identical layout to ODM `SpawnPoint` — 20 bytes, MM6 format (no MM7 Group field).
+/// Identical layout to ODM `SpawnPoint` — 20 bytes in MM6, 24 in MM7+ (trailing Group).
 /// Layout: 0x00: pos[3](i32), 0x0C: radius(u16), 0x0E: kind(u16), 0x10: index(u16), 0x12: bits(u16)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlvSpawnPoint {
@@ -293,6 +330,9 @@ pub struct BlvSpawnPoint {
     pub monster_index: u16,
     /// Spawn attribute flags. Offset 0x12.
     pub attributes: u16,
+    /// Monster group. MM7+ only, 0 for MM6. Offset 0x14.
+    #[serde(default)]
+    pub group: u32,
 }
 
 /// A map outline edge used for the minimap/automap display. 12 bytes.
@@ -1,5 +1,4 @@
 use std::{
-    error::Error,
     io::{Cursor, Read, Seek, Write},
     ops::{Add, Div, Mul, Sub},
 };
@@ -7,6 +6,7 @@ use std::{
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
 
+use crate::Error;
 use crate::{
     assets::enums::{ModelFaceAttributes, PolygonType},
     utils::try_read_string_block,
@@ -425,7 +425,7 @@ impl BSPNode {
 const MODEL_NAME_MAX_SIZE: usize = 32;
 const TEXTURE_NAME_MAX_SIZE: usize = 10;
 
-pub(super) fn read_bsp_models(cursor: &mut Cursor<&[u8]>, count: usize) -> Result<Vec<BSPModel>, Box<dyn Error>> {
+pub(super) fn read_bsp_models(cursor: &mut Cursor<&[u8]>, count: usize) -> Result<Vec<BSPModel>, Error> {
     let mut models: Vec<BSPModel> = Vec::with_capacity(count);
     for header in read_bsp_model_headers(cursor, count)? {
         models.push(read_bsp_model(cursor, header)?);
@@ -433,7 +433,7 @@ pub(super) fn read_bsp_models(cursor: &mut Cursor<&[u8]>, count: usize) -> Resul
     Ok(models)
 }
 
-fn read_bsp_model(cursor: &mut Cursor<&[u8]>, header: BSPModelHeader) -> Result<BSPModel, Box<dyn Error>> {
+fn read_bsp_model(cursor: &mut Cursor<&[u8]>, header: BSPModelHeader) -> Result<BSPModel, Error> {
     let mut model = BSPModel {
         vertices: Vec::with_capacity(header.vertex_count as usize),
         faces: Vec::with_capacity(header.faces_count as usize),
@@ -477,7 +477,7 @@ fn read_bsp_model(cursor: &mut Cursor<&[u8]>, header: BSPModelHeader) -> Result<
     Ok(model)
 }
 
-fn read_bsp_model_headers(cursor: &mut Cursor<&[u8]>, count: usize) -> Result<Vec<BSPModelHeader>, Box<dyn Error>> {
+fn read_bsp_model_headers(cursor: &mut Cursor<&[u8]>, count: usize) -> Result<Vec<BSPModelHeader>, Error> {
     let mut headers = Vec::with_capacity(count);
     for _i in 0..count {
         headers.push(read_bsp_model_header(cursor)?);
@@ -485,7 +485,7 @@ fn read_bsp_model_headers(cursor: &mut Cursor<&[u8]>, count: usize) -> Result<Ve
     Ok(headers)
 }
 
-fn read_bsp_model_header(cursor: &mut Cursor<&[u8]>) -> Result<BSPModelHeader, Box<dyn Error>> {
+fn read_bsp_model_header(cursor: &mut Cursor<&[u8]>) -> Result<BSPModelHeader, Error> {
     let mut header = BSPModelHeader {
         name: try_read_string_block(cursor, MODEL_NAME_MAX_SIZE)?,
         name2: try_read_string_block(cursor, MODEL_NAME_MAX_SIZE)?,
@@ -5,10 +5,10 @@
 
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::Cursor;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One player class entry from `class.txt`.
@@ -27,12 +27,12 @@ pub struct ClassTable {
 }
 
 impl ClassTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/class.txt")?;
         Self::try_from(raw.as_slice())
     }
 
-    fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+    fn parse(text: &str) -> Result<Self, Error> {
         let body: String = text.lines().skip(1).collect::<Vec<_>>().join("\n");
         let mut rdr = ReaderBuilder::new()
             .delimiter(b'\t')
@@ -42,7 +42,7 @@ impl ClassTable {
 
         let mut classes = Vec::new();
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("class.txt", e))?;
 
             let name = rec.get(0).unwrap_or("").trim().to_string();
             if name.is_empty() {
@@ -61,7 +61,7 @@ impl ClassTable {
 }
 
 impl TryFrom<&[u8]> for ClassTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
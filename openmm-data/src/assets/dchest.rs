@@ -2,12 +2,12 @@
 //! 36 bytes per record.
 
 use byteorder::{LittleEndian, ReadBytesExt};
-use std::error::Error;
 use std::io::{Cursor, Read};
 
 use serde::{Deserialize, Serialize};
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 use crate::assets::lod_data::LodData;
 
@@ -30,12 +30,12 @@ pub struct ChestList {
 }
 
 impl ChestList {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/dchest.bin")?;
         Self::try_from(raw.as_slice())
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         let count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut chests = Vec::with_capacity(count);
@@ -62,7 +62,7 @@ impl ChestList {
 }
 
 impl TryFrom<&[u8]> for ChestList {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
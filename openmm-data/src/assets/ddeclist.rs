@@ -1,9 +1,10 @@
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::{Cursor, Read};
 
+use crate::Error;
 use crate::LodSerialise;
+use crate::Version;
 use crate::{Assets, assets::enums::DecorationDescFlags, assets::lod_data::LodData, utils::try_read_name};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,13 +12,17 @@ pub struct DDecList {
     pub items: Vec<DDecListItem>,
 }
 
-/// A decoration descriptor from ddeclist.bin. 80 bytes per record.
+/// Size of an MM6 ddeclist.bin record; MM7+ records append 4 bytes.
+const MM6_ITEM_SIZE: usize = 80;
+
+/// A decoration descriptor from ddeclist.bin. 80 bytes per record in MM6, 84 in MM7+.
 ///
 /// Layout:
 ///   0x00: name[32], 0x20: display_name[32],
 ///   0x40: dec_type(u16), 0x42: height(u16), 0x44: radius(u16),
 ///   0x46: light_radius(u16), 0x48: sft(i16), 0x4A: attributes(u16),
 ///   0x4C: sound_id(u16), 0x4E: skip(u16)
+///   0x50: light_color[3], 0x53: pad (MM7+ only)
 #[allow(dead_code)]
 #[repr(C)]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -42,6 +47,10 @@ pub struct DDecListItem {
     pub sound_id: u16,
     /// Padding/unused. Offset 0x4E.
     skip: u16,
+    /// Coloured light (r, g, b). MM7+ only, zero for MM6. Offset 0x50.
+    pub light_color: [u8; 3],
+    /// Padding. Offset 0x53.
+    skip2: u8,
 }
 
 #[repr(C)]
@@ -122,16 +131,26 @@ impl DDecListItem {
 }
 
 impl DDecList {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
-        let raw = assets.get_bytes("icons/ddeclist.bin")?;
-        Self::try_from(raw.as_slice())
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
+        let data = assets.get_entry("icons/ddeclist.bin")?;
+        Self::parse_version(&data, assets.version())
+    }
+
+    /// Parse an MM6 decoration list.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        Self::parse_version(data, Version::MM6)
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    /// Parse a decoration list laid out for `version`.
+    pub fn parse_version(data: &[u8], version: Version) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         let mut items: Vec<DDecListItem> = Vec::new();
         let item_count = cursor.read_u32::<LittleEndian>()?;
-        let item_size = std::mem::size_of::<DDecListItem>();
+        let item_size = if version >= Version::MM7 {
+            std::mem::size_of::<DDecListItem>()
+        } else {
+            MM6_ITEM_SIZE
+        };
 
         for _i in 0..item_count {
             let mut item = DDecListItem::default();
@@ -144,7 +163,7 @@ impl DDecList {
 }
 
 impl TryFrom<&[u8]> for DDecList {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -152,18 +171,14 @@ impl TryFrom<&[u8]> for DDecList {
     }
 }
 
+// Always writes the MM6 layout.
 impl LodSerialise for DDecList {
     fn to_bytes(&self) -> Vec<u8> {
         use byteorder::{LittleEndian, WriteBytesExt};
         let mut buf = Vec::new();
         buf.write_u32::<LittleEndian>(self.items.len() as u32).unwrap();
         for item in &self.items {
-            let bytes = unsafe {
-                std::slice::from_raw_parts(
-                    item as *const DDecListItem as *const u8,
-                    std::mem::size_of::<DDecListItem>(),
-                )
-            };
+            let bytes = unsafe { std::slice::from_raw_parts(item as *const DDecListItem as *const u8, MM6_ITEM_SIZE) };
             buf.extend_from_slice(bytes);
         }
         buf
@@ -186,4 +201,24 @@ mod tests {
         assert_eq!(d_declist.items[6].name(), Some("fount1".to_string()));
         assert_eq!(d_declist.items[6].display_name(), Some("fountain".to_string()));
     }
+
+    #[test]
+    fn mm7_records_carry_light_colour() {
+        let mut data = 1u32.to_le_bytes().to_vec();
+        let mut rec = [0u8; 84];
+        rec[..6].copy_from_slice(b"torch1");
+        rec[0x46..0x48].copy_from_slice(&256u16.to_le_bytes());
+        rec[0x48..0x4A].copy_from_slice(&41i16.to_le_bytes());
+        rec[0x50..0x53].copy_from_slice(&[255, 160, 64]);
+        data.extend(rec);
+
+        let list = DDecList::parse_version(&data, crate::Version::MM7).unwrap();
+        let item = &list.items[0];
+        assert_eq!(item.name().as_deref(), Some("torch1"));
+        assert_eq!((item.light_radius, item.sft_index()), (256, 41));
+        assert_eq!(item.light_color, [255, 160, 64]);
+
+        // Written back as an 80-byte MM6 record.
+        assert_eq!(crate::LodSerialise::to_bytes(&list).len(), 4 + 80);
+    }
 }
@@ -1,16 +1,27 @@
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::error::Error;
-use std::io::{Cursor, Seek};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Read, Seek};
 
 use serde::{Deserialize, Serialize};
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
+use crate::Version;
+use crate::assets::dmonlist::MonsterDesc;
 use crate::assets::enums::ActorAttributes;
+use crate::assets::map_delta::{LocationHeader, MAP_VARS_SIZE, MapDelta};
 
 /// MM6 MapMonster struct size = 0x224 = 548 bytes.
 /// Layout from MMExtension: Scripts/Structs/01 common structs.lua (MapMonster).
-const ACTOR_SIZE_MM6: usize = 548;
+pub const ACTOR_SIZE_MM6: usize = 548;
+
+/// MM7 MapMonster struct size = 0x344 = 836 bytes.
+///
+/// Same fields as MM6 with a 0x58-byte CommonMonsterProps (so 0x74..0xC4
+/// sits at 0x84..0xD4), 22 spell buffs, 4 carried items (36 bytes each)
+/// before Group at 0x2C4, and a unique-name index plus padding after
+/// LastAttacker.
+pub const ACTOR_SIZE_MM7: usize = 0x344;
 
 /// One monster attack definition (5 bytes). Used in `CommonMonsterProps`.
 ///
@@ -48,6 +59,11 @@ pub struct MonsterAttackInfo {
 ///   0x44: _unknown(4) [MM6-only trailing field]
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct CommonMonsterProps {
+    /// Name/Picture runtime pointers. Offset 0x00 (8 bytes, zero in pristine files).
+    pub _runtime: [u8; 8],
+    /// Raw 1-indexed Id byte at offset 0x08, kept so an unset (0) id round-trips.
+    #[serde(skip)]
+    pub _id_raw: u8,
     /// Index into dmonlist.bin (1-based in file, stored 0-based here). Offset 0x08.
     pub monlist_id: u8,
     /// Monster level used for scaling. Offset 0x09.
@@ -102,8 +118,12 @@ pub struct CommonMonsterProps {
     pub magic_resistance: u8,
     /// Number of party members targeted per attack. Offset 0x2A.
     pub pref_num: u8,
+    /// Padding. Offset 0x2B.
+    pub _pad0x2b: u8,
     /// Quest item index (carried quest item ID, 0 = none). Offset 0x2C.
     pub quest_item: i16,
+    /// Padding. Offset 0x2E.
+    pub _pad0x2e: u16,
     /// Maximum HP for this actor instance. Offset 0x30.
     pub full_hp: i32,
     /// Armor class. Offset 0x34.
@@ -160,10 +180,13 @@ pub struct MonsterSchedule {
 ///   0xC4: SpellBuffs[14], 0x1A4: Group, 0x1A8: Ally,
 ///   0x1AC: Schedules[8], 0x20C: Summoner, 0x210: LastAttacker,
 ///   0x214: remaining padding to 0x224
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DdmActor {
     /// Actor name. Offset 0x00 (32 bytes, null-terminated).
     pub name: String,
+    /// Raw name buffer, kept so bytes after the terminator round-trip while `name` is unchanged.
+    #[serde(skip)]
+    pub _name_raw: [u8; 32],
     /// Index into dmonlist.bin (MonsterList). Mirrors `common_props.monlist_id`.
     /// Convenience shortcut — both are 0-indexed (file stores 1-indexed, we subtract 1).
     pub monlist_id: u8,
@@ -243,9 +266,34 @@ pub struct DdmActor {
     pub last_attacker: i32,
     /// Remaining padding bytes (16 bytes, offset 0x214 to 0x224).
     pub _pad0x214: [u8; 16],
+    /// The MM7 record this actor was read from, empty for MM6 actors. Holds
+    /// the MM7-only fields while the mapped ones are edited above.
+    #[serde(skip)]
+    pub _mm7_raw: Vec<u8>,
 }
 
 impl DdmActor {
+    /// A fresh record for a monster created from a spawn point.
+    ///
+    /// Body size, speed and sounds come from the dmonlist entry; the caller
+    /// fills in live state (HP, position, AI state).
+    pub fn spawned(name: &str, monlist_id: u8, desc: &MonsterDesc) -> Self {
+        Self {
+            name: name.to_string(),
+            monlist_id,
+            common_props: CommonMonsterProps {
+                monlist_id,
+                _id_raw: monlist_id.saturating_add(1),
+                ..Default::default()
+            },
+            radius: desc.radius,
+            height: desc.height,
+            move_speed: desc.move_speed,
+            sound_ids: desc.sound_ids,
+            ..Default::default()
+        }
+    }
+
     /// Returns typed actor attribute flags.
     pub fn actor_attributes(&self) -> ActorAttributes {
         ActorAttributes::from_bits_truncate(self.attributes)
@@ -261,143 +309,171 @@ impl DdmActor {
 /// Parsed DDM (outdoor) delta file.
 ///
 /// On-disk layout (sections in order):
-///   1. MapVars     — 200 × u8 event/barrel state variables (no count prefix)
-///   2. MapObjects  — u32 count + count × 0x64 bytes each (projectiles / dropped items)
-///   3. MapSprites  — u32 count + count × 0x1C bytes each (decoration instances)
+///   1. MapVars      — 200 × u8 event/barrel state variables (no count prefix)
+///   2. MapObjects   — u32 count + count × 0x64 bytes each (projectiles / dropped items)
+///   3. MapSprites   — u32 count + count × 0x1C bytes each (decoration instances)
 ///   4. SoundSprites — 10 × i32 (ambient sound positions, no count prefix)
-///   5. MapChests   — u32 count + count × ~4204 bytes each (chest contents)
-///   6. MapMonsters — u32 count + count × 548 bytes each (actors)
+///   5. MapChests    — u32 count + count × 0x106C bytes each (chest contents)
+///   6. MapMonsters  — u32 count + count × 548 bytes each (actors)
+///
+/// Sections 2–6 are shared with DLV and parsed by [`MapDelta`]. Any bytes after
+/// the actors are kept in `tail`, so `to_bytes` reproduces the input exactly.
 ///
-/// **Save support:** sections 1–5 are currently skipped by the heuristic actor-finder.
-/// To implement round-trip saving, parse all sections sequentially and store their raw
-/// bytes (or typed structs). Every section must be re-serialised in order with the
-/// correct C struct layout — all padding fields in `DdmActor` are already preserved for
-/// this purpose.
-#[derive(Debug, Serialize, Deserialize)]
+/// MM7 files are laid out differently:
+///   1. Header          — 40 bytes ([`LocationHeader`])
+///   2. RevealedCells   — 2 × 88 × 11 bytes (fully / partially mapped cells)
+///   3. FaceAttributes  — header face count × u32
+///   4. DecorationFlags — header decoration count × u16
+///   5. MapMonsters, MapObjects, MapChests ([`MapDelta`])
+///   6. MapVars         — 200 × u8
+///   7. LocationTime    — kept in `tail`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Ddm {
-    pub actors: Vec<DdmActor>,
-    /// Sections before actors (MapVars, MapObjects, MapSprites, etc.)
+    /// Layout the file was read in, and is written back in.
     #[serde(skip)]
-    pub prefix_data: Vec<u8>,
+    pub version: Version,
+    /// MM7+ header. Its counts are rewritten from the sections on save.
+    pub header: LocationHeader,
+    /// MM7+ automap cells, kept verbatim.
+    pub revealed_cells: Vec<u8>,
+    /// MM7+ attributes of every BSP model face.
+    pub face_attributes: Vec<u32>,
+    /// MM7+ decoration flags.
+    pub decoration_flags: Vec<u16>,
+    /// MapVars (always 200 bytes).
+    pub map_vars: Vec<u8>,
+    #[serde(flatten)]
+    pub delta: MapDelta,
+    /// Bytes following the last section, kept verbatim.
+    pub tail: Vec<u8>,
 }
 
+/// Size of the MM7 automap section: fully and partially revealed cell bits.
+const REVEALED_CELLS_SIZE: usize = 2 * 88 * 11;
+
 impl LodSerialise for Ddm {
     fn to_bytes(&self) -> Vec<u8> {
-        let mut out = self.prefix_data.clone();
-        use byteorder::{LittleEndian, WriteBytesExt};
-        out.write_u32::<LittleEndian>(self.actors.len() as u32).unwrap();
-        for actor in &self.actors {
-            out.extend_from_slice(&actor.to_bytes());
+        let mut out = Vec::new();
+        let map_vars = (0..MAP_VARS_SIZE).map(|i| self.map_vars.get(i).copied().unwrap_or(0));
+        if self.version >= Version::MM7 {
+            LocationHeader {
+                face_count: self.face_attributes.len() as u32,
+                decoration_count: self.decoration_flags.len() as u32,
+                ..self.header
+            }
+            .write(&mut out);
+            out.extend((0..REVEALED_CELLS_SIZE).map(|i| self.revealed_cells.get(i).copied().unwrap_or(0)));
+            for &a in &self.face_attributes {
+                out.write_u32::<LittleEndian>(a).unwrap();
+            }
+            for &f in &self.decoration_flags {
+                out.write_u16::<LittleEndian>(f).unwrap();
+            }
+            self.delta.write(&mut out, self.version);
+            out.extend(map_vars);
+        } else {
+            out.extend(map_vars);
+            self.delta.write(&mut out, self.version);
         }
+        out.extend_from_slice(&self.tail);
         out
     }
 }
 
 impl TryFrom<&[u8]> for Ddm {
-    type Error = Box<dyn Error>;
+    type Error = Error;
     fn try_from(raw: &[u8]) -> Result<Self, Self::Error> {
         Self::parse(raw)
     }
 }
 
 impl Ddm {
-    pub fn load(assets: &Assets, map_name: &str) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets, map_name: &str) -> Result<Self, Error> {
         let ddm_name = map_name.replace(".odm", ".ddm");
-        let raw = assets.get_decompressed(format!("games/{}", ddm_name))?;
-        Self::try_from(raw.as_slice())
+        check_delta_version("ddm", assets.version()).map_err(|e| e.in_file(&ddm_name))?;
+        let data = assets.get_entry(format!("games/{}", ddm_name))?;
+        Self::parse_version(&data, assets.version()).map_err(|e| e.in_file(ddm_name))
     }
 
-    /// Parse actors from raw (decompressed) delta file data.
-    /// Usable by both DDM (outdoor) and DLV (indoor) parsers since they share the same MapMonster struct.
-    pub fn parse_from_data(data: &[u8]) -> Result<Vec<DdmActor>, Box<dyn Error>> {
+    /// Parse actors from raw (decompressed) MM6 DDM data.
+    pub fn parse_from_data(data: &[u8]) -> Result<Vec<DdmActor>, Error> {
         let ddm = Self::parse(data)?;
-        Ok(ddm.actors)
+        Ok(ddm.delta.actors)
     }
 
-    fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
-        // Scan for actor count: a u32 followed by ASCII name bytes
-        let actor_start = Self::find_actors(data)?;
-        let actor_count = u32::from_le_bytes([
-            data[actor_start],
-            data[actor_start + 1],
-            data[actor_start + 2],
-            data[actor_start + 3],
-        ]) as usize;
-
-        let mut actors = Vec::with_capacity(actor_count);
-        let base = actor_start + 4;
-
-        for i in 0..actor_count {
-            let offset = base + i * ACTOR_SIZE_MM6;
-            if offset + ACTOR_SIZE_MM6 > data.len() {
-                break;
-            }
-            if let Some(actor) = Self::read_actor(&data[offset..offset + ACTOR_SIZE_MM6]) {
-                actors.push(actor);
-            }
-        }
-
-        Ok(Ddm {
-            actors,
-            prefix_data: data[..actor_start].to_vec(),
-        })
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        Self::parse_version(data, Version::MM6)
     }
 
-    /// Parse actors starting at a known byte offset (for DLV sequential parsing).
-    pub fn parse_actors_at(data: &[u8], offset: usize, count: usize) -> Vec<DdmActor> {
-        let mut actors = Vec::with_capacity(count);
-        for i in 0..count {
-            let start = offset + i * ACTOR_SIZE_MM6;
-            if start + ACTOR_SIZE_MM6 > data.len() {
-                break;
-            }
-            if let Some(actor) = Self::read_actor(&data[start..start + ACTOR_SIZE_MM6]) {
-                actors.push(actor);
-            }
-        }
-        actors
+    /// Parse decompressed DDM data laid out for `version`.
+    pub fn parse_version(data: &[u8], version: Version) -> Result<Self, Error> {
+        check_delta_version("ddm", version)?;
+        let mut c = Cursor::new(data);
+        let mut ddm = Self::read(data, version, &mut c).map_err(|e| e.at("ddm", c.position() as usize))?;
+        ddm.tail = data[c.position() as usize..].to_vec();
+        Ok(ddm)
     }
 
-    fn find_actors(data: &[u8]) -> Result<usize, Box<dyn Error>> {
-        for offset in (0..data.len().saturating_sub(40)).step_by(2) {
-            let val = u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]]);
-            if (1..=500).contains(&val) {
-                let name_start = offset + 4;
-                if name_start + 32 > data.len() {
-                    continue;
-                }
-                let name_bytes = &data[name_start..name_start + 32];
-                let is_name = name_bytes
-                    .iter()
-                    .take(20)
-                    .all(|&b| b == 0 || (0x20..=0x7E).contains(&b));
-                let has_alpha = name_bytes.iter().take(10).any(|&b| b.is_ascii_alphabetic());
-                if is_name && has_alpha {
-                    // Verify second actor name too
-                    let second = name_start + ACTOR_SIZE_MM6;
-                    if second + 32 <= data.len() && val > 1 {
-                        let name2 = &data[second..second + 32];
-                        let is_name2 = name2.iter().take(20).all(|&b| b == 0 || (0x20..=0x7E).contains(&b));
-                        let has_alpha2 = name2.iter().take(10).any(|&b| b.is_ascii_alphabetic());
-                        if is_name2 && has_alpha2 {
-                            return Ok(offset);
-                        }
-                    } else if val == 1 {
-                        return Ok(offset);
-                    }
-                }
-            }
+    fn read(data: &[u8], version: Version, c: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let mut ddm = Ddm {
+            version,
+            ..Default::default()
+        };
+        if version >= Version::MM7 {
+            ddm.header = LocationHeader::read(c)?;
+            ddm.revealed_cells = vec![0u8; REVEALED_CELLS_SIZE];
+            c.read_exact(&mut ddm.revealed_cells)?;
+            let faces = ddm.header.face_count as usize;
+            let decorations = ddm.header.decoration_count as usize;
+            Error::check_len("ddm", data, c.position() as usize, faces * 4 + decorations * 2)?;
+            ddm.face_attributes = vec![0u32; faces];
+            c.read_u32_into::<LittleEndian>(&mut ddm.face_attributes)?;
+            ddm.decoration_flags = vec![0u16; decorations];
+            c.read_u16_into::<LittleEndian>(&mut ddm.decoration_flags)?;
+            ddm.delta = MapDelta::read(c, version)?;
+            Error::check_len("ddm", data, c.position() as usize, MAP_VARS_SIZE)?;
+            ddm.map_vars = vec![0u8; MAP_VARS_SIZE];
+            c.read_exact(&mut ddm.map_vars)?;
+        } else {
+            Error::check_len("ddm", data, 0, MAP_VARS_SIZE)?;
+            ddm.map_vars = vec![0u8; MAP_VARS_SIZE];
+            c.read_exact(&mut ddm.map_vars)?;
+            ddm.delta = MapDelta::read(c, version)?;
         }
-        Err("Could not find actor data in DDM".into())
+        Ok(ddm)
     }
 
     #[cfg(test)]
     #[allow(dead_code)]
     fn actor_count(&self) -> usize {
-        self.actors.len()
+        self.delta.actors.len()
+    }
+
+    /// Decode one MapMonster record laid out for `version`.
+    ///
+    /// MM7 records are mapped onto the MM6 fields. What MM6 has no room for
+    /// (spell buffs past the 14th, carried items, the unique name, and the
+    /// props after the hostility type) is not decoded but kept in
+    /// [`DdmActor::_mm7_raw`], so [`DdmActor::to_bytes_version`] writes it back.
+    pub fn read_actor_version(data: &[u8], version: Version) -> Result<DdmActor, Error> {
+        let malformed = || Error::invalid("ddm", "malformed actor record");
+        match version {
+            Version::MM6 => {
+                Error::check_len("ddm", data, 0, ACTOR_SIZE_MM6)?;
+                Self::read_actor(data).ok_or_else(malformed)
+            }
+            Version::MM7 => {
+                Error::check_len("ddm", data, 0, ACTOR_SIZE_MM7)?;
+                let mut actor = Self::read_actor(&mm7_actor_as_mm6(data)?).ok_or_else(malformed)?;
+                actor._mm7_raw = data[..ACTOR_SIZE_MM7].to_vec();
+                Ok(actor)
+            }
+            Version::MM8 => Err(Error::invalid("ddm", "MM8 actor records are not supported yet")),
+        }
     }
 
-    fn read_actor(data: &[u8]) -> Option<DdmActor> {
+    /// Decode one 548-byte MapMonster record.
+    pub(crate) fn read_actor(data: &[u8]) -> Option<DdmActor> {
         let name_end = data[..32].iter().position(|&b| b == 0).unwrap_or(32);
         let name = String::from_utf8_lossy(&data[..name_end]).to_string();
 
@@ -415,7 +491,11 @@ impl Ddm {
         // Offsets within props are relative to 0x2C.
         // 0x00-0x07: Name/Picture runtime pointers — always 0 in file, skip.
         let p = &data[0x2C..0x2C + 72];
+        let mut _runtime = [0u8; 8];
+        _runtime.copy_from_slice(&p[..8]);
         let common_props = CommonMonsterProps {
+            _runtime,
+            _id_raw: p[0x08],
             monlist_id: p[0x08].saturating_sub(1), // 1-indexed → 0-indexed
             level: p[0x09],
             treasure_item_percent: p[0x0A],
@@ -455,9 +535,9 @@ impl Ddm {
             phys_resistance: p[0x28],
             magic_resistance: p[0x29],
             pref_num: p[0x2A],
-            // 0x2B: padding byte
+            _pad0x2b: p[0x2B],
             quest_item: i16::from_le_bytes([p[0x2C], p[0x2D]]),
-            // 0x2E-0x2F: padding
+            _pad0x2e: u16::from_le_bytes([p[0x2E], p[0x2F]]),
             full_hp: i32::from_le_bytes([p[0x30], p[0x31], p[0x32], p[0x33]]),
             armor_class: i32::from_le_bytes([p[0x34], p[0x35], p[0x36], p[0x37]]),
             experience: i32::from_le_bytes([p[0x38], p[0x39], p[0x3A], p[0x3B]]),
@@ -562,8 +642,12 @@ impl Ddm {
         let mut _pad0x214 = [0u8; 16];
         _pad0x214.copy_from_slice(&data[0x214..0x224]);
 
+        let mut _name_raw = [0u8; 32];
+        _name_raw.copy_from_slice(&data[..32]);
+
         Some(DdmActor {
             name,
+            _name_raw,
             monlist_id,
             npc_id,
             _pad0x22,
@@ -599,21 +683,43 @@ impl Ddm {
             summoner,
             last_attacker,
             _pad0x214,
+            _mm7_raw: Vec::new(),
         })
     }
 }
 
 impl DdmActor {
+    /// Encode the actor as a MapMonster record laid out for `version`.
+    pub fn to_bytes_version(&self, version: Version) -> Vec<u8> {
+        let mm6 = self.to_bytes();
+        if version < Version::MM7 {
+            return mm6.to_vec();
+        }
+        let mut out = if self._mm7_raw.len() == ACTOR_SIZE_MM7 {
+            self._mm7_raw.clone()
+        } else {
+            vec![0u8; ACTOR_SIZE_MM7]
+        };
+        mm6_actor_into_mm7(&mm6, &mut out);
+        out
+    }
+
+    /// Encode the actor as an MM6 MapMonster record.
     pub fn to_bytes(&self) -> [u8; ACTOR_SIZE_MM6] {
         let mut out = [0u8; ACTOR_SIZE_MM6];
         let mut cursor = Cursor::new(&mut out[..]);
-        use byteorder::{LittleEndian, WriteBytesExt};
         use std::io::Write;
 
-        // Name (32 bytes)
-        let mut name_bytes = [0u8; 32];
-        let n = self.name.len().min(31);
-        name_bytes[..n].copy_from_slice(&self.name.as_bytes()[..n]);
+        // Name (32 bytes). Reuse the raw buffer while the decoded name is unchanged.
+        let raw_end = self._name_raw.iter().position(|&b| b == 0).unwrap_or(32);
+        let name_bytes = if String::from_utf8_lossy(&self._name_raw[..raw_end]) == self.name {
+            self._name_raw
+        } else {
+            let mut buf = [0u8; 32];
+            let n = self.name.len().min(31);
+            buf[..n].copy_from_slice(&self.name.as_bytes()[..n]);
+            buf
+        };
         cursor.write_all(&name_bytes).unwrap();
 
         // 0x20: NPC_ID(2) + pad(2) + Bits(4) + HP(2) + pad(2)
@@ -628,8 +734,13 @@ impl DdmActor {
         // We'll write it field-by-field.
         cursor.set_position(0x2C);
         let p = &self.common_props;
-        cursor.write_all(&[0u8; 8]).unwrap(); // runtime pointers
-        cursor.write_u8(p.monlist_id.saturating_add(1)).unwrap();
+        cursor.write_all(&p._runtime).unwrap();
+        let id = if p._id_raw.saturating_sub(1) == p.monlist_id {
+            p._id_raw
+        } else {
+            p.monlist_id.saturating_add(1)
+        };
+        cursor.write_u8(id).unwrap();
         cursor.write_u8(p.level).unwrap();
         cursor.write_u8(p.treasure_item_percent).unwrap();
         cursor.write_u8(p.treasure_dice_count).unwrap();
@@ -664,9 +775,9 @@ impl DdmActor {
         cursor.write_u8(p.phys_resistance).unwrap();
         cursor.write_u8(p.magic_resistance).unwrap();
         cursor.write_u8(p.pref_num).unwrap();
-        cursor.write_u8(0).unwrap(); // pad 0x2B
+        cursor.write_u8(p._pad0x2b).unwrap();
         cursor.write_i16::<LittleEndian>(p.quest_item).unwrap();
-        cursor.write_all(&[0u8; 2]).unwrap(); // pad 0x2E
+        cursor.write_u16::<LittleEndian>(p._pad0x2e).unwrap();
         cursor.write_i32::<LittleEndian>(p.full_hp).unwrap();
         cursor.write_i32::<LittleEndian>(p.armor_class).unwrap();
         cursor.write_i32::<LittleEndian>(p.experience).unwrap();
@@ -737,6 +848,64 @@ impl DdmActor {
     }
 }
 
+/// Rearrange an MM7 MapMonster record into the MM6 layout.
+fn mm7_actor_as_mm6(data: &[u8]) -> Result<[u8; ACTOR_SIZE_MM6], Error> {
+    const PROPS: usize = 0x2C;
+    let mut out = [0u8; ACTOR_SIZE_MM6];
+    // Name, NPC id, bits and HP share offsets.
+    out[..PROPS].copy_from_slice(&data[..PROPS]);
+
+    // MM7 props: runtime pointers(8), Level..HostilityType at 0x08..0x12,
+    // Id(u16) at 0x34, FullHP/AC/Exp/MoveSpeed/AttackRecovery at 0x40.
+    // MM6 has a 1-based u8 Id at 0x08 with Level..HostilityType after it.
+    let p = &data[PROPS..PROPS + 0x58];
+    let id = u16::from_le_bytes([p[0x34], p[0x35]]);
+    let id =
+        u8::try_from(id).map_err(|_| Error::invalid("ddm", format!("monster id {id} does not fit an MM6 actor")))?;
+    let q = &mut out[PROPS..PROPS + 0x48];
+    q[..0x08].copy_from_slice(&p[..0x08]);
+    q[0x08] = id;
+    q[0x09..0x13].copy_from_slice(&p[0x08..0x12]);
+    q[0x30..0x44].copy_from_slice(&p[0x40..0x54]);
+
+    // RangeAttack .. Sounds move back by the 0x10 the props grew.
+    out[0x74..0xC4].copy_from_slice(&data[0x84..0xD4]);
+    // First 14 of 22 spell buffs.
+    out[0xC4..0x1A4].copy_from_slice(&data[0xD4..0x1B4]);
+    // Group, Ally, Schedules, Summoner, LastAttacker.
+    out[0x1A4..0x214].copy_from_slice(&data[0x2C4..0x334]);
+    Ok(out)
+}
+
+/// Write the fields of an MM6 MapMonster record back into an MM7 one: the
+/// inverse of [`mm7_actor_as_mm6`]. Bytes MM6 has no field for are left as is.
+fn mm6_actor_into_mm7(mm6: &[u8; ACTOR_SIZE_MM6], out: &mut [u8]) {
+    const PROPS: usize = 0x2C;
+    out[..PROPS].copy_from_slice(&mm6[..PROPS]);
+
+    let q = &mm6[PROPS..PROPS + 0x48];
+    let p = &mut out[PROPS..PROPS + 0x58];
+    p[..0x08].copy_from_slice(&q[..0x08]);
+    p[0x08..0x12].copy_from_slice(&q[0x09..0x13]);
+    p[0x34..0x36].copy_from_slice(&u16::from(q[0x08]).to_le_bytes());
+    p[0x40..0x54].copy_from_slice(&q[0x30..0x44]);
+
+    out[0x84..0xD4].copy_from_slice(&mm6[0x74..0xC4]);
+    out[0xD4..0x1B4].copy_from_slice(&mm6[0xC4..0x1A4]);
+    out[0x2C4..0x334].copy_from_slice(&mm6[0x1A4..0x214]);
+}
+
+/// DDM/DLV files are understood in their MM6 and MM7 layouts.
+pub(crate) fn check_delta_version(format: &'static str, version: Version) -> Result<(), Error> {
+    if version > Version::MM7 {
+        return Err(Error::invalid(
+            format,
+            format!("{version:?} map delta files are not supported yet"),
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[path = "ddm_tests.rs"]
 mod tests;
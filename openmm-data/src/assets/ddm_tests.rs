@@ -1,14 +1,201 @@
 use super::*;
+use crate::assets::items::ItemsTable;
+use crate::assets::map_delta::{ChestFlags, ItemRecord, LocationHeader, MapChest, MapDeltaFile, MapObject, MapSprite};
 use crate::assets::test_lod;
 
+#[test]
+fn synthetic_ddm_round_trip() {
+    let mut chest = MapChest {
+        picture: 2,
+        bits: 1,
+        ..Default::default()
+    };
+    chest.items[0] = ItemRecord {
+        number: 84,
+        charges: 5,
+        flags: 1,
+        ..Default::default()
+    };
+    chest.inventory[0] = 1;
+    let mut actor = DdmActor {
+        name: "Peasant".into(),
+        hp: 12,
+        ..Default::default()
+    };
+    actor.common_props.monlist_id = 4;
+    let ddm = Ddm {
+        map_vars: (0..200).map(|i| i as u8).collect(),
+        delta: MapDelta {
+            objects: vec![MapObject {
+                object_type: 800,
+                position: [1, -2, 3],
+                item: ItemRecord {
+                    number: 7,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            sprites: vec![MapSprite {
+                declist_id: 5,
+                event: 12,
+                ..Default::default()
+            }],
+            sound_sprites: [3; 10],
+            chests: vec![chest],
+            actors: vec![actor],
+        },
+        tail: vec![0xAB; 8],
+        ..Default::default()
+    };
+
+    let bytes = ddm.to_bytes();
+    assert_eq!(bytes.len(), 200 + 4 + 100 + 4 + 28 + 40 + 4 + 4204 + 4 + 548 + 8);
+    let parsed = Ddm::try_from(bytes.as_slice()).unwrap();
+    assert_eq!(parsed.to_bytes(), bytes);
+    assert_eq!(parsed.map_vars[199], 199);
+    assert_eq!(parsed.delta.objects[0].item.number, 7);
+    assert_eq!(parsed.delta.sprites[0].event, 12);
+    assert_eq!(parsed.delta.chests[0].stored_items().count(), 1);
+    assert_eq!(parsed.delta.chests[0].items[0].charges, 5);
+    assert_eq!(parsed.delta.actors[0].name, "Peasant");
+    assert_eq!(parsed.delta.actors[0].common_props.monlist_id, 4);
+    assert_eq!(parsed.tail, vec![0xAB; 8]);
+}
+
+#[test]
+fn chest_items_place_and_take() {
+    let mut chest = MapChest::default();
+    let item = |number| ItemRecord {
+        number,
+        ..Default::default()
+    };
+    // 3 × 2 grid: a 2×2 item fills the left, a 1×2 item the right column.
+    assert_eq!(chest.place_item(item(5), (2, 2), 3, 2), Some(0));
+    assert_eq!(chest.place_item(item(6), (1, 2), 3, 2), Some(1));
+    assert_eq!(chest.place_item(item(7), (1, 1), 3, 2), None);
+    assert_eq!(&chest.inventory[..6], &[1, -1, 2, -1, -1, -2]);
+    assert_eq!(chest.item_cell(1), Some(2));
+
+    assert_eq!(chest.take_item(0).map(|i| i.number), Some(5));
+    assert_eq!(chest.take_item(0), None);
+    assert_eq!(&chest.inventory[..6], &[0, 0, 2, 0, 0, -2]);
+    assert_eq!(chest.place_item(item(7), (1, 1), 3, 2), Some(0));
+}
+
+#[test]
+fn chest_generates_items_once() {
+    let table = ItemsTable::parse(
+        "Items.txt\nheader\n\
+        1\tdagger\tDagger\t20\tWeapon\tDagger\t2d2\t0\t0\t1\tDagger\t1\t1\t0\t0\t\n\
+        2\tplate\tPlate\t3000\tArmor\tPlate\t0\t0\t0\t1\tArmor\t2\t1\t0\t0\t\n",
+    )
+    .unwrap();
+    let mut chest = MapChest {
+        bits: ChestFlags::TRAPPED.bits(),
+        ..Default::default()
+    };
+    chest.items[3].number = -5;
+    chest.items[4].number = 1;
+    chest.generate_items(1, &table, 9, 5, |_| (1, 1), |_| 0);
+    assert!(chest.flags().contains(ChestFlags::TRAPPED | ChestFlags::ITEMS_PLACED));
+    let numbers: Vec<i32> = chest.stored_items().map(|(_, i)| i.number).collect();
+    assert_eq!(numbers, vec![1, 2]);
+    assert!(chest.item_cell(1).is_some());
+
+    // Already placed: taking everything out doesn't bring new items.
+    chest.take_item(0);
+    chest.take_item(1);
+    chest.generate_items(1, &table, 9, 5, |_| (1, 1), |_| 0);
+    assert_eq!(chest.stored_items().count(), 0);
+
+    // An empty chest rolls from the map's treasure level.
+    let mut empty = MapChest::default();
+    empty.generate_items(1, &table, 9, 5, |_| (1, 1), |_| 1);
+    assert_eq!(empty.stored_items().count(), 2);
+    assert!(empty.stored_items().all(|(_, i)| i.number == 1));
+}
+
+#[test]
+fn actor_raw_bytes_round_trip() {
+    let mut raw = DdmActor::default().to_bytes();
+    raw[..8].copy_from_slice(b"Rat\0junk");
+    raw[0x2C + 0x2B] = 0x5A;
+    let actor = Ddm::read_actor(&raw).unwrap();
+    assert_eq!(actor.name, "Rat");
+    assert_eq!(actor.to_bytes(), raw);
+}
+
+#[test]
+fn spawned_actor_round_trip() {
+    let desc = crate::assets::dmonlist::MonsterDesc {
+        height: 120,
+        radius: 40,
+        move_speed: 200,
+        to_hit_radius: 0,
+        tint: 0,
+        sound_ids: [1, 2, 3, 4],
+        internal_name: "GoblinB".into(),
+        sprite_names: Default::default(),
+    };
+    let mut actor = DdmActor::spawned("Goblin", 17, &desc);
+    actor.hp = 9;
+    actor.position = [100, -200, 30];
+
+    let parsed = Ddm::read_actor(&actor.to_bytes()).unwrap();
+    assert_eq!(parsed.name, "Goblin");
+    assert_eq!(parsed.monlist_id, 17);
+    assert_eq!(parsed.hp, 9);
+    assert_eq!(parsed.position, [100, -200, 30]);
+    assert_eq!((parsed.radius, parsed.height, parsed.move_speed), (40, 120, 200));
+    assert_eq!(parsed.sound_ids, [1, 2, 3, 4]);
+}
+
+#[test]
+fn map_delta_file_pads_map_vars() {
+    let mut file = MapDeltaFile::Ddm(Ddm::default());
+    file.set_map_vars(&[3, 4]);
+    assert_eq!(file.map_vars().len(), MAP_VARS_SIZE);
+    assert_eq!(&file.map_vars()[..3], &[3, 4, 0]);
+
+    let parsed = Ddm::try_from(file.to_bytes().as_slice()).unwrap();
+    assert_eq!(&parsed.map_vars[..2], &[3, 4]);
+}
+
+#[test]
+fn map_delta_file_last_visit_round_trip() {
+    let mut file = MapDeltaFile::Ddm(Ddm::default());
+    assert_eq!(file.last_visit(), 0);
+    file.set_last_visit(0x1_2345_6789);
+    let parsed = MapDeltaFile::Ddm(Ddm::try_from(file.to_bytes().as_slice()).unwrap());
+    assert_eq!(parsed.last_visit(), 0x1_2345_6789);
+
+    // Bytes after the timestamp survive restamping.
+    let mut file = MapDeltaFile::Ddm(Ddm {
+        tail: vec![0xAA; 12],
+        ..Default::default()
+    });
+    file.set_last_visit(-1);
+    let MapDeltaFile::Ddm(ddm) = &file else { unreachable!() };
+    assert_eq!(&ddm.tail[..8], &[0xFF; 8]);
+    assert_eq!(&ddm.tail[8..], &[0xAA; 4]);
+}
+
+#[test]
+fn ddm_rejects_truncated_sections() {
+    assert!(Ddm::try_from(&[0u8; 10][..]).is_err());
+    let mut data = vec![0u8; 200];
+    data.extend_from_slice(&1000u32.to_le_bytes());
+    assert!(Ddm::try_from(data.as_slice()).is_err());
+}
+
 #[test]
 fn parse_new_sorpigal_actors() {
     let Some(assets) = test_lod() else {
         return;
     };
     let ddm = Ddm::load(&assets, "oute3.odm").unwrap();
-    assert!(!ddm.actors.is_empty(), "oute3 DDM should have actors");
-    for actor in &ddm.actors {
+    assert!(!ddm.delta.actors.is_empty(), "oute3 DDM should have actors");
+    for actor in &ddm.delta.actors {
         assert!(!actor.name.is_empty(), "actor name should not be empty");
     }
 }
@@ -20,7 +207,7 @@ fn actor_monlist_id_is_zero_indexed() {
     };
     let ddm = Ddm::load(&assets, "oute3.odm").unwrap();
     let monlist = crate::assets::dmonlist::MonsterList::load(&assets).unwrap();
-    for actor in &ddm.actors {
+    for actor in &ddm.delta.actors {
         assert!(
             (actor.monlist_id as usize) < monlist.monsters.len(),
             "monlist_id {} out of bounds (monlist has {} entries)",
@@ -37,7 +224,7 @@ fn actor_attributes_typed_access() {
     };
     let ddm = Ddm::load(&assets, "oute3.odm").unwrap();
     // actor_attributes() should parse without panicking
-    for actor in &ddm.actors {
+    for actor in &ddm.delta.actors {
         let _attrs = actor.actor_attributes();
     }
 }
@@ -50,7 +237,7 @@ fn parse_multiple_maps() {
     // These maps are known to have actors
     for map in &["oute3.odm", "oute2.odm"] {
         if let Ok(ddm) = Ddm::load(&assets, map) {
-            assert!(!ddm.actors.is_empty(), "{} should have actors", map);
+            assert!(!ddm.delta.actors.is_empty(), "{} should have actors", map);
         }
     }
 }
@@ -65,6 +252,7 @@ fn oute3_ddm_actors_near_goblin_spawn() {
     let ddm = Ddm::load(&assets, "oute3.odm").unwrap();
     let target = (-13480_i32, -20192_i32);
     let mut nearby: Vec<_> = ddm
+        .delta
         .actors
         .iter()
         .filter(|a| {
@@ -94,3 +282,119 @@ fn oute3_ddm_actors_near_goblin_spawn() {
     }
     println!("Total nearby: {}", nearby.len());
 }
+
+#[test]
+fn mm7_actor_maps_onto_mm6_fields() {
+    let mut raw = vec![0u8; ACTOR_SIZE_MM7];
+    raw[..6].copy_from_slice(b"Archer");
+    raw[0x28..0x2A].copy_from_slice(&44i16.to_le_bytes());
+    raw[0x2C + 0x08] = 12; // level
+    raw[0x2C + 0x34..0x2C + 0x36].copy_from_slice(&200u16.to_le_bytes()); // id
+    raw[0x2C + 0x40..0x2C + 0x44].copy_from_slice(&90i32.to_le_bytes()); // full hp
+    raw[0x88..0x8A].copy_from_slice(&40u16.to_le_bytes()); // radius
+    raw[0x8E..0x90].copy_from_slice(&(-1500i16).to_le_bytes()); // x
+    raw[0xCC..0xCE].copy_from_slice(&31u16.to_le_bytes()); // first sound
+    raw[0xD4..0xDC].copy_from_slice(&1234i64.to_le_bytes()); // buff 0 expiry
+    raw[0x2C4..0x2C8].copy_from_slice(&3i32.to_le_bytes()); // group
+    raw[0x330..0x334].copy_from_slice(&(-1i32).to_le_bytes()); // last attacker
+
+    let actor = Ddm::read_actor_version(&raw, Version::MM7).unwrap();
+    assert_eq!(actor.name, "Archer");
+    assert_eq!(actor.hp, 44);
+    assert_eq!(actor.monlist_id, 199);
+    assert_eq!(actor.common_props.level, 12);
+    assert_eq!(actor.common_props.full_hp, 90);
+    assert_eq!(actor.radius, 40);
+    assert_eq!(actor.position[0], -1500);
+    assert_eq!(actor.sound_ids[0], 31);
+    assert_eq!(actor.spell_buffs[0].expire_time, 1234);
+    assert_eq!(actor.group, 3);
+    assert_eq!(actor.last_attacker, -1);
+
+    raw[0x2C + 0x34..0x2C + 0x36].copy_from_slice(&300u16.to_le_bytes());
+    assert!(Ddm::read_actor_version(&raw, Version::MM7).is_err());
+    assert!(Ddm::read_actor_version(&raw[..ACTOR_SIZE_MM6], Version::MM7).is_err());
+}
+
+#[test]
+fn mm7_actor_round_trips_unmapped_bytes() {
+    let mut raw: Vec<u8> = (0..ACTOR_SIZE_MM7).map(|i| (i * 7 + 3) as u8).collect();
+    raw[0x2C + 0x34..0x2C + 0x36].copy_from_slice(&17u16.to_le_bytes()); // id
+    let mut actor = Ddm::read_actor_version(&raw, Version::MM7).unwrap();
+    assert_eq!(actor.to_bytes_version(Version::MM7), raw);
+
+    actor.hp = -5;
+    actor.position[2] = 640;
+    raw[0x28..0x2A].copy_from_slice(&(-5i16).to_le_bytes());
+    raw[0x92..0x94].copy_from_slice(&640i16.to_le_bytes());
+    assert_eq!(actor.to_bytes_version(Version::MM7), raw);
+    assert_eq!(actor.to_bytes_version(Version::MM6).len(), ACTOR_SIZE_MM6);
+}
+
+#[test]
+fn synthetic_mm7_ddm_round_trip() {
+    let mut chest = MapChest::default();
+    chest.items[3] = ItemRecord {
+        number: 12,
+        expire_time: 0x7777,
+        ..Default::default()
+    };
+    let ddm = Ddm {
+        version: Version::MM7,
+        header: LocationHeader {
+            respawn_count: 2,
+            reputation: -40,
+            bmodel_count: 9,
+            ..Default::default()
+        },
+        revealed_cells: vec![0x5A; 2 * 88 * 11],
+        face_attributes: vec![0x0200_0001, 0x2000],
+        decoration_flags: vec![3],
+        map_vars: vec![1; MAP_VARS_SIZE],
+        delta: MapDelta {
+            objects: vec![MapObject {
+                object_type: 800,
+                item: ItemRecord {
+                    number: 7,
+                    expire_time: -1,
+                    ..Default::default()
+                },
+                _unknown_mm7: 0x1234,
+                start_position: [4, 5, 6],
+                ..Default::default()
+            }],
+            chests: vec![chest],
+            actors: vec![DdmActor {
+                name: "Lich".into(),
+                hp: 300,
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        tail: vec![0xCD; 56],
+    };
+
+    let bytes = ddm.to_bytes();
+    assert_eq!(
+        bytes.len(),
+        40 + 1936 + 2 * 4 + 2 + 4 + 836 + 4 + 112 + 4 + 5324 + 200 + 56
+    );
+    let parsed = Ddm::parse_version(&bytes, Version::MM7).unwrap();
+    assert_eq!(parsed.to_bytes(), bytes);
+    assert_eq!(parsed.header.face_count, 2);
+    assert_eq!(parsed.header.decoration_count, 1);
+    assert_eq!(parsed.header.reputation, -40);
+    assert_eq!(parsed.face_attributes, vec![0x0200_0001, 0x2000]);
+    assert_eq!(parsed.delta.objects[0].item.expire_time, -1);
+    assert_eq!(parsed.delta.objects[0]._unknown_mm7, 0x1234);
+    assert_eq!(parsed.delta.objects[0].start_position, [4, 5, 6]);
+    assert_eq!(parsed.delta.chests[0].items[3].expire_time, 0x7777);
+    assert_eq!(parsed.delta.actors[0].name, "Lich");
+    assert_eq!(parsed.delta.actors[0].hp, 300);
+    assert_eq!(parsed.map_vars, vec![1; MAP_VARS_SIZE]);
+    assert_eq!(parsed.tail, vec![0xCD; 56]);
+
+    // The same bytes are not a valid MM6 file, and MM8 is not understood.
+    assert!(Ddm::parse_version(&bytes, Version::MM6).is_err());
+    assert!(Ddm::parse_version(&bytes, Version::MM8).is_err());
+}
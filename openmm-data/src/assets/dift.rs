@@ -10,9 +10,9 @@
 
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::{Cursor, Read};
 
+use crate::Error;
 use crate::LodSerialise;
 use crate::{Assets, assets::lod_data::LodData, utils::try_read_name};
 
@@ -47,12 +47,12 @@ pub struct IFT {
 }
 
 impl IFT {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/dift.bin")?;
         Self::try_from(raw.as_slice())
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         let count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut frames = Vec::with_capacity(count);
@@ -98,7 +98,7 @@ impl IFT {
 }
 
 impl TryFrom<&[u8]> for IFT {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -1,13 +1,14 @@
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
-use crate::assets::blv::{BlvDoor, DoorState};
-use crate::assets::ddm::{Ddm, DdmActor};
-use crate::assets::lod_data::LodData;
+use crate::Version;
+use crate::assets::blv::{Blv, BlvDoor, DoorState};
+use crate::assets::ddm::check_delta_version;
+use crate::assets::map_delta::{LOCATION_HEADER_SIZE, LocationHeader, MAP_VARS_SIZE, MapDelta};
 
 fn skip_slice(offset: &mut usize, count: usize) {
     *offset += count;
@@ -23,22 +24,171 @@ fn skip_slice(offset: &mut usize, count: usize) {
 ///   5. MapObjects      — u32 count + count × 0x64 bytes
 ///   6. MapSprites      — u32 count + count × 0x1C bytes
 ///   7. SoundSprites    — 10 × i32
-///   8. MapChests       — u32 count + count × ~4204 bytes
+///   8. MapChests       — u32 count + count × 0x106C bytes
 ///   9. MapMonsters     — u32 count + count × 548 bytes
 ///  10. DoorHeaders     — door_count × 80 bytes (presized)
 ///  11. DoorsData       — doors_data_size bytes blob (presized)
+///  12. MapVars         — 200 × u8
+///  13. LocationTime    — remaining bytes
 ///
-/// **Save support:** only actors (9) and doors (10–11) are currently parsed; sections
-/// 1–8 are skipped. Round-trip saving requires storing all sections' raw bytes and
-/// re-serialising in the exact same order with correct C struct layout.
-#[derive(Debug, Serialize, Deserialize)]
+/// Sections 5–9 are shared with DDM and parsed by [`MapDelta`]. `doors` is a
+/// decoded view of sections 10–11; serialisation writes `door_headers` and
+/// `doors_data`, so `to_bytes` reproduces the input exactly.
+///
+/// MM7 files open with a 40-byte [`LocationHeader`] and 875 bytes of automap
+/// outline bits, store face attributes as u32, have no FaceExtras or FaceData,
+/// and order the [`MapDelta`] sections differently. Doors, MapVars and
+/// LocationTime follow as in MM6.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Dlv {
-    pub actors: Vec<DdmActor>,
+    /// Layout the file was read in, and is written back in.
+    #[serde(skip)]
+    pub version: Version,
+    /// MM7+ header. Its counts are rewritten from the sections on save.
+    pub header: LocationHeader,
+    /// MM7+ automap outline bits, kept verbatim.
+    pub visible_outlines: Vec<u8>,
+    /// Saved face attributes: the low 16 bits in MM6, the full word in MM7.
+    pub face_attributes: Vec<u32>,
+    pub decoration_flags: Vec<u16>,
+    pub face_extras: Vec<u32>,
+    pub face_data: Vec<u8>,
+    #[serde(flatten)]
+    pub delta: MapDelta,
+    pub door_headers: Vec<DlvDoorHeader>,
+    pub doors_data: Vec<i16>,
+    /// MapVars (200 bytes when present).
+    pub map_vars: Vec<u8>,
+    /// Location time block, kept verbatim.
+    pub location_time: Vec<u8>,
     pub doors: Vec<BlvDoor>,
 }
 
+/// Section sizes a DLV does not store itself. They come from the matching BLV.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DlvLayout {
+    pub face_count: usize,
+    pub decoration_count: usize,
+    pub face_extras_count: usize,
+    pub face_data_size: usize,
+    pub door_count: usize,
+    pub doors_data_size: usize,
+}
+
+impl DlvLayout {
+    pub fn from_blv(blv: &Blv) -> Self {
+        Self {
+            face_count: blv.faces.len(),
+            decoration_count: blv.decorations.len(),
+            face_extras_count: blv.face_extras.len(),
+            face_data_size: blv.face_data_size.max(0) as usize,
+            door_count: blv.door_count as usize,
+            doors_data_size: blv.doors_data_size.max(0) as usize,
+        }
+    }
+}
+
 /// Door header size: 80 bytes.
 const DOOR_HEADER_SIZE: usize = 80;
+/// Size of the MM7 automap outline section (one bit per BLV map outline).
+const VISIBLE_OUTLINES_SIZE: usize = 875;
+
+/// One 80-byte door header from the DLV door section.
+///
+/// Layout: Bits(4) + Id(4) + TimeSinceTriggered(4) + Direction[3](12, fixed 16.16) +
+/// MoveLength(4) + OpenSpeed(4) + CloseSpeed(4) + runtime pointers(32) +
+/// NumVertices(2) + NumFaces(2) + NumSectors(2) + NumOffsets(2) + State(2) + pad(2)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DlvDoorHeader {
+    pub attributes: u32,
+    pub door_id: u32,
+    pub time_since_triggered: u32,
+    /// Slide direction as 16.16 fixed point.
+    pub direction: [i32; 3],
+    pub move_length: i32,
+    pub open_speed: i32,
+    pub close_speed: i32,
+    /// Runtime pointers into `doors_data`, rebuilt by the engine on load.
+    pub _pointers: [u32; 8],
+    pub num_vertices: u16,
+    pub num_faces: u16,
+    pub num_sectors: u16,
+    pub num_offsets: u16,
+    pub state: u16,
+    pub _pad0x4e: i16,
+}
+
+impl DlvDoorHeader {
+    /// Decode the stored `state` (0=open, 1=closing, 2=closed, 3=opening).
+    pub fn door_state(&self) -> DoorState {
+        match self.state {
+            0 => DoorState::Open,
+            1 => DoorState::Closing,
+            2 => DoorState::Closed,
+            3 => DoorState::Opening,
+            _ => DoorState::Closed,
+        }
+    }
+
+    pub fn set_door_state(&mut self, state: DoorState) {
+        self.state = match state {
+            DoorState::Open => 0,
+            DoorState::Closing => 1,
+            DoorState::Closed => 2,
+            DoorState::Opening => 3,
+        };
+    }
+
+    fn read(c: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        let attributes = c.read_u32::<LittleEndian>()?;
+        let door_id = c.read_u32::<LittleEndian>()?;
+        let time_since_triggered = c.read_u32::<LittleEndian>()?;
+        let mut direction = [0i32; 3];
+        c.read_i32_into::<LittleEndian>(&mut direction)?;
+        let move_length = c.read_i32::<LittleEndian>()?;
+        let open_speed = c.read_i32::<LittleEndian>()?;
+        let close_speed = c.read_i32::<LittleEndian>()?;
+        let mut _pointers = [0u32; 8];
+        c.read_u32_into::<LittleEndian>(&mut _pointers)?;
+        Ok(Self {
+            attributes,
+            door_id,
+            time_since_triggered,
+            direction,
+            move_length,
+            open_speed,
+            close_speed,
+            _pointers,
+            num_vertices: c.read_u16::<LittleEndian>()?,
+            num_faces: c.read_u16::<LittleEndian>()?,
+            num_sectors: c.read_u16::<LittleEndian>()?,
+            num_offsets: c.read_u16::<LittleEndian>()?,
+            state: c.read_u16::<LittleEndian>()?,
+            _pad0x4e: c.read_i16::<LittleEndian>()?,
+        })
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        out.write_u32::<LittleEndian>(self.attributes).unwrap();
+        out.write_u32::<LittleEndian>(self.door_id).unwrap();
+        out.write_u32::<LittleEndian>(self.time_since_triggered).unwrap();
+        for &d in &self.direction {
+            out.write_i32::<LittleEndian>(d).unwrap();
+        }
+        out.write_i32::<LittleEndian>(self.move_length).unwrap();
+        out.write_i32::<LittleEndian>(self.open_speed).unwrap();
+        out.write_i32::<LittleEndian>(self.close_speed).unwrap();
+        for &p in &self._pointers {
+            out.write_u32::<LittleEndian>(p).unwrap();
+        }
+        out.write_u16::<LittleEndian>(self.num_vertices).unwrap();
+        out.write_u16::<LittleEndian>(self.num_faces).unwrap();
+        out.write_u16::<LittleEndian>(self.num_sectors).unwrap();
+        out.write_u16::<LittleEndian>(self.num_offsets).unwrap();
+        out.write_u16::<LittleEndian>(self.state).unwrap();
+        out.write_i16::<LittleEndian>(self._pad0x4e).unwrap();
+    }
+}
 
 impl Dlv {
     /// Parse a DLV file from a LOD archive.
@@ -49,263 +199,142 @@ impl Dlv {
     ///
     /// Similarly, the faceAttributes and decorationFlags sections are presized
     /// (no count prefix) using the BLV's face count and decoration count.
-    pub fn new(assets: &Assets, map_name: &str, door_count: u32, doors_data_size: i32) -> Result<Self, Box<dyn Error>> {
+    pub fn new(assets: &Assets, map_name: &str, door_count: u32, doors_data_size: i32) -> Result<Self, Error> {
         let dlv_name = map_name
             .rsplit_once('.')
             .map(|(base, _)| format!("{}.dlv", base))
             .unwrap_or_else(|| format!("{}.dlv", map_name));
+        check_delta_version("dlv", assets.version()).map_err(|e| e.in_file(&dlv_name))?;
 
         let blv_name = map_name
             .rsplit_once('.')
             .map(|(base, _)| format!("{}.blv", base))
             .unwrap_or_else(|| format!("{}.blv", map_name));
 
-        // We need face_count and decoration_count from BLV to know the presized
-        // section lengths. Parse the BLV to get them.
-        let blv = crate::assets::blv::Blv::load(assets, &blv_name)?;
-        let face_count = blv.faces.len();
-        let decoration_count = blv.decorations.len();
-        let face_extras_count = blv.face_extras.len();
-        let face_data_size = blv.face_data_size;
-
-        let raw = assets.get_bytes(format!("games/{}", dlv_name))?;
-        let data = LodData::try_from(raw.as_slice())?;
-        Self::parse(
-            &data.data,
-            door_count,
-            doors_data_size,
-            face_count,
-            decoration_count,
-            face_extras_count,
-            face_data_size,
-        )
-    }
-
-    fn parse(
-        data: &[u8],
-        door_count: u32,
-        doors_data_size: i32,
-        _face_count: usize,
-        _decoration_count: usize,
-        _face_extras_count: usize,
-        _face_data_size: i32,
-    ) -> Result<Self, Box<dyn Error>> {
-        // MM6 DLV format has additional sections compared to MM7 (face extras, face data blob)
-        // that make exact offset calculation unreliable. Instead, scan backwards from the end
-        // to locate the door and actor sections.
-        //
-        // Known end layout: ... | doors (door_count × 80) | doorsData (doors_data_size) | tail
-        // Tail = PersistentVariables (200 bytes) + LocationTime (~56-136 bytes)
-        //
-        // We locate doors by scanning for valid door headers (non-zero door_id with reasonable
-        // parameters) and verify the doorsData blob follows at the expected offset.
-        // Use heuristic actor scan (works across MM6/MM7 format differences)
-        let actors = Ddm::parse_from_data(data).unwrap_or_default();
-
-        // Locate doors by scanning for valid door headers.
-        // A valid door header has: door_id > 0, move_length > 0, speed > 0, num_vertices > 0.
-        // Door headers are contiguous (door_count × 80 bytes), followed by doorsData.
-        let dc = door_count as usize;
-        let doors = if dc > 0 && doors_data_size > 0 {
-            Self::scan_doors(data, dc, doors_data_size)
-        } else {
-            Vec::new()
+        let blv = Blv::load(assets, &blv_name)?;
+        let layout = DlvLayout {
+            door_count: door_count as usize,
+            doors_data_size: doors_data_size.max(0) as usize,
+            ..DlvLayout::from_blv(&blv)
         };
 
-        Ok(Dlv { actors, doors })
+        let data = assets.get_entry(format!("games/{}", dlv_name))?;
+        Self::parse_version(&data, &layout, assets.version()).map_err(|e| e.in_file(dlv_name))
     }
 
-    /// Scan the DLV data for door headers by finding a valid door and verifying
-    /// it's part of a contiguous 80-byte-aligned array.
-    fn scan_doors(data: &[u8], door_count: usize, doors_data_size: i32) -> Vec<BlvDoor> {
-        let mut best_start: Option<usize> = None;
-
-        // Scan for valid door headers and verify array alignment
-        for offset in 0..data.len().saturating_sub(DOOR_HEADER_SIZE) {
-            if !Self::is_valid_door_header(data, offset) {
-                continue;
-            }
-
-            // Found a candidate. Compute potential array start by trying each slot index.
-            for slot in 0..door_count {
-                let array_start = offset.checked_sub(slot * DOOR_HEADER_SIZE);
-                let Some(start) = array_start else { continue };
-
-                // Verify: the array should fit door_count × 80 bytes + doorsData
-                let array_end = start + door_count * DOOR_HEADER_SIZE;
-                let total_end = array_end + doors_data_size as usize;
-                if total_end > data.len() {
-                    continue;
-                }
-
-                // Count how many slots in this array have valid door headers
-                let valid_count = (0..door_count)
-                    .filter(|&s| Self::is_valid_door_header(data, start + s * DOOR_HEADER_SIZE))
-                    .count();
-
-                // Also check that the doorsData blob has non-zero entries
-                let data_nonzero = (0..((doors_data_size / 2) as usize).min(100)).any(|i| {
-                    let off = array_end + i * 2;
-                    off + 2 <= data.len() && read_u16(data, off) != 0
-                });
-
-                if valid_count >= 1 && data_nonzero {
-                    best_start = Some(start);
-                    break;
-                }
-            }
-
-            if best_start.is_some() {
-                break;
-            }
+    /// Attributes of `face` as saved. An MM6 DLV stores only the low 16 bits,
+    /// which replace the low half of the BLV's `blv_attributes`; the upper
+    /// half (clickable, touch and blocking flags) comes from the BLV. MM7
+    /// stores the whole word.
+    pub fn face_attributes_over(&self, face: usize, blv_attributes: u32) -> u32 {
+        match self.face_attributes.get(face) {
+            Some(&saved) if self.version >= Version::MM7 => saved,
+            Some(&saved) => (blv_attributes & 0xFFFF_0000) | saved,
+            None => blv_attributes,
         }
+    }
 
-        let Some(array_start) = best_start else {
-            // No valid doors found — return empty doors for all slots
-            return (0..door_count)
-                .map(|_| BlvDoor {
-                    attributes: 0,
-                    door_id: 0,
-                    direction: [0.0; 3],
-                    move_length: 0,
-                    open_speed: 0,
-                    close_speed: 0,
-                    vertex_ids: Vec::new(),
-                    face_ids: Vec::new(),
-                    x_offsets: Vec::new(),
-                    y_offsets: Vec::new(),
-                    z_offsets: Vec::new(),
-                    delta_us: Vec::new(),
-                    delta_vs: Vec::new(),
-                    state: DoorState::Open,
-                })
-                .collect();
-        };
-
-        // Parse door headers from the array start
-        let mut door_headers = Vec::with_capacity(door_count);
-        let mut offset = array_start;
-        for _ in 0..door_count {
-            if offset + DOOR_HEADER_SIZE > data.len() {
-                break;
-            }
-            if let Ok(header) = parse_door_header(data, offset) {
-                door_headers.push(header);
-            }
-            offset += DOOR_HEADER_SIZE;
+    /// Store `attributes` as the saved word of `face`: only its low half in MM6.
+    pub fn set_face_attributes(&mut self, face: usize, attributes: u32) {
+        let mm7 = self.version >= Version::MM7;
+        if let Some(saved) = self.face_attributes.get_mut(face) {
+            *saved = if mm7 { attributes } else { attributes & 0xFFFF };
         }
+    }
 
-        // Parse doorsData blob right after the door headers
-        let doors_data_i16_count = (doors_data_size / 2) as usize;
-        if offset + doors_data_i16_count * 2 > data.len() {
-            return partition_door_data(door_headers, &Vec::new());
-        }
-        let mut doors_data = Vec::with_capacity(doors_data_i16_count);
-        let mut cursor = Cursor::new(&data[offset..offset + doors_data_i16_count * 2]);
-        for _ in 0..doors_data_i16_count {
-            if let Ok(v) = cursor.read_i16::<LittleEndian>() {
-                doors_data.push(v);
-            } else {
-                break;
-            }
-        }
+    /// Parse decompressed MM6 DLV data using section sizes from the matching BLV.
+    pub fn parse(data: &[u8], layout: &DlvLayout) -> Result<Self, Error> {
+        Self::parse_version(data, layout, Version::MM6)
+    }
 
-        partition_door_data(door_headers, &doors_data)
+    /// Parse decompressed DLV data laid out for `version`, using section
+    /// sizes from the matching BLV.
+    pub fn parse_version(data: &[u8], layout: &DlvLayout, version: Version) -> Result<Self, Error> {
+        check_delta_version("dlv", version)?;
+        let mut c = Cursor::new(data);
+        Self::read(data, layout, version, &mut c).map_err(|e| e.at("dlv", c.position() as usize))
     }
 
-    fn is_valid_door_header(data: &[u8], offset: usize) -> bool {
-        if offset + DOOR_HEADER_SIZE > data.len() {
-            return false;
+    fn read(data: &[u8], layout: &DlvLayout, version: Version, c: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let mm7 = version >= Version::MM7;
+        let presized = if mm7 {
+            LOCATION_HEADER_SIZE + VISIBLE_OUTLINES_SIZE + layout.face_count * 4 + layout.decoration_count * 2
+        } else {
+            layout.face_count * 2 + layout.decoration_count * 2 + layout.face_extras_count * 4 + layout.face_data_size
+        };
+        if presized > data.len() {
+            return Err(Error::Truncated {
+                format: "dlv",
+                offset: 0,
+                expected: presized,
+            });
         }
-        let door_id = read_u32(data, offset + 4);
-        if door_id == 0 || door_id > 200 {
-            return false;
+        let mut header = LocationHeader::default();
+        let mut visible_outlines = Vec::new();
+        let mut face_attributes = vec![0u32; layout.face_count];
+        let mut face_extras = Vec::new();
+        let mut face_data = Vec::new();
+        if mm7 {
+            header = LocationHeader::read(c)?;
+            visible_outlines = vec![0u8; VISIBLE_OUTLINES_SIZE];
+            c.read_exact(&mut visible_outlines)?;
+            c.read_u32_into::<LittleEndian>(&mut face_attributes)?;
+        } else {
+            let mut words = vec![0u16; layout.face_count];
+            c.read_u16_into::<LittleEndian>(&mut words)?;
+            face_attributes = words.into_iter().map(u32::from).collect();
+        }
+        let mut decoration_flags = vec![0u16; layout.decoration_count];
+        c.read_u16_into::<LittleEndian>(&mut decoration_flags)?;
+        if !mm7 {
+            face_extras = vec![0u32; layout.face_extras_count];
+            c.read_u32_into::<LittleEndian>(&mut face_extras)?;
+            face_data = vec![0u8; layout.face_data_size];
+            c.read_exact(&mut face_data)?;
         }
-        let move_length = read_i32(data, offset + 24);
-        let open_speed = read_i32(data, offset + 28);
-        let close_speed = read_i32(data, offset + 32);
-        let num_vertices = read_u16(data, offset + 68);
-        let num_faces = read_u16(data, offset + 70);
-        move_length > 0
-            && move_length < 10000
-            && open_speed > 0
-            && open_speed < 10000
-            && close_speed > 0
-            && close_speed < 10000
-            && num_vertices > 0
-            && num_vertices < 200
-            && num_faces > 0
-            && num_faces < 200
-    }
-}
 
-/// Intermediate door header parsed from the 80-byte block.
-struct DoorHeader {
-    attributes: u32,
-    door_id: u32,
-    direction: [f32; 3],
-    move_length: i32,
-    open_speed: i32,
-    close_speed: i32,
-    num_vertices: u16,
-    num_faces: u16,
-    num_sectors: u16,
-    num_offsets: u16,
-    state: u16,
-}
+        let delta = MapDelta::read(c, version)?;
 
-fn read_u32(data: &[u8], offset: usize) -> u32 {
-    u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
-}
+        let doors_size = layout.door_count * DOOR_HEADER_SIZE + layout.doors_data_size;
+        Error::check_len("dlv", data, c.position() as usize, doors_size)?;
+        let mut door_headers = Vec::with_capacity(layout.door_count);
+        for _ in 0..layout.door_count {
+            door_headers.push(DlvDoorHeader::read(c)?);
+        }
+        let mut doors_data = vec![0i16; layout.doors_data_size / 2];
+        c.read_i16_into::<LittleEndian>(&mut doors_data)?;
 
-fn read_i32(data: &[u8], offset: usize) -> i32 {
-    i32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
-}
+        let rest = &data[c.position() as usize..];
+        let split = rest.len().min(MAP_VARS_SIZE);
+        let map_vars = rest[..split].to_vec();
+        let location_time = rest[split..].to_vec();
 
-fn read_u16(data: &[u8], offset: usize) -> u16 {
-    u16::from_le_bytes([data[offset], data[offset + 1]])
-}
+        let doors = partition_door_data(&door_headers, &doors_data);
 
-fn parse_door_header(data: &[u8], base: usize) -> Result<DoorHeader, Box<dyn Error>> {
-    let attributes = read_u32(data, base);
-    let door_id = read_u32(data, base + 4);
-    // base + 8: time_since_triggered (u32, runtime — skip)
-    // base + 12: direction[3] as i32 fixed-point 16.16
-    let dir_x = read_i32(data, base + 12) as f32 / 65536.0;
-    let dir_y = read_i32(data, base + 16) as f32 / 65536.0;
-    let dir_z = read_i32(data, base + 20) as f32 / 65536.0;
-    let move_length = read_i32(data, base + 24);
-    let open_speed = read_i32(data, base + 28);
-    let close_speed = read_i32(data, base + 32);
-    // base + 36: 8 runtime pointers (32 bytes) — skip
-    // base + 68: num_vertices(u16), num_faces(u16), num_sectors(u16), num_offsets(u16)
-    let num_vertices = read_u16(data, base + 68);
-    let num_faces = read_u16(data, base + 70);
-    let num_sectors = read_u16(data, base + 72);
-    let num_offsets = read_u16(data, base + 74);
-    // base + 76: state(u16), padding(i16)
-    let state = read_u16(data, base + 76);
-
-    Ok(DoorHeader {
-        attributes,
-        door_id,
-        direction: [dir_x, dir_y, dir_z],
-        move_length,
-        open_speed,
-        close_speed,
-        num_vertices,
-        num_faces,
-        num_sectors,
-        num_offsets,
-        state,
-    })
+        Ok(Dlv {
+            version,
+            header,
+            visible_outlines,
+            face_attributes,
+            decoration_flags,
+            face_extras,
+            face_data,
+            delta,
+            door_headers,
+            doors_data,
+            map_vars,
+            location_time,
+            doors,
+        })
+    }
 }
 
-fn partition_door_data(headers: Vec<DoorHeader>, blob: &[i16]) -> Vec<BlvDoor> {
+fn partition_door_data(headers: &[DlvDoorHeader], blob: &[i16]) -> Vec<BlvDoor> {
     let mut offset = 0;
     let mut doors = Vec::with_capacity(headers.len());
 
     for h in headers {
+        let direction = h.direction.map(|d| d as f32 / 65536.0);
         let nv = h.num_vertices as usize;
         let nf = h.num_faces as usize;
         let ns = h.num_sectors as usize;
@@ -328,7 +357,7 @@ fn partition_door_data(headers: Vec<DoorHeader>, blob: &[i16]) -> Vec<BlvDoor> {
             doors.push(BlvDoor {
                 attributes: h.attributes,
                 door_id: h.door_id,
-                direction: h.direction,
+                direction,
                 move_length: h.move_length,
                 open_speed: h.open_speed,
                 close_speed: h.close_speed,
@@ -356,18 +385,10 @@ fn partition_door_data(headers: Vec<DoorHeader>, blob: &[i16]) -> Vec<BlvDoor> {
         let y_offsets = read_slice_i16(blob, &mut offset, no, end);
         let z_offsets = read_slice_i16(blob, &mut offset, no, end);
 
-        let state = match h.state {
-            0 => DoorState::Open,
-            1 => DoorState::Closing,
-            2 => DoorState::Closed,
-            3 => DoorState::Opening,
-            _ => DoorState::Closed,
-        };
-
         doors.push(BlvDoor {
             attributes: h.attributes,
             door_id: h.door_id,
-            direction: h.direction,
+            direction,
             move_length: h.move_length,
             open_speed: h.open_speed,
             close_speed: h.close_speed,
@@ -378,7 +399,7 @@ fn partition_door_data(headers: Vec<DoorHeader>, blob: &[i16]) -> Vec<BlvDoor> {
             z_offsets,
             delta_us,
             delta_vs,
-            state,
+            state: h.door_state(),
         });
     }
 
@@ -399,37 +420,187 @@ fn read_slice_i16(blob: &[i16], offset: &mut usize, count: usize, end: usize) ->
     blob[start..start + actual].to_vec()
 }
 
-impl TryFrom<&[u8]> for Dlv {
-    type Error = Box<dyn Error>;
-
-    /// NOTE: DLV files are not self-contained and require BLV metadata (door count, etc.)
-    /// for a full parse. This implementation uses heuristic actor scanning but may
-    /// result in empty doors if no metadata is available.
-    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
-        let data = LodData::try_from(data)?;
-        let actors = Ddm::parse_from_data(&data.data).unwrap_or_default();
-        // Doors cannot be reliably parsed without BLV metadata.
-        Ok(Dlv {
-            actors,
-            doors: Vec::new(),
-        })
-    }
-}
-
 impl LodSerialise for Dlv {
     fn to_bytes(&self) -> Vec<u8> {
-        // TODO: Implement full DLV serialization if needed.
-        // For now, this is a placeholder to satisfy the trait.
-        Vec::new()
+        let mut out = Vec::new();
+        let mm7 = self.version >= Version::MM7;
+        if mm7 {
+            LocationHeader {
+                face_count: self.face_attributes.len() as u32,
+                decoration_count: self.decoration_flags.len() as u32,
+                ..self.header
+            }
+            .write(&mut out);
+            out.extend((0..VISIBLE_OUTLINES_SIZE).map(|i| self.visible_outlines.get(i).copied().unwrap_or(0)));
+            for &a in &self.face_attributes {
+                out.write_u32::<LittleEndian>(a).unwrap();
+            }
+        } else {
+            for &a in &self.face_attributes {
+                out.write_u16::<LittleEndian>(a as u16).unwrap();
+            }
+        }
+        for &f in &self.decoration_flags {
+            out.write_u16::<LittleEndian>(f).unwrap();
+        }
+        if !mm7 {
+            for &e in &self.face_extras {
+                out.write_u32::<LittleEndian>(e).unwrap();
+            }
+            out.extend_from_slice(&self.face_data);
+        }
+        self.delta.write(&mut out, self.version);
+        for header in &self.door_headers {
+            header.write(&mut out);
+        }
+        for &d in &self.doors_data {
+            out.write_i16::<LittleEndian>(d).unwrap();
+        }
+        out.extend_from_slice(&self.map_vars);
+        out.extend_from_slice(&self.location_time);
+        out
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assets::blv::Blv;
+    use crate::assets::ddm::DdmActor;
+    use crate::assets::map_delta::MapChest;
     use crate::assets::test_lod;
 
+    #[test]
+    fn door_header_state_round_trip() {
+        let mut header = DlvDoorHeader::default();
+        for state in [
+            DoorState::Open,
+            DoorState::Closing,
+            DoorState::Closed,
+            DoorState::Opening,
+        ] {
+            header.set_door_state(state);
+            assert_eq!(header.door_state(), state);
+        }
+        header.set_door_state(DoorState::Closed);
+        assert_eq!(header.state, 2);
+    }
+
+    #[test]
+    fn synthetic_dlv_round_trip() {
+        let layout = DlvLayout {
+            face_count: 3,
+            decoration_count: 2,
+            face_extras_count: 1,
+            face_data_size: 6,
+            door_count: 1,
+            doors_data_size: 10,
+        };
+        let mut dlv = Dlv {
+            face_attributes: vec![1, 2, 3],
+            decoration_flags: vec![4, 5],
+            face_extras: vec![6],
+            face_data: vec![7; 6],
+            door_headers: vec![DlvDoorHeader {
+                door_id: 3,
+                direction: [65536, 0, 0],
+                num_vertices: 1,
+                num_faces: 1,
+                num_sectors: 1,
+                state: 2,
+                ..Default::default()
+            }],
+            doors_data: vec![10, 20, 30, 40, 50],
+            map_vars: vec![9; MAP_VARS_SIZE],
+            location_time: vec![1, 2, 3, 4],
+            ..Default::default()
+        };
+        dlv.delta.chests.push(MapChest::default());
+        dlv.delta.actors.push(DdmActor {
+            name: "Goblin".into(),
+            ..Default::default()
+        });
+
+        let bytes = dlv.to_bytes();
+        let parsed = Dlv::parse(&bytes, &layout).unwrap();
+        assert_eq!(parsed.to_bytes(), bytes);
+        assert_eq!(parsed.face_attributes, vec![1, 2, 3]);
+        assert_eq!(parsed.delta.actors[0].name, "Goblin");
+        assert_eq!(parsed.map_vars.len(), MAP_VARS_SIZE);
+        assert_eq!(parsed.location_time, vec![1, 2, 3, 4]);
+
+        let door = &parsed.doors[0];
+        assert_eq!(door.direction, [1.0, 0.0, 0.0]);
+        assert_eq!(door.vertex_ids, vec![10]);
+        assert_eq!(door.face_ids, vec![20]);
+        assert_eq!(door.delta_us, vec![40]);
+        assert_eq!(door.delta_vs, vec![50]);
+        assert_eq!(door.state, DoorState::Closed);
+
+        assert!(Dlv::parse(&bytes[..bytes.len() - 220], &layout).is_err());
+    }
+
+    #[test]
+    fn synthetic_mm7_dlv_round_trip() {
+        let layout = DlvLayout {
+            face_count: 2,
+            decoration_count: 1,
+            // MM7 DLVs have no FaceExtras/FaceData sections.
+            face_extras_count: 5,
+            face_data_size: 9,
+            door_count: 1,
+            doors_data_size: 4,
+        };
+        let mut dlv = Dlv {
+            version: Version::MM7,
+            visible_outlines: vec![0xF0; VISIBLE_OUTLINES_SIZE],
+            face_attributes: vec![0x0200_2001, 4],
+            decoration_flags: vec![8],
+            door_headers: vec![DlvDoorHeader {
+                door_id: 1,
+                state: 3,
+                ..Default::default()
+            }],
+            doors_data: vec![11, 22],
+            map_vars: vec![2; MAP_VARS_SIZE],
+            location_time: vec![7; 56],
+            ..Default::default()
+        };
+        dlv.delta.chests.push(MapChest::default());
+        dlv.delta.actors.push(DdmActor {
+            name: "Guard".into(),
+            ..Default::default()
+        });
+
+        let bytes = dlv.to_bytes();
+        assert_eq!(
+            bytes.len(),
+            40 + 875 + 2 * 4 + 2 + 4 + 836 + 4 + 4 + 5324 + 80 + 4 + 200 + 56
+        );
+        let parsed = Dlv::parse_version(&bytes, &layout, Version::MM7).unwrap();
+        assert_eq!(parsed.to_bytes(), bytes);
+        assert_eq!(parsed.header.face_count, 2);
+        assert_eq!(parsed.delta.actors[0].name, "Guard");
+        assert_eq!(parsed.door_headers[0].door_state(), DoorState::Opening);
+        assert_eq!(parsed.location_time, vec![7; 56]);
+        // MM7 saves the whole attribute word, high half included.
+        assert_eq!(parsed.face_attributes_over(0, 0x0000_0001), 0x0200_2001);
+    }
+
+    #[test]
+    fn face_attributes_keep_blv_high_half() {
+        let mut dlv = Dlv {
+            face_attributes: vec![0; 2],
+            ..Default::default()
+        };
+        let blv_attrs = 0x0200_0001; // CLICKABLE | PORTAL
+        dlv.set_face_attributes(1, blv_attrs | 0x2000);
+        assert_eq!(dlv.face_attributes, vec![0, 0x2001]);
+        assert_eq!(dlv.face_attributes_over(1, blv_attrs), 0x0200_2001);
+        assert_eq!(dlv.face_attributes_over(0, blv_attrs), 0x0200_0000);
+        // Faces past the saved section keep their BLV attributes.
+        assert_eq!(dlv.face_attributes_over(5, blv_attrs), blv_attrs);
+    }
+
     #[test]
     fn scan_all_dlv_actors() {
         let Some(assets) = test_lod() else { return };
@@ -441,12 +612,12 @@ mod tests {
             let Ok(dlv) = Dlv::new(&assets, &blv_name, blv.door_count, blv.doors_data_size) else {
                 continue;
             };
-            let monsters: Vec<_> = dlv.actors.iter().filter(|a| a.npc_id == 0).collect();
-            let npcs: Vec<_> = dlv.actors.iter().filter(|a| a.npc_id != 0).collect();
+            let monsters: Vec<_> = dlv.delta.actors.iter().filter(|a| a.npc_id == 0).collect();
+            let npcs: Vec<_> = dlv.delta.actors.iter().filter(|a| a.npc_id != 0).collect();
             println!(
                 "{}: {} actors ({} monsters, {} npcs)",
                 name,
-                dlv.actors.len(),
+                dlv.delta.actors.len(),
                 monsters.len(),
                 npcs.len()
             );
@@ -473,7 +644,7 @@ mod tests {
             blv.doors_data_size,
             blv.face_extras.len()
         );
-        println!("d01.dlv: {} actors, {} doors", dlv.actors.len(), dlv.doors.len());
+        println!("d01.dlv: {} actors, {} doors", dlv.delta.actors.len(), dlv.doors.len());
         let nonempty: Vec<_> = dlv
             .doors
             .iter()
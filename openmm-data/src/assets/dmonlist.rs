@@ -1,12 +1,18 @@
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
+use crate::Error;
 use crate::LodSerialise;
+use crate::Version;
 use crate::{Assets, assets::lod_data::LodData};
 
 const RECORD_SIZE: usize = 148;
+/// MM7+ records insert a u32 tint colour at 0x08.
+const RECORD_SIZE_MM7: usize = 152;
 
 /// A monster/NPC description from dmonlist.bin.
+///
+/// Offsets below are MM6's. MM7+ inserts `tint` at 0x08, moving everything
+/// after it by 4.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonsterDesc {
     /// Sprite height in MM6 units. Offset 0x00.
@@ -18,6 +24,9 @@ pub struct MonsterDesc {
     /// Bytes 6-7. Always 0 in MM6 (this is the MM7+ `Radius2` field). Do not use for attack range.
     /// Use `radius * 2` for melee reach instead. Offset 0x06.
     pub to_hit_radius: u16,
+    /// Colour multiplied into the sprite. MM7+ only, 0 for MM6.
+    #[serde(default)]
+    pub tint: u32,
     /// Sound IDs: [0]=attack, [1]=die, [2]=got_hit, [3]=fidget. Offset 0x08.
     pub sound_ids: [u16; 4],
     /// Internal monster name from dmonlist.bin, e.g. "GoblinA". Null-terminated, 32 bytes. Offset 0x10.
@@ -35,35 +44,49 @@ pub struct MonsterList {
 }
 
 impl MonsterList {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
-        let raw = assets.get_bytes("icons/dmonlist.bin")?;
-        Self::try_from(raw.as_slice())
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
+        let data = assets.get_entry("icons/dmonlist.bin")?;
+        Self::parse_version(&data, assets.version())
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
-        if data.len() < 4 {
-            return Err("dmonlist.bin too short".into());
-        }
+    /// Parse an MM6 monster list.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        Self::parse_version(data, Version::MM6)
+    }
+
+    /// Parse a monster list laid out for `version`.
+    pub fn parse_version(data: &[u8], version: Version) -> Result<Self, Error> {
+        Error::check_len("dmonlist", data, 0, 4)?;
+        let record_size = if version >= Version::MM7 {
+            RECORD_SIZE_MM7
+        } else {
+            RECORD_SIZE
+        };
         let count = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
-        if data.len() < 4 + count * RECORD_SIZE {
-            return Err("dmonlist.bin truncated".into());
-        }
+        Error::check_len("dmonlist", data, 4, count * record_size)?;
 
         let mut monsters = Vec::with_capacity(count);
         for i in 0..count {
-            let off = 4 + i * RECORD_SIZE;
-            let rec = &data[off..off + RECORD_SIZE];
-            monsters.push(Self::parse_record(rec));
+            let off = 4 + i * record_size;
+            let rec = &data[off..off + record_size];
+            monsters.push(Self::parse_record(rec, record_size - RECORD_SIZE));
         }
 
         Ok(MonsterList { monsters })
     }
 
-    fn parse_record(rec: &[u8]) -> MonsterDesc {
+    /// Decode one record; `shift` is the size of the MM7 tint field (0 for MM6).
+    fn parse_record(rec: &[u8], shift: usize) -> MonsterDesc {
         let height = u16::from_le_bytes([rec[0], rec[1]]);
         let radius = u16::from_le_bytes([rec[2], rec[3]]);
         let move_speed = u16::from_le_bytes([rec[4], rec[5]]);
         let to_hit_radius = u16::from_le_bytes([rec[6], rec[7]]);
+        let tint = if shift == 4 {
+            u32::from_le_bytes([rec[8], rec[9], rec[10], rec[11]])
+        } else {
+            0
+        };
+        let rec = &rec[shift..];
         let sound_ids = [
             u16::from_le_bytes([rec[8], rec[9]]),
             u16::from_le_bytes([rec[10], rec[11]]),
@@ -92,6 +115,7 @@ impl MonsterList {
             radius,
             move_speed,
             to_hit_radius,
+            tint,
             sound_ids,
             internal_name,
             sprite_names,
@@ -127,6 +151,11 @@ impl MonsterList {
     /// Find a monster by internal name prefix + difficulty (1=A, 2=B, 3=C).
     /// Tries the requested variant first, then falls back to A, B, C.
     pub fn find_by_name(&self, name: &str, difficulty: u8) -> Option<&MonsterDesc> {
+        self.find_index_by_name(name, difficulty).map(|i| &self.monsters[i])
+    }
+
+    /// Same lookup as [`find_by_name`](Self::find_by_name), returning the 0-based monlist index.
+    pub fn find_index_by_name(&self, name: &str, difficulty: u8) -> Option<usize> {
         let preferred = match difficulty {
             1 => "A",
             2 => "B",
@@ -135,12 +164,12 @@ impl MonsterList {
         // Try preferred variant first, then all variants
         for suffix in &[preferred, "A", "B", "C"] {
             let target = format!("{}{}", name, suffix);
-            if let Some(m) = self
+            if let Some(i) = self
                 .monsters
                 .iter()
-                .find(|m| m.internal_name.eq_ignore_ascii_case(&target))
+                .position(|m| m.internal_name.eq_ignore_ascii_case(&target))
             {
-                return Some(m);
+                return Some(i);
             }
         }
         None
@@ -189,7 +218,7 @@ impl MonsterList {
 }
 
 impl TryFrom<&[u8]> for MonsterList {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -197,6 +226,7 @@ impl TryFrom<&[u8]> for MonsterList {
     }
 }
 
+// Always writes the MM6 layout.
 impl LodSerialise for MonsterList {
     /// Serialize the full monster list back to dmonlist.bin binary format.
     fn to_bytes(&self) -> Vec<u8> {
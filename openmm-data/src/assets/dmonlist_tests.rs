@@ -151,3 +151,23 @@ fn ghost_walking_and_standing_palette_match() {
         }
     }
 }
+
+#[test]
+fn parses_mm7_records() {
+    let mut rec = vec![0u8; 152];
+    rec[..2].copy_from_slice(&120u16.to_le_bytes());
+    rec[8..12].copy_from_slice(&0xFF80_4020u32.to_le_bytes());
+    rec[12..14].copy_from_slice(&7u16.to_le_bytes());
+    rec[20..27].copy_from_slice(b"GoblinA");
+    rec[52..57].copy_from_slice(b"GobSt");
+    let mut data = 1u32.to_le_bytes().to_vec();
+    data.extend(&rec);
+
+    let list = MonsterList::parse_version(&data, crate::Version::MM7).unwrap();
+    let m = &list.monsters[0];
+    assert_eq!(m.height, 120);
+    assert_eq!(m.tint, 0xFF80_4020);
+    assert_eq!(m.sound_ids[0], 7);
+    assert_eq!(m.internal_name, "GoblinA");
+    assert_eq!(m.sprite_names[0], "gobst");
+}
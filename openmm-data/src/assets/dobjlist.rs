@@ -1,9 +1,9 @@
 use bitflags::bitflags;
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::{Cursor, Read};
 
+use crate::Error;
 use crate::LodSerialise;
 use crate::{Assets, assets::lod_data::LodData};
 
@@ -61,12 +61,12 @@ pub struct ObjectList {
 }
 
 impl ObjectList {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/dobjlist.bin")?;
         Self::try_from(raw.as_slice())
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         let count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut objects = Vec::with_capacity(count);
@@ -116,7 +116,7 @@ impl ObjectList {
 }
 
 impl TryFrom<&[u8]> for ObjectList {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -1,8 +1,8 @@
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::Cursor;
 
+use crate::Error;
 use crate::LodSerialise;
 use crate::{Assets, assets::lod_data::LodData};
 
@@ -25,12 +25,12 @@ pub struct OverlayList {
 }
 
 impl OverlayList {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/doverlay.bin")?;
         Self::try_from(raw.as_slice())
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         let count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut overlays = Vec::with_capacity(count);
@@ -53,7 +53,7 @@ impl OverlayList {
 }
 
 impl TryFrom<&[u8]> for OverlayList {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
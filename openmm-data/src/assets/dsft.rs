@@ -1,9 +1,10 @@
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::{Cursor, Read};
 
+use crate::Error;
 use crate::LodSerialise;
+use crate::Version;
 use crate::{Assets, assets::enums::SpriteFrameFlags, assets::lod_data::LodData, utils::try_read_name};
 
 /// Sprite Frame Table: maps animation group names to sprites and frame timing.
@@ -23,6 +24,10 @@ pub struct DSFT {
 ///   0x2C: attributes(u16), 0x2E: light_radius(i16),
 ///   0x30: palette_id(i16), 0x32: palette_index(i16),
 ///   0x34: time(i16), 0x36: time_total(i16)
+///
+/// MM7+ records are 60 bytes: attributes widens to u32 (only the low 16 bits
+/// carry flags), the fields after it move by 2 and 2 bytes of padding end
+/// the record.
 #[allow(dead_code)]
 #[repr(C)]
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -144,12 +149,18 @@ impl DSFT {
         1.0
     }
 
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
-        let raw = assets.get_bytes("icons/dsft.bin")?;
-        Self::try_from(raw.as_slice())
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
+        let data = assets.get_entry("icons/dsft.bin")?;
+        Self::parse_version(&data, assets.version())
+    }
+
+    /// Parse an MM6 sprite frame table.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        Self::parse_version(data, Version::MM6)
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    /// Parse a sprite frame table laid out for `version`.
+    pub fn parse_version(data: &[u8], version: Version) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
 
         let mut frames = Vec::new();
@@ -159,8 +170,13 @@ impl DSFT {
 
         for _ in 0..frame_count {
             let mut frame = DSFTFrame::default();
-            cursor
-                .read_exact(unsafe { std::slice::from_raw_parts_mut(&mut frame as *mut _ as *mut u8, frame_size) })?;
+            if version >= Version::MM7 {
+                frame = Self::read_frame_mm7(&mut cursor)?;
+            } else {
+                cursor.read_exact(unsafe {
+                    std::slice::from_raw_parts_mut(&mut frame as *mut _ as *mut u8, frame_size)
+                })?;
+            }
             frames.push(frame);
         }
 
@@ -172,10 +188,27 @@ impl DSFT {
 
         Ok(Self { frames, groups })
     }
+
+    /// Read one 60-byte MM7+ frame.
+    fn read_frame_mm7(c: &mut Cursor<&[u8]>) -> std::io::Result<DSFTFrame> {
+        let mut frame = DSFTFrame::default();
+        c.read_exact(&mut frame.group_name)?;
+        c.read_exact(&mut frame.sprite_name)?;
+        c.read_i16_into::<LittleEndian>(&mut frame.sprite_index)?;
+        frame.scale = c.read_i32::<LittleEndian>()?;
+        frame.attributes = c.read_u32::<LittleEndian>()? as u16;
+        frame.light_radius = c.read_i16::<LittleEndian>()?;
+        frame.palette_id = c.read_i16::<LittleEndian>()?;
+        frame.palette_index = c.read_i16::<LittleEndian>()?;
+        frame.time = c.read_i16::<LittleEndian>()?;
+        frame.time_total = c.read_i16::<LittleEndian>()?;
+        c.read_u16::<LittleEndian>()?;
+        Ok(frame)
+    }
 }
 
 impl TryFrom<&[u8]> for DSFT {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -183,6 +216,7 @@ impl TryFrom<&[u8]> for DSFT {
     }
 }
 
+// Always writes the MM6 layout.
 impl LodSerialise for DSFT {
     fn to_bytes(&self) -> Vec<u8> {
         use byteorder::{LittleEndian, WriteBytesExt};
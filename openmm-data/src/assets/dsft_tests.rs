@@ -328,3 +328,29 @@ fn monster_variants_resolve_through_dsft() {
         }
     }
 }
+
+#[test]
+fn parses_mm7_frames() {
+    let mut data = Vec::new();
+    data.extend(1u32.to_le_bytes());
+    data.extend(1u32.to_le_bytes());
+    let mut frame = [0u8; 60];
+    frame[..4].copy_from_slice(b"gob1");
+    frame[12..18].copy_from_slice(b"gob1a0");
+    frame[0x28..0x2C].copy_from_slice(&0x10000i32.to_le_bytes());
+    frame[0x2C..0x30].copy_from_slice(&0x1_0015u32.to_le_bytes());
+    for (i, v) in [64i16, 225, 0, 8, 32].into_iter().enumerate() {
+        frame[0x30 + i * 2..0x32 + i * 2].copy_from_slice(&v.to_le_bytes());
+    }
+    data.extend(frame);
+    data.extend(0u16.to_le_bytes());
+
+    let dsft = DSFT::parse_version(&data, crate::Version::MM7).unwrap();
+    let f = &dsft.frames[0];
+    assert_eq!(f.group_name().as_deref(), Some("gob1"));
+    assert_eq!(f.sprite_name().as_deref(), Some("gob1a0"));
+    assert_eq!(f.scale, 0x10000);
+    assert_eq!(f.attributes, 0x15);
+    assert_eq!((f.light_radius, f.palette_id, f.time, f.time_total), (64, 225, 8, 32));
+    assert_eq!(dsft.groups, [0]);
+}
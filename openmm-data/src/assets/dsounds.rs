@@ -1,12 +1,10 @@
-use std::{
-    error::Error,
-    io::{Cursor, Read},
-};
+use std::io::{Cursor, Read};
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
 use serde::{Deserialize, Serialize};
 
+use crate::Error;
 use crate::{
     Assets, LodSerialise,
     assets::enums::{SoundAttributes, SoundType},
@@ -78,12 +76,12 @@ impl DSoundInfo {
 }
 
 impl DSounds {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/dsounds.bin")?;
         Self::try_from(raw.as_slice())
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         let item_count = cursor.read_u32::<LittleEndian>()?;
         let item_size = std::mem::size_of::<DSoundInfo>();
@@ -112,7 +110,7 @@ impl DSounds {
 }
 
 impl TryFrom<&[u8]> for DSounds {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::Error;
 use crate::LodSerialise;
 use crate::{
     Assets, assets::enums::TileFlags, assets::image::get_atlas, assets::lod_data::LodData, utils::try_read_name,
@@ -57,10 +58,7 @@ impl Tileset {
 }
 use byteorder::{LittleEndian, ReadBytesExt};
 use image::DynamicImage;
-use std::{
-    error::Error,
-    io::{Cursor, Read},
-};
+use std::io::{Cursor, Read};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Dtile {
@@ -138,12 +136,12 @@ impl Tile {
 }
 
 impl Dtile {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/dtile.bin")?;
         Self::try_from(raw.as_slice())
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         let tile_count = cursor.read_u32::<LittleEndian>()?;
         let mut tiles = Vec::with_capacity(tile_count as usize);
@@ -248,7 +246,7 @@ impl Dtile {
 }
 
 impl TryFrom<&[u8]> for Dtile {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -346,7 +344,7 @@ impl TileTable {
         set
     }
 
-    pub fn atlas_image(&self, assets: &Assets) -> Result<DynamicImage, Box<dyn Error>> {
+    pub fn atlas_image(&self, assets: &Assets) -> Result<DynamicImage, Error> {
         let ts: Vec<&str> = self.names_set.iter().map(|s| s.as_str()).collect();
         get_atlas(assets, ts.as_slice(), self.size.0 as usize)
     }
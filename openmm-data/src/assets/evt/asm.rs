@@ -0,0 +1,402 @@
+//! Text assembly for .evt/.str pairs: `dump_evt` prints it, `evt_asm`
+//! turns an edited listing back into the binary files.
+//!
+//! ```text
+//! str 0 "Well"
+//! str 1 "Drink from the Well"
+//!
+//! event 101
+//!   0 MouseOver str=1                        ; Hint('Drink from the Well')
+//!   1 Compare var=16 value=12 jump=4         ; Compare(QBit[12] set? skip step 4)
+//!   2 SetTexture face=7 name="t1swb" +00     ; SetTexture(face=7 tex='t1swb')
+//!   3 OnLongTimer raw=0100000000
+//!   4 Exit
+//! ```
+//!
+//! Each instruction is `<step> <opcode> <field>=<value>...` under the
+//! `event <id>` it belongs to, in file order. Fields follow the opcode's
+//! parameter layout; bytes past the layout are kept as a trailing `+<hex>`,
+//! and params that don't fit the layout (or opcodes without one) are written
+//! as `raw=<hex>`. Opcodes are named as in [`EvtOpcode::name`] or given as
+//! `0xNN`. Strings are double-quoted with `\"`, `\\` and `\xNN` escapes, one
+//! byte per character. `;` starts a comment. Disassembling then assembling
+//! reproduces both files byte for byte.
+
+use std::fmt::Write as _;
+
+use super::{EvtFile, EvtRecord, decode_record, parse_str_table};
+use crate::Error;
+use crate::assets::enums::EvtOpcode;
+
+/// Parameter field types.
+#[derive(Clone, Copy)]
+enum Kind {
+    U8,
+    I32,
+    U32,
+    /// NUL-terminated string.
+    Str,
+}
+
+type Layout = &'static [(&'static str, Kind)];
+
+/// Parameter layout of an opcode, matching [`decode_record`]. `None` for
+/// opcodes whose params are opaque (timers, random jumps, unknowns).
+fn layout(opcode: EvtOpcode) -> Option<Layout> {
+    use Kind::*;
+    Some(match opcode {
+        EvtOpcode::Exit
+        | EvtOpcode::OnMapReload
+        | EvtOpcode::OnMapLeave
+        | EvtOpcode::EndCanShowDialogItem
+        | EvtOpcode::PressAnyKey => &[],
+        EvtOpcode::SpeakInHouse => &[("house", U32)],
+        EvtOpcode::PlaySound => &[("sound", U32)],
+        EvtOpcode::MouseOver | EvtOpcode::LocationName | EvtOpcode::ShowMessage | EvtOpcode::StatusText => {
+            &[("str", U8)]
+        }
+        EvtOpcode::MoveToMap => &[
+            ("x", I32),
+            ("y", I32),
+            ("z", I32),
+            ("dir", I32),
+            ("pitch", I32),
+            ("zspeed", I32),
+            ("house", U8),
+            ("icon", U8),
+            ("map", Str),
+        ],
+        EvtOpcode::OpenChest => &[("chest", U8)],
+        EvtOpcode::ChangeDoorState => &[("door", U8), ("action", U8)],
+        EvtOpcode::Compare => &[("var", U8), ("value", I32), ("jump", U8)],
+        EvtOpcode::Jmp => &[("step", U8)],
+        EvtOpcode::ForPartyMember => &[("player", U8)],
+        EvtOpcode::Add | EvtOpcode::Subtract | EvtOpcode::Set | EvtOpcode::OnCanShowDialogItemCmp => {
+            &[("var", U8), ("value", I32)]
+        }
+        EvtOpcode::GiveItem => &[("strength", U8), ("type", U8), ("item", U32)],
+        EvtOpcode::SetNPCTopic => &[("npc", I32), ("topic", U8), ("event", I32)],
+        EvtOpcode::MoveNPC => &[("npc", I32), ("location", I32)],
+        EvtOpcode::SpeakNPC => &[("npc", I32)],
+        EvtOpcode::ChangeEvent => &[("target", I32), ("event", I32)],
+        EvtOpcode::SetNPCGreeting => &[("npc", I32), ("greeting", I32)],
+        EvtOpcode::SetFacesBit => &[("face", I32), ("bit", I32), ("on", U8)],
+        EvtOpcode::ToggleActorFlag => &[("actor", I32), ("flag", I32), ("on", U8)],
+        EvtOpcode::SetTexture => &[("face", I32), ("name", Str)],
+        EvtOpcode::SetSprite => &[("decoration", I32), ("hide", U8), ("name", Str)],
+        EvtOpcode::ToggleIndoorLight => &[("light", I32), ("on", U8)],
+        EvtOpcode::SetSnow | EvtOpcode::SetCanShowDialogItem => &[("on", U8)],
+        EvtOpcode::SummonMonsters => &[("monster", I32), ("count", I32), ("x", I32), ("y", I32), ("z", I32)],
+        EvtOpcode::CastSpell => &[
+            ("spell", I32),
+            ("level", I32),
+            ("mastery", I32),
+            ("from_x", I32),
+            ("from_y", I32),
+            ("from_z", I32),
+            ("to_x", I32),
+            ("to_y", I32),
+            ("to_z", I32),
+        ],
+        EvtOpcode::ReceiveDamage => &[("type", I32), ("amount", I32)],
+        EvtOpcode::ShowFace => &[("player", U8), ("expression", I32)],
+        EvtOpcode::IsActorKilled => &[("group", I32), ("count", I32), ("jump", U8)],
+        EvtOpcode::CheckSkill => &[("skill", U8), ("level", U8), ("jump", U8)],
+        EvtOpcode::SummonItem => &[("item", I32), ("x", I32), ("y", I32), ("z", I32)],
+        EvtOpcode::CharacterAnimation => &[("player", U8), ("anim", U8)],
+        EvtOpcode::SetTextureOutdoors => &[("model", U32), ("facet", U32), ("name", Str)],
+        EvtOpcode::CheckItemsCount => &[("item", I32), ("count", I32), ("jump", U8)],
+        EvtOpcode::RemoveItems => &[("item", I32), ("count", I32)],
+        EvtOpcode::SetNPCGroupNews => &[("group", I32), ("news", I32)],
+        EvtOpcode::SetActorGroup => &[("actor", I32), ("group", I32)],
+        EvtOpcode::NPCSetItem => &[("npc", I32), ("item", I32), ("on", U8)],
+        EvtOpcode::CanShowTopicIsActorKilled => &[("group", I32), ("count", I32)],
+        EvtOpcode::ChangeGroup => &[("old", I32), ("new", I32)],
+        EvtOpcode::ChangeGroupAlly => &[("group", I32), ("ally", I32)],
+        EvtOpcode::CheckSeason => &[("season", I32), ("jump", U8)],
+        EvtOpcode::ToggleActorGroupFlag => &[("group", I32), ("flag", I32), ("on", U8)],
+        EvtOpcode::ToggleChestFlag => &[("chest", I32), ("flag", I32), ("on", U8)],
+        EvtOpcode::SetActorItem => &[("actor", I32), ("item", I32), ("on", U8)],
+        EvtOpcode::EnableDateTimer => &[("timer", I32), ("on", U8)],
+        EvtOpcode::StopAnimation => &[("decoration", I32)],
+        EvtOpcode::SpecialJump => &[("value", I32)],
+        EvtOpcode::IsTotalBountyHuntingAwardInRange => &[("min", I32), ("max", I32), ("jump", U8)],
+        EvtOpcode::IsNPCInParty => &[("npc", I32), ("jump", U8)],
+        _ => return None,
+    })
+}
+
+impl EvtFile {
+    /// Render the script and string table as editable assembly text.
+    pub fn disassemble(&self) -> String {
+        let str_table = parse_str_table(&self.str_bytes());
+        let mut out = String::new();
+        for (i, s) in self.strings.iter().enumerate() {
+            let _ = writeln!(out, "str {} {}", i, quote(s.chars().map(|c| c as u32 as u8)));
+        }
+
+        let mut current = None;
+        for record in &self.records {
+            if current != Some(record.event_id) {
+                current = Some(record.event_id);
+                let _ = writeln!(out, "\nevent {}", record.event_id);
+            }
+            let mut line = format!(
+                "  {} {}{}",
+                record.step,
+                opcode_name(record.opcode),
+                format_params(record)
+            );
+            if let Some(event) = decode_record(record, &str_table) {
+                line = format!("{line:<44} ; {}", event.to_string().replace(['\n', '\r'], " "));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+
+        if !self.tail.is_empty() {
+            let _ = writeln!(out, "\ntail {}", hex(&self.tail));
+        }
+        out
+    }
+
+    /// Parse assembly text produced by [`disassemble`](Self::disassemble)
+    /// (possibly edited). Errors are [`Error::Invalid`] naming the
+    /// offending line.
+    pub fn assemble(text: &str) -> Result<Self, Error> {
+        let mut strings = Vec::new();
+        let mut records = Vec::new();
+        let mut tail = Vec::new();
+        let mut event_id = None;
+
+        for (n, line) in text.lines().enumerate() {
+            let at = |e: String| Error::invalid("evt", format!("line {}: {e}", n + 1));
+            let tokens = tokenize(line).map_err(at)?;
+            let Some((first, args)) = tokens.split_first() else {
+                continue;
+            };
+            match first.as_str() {
+                "str" => {
+                    let [index, value] = args else {
+                        return Err(at("expected `str <index> \"text\"`".to_string()));
+                    };
+                    let index: usize = index.parse().map_err(|_| at(format!("bad string index `{index}`")))?;
+                    if index != strings.len() {
+                        return Err(at(format!("string {index} out of order, expected {}", strings.len())));
+                    }
+                    let bytes = unquote(value).map_err(at)?;
+                    strings.push(bytes.iter().map(|&b| b as char).collect());
+                }
+                "event" => {
+                    let [id] = args else {
+                        return Err(at("expected `event <id>`".to_string()));
+                    };
+                    event_id = Some(id.parse::<u16>().map_err(|_| at(format!("bad event id `{id}`")))?);
+                }
+                "tail" => {
+                    let [bytes] = args else {
+                        return Err(at("expected `tail <hex>`".to_string()));
+                    };
+                    tail = unhex(bytes).map_err(at)?;
+                }
+                step => {
+                    let event_id = event_id.ok_or_else(|| at("instruction before any `event`".to_string()))?;
+                    let step = step.parse::<u8>().map_err(|_| at(format!("bad step `{step}`")))?;
+                    let (opcode, fields) = args.split_first().ok_or_else(|| at("missing opcode".to_string()))?;
+                    let opcode = parse_opcode(opcode).map_err(at)?;
+                    let params = encode_params(opcode, fields).map_err(at)?;
+                    if params.len() > u8::MAX as usize - 4 {
+                        return Err(at(format!("{} parameter bytes, at most {}", params.len(), u8::MAX - 4)));
+                    }
+                    records.push(EvtRecord {
+                        event_id,
+                        step,
+                        opcode,
+                        params,
+                    });
+                }
+            }
+        }
+        Ok(Self::from_records(records, strings, tail))
+    }
+}
+
+fn opcode_name(opcode: u8) -> String {
+    match EvtOpcode::from_u8(opcode) {
+        Some(op) => op.name().to_string(),
+        None => format!("0x{opcode:02x}"),
+    }
+}
+
+fn parse_opcode(name: &str) -> Result<u8, String> {
+    if let Some(hex) = name.strip_prefix("0x") {
+        return u8::from_str_radix(hex, 16).map_err(|_| format!("bad opcode `{name}`"));
+    }
+    (0..=u8::MAX)
+        .find(|&op| EvtOpcode::from_u8(op).is_some_and(|o| o.name() == name))
+        .ok_or_else(|| format!("unknown opcode `{name}`"))
+}
+
+/// ` field=value...` per the opcode layout, or ` raw=<hex>` if it doesn't fit.
+fn format_params(record: &EvtRecord) -> String {
+    let params = &record.params;
+    let Some(layout) = EvtOpcode::from_u8(record.opcode).and_then(layout) else {
+        return format!(" raw={}", hex(params));
+    };
+
+    let mut out = String::new();
+    let mut pos = 0;
+    for &(name, kind) in layout {
+        let rest = &params[pos.min(params.len())..];
+        let (value, len) = match kind {
+            Kind::U8 if !rest.is_empty() => (rest[0].to_string(), 1),
+            Kind::I32 if rest.len() >= 4 => (i32::from_le_bytes(rest[..4].try_into().unwrap()).to_string(), 4),
+            Kind::U32 if rest.len() >= 4 => (u32::from_le_bytes(rest[..4].try_into().unwrap()).to_string(), 4),
+            Kind::Str => match rest.iter().position(|&b| b == 0) {
+                Some(end) => (quote(rest[..end].iter().copied()), end + 1),
+                None => return format!(" raw={}", hex(params)),
+            },
+            _ => return format!(" raw={}", hex(params)),
+        };
+        let _ = write!(out, " {name}={value}");
+        pos += len;
+    }
+    if pos < params.len() {
+        let _ = write!(out, " +{}", hex(&params[pos..]));
+    }
+    out
+}
+
+/// Inverse of [`format_params`].
+fn encode_params(opcode: u8, fields: &[String]) -> Result<Vec<u8>, String> {
+    if let [field] = fields
+        && let Some(bytes) = field.strip_prefix("raw=")
+    {
+        return unhex(bytes);
+    }
+    let layout = EvtOpcode::from_u8(opcode)
+        .and_then(layout)
+        .ok_or_else(|| format!("{} has no field layout, use raw=<hex>", opcode_name(opcode)))?;
+
+    let (fields, extra) = match fields.split_last() {
+        Some((last, init)) if last.starts_with('+') => (init, unhex(&last[1..])?),
+        _ => (fields, Vec::new()),
+    };
+    if fields.len() != layout.len() {
+        let names: Vec<_> = layout.iter().map(|(name, _)| *name).collect();
+        return Err(format!("{} takes fields [{}]", opcode_name(opcode), names.join(" ")));
+    }
+
+    let mut buf = Vec::new();
+    for (field, &(name, kind)) in fields.iter().zip(layout) {
+        let value = field
+            .strip_prefix(name)
+            .and_then(|v| v.strip_prefix('='))
+            .ok_or_else(|| format!("expected `{name}=...`, got `{field}`"))?;
+        let bad = || format!("bad value for {name}: `{value}`");
+        match kind {
+            Kind::U8 => buf.push(value.parse::<u8>().map_err(|_| bad())?),
+            Kind::I32 => buf.extend(value.parse::<i32>().map_err(|_| bad())?.to_le_bytes()),
+            Kind::U32 => buf.extend(value.parse::<u32>().map_err(|_| bad())?.to_le_bytes()),
+            Kind::Str => {
+                buf.extend(unquote(value)?);
+                buf.push(0);
+            }
+        }
+    }
+    buf.extend(extra);
+    Ok(buf)
+}
+
+/// Split a line into whitespace-separated tokens, keeping quoted strings
+/// whole and dropping a trailing `;` comment.
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => break,
+            '"' => {
+                token.push(c);
+                loop {
+                    let c = chars.next().ok_or("unterminated string")?;
+                    token.push(c);
+                    match c {
+                        '\\' => token.push(chars.next().ok_or("unterminated string")?),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn quote(bytes: impl Iterator<Item = u8>) -> String {
+    let mut out = String::from("\"");
+    for b in bytes {
+        match b {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            0x20..=0x7E => out.push(b as char),
+            _ => {
+                let _ = write!(out, "\\x{b:02x}");
+            }
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn unquote(token: &str) -> Result<Vec<u8>, String> {
+    let inner = token
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .ok_or_else(|| format!("expected a quoted string, got `{token}`"))?;
+    let mut out = Vec::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('x') => {
+                    let hex: String = chars.by_ref().take(2).collect();
+                    out.push(u8::from_str_radix(&hex, 16).map_err(|_| format!("bad escape `\\x{hex}`"))?);
+                }
+                Some(c @ ('"' | '\\')) => out.push(c as u8),
+                other => return Err(format!("bad escape `\\{}`", other.unwrap_or(' '))),
+            },
+            c if c.is_ascii() => out.push(c as u8),
+            c => return Err(format!("non-ASCII `{c}` in string, use \\xNN")),
+        }
+    }
+    Ok(out)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn unhex(text: &str) -> Result<Vec<u8>, String> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return Err(format!("bad hex `{text}`"));
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|_| format!("bad hex `{text}`")))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "asm_tests.rs"]
+mod tests;
@@ -0,0 +1,156 @@
+use super::*;
+use crate::LodSerialise;
+use crate::assets::evt::{EvtStep, GameEvent};
+use crate::assets::test_lod;
+
+fn record(event_id: u16, step: u8, opcode: u8, params: &[u8]) -> Vec<u8> {
+    let mut buf = vec![(params.len() + 4) as u8];
+    buf.extend_from_slice(&event_id.to_le_bytes());
+    buf.push(step);
+    buf.push(opcode);
+    buf.extend_from_slice(params);
+    buf
+}
+
+fn sample_evt() -> Vec<u8> {
+    let mut compare = vec![0x10];
+    compare.extend(12i32.to_le_bytes());
+    compare.push(3);
+    let mut move_to_map = Vec::new();
+    for v in [-100i32, 200, 0, 512, 0, 0] {
+        move_to_map.extend(v.to_le_bytes());
+    }
+    move_to_map.extend([5, 1]);
+    move_to_map.extend(b"d01.blv\0");
+    let mut texture = 7i32.to_le_bytes().to_vec();
+    texture.extend(b"t1\"q\\\0\x00\x01");
+
+    [
+        record(101, 0, EvtOpcode::MouseOver as u8, &[1]),
+        record(101, 1, EvtOpcode::Compare as u8, &compare),
+        record(101, 2, EvtOpcode::MoveToMap as u8, &move_to_map),
+        record(101, 3, EvtOpcode::Exit as u8, &[]),
+        record(102, 0, EvtOpcode::SetTexture as u8, &texture),
+        record(102, 1, EvtOpcode::OnLongTimer as u8, &[1, 0, 0, 0, 9]),
+        record(102, 2, EvtOpcode::SpeakNPC as u8, &[1, 2]),
+        record(102, 3, 0x99, &[0xAA]),
+        vec![0x02, 0xFF],
+    ]
+    .concat()
+}
+
+fn sample_str() -> Vec<u8> {
+    b"Well\0Drink; from the \x93Well\x94\nnow\0\0".to_vec()
+}
+
+#[test]
+fn disassemble_assemble_is_byte_identical() {
+    let evt = sample_evt();
+    let strs = sample_str();
+    let file = EvtFile::from_bytes(&evt, &strs);
+    assert_eq!(file.records.len(), 8);
+    assert_eq!(file.tail, vec![0x02, 0xFF]);
+    assert_eq!(file.to_bytes(), evt);
+    assert_eq!(file.str_bytes(), strs);
+
+    let text = file.disassemble();
+    let again = EvtFile::assemble(&text).unwrap();
+    assert_eq!(again.to_bytes(), evt, "{text}");
+    assert_eq!(again.str_bytes(), strs, "{text}");
+    assert_eq!(again.disassemble(), text);
+
+    assert!(text.contains("  2 MoveToMap x=-100 y=200 z=0 dir=512 pitch=0 zspeed=0 house=5 icon=1 map=\"d01.blv\""));
+    assert!(text.contains("name=\"t1\\\"q\\\\\" +0001"), "{text}");
+    assert!(text.contains("  1 OnLongTimer raw=0100000009"));
+    assert!(text.contains("  2 SpeakNPC raw=0102"));
+    assert!(text.contains("  3 0x99 raw=aa"));
+    assert!(text.contains("str 1 \"Drink; from the \\x93Well\\x94\\x0anow\""));
+}
+
+#[test]
+fn edited_listing_decodes_to_events() {
+    let text = r#"
+        str 0 "Fountain"
+        event 7
+          0 MouseOver str=0        ; tooltip
+          1 Compare var=16 value=40 jump=3
+          2 Add var=16 value=40
+          3 Exit
+    "#;
+    let file = EvtFile::assemble(text).unwrap();
+    let steps = &file.events[&7];
+    assert_eq!(steps.len(), 4);
+    assert!(matches!(&steps[0].event, GameEvent::Hint { text, .. } if text == "Fountain"));
+    assert!(matches!(
+        steps[1].event,
+        GameEvent::Compare {
+            value: 40,
+            jump_step: 3,
+            ..
+        }
+    ));
+    assert_eq!(file.str_bytes(), b"Fountain");
+    assert_eq!(file.to_bytes()[0] as usize + 1, 5 + 1);
+}
+
+#[test]
+fn record_edits_are_written_and_merged_events_are_not() {
+    let mut file = EvtFile::from_bytes(&sample_evt(), &sample_str());
+    let shared = EvtFile::from_bytes(&record(900, 0, EvtOpcode::Exit as u8, &[]), &[]);
+    file.merge_events(shared);
+    assert!(file.events().contains_key(&900));
+    assert_eq!(file.to_bytes(), sample_evt());
+
+    // Edits go through the records; the view is rebuilt from them.
+    file.records[3].event_id = 103;
+    let file = EvtFile::from_records(file.records, file.strings, file.tail);
+    assert_eq!(EvtFile::from_bytes(&file.to_bytes(), &[]).records[3].event_id, 103);
+    assert!(matches!(
+        file.events()[&103][..],
+        [EvtStep {
+            step: 3,
+            event: GameEvent::Exit
+        }]
+    ));
+    assert!(!file.events().contains_key(&900));
+}
+
+#[test]
+fn assembler_errors_name_the_line() {
+    for (text, needle) in [
+        ("0 Exit", "evt: line 1: instruction before any `event`"),
+        ("event 1\n0 Bogus", "evt: line 2: unknown opcode `Bogus`"),
+        (
+            "event 1\n0 Compare var=1",
+            "evt: line 2: Compare takes fields [var value jump]",
+        ),
+        ("event 1\n0 Jmp step=300", "evt: line 2: bad value for step: `300`"),
+        ("str 1 \"x\"", "evt: line 1: string 1 out of order, expected 0"),
+        (
+            "event 1\n0 SetTexture face=1 name=\"abc",
+            "evt: line 2: unterminated string",
+        ),
+    ] {
+        let err = EvtFile::assemble(text).err().expect(text).to_string();
+        assert!(err.starts_with(needle), "{text:?}: {err}");
+    }
+}
+
+#[test]
+fn every_map_script_round_trips() {
+    let Some(assets) = test_lod() else {
+        return;
+    };
+    for archive in assets.archives() {
+        for name in assets.files_in(&archive).unwrap_or_default() {
+            let Some(base) = name.to_lowercase().strip_suffix(".evt").map(str::to_string) else {
+                continue;
+            };
+            let original = EvtFile::parse(&assets, &base).unwrap();
+            let reassembled = EvtFile::assemble(&original.disassemble()).unwrap();
+            assert_eq!(reassembled.to_bytes(), original.to_bytes(), "{base}.evt");
+            assert_eq!(reassembled.str_bytes(), original.str_bytes(), "{base}.str");
+            assert_eq!(reassembled.records, original.records, "{base}.evt");
+        }
+    }
+}
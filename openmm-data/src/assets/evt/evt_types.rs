@@ -112,15 +112,8 @@ pub enum GameEvent {
     ShowFace { player: u8, expression: i32 },
 
     // ── Timer / conditional ─────────────────────────────────────────────
-    /// Timer-based event (fires after delay).
-    OnTimer {
-        year: u16,
-        month: u8,
-        week: u8,
-        day: u16,
-        hour: u8,
-        minute: u8,
-    },
+    /// Timer-based event (fires after delay); params as in [`super::timer`].
+    OnTimer { timer_data: Vec<u8> },
     /// Long timer (date-based).
     OnLongTimer { timer_data: Vec<u8> },
     /// Map reload hook.
@@ -343,18 +336,7 @@ impl std::fmt::Display for GameEvent {
                 write!(f, "ReceiveDamage(type={} amount={})", damage_type, amount)
             }
             Self::ShowFace { player, expression } => write!(f, "ShowFace(player={} expr={})", player, expression),
-            Self::OnTimer {
-                year,
-                month,
-                week,
-                day,
-                hour,
-                minute,
-            } => write!(
-                f,
-                "OnTimer(y={} m={} w={} d={} h={} min={})",
-                year, month, week, day, hour, minute
-            ),
+            Self::OnTimer { timer_data } => write!(f, "OnTimer(data={:02x?})", timer_data),
             Self::OnLongTimer { timer_data } => write!(f, "OnLongTimer(data={:02x?})", timer_data),
             Self::OnMapReload => write!(f, "OnMapReload"),
             Self::OnMapLeave => write!(f, "OnMapLeave"),
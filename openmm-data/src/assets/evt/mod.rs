@@ -10,31 +10,64 @@
 //!   byte 3: step (u8)
 //!   byte 4: opcode (u8)
 //!   bytes 5+: params (opcode-dependent)
+//!
+//! The raw records and .str table are kept alongside the decoded events so
+//! a file re-serialises byte for byte; `asm` adds an editable text form.
+//! The decoded events are a read-only view: edit `records` (and rebuild
+//! with [`EvtFile::from_records`]) to change what gets written.
 
 use std::collections::HashMap;
-use std::error::Error;
 use std::io::Read;
 
 use crate::Assets;
+use crate::Error;
+use crate::LodSerialise;
 use crate::assets::enums::{DoorAction, EvtOpcode, EvtVariable};
 
+mod asm;
 mod evt_types;
+mod timer;
 pub use evt_types::{EvtStep, GameEvent};
+pub use timer::{TimerPeriod, TimerSchedule};
 
 /// Parsed events from a .evt file, keyed by event_id.
+#[derive(Default)]
 pub struct EvtFile {
-    /// For each event_id, the list of steps (step number + action).
-    pub events: HashMap<u16, Vec<EvtStep>>,
+    /// For each event_id, the list of steps (step number + action), decoded
+    /// from `records`. Not written back; see [`events`](Self::events).
+    events: HashMap<u16, Vec<EvtStep>>,
+    /// Every instruction in file order, including ones `events` skips.
+    /// [`LodSerialise::to_bytes`] writes these back verbatim, so this is
+    /// what to edit.
+    pub records: Vec<EvtRecord>,
+    /// The map's .str table split on NUL, bytes mapped 1:1 to chars
+    /// (Latin-1) so non-UTF-8 text survives. The piece after the final NUL
+    /// (normally empty) is kept so the table re-joins exactly.
+    pub strings: Vec<String>,
+    /// Bytes after the last whole record.
+    pub tail: Vec<u8>,
 }
 
-/// Parse a .str string table: null-separated strings indexed from 0.
-fn parse_str_table(assets: &Assets, map_base: &str) -> Vec<String> {
-    let data = assets
+/// One instruction exactly as stored in the .evt file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvtRecord {
+    pub event_id: u16,
+    pub step: u8,
+    pub opcode: u8,
+    pub params: Vec<u8>,
+}
+
+/// Raw .str bytes for a map, or empty if it has none.
+fn load_str_data(assets: &Assets, map_base: &str) -> Vec<u8> {
+    assets
         .get_decompressed(format!("icons/{}.str", map_base))
         .or_else(|_| assets.get_decompressed(format!("games/{}.str", map_base)))
-        .or_else(|_| assets.get_decompressed(format!("new/{}.str", map_base)));
-    let Ok(data) = data else { return Vec::new() };
+        .or_else(|_| assets.get_decompressed(format!("new/{}.str", map_base)))
+        .unwrap_or_default()
+}
 
+/// Parse a .str string table: null-separated strings indexed from 0.
+fn parse_str_table(data: &[u8]) -> Vec<String> {
     data.split(|&b| b == 0)
         .filter_map(|s| std::str::from_utf8(s).ok())
         .filter(|s: &&str| !s.is_empty())
@@ -72,8 +105,8 @@ fn read_string(params: &[u8]) -> String {
 impl EvtFile {
     /// Parse an .evt file from raw (possibly compressed) LOD data.
     /// Also loads the corresponding .str file for hint text resolution.
-    pub fn parse(assets: &Assets, map_base: &str) -> Result<Self, Box<dyn Error>> {
-        let str_table = parse_str_table(assets, map_base);
+    pub fn parse(assets: &Assets, map_base: &str) -> Result<Self, Error> {
+        let str_data = load_str_data(assets, map_base);
 
         // Try multiple archive locations
         let path = format!("icons/{}.evt", map_base);
@@ -86,586 +119,98 @@ impl EvtFile {
         let data = if let Some(zlib_pos) = raw.windows(2).position(|w| w[0] == 0x78 && w[1] == 0x9c) {
             let mut decoder = flate2::read::ZlibDecoder::new(&raw[zlib_pos..]);
             let mut decompressed = Vec::new();
-            decoder.read_to_end(&mut decompressed)?;
+            decoder
+                .read_to_end(&mut decompressed)
+                .map_err(|source| Error::Decompress {
+                    name: format!("{map_base}.evt"),
+                    source,
+                })?;
             decompressed
         } else {
             raw.to_vec()
         };
 
-        let mut events: HashMap<u16, Vec<EvtStep>> = HashMap::new();
+        Ok(Self::from_bytes(&data, &str_data))
+    }
+
+    /// Build from decompressed .evt bytes and raw .str bytes (empty if the
+    /// map has no string table).
+    pub fn from_bytes(evt: &[u8], str_data: &[u8]) -> Self {
+        let mut records = Vec::new();
         let mut pos = 0;
 
-        while pos < data.len() {
-            let size_byte = data[pos] as usize;
+        while pos < evt.len() {
+            let size_byte = evt[pos] as usize;
             let total = size_byte + 1;
-            if total < 5 || pos + total > data.len() {
+            if total < 5 || pos + total > evt.len() {
                 break;
             }
+            records.push(EvtRecord {
+                event_id: u16::from_le_bytes([evt[pos + 1], evt[pos + 2]]),
+                step: evt[pos + 3],
+                opcode: evt[pos + 4],
+                params: evt[pos + 5..pos + total].to_vec(),
+            });
+            pos += total;
+        }
 
-            let event_id = u16::from_le_bytes([data[pos + 1], data[pos + 2]]);
-            let step = data[pos + 3];
-            let opcode = data[pos + 4];
-            let params = &data[pos + 5..pos + total];
-
-            let evt_opcode = EvtOpcode::from_u8(opcode);
-
-            let action = match evt_opcode {
-                Some(EvtOpcode::Exit) => Some(GameEvent::Exit),
-                Some(EvtOpcode::SpeakInHouse) => {
-                    read_i32(params).map(|v| GameEvent::SpeakInHouse { house_id: v as u32 })
-                }
-                Some(EvtOpcode::PlaySound) => read_i32(params).map(|v| GameEvent::PlaySound { sound_id: v as u32 }),
-                Some(EvtOpcode::MouseOver) => {
-                    let str_id = params.first().copied().unwrap_or(0);
-                    let text = str_table.get(str_id as usize).cloned().unwrap_or_default();
-                    Some(GameEvent::Hint { str_id, text })
-                }
-                Some(EvtOpcode::LocationName) => {
-                    let str_id = params.first().copied().unwrap_or(0);
-                    let text = str_table.get(str_id as usize).cloned().unwrap_or_default();
-                    Some(GameEvent::LocationName { str_id, text })
-                }
-                Some(EvtOpcode::MoveToMap) => {
-                    if params.len() >= 26 {
-                        let x = i32_at(params, 0);
-                        let y = i32_at(params, 4);
-                        let z = i32_at(params, 8);
-                        let direction = i32_at(params, 12);
-                        let name_bytes = &params[26..];
-                        let end = name_bytes.iter().position(|&b| b == 0).unwrap_or(name_bytes.len());
-                        let map_name = String::from_utf8_lossy(&name_bytes[..end]).to_string();
-                        Some(GameEvent::MoveToMap {
-                            x,
-                            y,
-                            z,
-                            direction,
-                            map_name,
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::OpenChest) => Some(GameEvent::OpenChest {
-                    id: params.first().copied().unwrap_or(0),
-                }),
-                Some(EvtOpcode::ShowMessage) => {
-                    let str_id = params.first().copied().unwrap_or(0);
-                    let text = str_table.get(str_id as usize).cloned().unwrap_or_default();
-                    Some(GameEvent::ShowMessage { str_id, text })
-                }
-                Some(EvtOpcode::StatusText) => {
-                    let str_id = params.first().copied().unwrap_or(0);
-                    let text = str_table.get(str_id as usize).cloned().unwrap_or_default();
-                    Some(GameEvent::StatusText { str_id, text })
-                }
-                Some(EvtOpcode::ChangeDoorState) => {
-                    if params.len() >= 2 {
-                        Some(GameEvent::ChangeDoorState {
-                            door_id: params[0],
-                            action: DoorAction::from_u8(params[1]).unwrap_or(DoorAction::Toggle),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                // ── Control flow ────────────────────────────────────────
-                // MM6: Compare = var_id(u8) + value(i32 LE) + jump_step(u8) = 6 bytes
-                Some(EvtOpcode::Compare) => {
-                    if params.len() >= 6 {
-                        Some(GameEvent::Compare {
-                            var: EvtVariable(params[0]),
-                            value: i32_at(params, 1),
-                            jump_step: params[5],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::Jmp) => Some(GameEvent::Jmp {
-                    target_step: params.first().copied().unwrap_or(0),
-                }),
-                Some(EvtOpcode::ForPartyMember) => Some(GameEvent::ForPartyMember {
-                    player: params.first().copied().unwrap_or(0),
-                }),
-                // ── Variable operations ─────────────────────────────────
-                // MM6: Add/Subtract/Set = var_id(u8) + value(i32 LE) = 5 bytes
-                Some(EvtOpcode::Add) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::Add {
-                            var: EvtVariable(params[0]),
-                            value: i32_at(params, 1),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::Subtract) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::Subtract {
-                            var: EvtVariable(params[0]),
-                            value: i32_at(params, 1),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::Set) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::Set {
-                            var: EvtVariable(params[0]),
-                            value: i32_at(params, 1),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                // ── NPC / item operations ───────────────────────────────
-                // MM6: GiveItem = strength(u8) + type(u8) + id(u32 LE) = 6 bytes
-                Some(EvtOpcode::GiveItem) => {
-                    if params.len() >= 6 {
-                        Some(GameEvent::GiveItem {
-                            strength: params[0],
-                            item_type: params[1],
-                            item_id: u32_at(params, 2),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                // MM6: SetNPCTopic = npc_id(i32) + index(u8) + event_id(i32) = 9 bytes
-                Some(EvtOpcode::SetNPCTopic) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::SetNPCTopic {
-                            npc_id: i32_at(params, 0),
-                            topic_index: params[4],
-                            event_id: i32_at(params, 5),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::MoveNPC) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::MoveNPC {
-                            npc_id: i32_at(params, 0),
-                            map_id: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::SpeakNPC) => read_i32(params).map(|v| GameEvent::SpeakNPC { npc_id: v }),
-                Some(EvtOpcode::ChangeEvent) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::ChangeEvent {
-                            target: i32_at(params, 0),
-                            new_event_id: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::SetNPCGreeting) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::SetNPCGreeting {
-                            npc_id: i32_at(params, 0),
-                            greeting_id: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                // ── World operations ────────────────────────────────────
-                // SetFacesBit = face_id(i32) + bit(i32) + on(u8) = 9 bytes
-                Some(EvtOpcode::SetFacesBit) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::SetFacesBit {
-                            face_id: i32_at(params, 0),
-                            bit: i32_at(params, 4),
-                            on: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::ToggleActorFlag) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::ToggleActorFlag {
-                            actor_id: i32_at(params, 0),
-                            flag: i32_at(params, 4),
-                            on: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::SetTexture) => {
-                    if params.len() >= 5 {
-                        let face_id = i32_at(params, 0);
-                        let name = read_string(&params[4..]);
-                        Some(GameEvent::SetTexture {
-                            face_id,
-                            texture_name: name,
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::SetSprite) => {
-                    // Format: cog(u32) + hide(u8) + name(null-terminated string)
-                    if params.len() >= 6 {
-                        let decoration_id = i32_at(params, 0);
-                        let name = read_string(&params[5..]);
-                        Some(GameEvent::SetSprite {
-                            decoration_id,
-                            sprite_name: name,
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::ToggleIndoorLight) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::ToggleIndoorLight {
-                            light_id: i32_at(params, 0),
-                            on: params[4],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::SetSnow) => Some(GameEvent::SetSnow {
-                    on: params.first().copied().unwrap_or(0),
-                }),
-                Some(EvtOpcode::SummonMonsters) => {
-                    if params.len() >= 20 {
-                        Some(GameEvent::SummonMonsters {
-                            monster_id: i32_at(params, 0),
-                            count: i32_at(params, 4),
-                            x: i32_at(params, 8),
-                            y: i32_at(params, 12),
-                            z: i32_at(params, 16),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::CastSpell) => {
-                    if params.len() >= 36 {
-                        Some(GameEvent::CastSpell {
-                            spell_id: i32_at(params, 0),
-                            skill_level: i32_at(params, 4),
-                            skill_mastery: i32_at(params, 8),
-                            from_x: i32_at(params, 12),
-                            from_y: i32_at(params, 16),
-                            from_z: i32_at(params, 20),
-                            to_x: i32_at(params, 24),
-                            to_y: i32_at(params, 28),
-                            to_z: i32_at(params, 32),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::ReceiveDamage) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::ReceiveDamage {
-                            damage_type: i32_at(params, 0),
-                            amount: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::ShowFace) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::ShowFace {
-                            player: params[0],
-                            expression: i32_at(params, 1),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                // ── Timer / conditional ─────────────────────────────────
-                Some(EvtOpcode::OnTimer) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::OnTimer {
-                            year: u16::from_le_bytes([params[0], params[1]]),
-                            month: params[2],
-                            week: params[3],
-                            day: u16::from_le_bytes([params[4], params[5]]),
-                            hour: params[6],
-                            minute: params[7],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::OnLongTimer) => Some(GameEvent::OnLongTimer {
-                    timer_data: params.to_vec(),
-                }),
-                Some(EvtOpcode::OnMapReload) => Some(GameEvent::OnMapReload),
-                Some(EvtOpcode::OnMapLeave) => Some(GameEvent::OnMapLeave),
-                Some(EvtOpcode::OnCanShowDialogItemCmp) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::OnCanShowDialogItemCmp {
-                            var: EvtVariable(params[0]),
-                            value: i32_at(params, 1),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::EndCanShowDialogItem) => Some(GameEvent::EndCanShowDialogItem),
-                Some(EvtOpcode::SetCanShowDialogItem) => Some(GameEvent::SetCanShowDialogItem {
-                    on: params.first().copied().unwrap_or(0),
-                }),
-                // ── Misc ────────────────────────────────────────────────
-                Some(EvtOpcode::IsActorKilled) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::IsActorKilled {
-                            actor_group: i32_at(params, 0),
-                            count: i32_at(params, 4),
-                            jump_step: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::CheckSkill) => {
-                    if params.len() >= 3 {
-                        Some(GameEvent::CheckSkill {
-                            skill_id: params[0],
-                            skill_level: params[1],
-                            jump_step: params[2],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::RandomGoTo) => Some(GameEvent::RandomGoTo { steps: params.to_vec() }),
-                Some(EvtOpcode::SummonItem) => {
-                    if params.len() >= 16 {
-                        Some(GameEvent::SummonItem {
-                            item_id: i32_at(params, 0),
-                            x: i32_at(params, 4),
-                            y: i32_at(params, 8),
-                            z: i32_at(params, 12),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::CharacterAnimation) => {
-                    if params.len() >= 2 {
-                        Some(GameEvent::CharacterAnimation {
-                            player: params[0],
-                            anim_id: params[1],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::PressAnyKey) => Some(GameEvent::PressAnyKey),
-                Some(EvtOpcode::SetTextureOutdoors) => {
-                    // MM6 opcode 0x0C: SetTextureOutdoors
-                    // Params: model(u32) + facet(u32) + texture_name(null-terminated)
-                    if params.len() >= 9 {
-                        let model = u32_at(params, 0);
-                        let facet = u32_at(params, 4);
-                        let texture_name = read_string(&params[8..]);
-                        Some(GameEvent::SetTextureOutdoors {
-                            model,
-                            facet,
-                            texture_name,
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::CheckItemsCount) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::CheckItemsCount {
-                            item_id: i32_at(params, 0),
-                            count: i32_at(params, 4),
-                            jump_step: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::RemoveItems) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::RemoveItems {
-                            item_id: i32_at(params, 0),
-                            count: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                // ── Remaining opcodes with param parsing ─────────
-                Some(EvtOpcode::InputString) => Some(GameEvent::InputString {
-                    params: params.to_vec(),
-                }),
-                Some(EvtOpcode::SetNPCGroupNews) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::SetNPCGroupNews {
-                            npc_group: i32_at(params, 0),
-                            news_id: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::SetActorGroup) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::SetActorGroup {
-                            actor_id: i32_at(params, 0),
-                            group_id: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::NPCSetItem) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::NPCSetItem {
-                            npc_id: i32_at(params, 0),
-                            item_id: i32_at(params, 4),
-                            on: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::CanShowTopicIsActorKilled) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::CanShowTopicIsActorKilled {
-                            actor_group: i32_at(params, 0),
-                            count: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::ChangeGroup) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::ChangeGroup {
-                            old_group: i32_at(params, 0),
-                            new_group: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::ChangeGroupAlly) => {
-                    if params.len() >= 8 {
-                        Some(GameEvent::ChangeGroupAlly {
-                            group_id: i32_at(params, 0),
-                            ally_group: i32_at(params, 4),
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::CheckSeason) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::CheckSeason {
-                            season: i32_at(params, 0),
-                            jump_step: params[4],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::ToggleActorGroupFlag) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::ToggleActorGroupFlag {
-                            group_id: i32_at(params, 0),
-                            flag: i32_at(params, 4),
-                            on: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::ToggleChestFlag) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::ToggleChestFlag {
-                            chest_id: i32_at(params, 0),
-                            flag: i32_at(params, 4),
-                            on: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::SetActorItem) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::SetActorItem {
-                            actor_id: i32_at(params, 0),
-                            item_id: i32_at(params, 4),
-                            on: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::OnDateTimer) => Some(GameEvent::OnDateTimer {
-                    timer_data: params.to_vec(),
-                }),
-                Some(EvtOpcode::EnableDateTimer) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::EnableDateTimer {
-                            timer_id: i32_at(params, 0),
-                            on: params[4],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::StopAnimation) => {
-                    read_i32(params).map(|v| GameEvent::StopAnimation { decoration_id: v })
-                }
-                Some(EvtOpcode::SpecialJump) => read_i32(params).map(|v| GameEvent::SpecialJump { jump_value: v }),
-                Some(EvtOpcode::IsTotalBountyHuntingAwardInRange) => {
-                    if params.len() >= 9 {
-                        Some(GameEvent::IsTotalBountyHuntingAwardInRange {
-                            min: i32_at(params, 0),
-                            max: i32_at(params, 4),
-                            jump_step: params[8],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                Some(EvtOpcode::IsNPCInParty) => {
-                    if params.len() >= 5 {
-                        Some(GameEvent::IsNPCInParty {
-                            npc_id: i32_at(params, 0),
-                            jump_step: params[4],
-                        })
-                    } else {
-                        None
-                    }
-                }
-                _ => Some(GameEvent::Unhandled {
-                    opcode,
-                    opcode_name: evt_opcode.map_or("Unknown", |o| o.name()),
-                    params: params.to_vec(),
-                }),
-            };
+        let strings = if str_data.is_empty() {
+            Vec::new()
+        } else {
+            str_data
+                .split(|&b| b == 0)
+                .map(|s| s.iter().map(|&b| b as char).collect())
+                .collect()
+        };
+        Self::from_records(records, strings, evt[pos..].to_vec())
+    }
 
-            if let Some(event) = action {
-                events.entry(event_id).or_default().push(EvtStep { step, event });
+    /// Assemble from raw parts, decoding `events` from `records`.
+    pub fn from_records(records: Vec<EvtRecord>, strings: Vec<String>, tail: Vec<u8>) -> Self {
+        let mut file = Self {
+            events: HashMap::new(),
+            records,
+            strings,
+            tail,
+        };
+        let str_table = parse_str_table(&file.str_bytes());
+        for record in &file.records {
+            if let Some(event) = decode_record(record, &str_table) {
+                file.events.entry(record.event_id).or_default().push(EvtStep {
+                    step: record.step,
+                    event,
+                });
             }
+        }
+        file
+    }
 
-            pos += total;
+    /// Steps of each event, decoded from [`records`](Self::records) (plus
+    /// any [`merge_events`](Self::merge_events)). Decoding drops bytes some
+    /// opcodes carry, so this view is read-only and not what gets written.
+    pub fn events(&self) -> &HashMap<u16, Vec<EvtStep>> {
+        &self.events
+    }
+
+    /// Add `other`'s events (out.evt, global.evt) after this file's own
+    /// steps of the same id, for running them together. Only the decoded
+    /// view grows: `records`, and so the bytes written, stay this file's.
+    pub fn merge_events(&mut self, other: EvtFile) {
+        for (id, steps) in other.events {
+            self.events.entry(id).or_default().extend(steps);
         }
+    }
 
-        Ok(EvtFile { events })
+    /// Regenerate the .str file.
+    pub fn str_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for (i, s) in self.strings.iter().enumerate() {
+            if i > 0 {
+                buf.push(0);
+            }
+            buf.extend(s.chars().map(|c| c as u32 as u8));
+        }
+        buf
     }
 
     /// Get the primary action for an event (first SpeakInHouse or MoveToMap).
@@ -676,3 +221,561 @@ impl EvtFile {
         })
     }
 }
+
+impl LodSerialise for EvtFile {
+    /// Regenerate the (uncompressed) .evt file; see [`EvtFile::str_bytes`]
+    /// for its .str partner.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for r in &self.records {
+            buf.push((r.params.len() + 4) as u8);
+            buf.extend_from_slice(&r.event_id.to_le_bytes());
+            buf.push(r.step);
+            buf.push(r.opcode);
+            buf.extend_from_slice(&r.params);
+        }
+        buf.extend_from_slice(&self.tail);
+        buf
+    }
+}
+
+/// Decode one record into a [`GameEvent`]; `None` if its params are too short.
+fn decode_record(record: &EvtRecord, str_table: &[String]) -> Option<GameEvent> {
+    let opcode = record.opcode;
+    let params = record.params.as_slice();
+    let evt_opcode = EvtOpcode::from_u8(opcode);
+
+    match evt_opcode {
+        Some(EvtOpcode::Exit) => Some(GameEvent::Exit),
+        Some(EvtOpcode::SpeakInHouse) => read_i32(params).map(|v| GameEvent::SpeakInHouse { house_id: v as u32 }),
+        Some(EvtOpcode::PlaySound) => read_i32(params).map(|v| GameEvent::PlaySound { sound_id: v as u32 }),
+        Some(EvtOpcode::MouseOver) => {
+            let str_id = params.first().copied().unwrap_or(0);
+            let text = str_table.get(str_id as usize).cloned().unwrap_or_default();
+            Some(GameEvent::Hint { str_id, text })
+        }
+        Some(EvtOpcode::LocationName) => {
+            let str_id = params.first().copied().unwrap_or(0);
+            let text = str_table.get(str_id as usize).cloned().unwrap_or_default();
+            Some(GameEvent::LocationName { str_id, text })
+        }
+        Some(EvtOpcode::MoveToMap) => {
+            if params.len() >= 26 {
+                let x = i32_at(params, 0);
+                let y = i32_at(params, 4);
+                let z = i32_at(params, 8);
+                let direction = i32_at(params, 12);
+                let name_bytes = &params[26..];
+                let end = name_bytes.iter().position(|&b| b == 0).unwrap_or(name_bytes.len());
+                let map_name = String::from_utf8_lossy(&name_bytes[..end]).to_string();
+                Some(GameEvent::MoveToMap {
+                    x,
+                    y,
+                    z,
+                    direction,
+                    map_name,
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::OpenChest) => Some(GameEvent::OpenChest {
+            id: params.first().copied().unwrap_or(0),
+        }),
+        Some(EvtOpcode::ShowMessage) => {
+            let str_id = params.first().copied().unwrap_or(0);
+            let text = str_table.get(str_id as usize).cloned().unwrap_or_default();
+            Some(GameEvent::ShowMessage { str_id, text })
+        }
+        Some(EvtOpcode::StatusText) => {
+            let str_id = params.first().copied().unwrap_or(0);
+            let text = str_table.get(str_id as usize).cloned().unwrap_or_default();
+            Some(GameEvent::StatusText { str_id, text })
+        }
+        Some(EvtOpcode::ChangeDoorState) => {
+            if params.len() >= 2 {
+                Some(GameEvent::ChangeDoorState {
+                    door_id: params[0],
+                    action: DoorAction::from_u8(params[1]).unwrap_or(DoorAction::Toggle),
+                })
+            } else {
+                None
+            }
+        }
+        // ── Control flow ────────────────────────────────────────
+        // MM6: Compare = var_id(u8) + value(i32 LE) + jump_step(u8) = 6 bytes
+        Some(EvtOpcode::Compare) => {
+            if params.len() >= 6 {
+                Some(GameEvent::Compare {
+                    var: EvtVariable(params[0]),
+                    value: i32_at(params, 1),
+                    jump_step: params[5],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::Jmp) => Some(GameEvent::Jmp {
+            target_step: params.first().copied().unwrap_or(0),
+        }),
+        Some(EvtOpcode::ForPartyMember) => Some(GameEvent::ForPartyMember {
+            player: params.first().copied().unwrap_or(0),
+        }),
+        // ── Variable operations ─────────────────────────────────
+        // MM6: Add/Subtract/Set = var_id(u8) + value(i32 LE) = 5 bytes
+        Some(EvtOpcode::Add) => {
+            if params.len() >= 5 {
+                Some(GameEvent::Add {
+                    var: EvtVariable(params[0]),
+                    value: i32_at(params, 1),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::Subtract) => {
+            if params.len() >= 5 {
+                Some(GameEvent::Subtract {
+                    var: EvtVariable(params[0]),
+                    value: i32_at(params, 1),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::Set) => {
+            if params.len() >= 5 {
+                Some(GameEvent::Set {
+                    var: EvtVariable(params[0]),
+                    value: i32_at(params, 1),
+                })
+            } else {
+                None
+            }
+        }
+        // ── NPC / item operations ───────────────────────────────
+        // MM6: GiveItem = strength(u8) + type(u8) + id(u32 LE) = 6 bytes
+        Some(EvtOpcode::GiveItem) => {
+            if params.len() >= 6 {
+                Some(GameEvent::GiveItem {
+                    strength: params[0],
+                    item_type: params[1],
+                    item_id: u32_at(params, 2),
+                })
+            } else {
+                None
+            }
+        }
+        // MM6: SetNPCTopic = npc_id(i32) + index(u8) + event_id(i32) = 9 bytes
+        Some(EvtOpcode::SetNPCTopic) => {
+            if params.len() >= 9 {
+                Some(GameEvent::SetNPCTopic {
+                    npc_id: i32_at(params, 0),
+                    topic_index: params[4],
+                    event_id: i32_at(params, 5),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::MoveNPC) => {
+            if params.len() >= 8 {
+                Some(GameEvent::MoveNPC {
+                    npc_id: i32_at(params, 0),
+                    map_id: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::SpeakNPC) => read_i32(params).map(|v| GameEvent::SpeakNPC { npc_id: v }),
+        Some(EvtOpcode::ChangeEvent) => {
+            if params.len() >= 8 {
+                Some(GameEvent::ChangeEvent {
+                    target: i32_at(params, 0),
+                    new_event_id: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::SetNPCGreeting) => {
+            if params.len() >= 8 {
+                Some(GameEvent::SetNPCGreeting {
+                    npc_id: i32_at(params, 0),
+                    greeting_id: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        // ── World operations ────────────────────────────────────
+        // SetFacesBit = face_id(i32) + bit(i32) + on(u8) = 9 bytes
+        Some(EvtOpcode::SetFacesBit) => {
+            if params.len() >= 9 {
+                Some(GameEvent::SetFacesBit {
+                    face_id: i32_at(params, 0),
+                    bit: i32_at(params, 4),
+                    on: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::ToggleActorFlag) => {
+            if params.len() >= 9 {
+                Some(GameEvent::ToggleActorFlag {
+                    actor_id: i32_at(params, 0),
+                    flag: i32_at(params, 4),
+                    on: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::SetTexture) => {
+            if params.len() >= 5 {
+                let face_id = i32_at(params, 0);
+                let name = read_string(&params[4..]);
+                Some(GameEvent::SetTexture {
+                    face_id,
+                    texture_name: name,
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::SetSprite) => {
+            // Format: cog(u32) + hide(u8) + name(null-terminated string)
+            if params.len() >= 6 {
+                let decoration_id = i32_at(params, 0);
+                let name = read_string(&params[5..]);
+                Some(GameEvent::SetSprite {
+                    decoration_id,
+                    sprite_name: name,
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::ToggleIndoorLight) => {
+            if params.len() >= 5 {
+                Some(GameEvent::ToggleIndoorLight {
+                    light_id: i32_at(params, 0),
+                    on: params[4],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::SetSnow) => Some(GameEvent::SetSnow {
+            on: params.first().copied().unwrap_or(0),
+        }),
+        Some(EvtOpcode::SummonMonsters) => {
+            if params.len() >= 20 {
+                Some(GameEvent::SummonMonsters {
+                    monster_id: i32_at(params, 0),
+                    count: i32_at(params, 4),
+                    x: i32_at(params, 8),
+                    y: i32_at(params, 12),
+                    z: i32_at(params, 16),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::CastSpell) => {
+            if params.len() >= 36 {
+                Some(GameEvent::CastSpell {
+                    spell_id: i32_at(params, 0),
+                    skill_level: i32_at(params, 4),
+                    skill_mastery: i32_at(params, 8),
+                    from_x: i32_at(params, 12),
+                    from_y: i32_at(params, 16),
+                    from_z: i32_at(params, 20),
+                    to_x: i32_at(params, 24),
+                    to_y: i32_at(params, 28),
+                    to_z: i32_at(params, 32),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::ReceiveDamage) => {
+            if params.len() >= 8 {
+                Some(GameEvent::ReceiveDamage {
+                    damage_type: i32_at(params, 0),
+                    amount: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::ShowFace) => {
+            if params.len() >= 5 {
+                Some(GameEvent::ShowFace {
+                    player: params[0],
+                    expression: i32_at(params, 1),
+                })
+            } else {
+                None
+            }
+        }
+        // ── Timer / conditional ─────────────────────────────────
+        Some(EvtOpcode::OnTimer) => Some(GameEvent::OnTimer {
+            timer_data: params.to_vec(),
+        }),
+        Some(EvtOpcode::OnLongTimer) => Some(GameEvent::OnLongTimer {
+            timer_data: params.to_vec(),
+        }),
+        Some(EvtOpcode::OnMapReload) => Some(GameEvent::OnMapReload),
+        Some(EvtOpcode::OnMapLeave) => Some(GameEvent::OnMapLeave),
+        Some(EvtOpcode::OnCanShowDialogItemCmp) => {
+            if params.len() >= 5 {
+                Some(GameEvent::OnCanShowDialogItemCmp {
+                    var: EvtVariable(params[0]),
+                    value: i32_at(params, 1),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::EndCanShowDialogItem) => Some(GameEvent::EndCanShowDialogItem),
+        Some(EvtOpcode::SetCanShowDialogItem) => Some(GameEvent::SetCanShowDialogItem {
+            on: params.first().copied().unwrap_or(0),
+        }),
+        // ── Misc ────────────────────────────────────────────────
+        Some(EvtOpcode::IsActorKilled) => {
+            if params.len() >= 9 {
+                Some(GameEvent::IsActorKilled {
+                    actor_group: i32_at(params, 0),
+                    count: i32_at(params, 4),
+                    jump_step: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::CheckSkill) => {
+            if params.len() >= 3 {
+                Some(GameEvent::CheckSkill {
+                    skill_id: params[0],
+                    skill_level: params[1],
+                    jump_step: params[2],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::RandomGoTo) => Some(GameEvent::RandomGoTo { steps: params.to_vec() }),
+        Some(EvtOpcode::SummonItem) => {
+            if params.len() >= 16 {
+                Some(GameEvent::SummonItem {
+                    item_id: i32_at(params, 0),
+                    x: i32_at(params, 4),
+                    y: i32_at(params, 8),
+                    z: i32_at(params, 12),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::CharacterAnimation) => {
+            if params.len() >= 2 {
+                Some(GameEvent::CharacterAnimation {
+                    player: params[0],
+                    anim_id: params[1],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::PressAnyKey) => Some(GameEvent::PressAnyKey),
+        Some(EvtOpcode::SetTextureOutdoors) => {
+            // MM6 opcode 0x0C: SetTextureOutdoors
+            // Params: model(u32) + facet(u32) + texture_name(null-terminated)
+            if params.len() >= 9 {
+                let model = u32_at(params, 0);
+                let facet = u32_at(params, 4);
+                let texture_name = read_string(&params[8..]);
+                Some(GameEvent::SetTextureOutdoors {
+                    model,
+                    facet,
+                    texture_name,
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::CheckItemsCount) => {
+            if params.len() >= 9 {
+                Some(GameEvent::CheckItemsCount {
+                    item_id: i32_at(params, 0),
+                    count: i32_at(params, 4),
+                    jump_step: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::RemoveItems) => {
+            if params.len() >= 8 {
+                Some(GameEvent::RemoveItems {
+                    item_id: i32_at(params, 0),
+                    count: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        // ── Remaining opcodes with param parsing ─────────
+        Some(EvtOpcode::InputString) => Some(GameEvent::InputString {
+            params: params.to_vec(),
+        }),
+        Some(EvtOpcode::SetNPCGroupNews) => {
+            if params.len() >= 8 {
+                Some(GameEvent::SetNPCGroupNews {
+                    npc_group: i32_at(params, 0),
+                    news_id: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::SetActorGroup) => {
+            if params.len() >= 8 {
+                Some(GameEvent::SetActorGroup {
+                    actor_id: i32_at(params, 0),
+                    group_id: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::NPCSetItem) => {
+            if params.len() >= 9 {
+                Some(GameEvent::NPCSetItem {
+                    npc_id: i32_at(params, 0),
+                    item_id: i32_at(params, 4),
+                    on: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::CanShowTopicIsActorKilled) => {
+            if params.len() >= 8 {
+                Some(GameEvent::CanShowTopicIsActorKilled {
+                    actor_group: i32_at(params, 0),
+                    count: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::ChangeGroup) => {
+            if params.len() >= 8 {
+                Some(GameEvent::ChangeGroup {
+                    old_group: i32_at(params, 0),
+                    new_group: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::ChangeGroupAlly) => {
+            if params.len() >= 8 {
+                Some(GameEvent::ChangeGroupAlly {
+                    group_id: i32_at(params, 0),
+                    ally_group: i32_at(params, 4),
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::CheckSeason) => {
+            if params.len() >= 5 {
+                Some(GameEvent::CheckSeason {
+                    season: i32_at(params, 0),
+                    jump_step: params[4],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::ToggleActorGroupFlag) => {
+            if params.len() >= 9 {
+                Some(GameEvent::ToggleActorGroupFlag {
+                    group_id: i32_at(params, 0),
+                    flag: i32_at(params, 4),
+                    on: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::ToggleChestFlag) => {
+            if params.len() >= 9 {
+                Some(GameEvent::ToggleChestFlag {
+                    chest_id: i32_at(params, 0),
+                    flag: i32_at(params, 4),
+                    on: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::SetActorItem) => {
+            if params.len() >= 9 {
+                Some(GameEvent::SetActorItem {
+                    actor_id: i32_at(params, 0),
+                    item_id: i32_at(params, 4),
+                    on: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::OnDateTimer) => Some(GameEvent::OnDateTimer {
+            timer_data: params.to_vec(),
+        }),
+        Some(EvtOpcode::EnableDateTimer) => {
+            if params.len() >= 5 {
+                Some(GameEvent::EnableDateTimer {
+                    timer_id: i32_at(params, 0),
+                    on: params[4],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::StopAnimation) => read_i32(params).map(|v| GameEvent::StopAnimation { decoration_id: v }),
+        Some(EvtOpcode::SpecialJump) => read_i32(params).map(|v| GameEvent::SpecialJump { jump_value: v }),
+        Some(EvtOpcode::IsTotalBountyHuntingAwardInRange) => {
+            if params.len() >= 9 {
+                Some(GameEvent::IsTotalBountyHuntingAwardInRange {
+                    min: i32_at(params, 0),
+                    max: i32_at(params, 4),
+                    jump_step: params[8],
+                })
+            } else {
+                None
+            }
+        }
+        Some(EvtOpcode::IsNPCInParty) => {
+            if params.len() >= 5 {
+                Some(GameEvent::IsNPCInParty {
+                    npc_id: i32_at(params, 0),
+                    jump_step: params[4],
+                })
+            } else {
+                None
+            }
+        }
+        _ => Some(GameEvent::Unhandled {
+            opcode,
+            opcode_name: evt_opcode.map_or("Unknown", |o| o.name()),
+            params: params.to_vec(),
+        }),
+    }
+}
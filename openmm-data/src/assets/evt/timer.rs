@@ -0,0 +1,177 @@
+//! Timer specs of `OnTimer`/`OnLongTimer`/`OnDateTimer` steps.
+//!
+//! All three share one parameter layout (missing trailing bytes read as 0):
+//!   byte 0: yearly flag, 1: monthly flag, 2: weekly flag,
+//!   byte 3: hour, 4: minute, 5: second (time of day for the flags above),
+//!   bytes 6-7: interval in half game minutes (u16 LE; 0 = calendar timer)
+//!
+//! A non-zero interval makes a repeating timer; otherwise the timer fires
+//! at the time of day every year (Jan 1), month (the 1st), week (Monday) or,
+//! with no flag set, every day. Times are in game minutes since the epoch
+//! (midnight, Jan 1, Year 1000 — a Monday), as in [`crate::utils::time`].
+
+use super::GameEvent;
+use crate::utils::time::{self, MINS_PER_DAY};
+
+/// How often a timer step fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerSchedule {
+    /// Every `minutes` game minutes, counted from when the timer was armed.
+    Interval { minutes: u64 },
+    /// At `minute_of_day` on each day / Monday / 1st of the month / Jan 1.
+    Calendar { period: TimerPeriod, minute_of_day: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl TimerSchedule {
+    /// Decode the shared timer parameter layout.
+    pub fn decode(params: &[u8]) -> Self {
+        let byte = |i: usize| params.get(i).copied().unwrap_or(0);
+        let half_minutes = u16::from_le_bytes([byte(6), byte(7)]) as u64;
+        if half_minutes > 0 {
+            return Self::Interval {
+                minutes: half_minutes.div_ceil(2),
+            };
+        }
+        let period = if byte(0) != 0 {
+            TimerPeriod::Yearly
+        } else if byte(1) != 0 {
+            TimerPeriod::Monthly
+        } else if byte(2) != 0 {
+            TimerPeriod::Weekly
+        } else {
+            TimerPeriod::Daily
+        };
+        // Seconds are below the clock's resolution; round them up.
+        let minute_of_day = (byte(3) as u64 * 60 + byte(4) as u64 + (byte(5) > 0) as u64) % MINS_PER_DAY;
+        Self::Calendar { period, minute_of_day }
+    }
+
+    /// The schedule of a timer step, or `None` for any other event.
+    pub fn of(event: &GameEvent) -> Option<Self> {
+        match event {
+            GameEvent::OnTimer { timer_data }
+            | GameEvent::OnLongTimer { timer_data }
+            | GameEvent::OnDateTimer { timer_data } => Some(Self::decode(timer_data)),
+            _ => None,
+        }
+    }
+
+    /// First fire time strictly after `now`. Interval timers stay on the grid
+    /// that starts at `armed_at`, so a long jump lands on the next tick rather
+    /// than restarting the interval from `now`.
+    pub fn next_after(&self, now: u64, armed_at: u64) -> u64 {
+        match *self {
+            Self::Interval { minutes } => {
+                let minutes = minutes.max(1);
+                let elapsed = now.saturating_sub(armed_at);
+                armed_at + (elapsed / minutes + 1) * minutes
+            }
+            Self::Calendar { period, minute_of_day } => {
+                let mut day = now / MINS_PER_DAY;
+                loop {
+                    let at = day * MINS_PER_DAY + minute_of_day;
+                    if at > now && period.starts_on(day) {
+                        return at;
+                    }
+                    day += 1;
+                }
+            }
+        }
+    }
+}
+
+impl TimerPeriod {
+    /// Whether `day` (days since the epoch) starts this period.
+    fn starts_on(self, day: u64) -> bool {
+        let (_, month, date) = time::date(day * MINS_PER_DAY);
+        match self {
+            Self::Daily => true,
+            Self::Weekly => day.is_multiple_of(7),
+            Self::Monthly => date == 1,
+            Self::Yearly => month == 1 && date == 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 60;
+    const DAY: u64 = MINS_PER_DAY;
+
+    #[test]
+    fn decodes_interval_and_calendar_timers() {
+        assert_eq!(
+            TimerSchedule::decode(&[0, 0, 0, 0, 0, 0, 0x78, 0x00]),
+            TimerSchedule::Interval { minutes: 60 }
+        );
+        assert_eq!(
+            TimerSchedule::decode(&[0, 0, 0, 6, 30]),
+            TimerSchedule::Calendar {
+                period: TimerPeriod::Daily,
+                minute_of_day: 6 * HOUR + 30
+            }
+        );
+        assert_eq!(
+            TimerSchedule::decode(&[0, 1, 0, 0, 0, 0, 0, 0]),
+            TimerSchedule::Calendar {
+                period: TimerPeriod::Monthly,
+                minute_of_day: 0
+            }
+        );
+        // Short params read as zero-padded: byte 2 is the weekly flag.
+        let on_timer = GameEvent::OnTimer {
+            timer_data: vec![0, 0, 1],
+        };
+        assert_eq!(
+            TimerSchedule::of(&on_timer),
+            Some(TimerSchedule::Calendar {
+                period: TimerPeriod::Weekly,
+                minute_of_day: 0
+            })
+        );
+        assert_eq!(TimerSchedule::of(&GameEvent::Exit), None);
+    }
+
+    #[test]
+    fn next_fire_catches_up_after_a_jump() {
+        let every_hour = TimerSchedule::Interval { minutes: 60 };
+        assert_eq!(every_hour.next_after(100, 100), 160);
+        // Resting 10 hours and a bit lands on the next tick of the same grid.
+        assert_eq!(every_hour.next_after(100 + 10 * HOUR + 5, 100), 100 + 11 * HOUR);
+
+        let dawn = TimerSchedule::Calendar {
+            period: TimerPeriod::Daily,
+            minute_of_day: 6 * HOUR,
+        };
+        assert_eq!(dawn.next_after(5 * HOUR, 0), 6 * HOUR);
+        assert_eq!(dawn.next_after(6 * HOUR, 0), DAY + 6 * HOUR);
+        assert_eq!(dawn.next_after(3 * DAY + 12 * HOUR, 0), 4 * DAY + 6 * HOUR);
+
+        // Day 0 is Monday, Jan 1 1000.
+        let weekly = TimerSchedule::Calendar {
+            period: TimerPeriod::Weekly,
+            minute_of_day: 0,
+        };
+        assert_eq!(weekly.next_after(1, 0), 7 * DAY);
+        let monthly = TimerSchedule::Calendar {
+            period: TimerPeriod::Monthly,
+            minute_of_day: 0,
+        };
+        assert_eq!(monthly.next_after(1, 0), 31 * DAY);
+        let yearly = TimerSchedule::Calendar {
+            period: TimerPeriod::Yearly,
+            minute_of_day: 0,
+        };
+        assert_eq!(yearly.next_after(1, 0), 365 * DAY);
+    }
+}
@@ -0,0 +1,347 @@
+//! FLAC for Audio.snd export and import: [`encode`] writes 16-bit PCM
+//! losslessly, [`decode`] reads replacement sounds back in.
+//!
+//! The encoder writes a STREAMINFO block and 4096-sample frames whose
+//! subframes are constant, verbatim or fixed-predictor (orders 0-4) with
+//! partitioned Rice residuals; channels are coded independently and the MD5
+//! field is left zero ("unknown"). Decoding is done by `claxon`, which also
+//! checks the encoder's frame CRCs in the tests.
+
+use crate::Error;
+
+const BLOCK_SIZE: usize = 4096;
+/// Highest Rice partition order tried when encoding.
+const MAX_PARTITION_ORDER: u32 = 6;
+
+/// Encode interleaved 16-bit samples as a FLAC file.
+pub fn encode(samples: &[i16], channels: u16, sample_rate: u32) -> Result<Vec<u8>, Error> {
+    if !(1..=8).contains(&channels) || sample_rate == 0 || sample_rate >= 1 << 20 {
+        return Err(Error::invalid(
+            "flac",
+            format!("unsupported format: {sample_rate} Hz, {channels} channels"),
+        ));
+    }
+    let ch = channels as usize;
+    let total = samples.len() / ch;
+
+    let mut out = BitWriter::default();
+    out.bytes.extend_from_slice(b"fLaC");
+    // Last metadata block, type 0 (STREAMINFO), 34 bytes.
+    out.bits(1, 1);
+    out.bits(0, 7);
+    out.bits(34, 24);
+    out.bits(BLOCK_SIZE as u64, 16);
+    out.bits(BLOCK_SIZE as u64, 16);
+    out.bits(0, 24);
+    out.bits(0, 24);
+    out.bits(sample_rate as u64, 20);
+    out.bits(ch as u64 - 1, 3);
+    out.bits(15, 5);
+    out.bits(total as u64, 36);
+    out.bytes.extend_from_slice(&[0; 16]);
+
+    let mut channel = Vec::with_capacity(BLOCK_SIZE);
+    for (index, start) in (0..total).step_by(BLOCK_SIZE).enumerate() {
+        let len = BLOCK_SIZE.min(total - start);
+        let frame_start = out.bytes.len();
+
+        out.bits(0xFFF8, 16);
+        out.bits(0b0111, 4);
+        out.bits(0, 4);
+        out.bits(ch as u64 - 1, 4);
+        out.bits(0b100, 3);
+        out.bits(0, 1);
+        write_utf8(&mut out, index as u32);
+        out.bits(len as u64 - 1, 16);
+        let crc = crc8(&out.bytes[frame_start..]);
+        out.bits(crc as u64, 8);
+
+        for c in 0..ch {
+            channel.clear();
+            channel.extend((start..start + len).map(|i| samples[i * ch + c] as i32));
+            write_subframe(&mut out, &channel);
+        }
+        out.align();
+        let crc = crc16(&out.bytes[frame_start..]);
+        out.bits(crc as u64, 16);
+    }
+    Ok(out.bytes)
+}
+
+/// Decode a FLAC file to interleaved 16-bit samples, channel count and
+/// rate. Other bit depths are scaled to 16 bits.
+pub fn decode(data: &[u8]) -> Result<(Vec<i16>, u16, u32), Error> {
+    if data.len() < 4 || &data[..4] != b"fLaC" {
+        return Err(Error::BadMagic {
+            format: "flac",
+            found: data[..data.len().min(4)].to_vec(),
+        });
+    }
+    let mut reader = claxon::FlacReader::new(data).map_err(flac_error)?;
+    let info = reader.streaminfo();
+    let bps = info.bits_per_sample;
+    let samples = reader
+        .samples()
+        .map(|s| {
+            s.map(|s| match bps {
+                ..16 => (s << (16 - bps)) as i16,
+                _ => (s >> (bps - 16)) as i16,
+            })
+        })
+        .collect::<Result<_, _>>()
+        .map_err(flac_error)?;
+    Ok((samples, info.channels as u16, info.sample_rate))
+}
+
+fn flac_error(e: claxon::Error) -> Error {
+    match e {
+        claxon::Error::IoError(e) => Error::from(e),
+        claxon::Error::FormatError(msg) | claxon::Error::Unsupported(msg) => Error::invalid("flac", msg),
+    }
+}
+
+fn write_subframe(out: &mut BitWriter, samples: &[i32]) {
+    if samples.iter().all(|&s| s == samples[0]) {
+        out.bits(0, 8);
+        out.signed(samples[0], 16);
+        return;
+    }
+
+    let verbatim_bits = 16 * samples.len() as u64;
+    let best = (0..=4usize)
+        .filter(|&order| order < samples.len())
+        .map(|order| {
+            let residual = fixed_residual(samples, order);
+            let (bits, plan) = rice_plan(&residual, order, samples.len());
+            (bits + 16 * order as u64, order, residual, plan)
+        })
+        .min_by_key(|&(bits, order, ..)| (bits, order));
+
+    match best {
+        Some((bits, order, residual, plan)) if bits < verbatim_bits => {
+            out.bits(0b001000 | order as u64, 7);
+            out.bits(0, 1);
+            for &s in &samples[..order] {
+                out.signed(s, 16);
+            }
+            write_residual(out, &residual, &plan, order, samples.len());
+        }
+        _ => {
+            out.bits(0b000001, 7);
+            out.bits(0, 1);
+            for &s in samples {
+                out.signed(s, 16);
+            }
+        }
+    }
+}
+
+/// Residual of the fixed predictor of `order`, for samples `order..`.
+fn fixed_residual(s: &[i32], order: usize) -> Vec<i32> {
+    (order..s.len())
+        .map(|i| match order {
+            0 => s[i],
+            1 => s[i] - s[i - 1],
+            2 => s[i] - 2 * s[i - 1] + s[i - 2],
+            3 => s[i] - 3 * s[i - 1] + 3 * s[i - 2] - s[i - 3],
+            _ => s[i] - 4 * s[i - 1] + 6 * s[i - 2] - 4 * s[i - 3] + s[i - 4],
+        })
+        .collect()
+}
+
+/// Rice partitioning: partition order and parameters.
+struct RicePlan {
+    order: u32,
+    params: Vec<u32>,
+}
+
+fn zigzag(r: i32) -> u64 {
+    ((r as i64) << 1 ^ (r as i64) >> 63) as u64
+}
+
+/// Cheapest partition order (and per-partition parameters) for `residual`,
+/// with the residual section's size in bits.
+fn rice_plan(residual: &[i32], predictor_order: usize, block_size: usize) -> (u64, RicePlan) {
+    let mut best: Option<(u64, RicePlan)> = None;
+    for order in 0..=MAX_PARTITION_ORDER {
+        let partitions = 1usize << order;
+        if !block_size.is_multiple_of(partitions) || block_size / partitions <= predictor_order {
+            break;
+        }
+        let mut bits = 6 + 5 * partitions as u64;
+        let mut params = Vec::with_capacity(partitions);
+        let mut start = 0;
+        for p in 0..partitions {
+            let len = block_size / partitions - if p == 0 { predictor_order } else { 0 };
+            let part = &residual[start..start + len];
+            start += len;
+            let sum: u64 = part.iter().map(|&r| zigzag(r)).sum();
+            let mean = sum / len.max(1) as u64;
+            let guess = (64 - mean.leading_zeros()).saturating_sub(1);
+            let (cost, k) = (guess.saturating_sub(1)..=(guess + 1).min(30))
+                .map(|k| {
+                    (
+                        len as u64 * (k as u64 + 1) + part.iter().map(|&r| zigzag(r) >> k).sum::<u64>(),
+                        k,
+                    )
+                })
+                .min()
+                .unwrap_or((0, 0));
+            bits += cost;
+            params.push(k);
+        }
+        if best.as_ref().is_none_or(|(b, _)| bits < *b) {
+            best = Some((bits, RicePlan { order, params }));
+        }
+    }
+    best.unwrap_or((
+        u64::MAX,
+        RicePlan {
+            order: 0,
+            params: vec![0],
+        },
+    ))
+}
+
+fn write_residual(out: &mut BitWriter, residual: &[i32], plan: &RicePlan, predictor_order: usize, block_size: usize) {
+    // Method 1 (5-bit parameters) only when a parameter does not fit in 4 bits.
+    let wide = plan.params.iter().any(|&k| k >= 15);
+    out.bits(wide as u64, 2);
+    out.bits(plan.order as u64, 4);
+    let partitions = 1usize << plan.order;
+    let mut start = 0;
+    for (p, &k) in plan.params.iter().enumerate() {
+        let len = block_size / partitions - if p == 0 { predictor_order } else { 0 };
+        out.bits(k as u64, if wide { 5 } else { 4 });
+        for &r in &residual[start..start + len] {
+            let u = zigzag(r);
+            out.zeros(u >> k);
+            out.bits(1, 1);
+            out.bits(u & ((1 << k) - 1), k);
+        }
+        start += len;
+    }
+}
+
+/// Frame numbers use UTF-8's variable-length scheme, extended to 31 bits.
+fn write_utf8(out: &mut BitWriter, v: u32) {
+    if v < 0x80 {
+        out.bits(v as u64, 8);
+        return;
+    }
+    let extra = match v {
+        0x80..0x800 => 1,
+        0x800..0x1_0000 => 2,
+        0x1_0000..0x20_0000 => 3,
+        0x20_0000..0x400_0000 => 4,
+        _ => 5,
+    };
+    let lead = (0xFF00u32 >> (extra + 1)) as u8 | (v >> (6 * extra)) as u8;
+    out.bits(lead as u64, 8);
+    for i in (0..extra).rev() {
+        out.bits(0x80 | ((v >> (6 * i)) & 0x3F) as u64, 8);
+    }
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |crc, &b| {
+        (0..8).fold(crc ^ b, |c, _| if c & 0x80 != 0 { c << 1 ^ 0x07 } else { c << 1 })
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, &b| {
+        (0..8).fold(crc ^ (b as u16) << 8, |c, _| {
+            if c & 0x8000 != 0 { c << 1 ^ 0x8005 } else { c << 1 }
+        })
+    })
+}
+
+/// MSB-first bit packer.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    used: u32,
+}
+
+impl BitWriter {
+    fn bits(&mut self, value: u64, count: u32) {
+        for i in (0..count).rev() {
+            if self.used == 0 {
+                self.bytes.push(0);
+            }
+            if value >> i & 1 != 0 {
+                *self.bytes.last_mut().expect("pushed above") |= 0x80 >> self.used;
+            }
+            self.used = (self.used + 1) % 8;
+        }
+    }
+
+    fn signed(&mut self, value: i32, count: u32) {
+        self.bits(value as u64 & ((1 << count) - 1), count);
+    }
+
+    fn zeros(&mut self, count: u64) {
+        for _ in 0..count {
+            self.bits(0, 1);
+        }
+    }
+
+    fn align(&mut self) {
+        self.used = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_mono_and_stereo() {
+        // Tone + noise, a silent stretch (constant subframes) and a short tail.
+        let mut seed = 1u32;
+        let mut noise = || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) as i32 % 2000 - 1000
+        };
+        let mono: Vec<i16> = (0..10_000)
+            .map(|i| match i {
+                4096..8192 => 0,
+                _ => ((i as f32 * 0.05).sin() * 20000.0) as i16 + noise() as i16,
+            })
+            .collect();
+        let bytes = encode(&mono, 1, 22050).unwrap();
+        assert!(bytes.len() < mono.len() * 2, "should compress");
+        assert_eq!(decode(&bytes).unwrap(), (mono.clone(), 1, 22050));
+
+        let stereo: Vec<i16> = mono.iter().flat_map(|&s| [s, s.saturating_neg()]).collect();
+        let bytes = encode(&stereo, 2, 44100).unwrap();
+        assert_eq!(decode(&bytes).unwrap(), (stereo, 2, 44100));
+    }
+
+    #[test]
+    fn extreme_samples_survive() {
+        let samples: Vec<i16> = (0..300).map(|i| if i % 2 == 0 { i16::MAX } else { i16::MIN }).collect();
+        let bytes = encode(&samples, 1, 8000).unwrap();
+        assert_eq!(decode(&bytes).unwrap().0, samples);
+    }
+
+    #[test]
+    fn multi_byte_frame_numbers_decode() {
+        // 200 frames: numbers from 128 on take two UTF-8 bytes.
+        let samples: Vec<i16> = (0..200 * BLOCK_SIZE).map(|i| (i / BLOCK_SIZE) as i16).collect();
+        let bytes = encode(&samples, 1, 11025).unwrap();
+        assert_eq!(decode(&bytes).unwrap(), (samples, 1, 11025));
+    }
+
+    #[test]
+    fn rejects_corrupt_streams() {
+        let samples: Vec<i16> = (0..5000).map(|i| (i * 7 % 300) as i16).collect();
+        let mut bytes = encode(&samples, 1, 8000).unwrap();
+        assert!(matches!(decode(b"RIFF...."), Err(Error::BadMagic { .. })));
+        // A flipped bit in the last frame fails its CRC check.
+        let last = bytes.len() - 1;
+        bytes[last - 200] ^= 0x10;
+        assert!(decode(&bytes).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-//! MM6/MM7 bitmap font parser (.fnt files from LOD archives).
+//! MM6/MM7 bitmap font parser and writer (.fnt files from LOD archives).
 //!
 //! Font files are stored in the icons LOD archive as zlib-compressed binary data.
 //! After decompression the format is:
@@ -7,18 +7,24 @@
 //! - **Metrics** (256 × 12 bytes): per-character left_spacing (i32), width (i32), right_spacing (i32)
 //! - **Offsets** (256 × 4 bytes): byte offset into pixel data for each character
 //! - **Pixels** (variable): grayscale glyph bitmaps (0 = transparent, 1 = shadow, 255 = text)
+//!
+//! [`LodSerialise`] writes the same layout back; `font_raster` builds new
+//! fonts from TrueType/OpenType files.
 
-use std::error::Error;
-
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::Cursor;
 
+use crate::Error;
+use crate::LodSerialise;
+
 const HEADER_SIZE: usize = 32;
 const NUM_CHARS: usize = 256;
 const METRICS_SIZE: usize = NUM_CHARS * 12; // 3 × i32 per character
 const OFFSETS_SIZE: usize = NUM_CHARS * 4; // 1 × u32 per character
 const TABLE_SIZE: usize = METRICS_SIZE + OFFSETS_SIZE; // 4096
 const SHADOW_ALPHA: u8 = 192;
+/// Header byte 2 in every shipped font; new fonts copy it.
+const DEFAULT_FIELD_2: u8 = 8;
 
 /// Per-character spacing and width.
 #[derive(Debug, Clone, Copy, Default)]
@@ -53,29 +59,27 @@ pub struct Font {
     offsets: [u32; NUM_CHARS],
     /// Raw glyph pixel data (0 = transparent, 1 = shadow, 255 = text body).
     pixels: Vec<u8>,
+    /// Header as read, re-emitted with the char range and height patched in.
+    header: [u8; HEADER_SIZE],
 }
 
 impl Font {
     /// Parse a font from decompressed .fnt data.
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
-        if data.len() < HEADER_SIZE + TABLE_SIZE {
-            return Err(format!(
-                "font data too short: {} bytes (need at least {})",
-                data.len(),
-                HEADER_SIZE + TABLE_SIZE
-            )
-            .into());
-        }
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        Error::check_len("fnt", data, 0, HEADER_SIZE + TABLE_SIZE)?;
 
         let first_char = data[0];
         let last_char = data[1];
         let height = data[5];
 
         if first_char > last_char {
-            return Err(format!("invalid char range: {}..{}", first_char, last_char).into());
+            return Err(Error::invalid(
+                "fnt",
+                format!("invalid char range: {}..{}", first_char, last_char),
+            ));
         }
         if height == 0 {
-            return Err("font height is zero".into());
+            return Err(Error::invalid("fnt", "font height is zero"));
         }
 
         // Parse metrics (256 entries × 12 bytes each)
@@ -102,6 +106,66 @@ impl Font {
             metrics,
             offsets,
             pixels,
+            header: data[..HEADER_SIZE].try_into()?,
+        })
+    }
+
+    /// Build a font from per-character metrics and glyph bitmaps.
+    ///
+    /// `glyphs[i]` describes character `first_char + i`; its bitmap holds
+    /// `width × height` bytes in the same 0/1/255 encoding as parsed fonts.
+    pub fn from_glyphs(
+        first_char: u8,
+        last_char: u8,
+        height: u8,
+        glyphs: &[(GlyphMetrics, Vec<u8>)],
+    ) -> Result<Self, Error> {
+        if first_char > last_char {
+            return Err(Error::invalid(
+                "fnt",
+                format!("invalid char range: {}..{}", first_char, last_char),
+            ));
+        }
+        if height == 0 {
+            return Err(Error::invalid("fnt", "font height is zero"));
+        }
+        let count = (last_char - first_char) as usize + 1;
+        if glyphs.len() != count {
+            return Err(Error::invalid(
+                "fnt",
+                format!(
+                    "expected {count} glyphs for {first_char}..={last_char}, got {}",
+                    glyphs.len()
+                ),
+            ));
+        }
+
+        let mut metrics = [GlyphMetrics::default(); NUM_CHARS];
+        let mut offsets = [0u32; NUM_CHARS];
+        let mut pixels = Vec::new();
+        for (ch, (m, bitmap)) in (first_char..=last_char).zip(glyphs) {
+            let expected = m.width.max(0) as usize * height as usize;
+            if bitmap.len() != expected {
+                return Err(Error::invalid(
+                    "fnt",
+                    format!("glyph {ch}: {} bytes, expected {expected}", bitmap.len()),
+                ));
+            }
+            metrics[ch as usize] = *m;
+            offsets[ch as usize] = pixels.len() as u32;
+            pixels.extend_from_slice(bitmap);
+        }
+
+        let mut header = [0u8; HEADER_SIZE];
+        header[2] = DEFAULT_FIELD_2;
+        Ok(Self {
+            first_char,
+            last_char,
+            height,
+            metrics,
+            offsets,
+            pixels,
+            header,
         })
     }
 
@@ -192,10 +256,32 @@ impl Font {
     }
 }
 
+impl LodSerialise for Font {
+    /// Uncompressed .fnt bytes; pack with [`LodData`](super::LodData) for a LOD entry.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_SIZE + TABLE_SIZE + self.pixels.len());
+        let mut header = self.header;
+        header[0] = self.first_char;
+        header[1] = self.last_char;
+        header[5] = self.height;
+        out.extend_from_slice(&header);
+        for m in &self.metrics {
+            out.write_i32::<LittleEndian>(m.left_spacing).unwrap();
+            out.write_i32::<LittleEndian>(m.width).unwrap();
+            out.write_i32::<LittleEndian>(m.right_spacing).unwrap();
+        }
+        for &o in &self.offsets {
+            out.write_u32::<LittleEndian>(o).unwrap();
+        }
+        out.extend_from_slice(&self.pixels);
+        out
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use byteorder::WriteBytesExt;
+    use crate::assets::test_lod;
 
     /// Build a minimal synthetic font with one glyph ('A' = 65) of width 3, height 2.
     fn make_test_font() -> Vec<u8> {
@@ -276,9 +362,53 @@ mod tests {
         assert_eq!(&buf[16..20], &[0, 0, 0, SHADOW_ALPHA]);
     }
 
+    #[test]
+    fn serialise_is_byte_identical() {
+        let data = make_test_font();
+        let font = Font::parse(&data).unwrap();
+        assert_eq!(font.to_bytes(), data);
+    }
+
+    #[test]
+    fn from_glyphs_round_trips() {
+        let a = GlyphMetrics {
+            left_spacing: 1,
+            width: 2,
+            right_spacing: 0,
+        };
+        let glyphs = vec![(GlyphMetrics::default(), vec![]), (a, vec![255, 0, 1, 255])];
+        let font = Font::from_glyphs(64, 65, 2, &glyphs).unwrap();
+        let reparsed = Font::parse(&font.to_bytes()).unwrap();
+        assert_eq!((reparsed.first_char, reparsed.last_char, reparsed.height), (64, 65, 2));
+        assert!(!reparsed.has_glyph(b'@'));
+        assert_eq!(reparsed.glyph_pixels(b'A').unwrap(), &[255, 0, 1, 255]);
+        assert_eq!(reparsed.measure("A"), 3);
+
+        assert!(Font::from_glyphs(64, 65, 2, &glyphs[..1]).is_err());
+        assert!(Font::from_glyphs(65, 65, 2, &[(a, vec![255])]).is_err());
+    }
+
+    #[test]
+    fn game_fonts_round_trip() {
+        let Some(assets) = test_lod() else {
+            return;
+        };
+        for name in assets.files_in("icons").unwrap_or_default() {
+            if !name.to_lowercase().ends_with(".fnt") {
+                continue;
+            }
+            let data = assets.get_decompressed(format!("icons/{name}")).unwrap();
+            let font = Font::parse(&data).unwrap();
+            assert_eq!(font.to_bytes(), data, "{name}");
+        }
+    }
+
     #[test]
     fn reject_invalid_data() {
-        assert!(Font::parse(&[0; 10]).is_err()); // too short
+        assert!(matches!(
+            Font::parse(&[0; 10]),
+            Err(Error::Truncated { format: "fnt", .. })
+        ));
         let mut data = vec![0u8; HEADER_SIZE + TABLE_SIZE];
         data[0] = 100; // first_char > last_char
         data[1] = 50;
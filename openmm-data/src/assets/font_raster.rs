@@ -0,0 +1,264 @@
+//! Rasterize TrueType/OpenType fonts into MM6 bitmap [`Font`]s.
+//!
+//! Game text is single-byte, so each font slot is a byte in some Windows
+//! code page; [`Codepage`] picks which Unicode character is drawn into it.
+//! Glyphs are thresholded to the 0/255 body of the original fonts and get
+//! the same one-pixel drop shadow (value 1) below and to the right.
+
+use std::ops::RangeInclusive;
+
+use ab_glyph::{Font as _, FontRef, PxScale, ScaleFont, point};
+
+use crate::Error;
+
+use super::font::{Font, GlyphMetrics};
+
+/// Coverage at or above this becomes a text pixel.
+const COVERAGE_THRESHOLD: f32 = 0.35;
+
+const TEXT: u8 = 255;
+const SHADOW: u8 = 1;
+
+/// Byte → character mapping for font slots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Codepage {
+    /// Western European; what the shipped MM6 data uses.
+    #[default]
+    Windows1252,
+    /// Cyrillic.
+    Windows1251,
+}
+
+/// Windows-1252 0x80..=0x9F (the rest is Latin-1).
+const CP1252_80: [Option<char>; 32] = [
+    Some('€'),
+    None,
+    Some('‚'),
+    Some('ƒ'),
+    Some('„'),
+    Some('…'),
+    Some('†'),
+    Some('‡'),
+    Some('ˆ'),
+    Some('‰'),
+    Some('Š'),
+    Some('‹'),
+    Some('Œ'),
+    None,
+    Some('Ž'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201C}'),
+    Some('\u{201D}'),
+    Some('•'),
+    Some('–'),
+    Some('—'),
+    Some('˜'),
+    Some('™'),
+    Some('š'),
+    Some('›'),
+    Some('œ'),
+    None,
+    Some('ž'),
+    Some('Ÿ'),
+];
+
+/// Windows-1251 0x80..=0xBF (0xC0..=0xFF is А..я).
+const CP1251_80: &str =
+    "ЂЃ‚ѓ„…†‡€‰Љ‹ЊЌЋЏђ\u{2018}\u{2019}\u{201C}\u{201D}•–—\u{FFFD}™љ›њќћџ\u{A0}ЎўЈ¤Ґ¦§Ё©Є«¬\u{AD}®Ї°±Ііґµ¶·ё№є»јЅѕї";
+
+impl Codepage {
+    /// Character stored at `byte`, or `None` for control codes and unassigned slots.
+    pub fn char_for(self, byte: u8) -> Option<char> {
+        match byte {
+            0..=0x1F | 0x7F => None,
+            0x20..=0x7E => Some(byte as char),
+            _ => match self {
+                Self::Windows1252 => match byte {
+                    0x80..=0x9F => CP1252_80[(byte - 0x80) as usize],
+                    _ => Some(byte as char),
+                },
+                Self::Windows1251 => match byte {
+                    0x80..=0xBF => CP1251_80
+                        .chars()
+                        .nth((byte - 0x80) as usize)
+                        .filter(|&c| c != '\u{FFFD}'),
+                    _ => char::from_u32(0x410 + (byte - 0xC0) as u32),
+                },
+            },
+        }
+    }
+}
+
+/// Builds a [`Font`] from TrueType/OpenType data.
+///
+/// ```ignore
+/// let font = FontRaster::new(&ttf_bytes, 14)
+///     .chars(32..=255)
+///     .codepage(Codepage::Windows1251)
+///     .build()?;
+/// ```
+pub struct FontRaster<'a> {
+    data: &'a [u8],
+    height: u8,
+    chars: RangeInclusive<u8>,
+    codepage: Codepage,
+}
+
+impl<'a> FontRaster<'a> {
+    /// Rasterize `data` so ascent + descent spans `height` pixel rows.
+    pub fn new(data: &'a [u8], height: u8) -> Self {
+        Self {
+            data,
+            height,
+            chars: 32..=255,
+            codepage: Codepage::default(),
+        }
+    }
+
+    /// Byte range to fill (default `32..=255`).
+    pub fn chars(mut self, chars: RangeInclusive<u8>) -> Self {
+        self.chars = chars;
+        self
+    }
+
+    /// Code page mapping bytes to characters (default Windows-1252).
+    pub fn codepage(mut self, codepage: Codepage) -> Self {
+        self.codepage = codepage;
+        self
+    }
+
+    pub fn build(&self) -> Result<Font, Error> {
+        if self.height == 0 {
+            return Err(Error::invalid("fnt", "font height is zero"));
+        }
+        let ttf = FontRef::try_from_slice(self.data)?;
+        let scaled = ttf.as_scaled(PxScale::from(self.height as f32));
+        let rows = self.height as usize;
+
+        let glyphs = self
+            .chars
+            .clone()
+            .map(|byte| {
+                let Some(ch) = self.codepage.char_for(byte) else {
+                    return (GlyphMetrics::default(), Vec::new());
+                };
+                let id = ttf.glyph_id(ch);
+                if id.0 == 0 {
+                    return (GlyphMetrics::default(), Vec::new());
+                }
+                let advance = scaled.h_advance(id).round() as i32;
+                let glyph = id.with_scale_and_position(scaled.scale(), point(0.0, scaled.ascent()));
+                let Some(outline) = ttf.outline_glyph(glyph) else {
+                    // Blank glyph (space): advance only.
+                    return (
+                        GlyphMetrics {
+                            left_spacing: 0,
+                            width: 0,
+                            right_spacing: advance,
+                        },
+                        Vec::new(),
+                    );
+                };
+
+                let bounds = outline.px_bounds();
+                let left = bounds.min.x as i32;
+                let top = bounds.min.y as i32;
+                let ink_w = bounds.width() as usize;
+                let mut coverage = vec![0f32; ink_w * rows];
+                outline.draw(|x, y, c| {
+                    let row = top + y as i32;
+                    if (0..rows as i32).contains(&row) && (x as usize) < ink_w {
+                        coverage[row as usize * ink_w + x as usize] = c;
+                    }
+                });
+                let bitmap = shade(&coverage, ink_w, rows);
+                let width = ink_w as i32 + 1;
+                let left_spacing = left.max(0);
+                (
+                    GlyphMetrics {
+                        left_spacing,
+                        width,
+                        right_spacing: advance - left_spacing - width,
+                    },
+                    bitmap,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Font::from_glyphs(*self.chars.start(), *self.chars.end(), self.height, &glyphs)
+    }
+}
+
+/// Threshold `w × rows` coverage into text pixels and add the drop shadow.
+/// The result is one column wider to hold the shadow.
+fn shade(coverage: &[f32], w: usize, rows: usize) -> Vec<u8> {
+    let out_w = w + 1;
+    let mut out = vec![0u8; out_w * rows];
+    for y in 0..rows {
+        for x in 0..w {
+            if coverage[y * w + x] >= COVERAGE_THRESHOLD {
+                out[y * out_w + x] = TEXT;
+            }
+        }
+    }
+    for y in (0..rows.saturating_sub(1)).rev() {
+        for x in (0..w).rev() {
+            let below = (y + 1) * out_w + x + 1;
+            if out[y * out_w + x] == TEXT && out[below] == 0 {
+                out[below] = SHADOW;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codepages_map_bytes() {
+        let cp = Codepage::Windows1252;
+        assert_eq!(cp.char_for(b'A'), Some('A'));
+        assert_eq!(cp.char_for(0x0A), None);
+        assert_eq!(cp.char_for(0x92), Some('\u{2019}'));
+        assert_eq!(cp.char_for(0x81), None);
+        assert_eq!(cp.char_for(0xE9), Some('é'));
+
+        let cp = Codepage::Windows1251;
+        assert_eq!(CP1251_80.chars().count(), 64);
+        assert_eq!(cp.char_for(b'A'), Some('A'));
+        assert_eq!(cp.char_for(0x80), Some('Ђ'));
+        assert_eq!(cp.char_for(0x98), None);
+        assert_eq!(cp.char_for(0xA8), Some('Ё'));
+        assert_eq!(cp.char_for(0xB9), Some('№'));
+        assert_eq!(cp.char_for(0xBF), Some('ї'));
+        assert_eq!(cp.char_for(0xC0), Some('А'));
+        assert_eq!(cp.char_for(0xFF), Some('я'));
+    }
+
+    #[test]
+    fn shade_thresholds_and_drops_shadow() {
+        #[rustfmt::skip]
+        let coverage = [
+            1.0, 0.2,
+            0.6, 0.0,
+            0.0, 0.0,
+        ];
+        #[rustfmt::skip]
+        assert_eq!(shade(&coverage, 2, 3), vec![
+            255, 0, 0,
+            255, 1, 0,
+            0,   1, 0,
+        ]);
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(FontRaster::new(b"not a font", 12).build().is_err());
+        assert!(FontRaster::new(b"", 0).build().is_err());
+    }
+}
@@ -1,13 +1,13 @@
 use byteorder::{LittleEndian, ReadBytesExt};
 use image::{DynamicImage, GenericImageView, ImageBuffer, Rgba, imageops};
 use std::{
-    error::Error,
     io::{Cursor, Seek},
     path::Path,
 };
 
 use super::palette::Palettes;
 use crate::Assets;
+use crate::Error;
 
 #[derive(Debug)]
 pub struct Image {
@@ -24,7 +24,7 @@ const SPRITE_HEADER_SIZE: usize = 32;
 
 /// This is for bitmap images
 impl TryFrom<&[u8]> for Image {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let mut cursor = Cursor::new(data);
@@ -37,11 +37,9 @@ impl TryFrom<&[u8]> for Image {
         let uncompressed_size = cursor.read_u32::<LittleEndian>()? as usize;
 
         if pixel_size == 0 {
-            return Err("Pixel size is zero, this is not a valid image".into());
-        }
-        if data.len() <= BITMAP_HEADER_SIZE + PALETTE_SIZE {
-            return Err("Not enough data".into());
+            return Err(Error::invalid("bitmap", "pixel size is zero"));
         }
+        Error::check_len("bitmap", data, 0, BITMAP_HEADER_SIZE + PALETTE_SIZE + 1)?;
 
         let compressed_data = &data[BITMAP_HEADER_SIZE..data.len() - PALETTE_SIZE];
         let uncompressed_data = crate::assets::zlib::decompress(compressed_data, compressed_size, uncompressed_size)?;
@@ -61,7 +59,7 @@ impl TryFrom<&[u8]> for Image {
 
 /// This is for sprite images
 impl TryFrom<(&[u8], &Palettes)> for Image {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: (&[u8], &Palettes)) -> Result<Self, Self::Error> {
         let palettes = data.1;
@@ -77,16 +75,14 @@ impl TryFrom<(&[u8], &Palettes)> for Image {
         let palette_id = cursor.read_u16::<LittleEndian>()?;
         let palette = palettes
             .get(palette_id)
-            .ok_or_else(|| "Palette not found!".to_string())?;
+            .ok_or_else(|| Error::invalid("sprite", format!("palette {palette_id} not found")))?;
 
         cursor.seek(std::io::SeekFrom::Current(6))?;
         let uncompressed_size = cursor.read_u32::<LittleEndian>()? as usize;
 
         let table_size: usize = height * 8;
 
-        if data.len() <= SPRITE_HEADER_SIZE + table_size {
-            return Err("Not enough data".into());
-        }
+        Error::check_len("sprite", data, 0, SPRITE_HEADER_SIZE + table_size + 1)?;
 
         let table = &data[SPRITE_HEADER_SIZE..(SPRITE_HEADER_SIZE + table_size)];
 
@@ -108,11 +104,7 @@ impl TryFrom<(&[u8], &Palettes)> for Image {
 impl Image {
     /// Decode a sprite using a specific palette ID instead of the one in the sprite header.
     /// Used for monster variant palette swaps (e.g., GoblinB uses pal226 instead of pal225).
-    pub fn try_from_with_palette(
-        data: &[u8],
-        palettes: &Palettes,
-        override_palette_id: u16,
-    ) -> Result<Self, Box<dyn Error>> {
+    pub fn try_from_with_palette(data: &[u8], palettes: &Palettes, override_palette_id: u16) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         cursor.seek(std::io::SeekFrom::Start(12))?;
 
@@ -124,15 +116,13 @@ impl Image {
         // Use the override palette instead of the sprite's embedded one
         let palette = palettes
             .get(override_palette_id)
-            .ok_or_else(|| format!("Palette {} not found", override_palette_id))?;
+            .ok_or_else(|| Error::invalid("sprite", format!("palette {override_palette_id} not found")))?;
 
         cursor.seek(std::io::SeekFrom::Current(6))?;
         let uncompressed_size = cursor.read_u32::<LittleEndian>()? as usize;
 
         let table_size: usize = height * 8;
-        if data.len() <= SPRITE_HEADER_SIZE + table_size {
-            return Err("Not enough data".into());
-        }
+        Error::check_len("sprite", data, 0, SPRITE_HEADER_SIZE + table_size + 1)?;
 
         let table = &data[SPRITE_HEADER_SIZE..(SPRITE_HEADER_SIZE + table_size)];
         let compressed_data = &data[SPRITE_HEADER_SIZE + table_size..];
@@ -158,7 +148,7 @@ pub fn sprite_palette_id(data: &[u8]) -> Option<u16> {
     Some(u16::from_le_bytes([data[20], data[21]]))
 }
 
-fn process_sprite_data(data: &[u8], table: &[u8], width: usize, height: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+fn process_sprite_data(data: &[u8], table: &[u8], width: usize, height: usize) -> Result<Vec<u8>, Error> {
     let img_size = width * height;
     let mut img: Vec<u8> = vec![0; img_size];
     let mut current: usize = 0;
@@ -192,7 +182,7 @@ fn process_sprite_data(data: &[u8], table: &[u8], width: usize, height: usize) -
 }
 
 impl Image {
-    pub fn to_image_buffer(&self) -> Result<DynamicImage, Box<dyn Error>> {
+    pub fn to_image_buffer(&self) -> Result<DynamicImage, Error> {
         let image = raw_to_image_buffer(
             &self.data,
             &self.palette,
@@ -210,7 +200,7 @@ impl Image {
     }
 
     #[allow(dead_code)]
-    pub fn save<Q>(&self, path: Q) -> Result<(), Box<dyn Error>>
+    pub fn save<Q>(&self, path: Q) -> Result<(), Error>
     where
         Q: AsRef<Path>,
     {
@@ -231,15 +221,13 @@ fn raw_to_image_buffer<P>(
     pixel_converter: impl Fn(u8, &[u8; 3]) -> P,
     width: u32,
     height: u32,
-) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, Box<dyn Error>>
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, Error>
 where
     P: image::Pixel<Subpixel = u8> + 'static,
 {
     let mut image_buffer = ImageBuffer::<P, Vec<P::Subpixel>>::new(width, height);
     let pixel_count = (width * height) as usize;
-    if data.len() < pixel_count {
-        return Err(format!("data too short: {} < {}", data.len(), pixel_count).into());
-    }
+    Error::check_len("image", data, 0, pixel_count)?;
 
     for (i, pi) in data[..pixel_count].iter().enumerate() {
         let x = (i as u32).rem_euclid(width);
@@ -300,7 +288,7 @@ fn join_images_in_grid(
     DynamicImage::ImageRgba8(combined_image)
 }
 
-pub fn get_atlas(assets: &Assets, names: &[&str], row_size: usize) -> Result<DynamicImage, Box<dyn Error>> {
+pub fn get_atlas(assets: &Assets, names: &[&str], row_size: usize) -> Result<DynamicImage, Error> {
     let mut images: Vec<DynamicImage> = Vec::with_capacity(names.len());
 
     for name in names {
@@ -316,7 +304,10 @@ pub fn get_atlas(assets: &Assets, names: &[&str], row_size: usize) -> Result<Dyn
             continue;
         }
 
-        let mut image = assets.lod().bitmap(name).ok_or("image not found")?;
+        let mut image = assets
+            .lod()
+            .bitmap(name)
+            .ok_or_else(|| Error::not_found("bitmaps", *name))?;
         if image.dimensions() != (128, 128) {
             image = DynamicImage::ImageRgba8(imageops::resize(&image, 128, 128, imageops::FilterType::Triangle));
         }
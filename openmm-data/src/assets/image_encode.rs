@@ -0,0 +1,393 @@
+//! RGBA → MM6 bitmap and sprite LOD entries, the inverse of the decoders in
+//! `image.rs`.
+//!
+//! [`Image::from_rgba`] quantizes to 256 colours (median cut, or nearest
+//! colour in a fixed palette); [`Image::encode_bitmap`] and
+//! [`Image::encode_sprite`] produce bytes ready for `LodWriter::add_file`.
+//!
+//! Bitmap entry: 48-byte header, zlib'd pixels, 768-byte palette.
+//!   0x00: name[16], 0x10: pixel size u32 (w*h), compressed size u32,
+//!   0x18: width u16, height u16, 0x1C: log2 width u16, log2 height u16,
+//!   0x20: width-1 u16, height-1 u16, 0x24: palette id u16, ? u16,
+//!   0x28: uncompressed size u32 (all mip levels), 0x2C: flags u32
+//! Power-of-two textures carry three extra mip levels (1/2, 1/4, 1/8)
+//! after the full-size pixels.
+//!
+//! Sprite entry: 32-byte header, one 8-byte span per row, zlib'd span pixels.
+//!   0x00: name[12], 0x0C: compressed size u32, 0x10: width u16, height u16,
+//!   0x14: palette id u16, ? u16, 0x18: bottom rows to skip u16 (left 0),
+//!   ? u16, 0x1C: uncompressed size u32
+//!   span: first x i16, last x i16 (both -1 for an empty row), offset u32
+//! Palette index 0 is transparent in sprites.
+
+use std::collections::HashMap;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use image::DynamicImage;
+
+use crate::Error;
+
+use super::image::Image;
+use super::palette::Palette;
+
+const PALETTE_SIZE: usize = 256 * 3;
+const BITMAP_NAME_SIZE: usize = 16;
+const SPRITE_NAME_SIZE: usize = 12;
+/// Mip levels stored for power-of-two bitmaps (full size + three halvings).
+const BITMAP_MIP_LEVELS: usize = 4;
+/// Alpha below this is transparent when quantizing sprites.
+const ALPHA_THRESHOLD: u8 = 128;
+
+impl Image {
+    /// Quantize an RGBA image to 256 colours.
+    ///
+    /// With `palette` the pixels are mapped to its nearest colours, otherwise
+    /// a palette is generated by median cut (exact when the image has few
+    /// enough colours). With `transparency` (sprites) pixels with alpha below
+    /// 128 become index 0 and opaque pixels never use it.
+    pub fn from_rgba(image: &DynamicImage, palette: Option<&Palette>, transparency: bool) -> Result<Self, Error> {
+        let rgba = image.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        if width == 0 || height == 0 || width > u16::MAX as u32 || height > u16::MAX as u32 {
+            return Err(Error::invalid(
+                "image",
+                format!("image size {width}x{height} out of range 1..=65535"),
+            ));
+        }
+
+        let opaque = |a: u8| !transparency || a >= ALPHA_THRESHOLD;
+        let first = if transparency { 1 } else { 0 };
+        let palette = match palette {
+            Some(p) => p.data,
+            None => {
+                let mut histogram: HashMap<[u8; 3], u64> = HashMap::new();
+                for p in rgba.pixels().filter(|p| opaque(p[3])) {
+                    *histogram.entry([p[0], p[1], p[2]]).or_default() += 1;
+                }
+                let mut data = [0u8; PALETTE_SIZE];
+                for (i, c) in median_cut(histogram.into_iter().collect(), 256 - first)
+                    .iter()
+                    .enumerate()
+                {
+                    data[(first + i) * 3..(first + i) * 3 + 3].copy_from_slice(c);
+                }
+                data
+            }
+        };
+
+        let mut nearest = Nearest::new(&palette, first);
+        let data = rgba
+            .pixels()
+            .map(|p| {
+                if opaque(p[3]) {
+                    nearest.index([p[0], p[1], p[2]])
+                } else {
+                    0
+                }
+            })
+            .collect();
+        Ok(Self {
+            width: width as usize,
+            height: height as usize,
+            data,
+            palette,
+            transparency,
+        })
+    }
+
+    /// Encode as a bitmap LOD entry (bitmaps.lod textures, icons.lod UI).
+    /// `palette_id` is recorded in the header; the palette itself is embedded.
+    pub fn encode_bitmap(&self, name: &str, palette_id: u16) -> Result<Vec<u8>, Error> {
+        let (w, h) = (self.width, self.height);
+        let mut pixels = self.level0()?.to_vec();
+        if w.is_power_of_two() && h.is_power_of_two() && w >= 8 && h >= 8 {
+            let mut nearest = Nearest::new(&self.palette, 0);
+            let mut level = pixels.clone();
+            let (mut lw, mut lh) = (w, h);
+            for _ in 1..BITMAP_MIP_LEVELS {
+                level = downsample(&level, lw, lh, &self.palette, &mut nearest);
+                lw /= 2;
+                lh /= 2;
+                pixels.extend_from_slice(&level);
+            }
+        }
+        let compressed = crate::assets::zlib::compress(&pixels);
+
+        let mut buf = Vec::with_capacity(48 + compressed.len() + PALETTE_SIZE);
+        write_name(&mut buf, name, BITMAP_NAME_SIZE);
+        buf.write_u32::<LittleEndian>((w * h) as u32)?;
+        buf.write_u32::<LittleEndian>(compressed.len() as u32)?;
+        buf.write_u16::<LittleEndian>(w as u16)?;
+        buf.write_u16::<LittleEndian>(h as u16)?;
+        buf.write_u16::<LittleEndian>(w.ilog2() as u16)?;
+        buf.write_u16::<LittleEndian>(h.ilog2() as u16)?;
+        buf.write_u16::<LittleEndian>((w - 1) as u16)?;
+        buf.write_u16::<LittleEndian>((h - 1) as u16)?;
+        buf.write_u16::<LittleEndian>(palette_id)?;
+        buf.write_u16::<LittleEndian>(0)?;
+        buf.write_u32::<LittleEndian>(pixels.len() as u32)?;
+        buf.write_u32::<LittleEndian>(0)?;
+        buf.extend_from_slice(&compressed);
+        buf.extend_from_slice(&self.palette);
+        Ok(buf)
+    }
+
+    /// Encode as a sprite LOD entry (sprites.lod). Sprites carry no palette:
+    /// `palette_id` must name a `palNNN` entry in bitmaps.lod holding
+    /// [`Image::palette`]. Index 0 is written as transparent.
+    pub fn encode_sprite(&self, name: &str, palette_id: u16) -> Result<Vec<u8>, Error> {
+        let w = self.width;
+        let level0 = self.level0()?;
+
+        let mut table = Vec::with_capacity(self.height * 8);
+        let mut pixels = Vec::new();
+        for row in level0.chunks_exact(w) {
+            let first = row.iter().position(|&i| i != 0);
+            let last = row.iter().rposition(|&i| i != 0);
+            let (start, end) = match (first, last) {
+                (Some(s), Some(e)) => (s as i16, e as i16),
+                _ => (-1, -1),
+            };
+            table.write_i16::<LittleEndian>(start)?;
+            table.write_i16::<LittleEndian>(end)?;
+            table.write_u32::<LittleEndian>(pixels.len() as u32)?;
+            if let (Some(s), Some(e)) = (first, last) {
+                pixels.extend_from_slice(&row[s..=e]);
+            }
+        }
+        let compressed = crate::assets::zlib::compress(&pixels);
+
+        let mut buf = Vec::with_capacity(32 + table.len() + compressed.len());
+        write_name(&mut buf, name, SPRITE_NAME_SIZE);
+        buf.write_u32::<LittleEndian>(compressed.len() as u32)?;
+        buf.write_u16::<LittleEndian>(w as u16)?;
+        buf.write_u16::<LittleEndian>(self.height as u16)?;
+        buf.write_u16::<LittleEndian>(palette_id)?;
+        buf.write_u16::<LittleEndian>(0)?;
+        buf.write_u16::<LittleEndian>(0)?;
+        buf.write_u16::<LittleEndian>(0)?;
+        buf.write_u32::<LittleEndian>(pixels.len() as u32)?;
+        buf.extend_from_slice(&table);
+        buf.extend_from_slice(&compressed);
+        Ok(buf)
+    }
+
+    fn level0(&self) -> Result<&[u8], Error> {
+        if self.width == 0 || self.height == 0 || self.width > u16::MAX as usize || self.height > u16::MAX as usize {
+            return Err(Error::invalid(
+                "image",
+                format!("image size {}x{} out of range 1..=65535", self.width, self.height),
+            ));
+        }
+        let len = self.width * self.height;
+        Error::check_len("image", &self.data, 0, len)?;
+        Ok(&self.data[..len])
+    }
+}
+
+impl Palette {
+    /// Encode as a `palNNN` bitmaps.lod entry, so sprites can reference it by id.
+    pub fn encode(&self, id: u16) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(48 + PALETTE_SIZE);
+        write_name(&mut buf, &format!("pal{id:03}"), BITMAP_NAME_SIZE);
+        buf.resize(48, 0);
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+}
+
+fn write_name(buf: &mut Vec<u8>, name: &str, size: usize) {
+    let bytes = name.as_bytes();
+    let len = bytes.len().min(size - 1);
+    buf.extend_from_slice(&bytes[..len]);
+    buf.resize(buf.len() + size - len, 0);
+}
+
+/// Median-cut palette of at most `max` colours from a colour histogram.
+pub(super) fn median_cut(histogram: Vec<([u8; 3], u64)>, max: usize) -> Vec<[u8; 3]> {
+    if histogram.len() <= max {
+        let mut colors: Vec<_> = histogram.into_iter().map(|(c, _)| c).collect();
+        colors.sort_unstable();
+        return colors;
+    }
+
+    let mut boxes = vec![histogram];
+    while boxes.len() < max {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let (axis, range) = (0..3)
+                    .map(|a| {
+                        let lo = b.iter().map(|(c, _)| c[a]).min().unwrap_or(0);
+                        let hi = b.iter().map(|(c, _)| c[a]).max().unwrap_or(0);
+                        (a, hi - lo)
+                    })
+                    .max_by_key(|&(_, r)| r)
+                    .unwrap_or((0, 0));
+                (i, axis, range)
+            })
+            .max_by_key(|&(_, _, r)| r);
+        let Some((i, axis, range)) = widest else { break };
+        if range == 0 {
+            break;
+        }
+
+        let mut b = boxes.swap_remove(i);
+        b.sort_unstable_by_key(|(c, _)| c[axis]);
+        let total: u64 = b.iter().map(|(_, n)| n).sum();
+        let mut acc = 0;
+        let split = b
+            .iter()
+            .position(|(_, n)| {
+                acc += n;
+                acc * 2 >= total
+            })
+            .unwrap_or(0)
+            .clamp(0, b.len() - 2)
+            + 1;
+        let upper = b.split_off(split);
+        boxes.push(b);
+        boxes.push(upper);
+    }
+
+    boxes
+        .iter()
+        .map(|b| {
+            let total: u64 = b.iter().map(|(_, n)| n).sum::<u64>().max(1);
+            let mean = |a: usize| (b.iter().map(|(c, n)| c[a] as u64 * n).sum::<u64>() / total) as u8;
+            [mean(0), mean(1), mean(2)]
+        })
+        .collect()
+}
+
+/// Nearest palette index lookup over indices `first..256`, memoised per colour.
+pub(super) struct Nearest<'a> {
+    palette: &'a [u8; PALETTE_SIZE],
+    first: usize,
+    cache: HashMap<[u8; 3], u8>,
+}
+
+impl<'a> Nearest<'a> {
+    pub(super) fn new(palette: &'a [u8; PALETTE_SIZE], first: usize) -> Self {
+        Self {
+            palette,
+            first,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub(super) fn index(&mut self, color: [u8; 3]) -> u8 {
+        let (palette, first) = (self.palette, self.first);
+        *self.cache.entry(color).or_insert_with(|| {
+            (first..256)
+                .min_by_key(|&i| {
+                    let p = &palette[i * 3..i * 3 + 3];
+                    (0..3).map(|a| (p[a] as i32 - color[a] as i32).pow(2)).sum::<i32>()
+                })
+                .unwrap_or(first) as u8
+        })
+    }
+}
+
+/// Halve an indexed image with a 2x2 box filter in RGB.
+fn downsample(level: &[u8], w: usize, h: usize, palette: &[u8; PALETTE_SIZE], nearest: &mut Nearest) -> Vec<u8> {
+    let (nw, nh) = (w / 2, h / 2);
+    let mut out = Vec::with_capacity(nw * nh);
+    for y in 0..nh {
+        for x in 0..nw {
+            let mut sum = [0u32; 3];
+            for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                let i = level[(y * 2 + dy) * w + x * 2 + dx] as usize;
+                for a in 0..3 {
+                    sum[a] += palette[i * 3 + a] as u32;
+                }
+            }
+            out.push(nearest.index(sum.map(|s| (s / 4) as u8)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::palette::Palettes;
+    use image::{GenericImageView, ImageBuffer, Rgba};
+
+    fn rgba(w: u32, h: u32, f: impl Fn(u32, u32) -> [u8; 4]) -> DynamicImage {
+        DynamicImage::ImageRgba8(ImageBuffer::from_fn(w, h, |x, y| Rgba(f(x, y))))
+    }
+
+    #[test]
+    fn bitmap_round_trips_through_decoder() {
+        let src = rgba(16, 8, |x, y| [(x * 16) as u8, (y * 32) as u8, 200, 255]);
+        let image = Image::from_rgba(&src, None, false).unwrap();
+        let bytes = image.encode_bitmap("testtyl", 7).unwrap();
+
+        assert_eq!(&bytes[..8], b"testtyl\0");
+        assert_eq!(u16::from_le_bytes([bytes[0x24], bytes[0x25]]), 7);
+        let decoded = Image::try_from(bytes.as_slice()).unwrap();
+        assert_eq!((decoded.width, decoded.height), (16, 8));
+        assert_eq!(decoded.data.len(), 128 + 32 + 8 + 2);
+        assert_eq!(decoded.to_image_buffer().unwrap().to_rgba8(), src.to_rgba8());
+    }
+
+    #[test]
+    fn sprite_round_trips_with_transparency() {
+        // Transparent border around an opaque 3x2 block with a hole.
+        let src = rgba(5, 4, |x, y| match (x, y) {
+            (2, 2) => [0, 0, 0, 0],
+            (1..=3, 1..=2) => [10 * x as u8, 50, 90, 255],
+            _ => [255, 0, 255, 0],
+        });
+        let image = Image::from_rgba(&src, None, true).unwrap();
+        assert_eq!(image.data[0], 0);
+        let bytes = image.encode_sprite("testspr", 3).unwrap();
+
+        let palettes: Palettes = [(3, Palette { data: image.palette })].into_iter().collect();
+        let decoded = Image::try_from((bytes.as_slice(), &palettes)).unwrap();
+        let out = decoded.to_image_buffer().unwrap();
+        for (x, y, p) in src.pixels() {
+            let q = out.get_pixel(x, y);
+            if p[3] == 0 {
+                assert_eq!(q[3], 0, "({x},{y}) should be transparent");
+            } else {
+                assert_eq!(q, p, "({x},{y})");
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_palette_and_large_colour_counts() {
+        let gradient = rgba(64, 64, |x, y| [(x * 4) as u8, (y * 4) as u8, ((x + y) * 2) as u8, 255]);
+        let image = Image::from_rgba(&gradient, None, false).unwrap();
+        let decoded = image.to_image_buffer().unwrap();
+        for (x, y, p) in gradient.pixels() {
+            let q = decoded.get_pixel(x, y);
+            assert!((0..3).all(|a| p[a].abs_diff(q[a]) <= 24), "({x},{y}) {p:?} vs {q:?}");
+        }
+
+        let mut data = [255u8; PALETTE_SIZE];
+        data[0..3].copy_from_slice(&[0, 0, 0]);
+        data[3..6].copy_from_slice(&[255, 0, 0]);
+        let palette = Palette { data };
+        let two = rgba(2, 1, |x, _| if x == 0 { [250, 10, 0, 255] } else { [0, 0, 0, 255] });
+        assert_eq!(Image::from_rgba(&two, Some(&palette), false).unwrap().data, vec![1, 0]);
+        // Sprites never map opaque pixels to the transparent index.
+        assert_eq!(Image::from_rgba(&two, Some(&palette), true).unwrap().data, vec![1, 1]);
+    }
+
+    #[test]
+    fn palette_entry_and_bad_sizes() {
+        let palette = Palette {
+            data: [9; PALETTE_SIZE],
+        };
+        let bytes = palette.encode(42);
+        assert_eq!(&bytes[..6], b"pal042");
+        assert_eq!(Palette::try_from(bytes.as_slice()).unwrap(), palette);
+
+        let wide = rgba(70000, 1, |_, _| [0, 0, 0, 255]);
+        assert!(Image::from_rgba(&wide, None, false).is_err());
+    }
+}
@@ -5,14 +5,18 @@
 //!   0: Item#, 1: PicFile, 2: Name, 3: Value, 4: EquipStat, 5: SkillGroup,
 //!   6: Mod1, 7: Mod2, 8: Material, 9: ID/Rep/St, 10: NotIdentifiedName,
 //!   11: SpriteIndex, 12: Shape, 13: EquipX, 14: EquipY, 15: Notes/Description
+//!
+//! MM7+ replaces Shape with VarA and VarB, so EquipX, EquipY and Notes move
+//! one column right.
 
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::Cursor;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
+use crate::Version;
 
 /// A single item definition from `items.txt`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,8 +45,14 @@ pub struct ItemInfo {
     pub not_identified_name: String,
     /// Sprite index into dsft.bin.
     pub sprite_index: u16,
-    /// Shape index (inventory grid shape).
+    /// Shape index (inventory grid shape). MM6 only, 0 for MM7+.
     pub shape: u8,
+    /// Special bonus strength (VarA). MM7+ only, 0 for MM6.
+    #[serde(default)]
+    pub var_a: u8,
+    /// Special bonus kind (VarB). MM7+ only, 0 for MM6.
+    #[serde(default)]
+    pub var_b: u8,
     /// Inventory/doll equip X position.
     pub equip_x: u8,
     /// Inventory/doll equip Y position.
@@ -51,6 +61,24 @@ pub struct ItemInfo {
     pub notes: String,
 }
 
+/// Value range (inclusive) of the items each treasure level (1-6) can roll.
+/// Ranges overlap so neighbouring levels share their middle items.
+const TREASURE_VALUES: [(u32, u32); 6] = [
+    (1, 100),
+    (50, 300),
+    (150, 800),
+    (400, 2000),
+    (1000, 5000),
+    (2500, u32::MAX),
+];
+
+impl ItemInfo {
+    /// A pile of gold: picked up into the party purse, amount in `charges`.
+    pub fn is_gold(&self) -> bool {
+        self.equip_stat.eq_ignore_ascii_case("Gold")
+    }
+}
+
 /// All item definitions.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ItemsTable {
@@ -58,12 +86,21 @@ pub struct ItemsTable {
 }
 
 impl ItemsTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
-        let raw = assets.get_bytes("icons/items.txt")?;
-        Self::try_from(raw.as_slice())
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
+        let data = assets.get_entry("icons/items.txt")?;
+        Self::parse_version(&String::from_utf8_lossy(&data), assets.version())
     }
 
-    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+    /// Parse an MM6 items.txt.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        Self::parse_version(text, Version::MM6)
+    }
+
+    /// Parse an items.txt laid out for `version`.
+    pub fn parse_version(text: &str, version: Version) -> Result<Self, Error> {
+        let mm7 = version >= Version::MM7;
+        // Columns after SpriteIndex sit one further right in MM7+.
+        let tail = if mm7 { 1 } else { 0 };
         let body: String = text.lines().skip(2).collect::<Vec<_>>().join("\n");
         let mut rdr = ReaderBuilder::new()
             .delimiter(b'\t')
@@ -73,7 +110,7 @@ impl ItemsTable {
 
         let mut items = Vec::new();
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("items.txt", e))?;
 
             let id: u16 = match rec.get(0).unwrap_or("").trim().parse() {
                 Ok(v) => v,
@@ -83,6 +120,7 @@ impl ItemsTable {
                 continue;
             }
 
+            let num = |col: usize| rec.get(col).unwrap_or("0").trim().parse().unwrap_or(0);
             let name = rec.get(2).unwrap_or("").trim().to_string();
             if name.is_empty() {
                 continue;
@@ -101,10 +139,12 @@ impl ItemsTable {
                 id_rep_st: rec.get(9).unwrap_or("0").trim().parse().unwrap_or(0),
                 not_identified_name: rec.get(10).unwrap_or("").trim().to_string(),
                 sprite_index: rec.get(11).unwrap_or("0").trim().parse().unwrap_or(0),
-                shape: rec.get(12).unwrap_or("0").trim().parse().unwrap_or(0),
-                equip_x: rec.get(13).unwrap_or("0").trim().parse().unwrap_or(0),
-                equip_y: rec.get(14).unwrap_or("0").trim().parse().unwrap_or(0),
-                notes: rec.get(15).unwrap_or("").trim().to_string(),
+                shape: if mm7 { 0 } else { num(12) },
+                var_a: if mm7 { num(12) } else { 0 },
+                var_b: if mm7 { num(13) } else { 0 },
+                equip_x: num(13 + tail),
+                equip_y: num(14 + tail),
+                notes: rec.get(15 + tail).unwrap_or("").trim().to_string(),
             });
         }
         Ok(ItemsTable { items })
@@ -114,10 +154,26 @@ impl ItemsTable {
     pub fn get(&self, id: u16) -> Option<&ItemInfo> {
         self.items.iter().find(|i| i.id == id)
     }
+
+    /// Pick a random item for treasure `level` (1-6, clamped). Only ordinary
+    /// items with a value in the level's range qualify: no gold, artifacts or
+    /// worthless quest items. `roll(n)` returns a number in `0..n`.
+    pub fn random_item(&self, level: u8, mut roll: impl FnMut(usize) -> usize) -> Option<&ItemInfo> {
+        let (min, max) = TREASURE_VALUES[level.clamp(1, 6) as usize - 1];
+        let pool: Vec<&ItemInfo> = self
+            .items
+            .iter()
+            .filter(|i| i.material == 0 && !i.is_gold() && (min..=max).contains(&i.value))
+            .collect();
+        if pool.is_empty() {
+            return None;
+        }
+        Some(pool[roll(pool.len()) % pool.len()])
+    }
 }
 
 impl TryFrom<&[u8]> for ItemsTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -129,6 +185,7 @@ impl TryFrom<&[u8]> for ItemsTable {
     }
 }
 
+// Always writes the MM6 layout.
 impl LodSerialise for ItemsTable {
     fn to_bytes(&self) -> Vec<u8> {
         let mut out = String::new();
@@ -159,3 +216,40 @@ impl LodSerialise for ItemsTable {
         out.into_bytes()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mm7_columns_shift_after_sprite_index() {
+        let mm6 =
+            "Items.txt\nheader\n1\titem1\tLongsword\t50\tWeapon\tSword\t3d3\t0\t0\t1\tSword\t3\t4\t5\t6\tA blade\n";
+        let item = &ItemsTable::parse(mm6).unwrap().items[0];
+        assert_eq!((item.shape, item.equip_x, item.equip_y), (4, 5, 6));
+        assert_eq!(item.notes, "A blade");
+
+        let mm7 =
+            "Items.txt\nheader\n1\titem1\tLongsword\t50\tWeapon\tSword\t3d3\t0\t0\t1\tSword\t3\t2\t9\t5\t6\tA blade\n";
+        let item = &ItemsTable::parse_version(mm7, Version::MM7).unwrap().items[0];
+        assert_eq!((item.shape, item.var_a, item.var_b), (0, 2, 9));
+        assert_eq!((item.equip_x, item.equip_y), (5, 6));
+        assert_eq!(item.notes, "A blade");
+    }
+
+    #[test]
+    fn random_item_respects_treasure_level() {
+        let text = "Items.txt\nheader\n\
+            1\tdagger\tDagger\t20\tWeapon\tDagger\t2d2\t0\t0\t1\tDagger\t1\t1\t0\t0\t\n\
+            2\tplate\tPlate\t3000\tArmor\tPlate\t0\t0\t0\t1\tArmor\t2\t1\t0\t0\t\n\
+            3\tgold\tGold\t50\tGold\t\t0\t0\t0\t1\tGold\t3\t1\t0\t0\t\n\
+            4\tsword\tRelic\t40\tWeapon\tSword\t3d3\t0\t1\t1\tSword\t4\t1\t0\t0\t\n";
+        let table = ItemsTable::parse(text).unwrap();
+        assert!(table.get(3).unwrap().is_gold());
+        assert_eq!(table.random_item(1, |_| 0).unwrap().id, 1);
+        assert_eq!(table.random_item(5, |_| 0).unwrap().id, 2);
+        // Out-of-range levels clamp to 1..=6.
+        assert_eq!(table.random_item(0, |_| 0).unwrap().id, 1);
+        assert!(table.random_item(3, |_| 0).is_none());
+    }
+}
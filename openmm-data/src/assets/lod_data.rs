@@ -1,11 +1,12 @@
 use std::{
-    error::Error,
     io::{Cursor, Seek},
     path::Path,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
+use crate::Error;
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum CompressionKind {
@@ -22,7 +23,7 @@ pub struct LodData {
 }
 
 impl TryFrom<&[u8]> for LodData {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         if let Ok(decompressed) = decompress_with_8_bytes_header(data) {
@@ -44,10 +45,33 @@ impl TryFrom<&[u8]> for LodData {
     }
 }
 
-fn decompress_with_48_bytes_header(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
-    if data.len() < 48 {
-        return Err("data too short for 48-byte header".into());
+/// Where the zlib stream of a packed entry starts and how large it
+/// inflates, judged from the 8- or 48-byte header alone (no inflating).
+/// `None` for entries stored uncompressed.
+pub(crate) fn zlib_payload(data: &[u8]) -> Option<(usize, usize)> {
+    let u32_at = |at: usize| {
+        data.get(at..at + 4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()) as usize)
+    };
+    let is_zlib = |at: usize| data.get(at) == Some(&0x78);
+    if let (Some(compressed), Some(size)) = (u32_at(0), u32_at(4))
+        && (compressed == data.len() - 8 || compressed == data.len())
+        && is_zlib(8)
+    {
+        return Some((8, size));
+    }
+    if let (Some(compressed), Some(size)) = (u32_at(20), u32_at(40))
+        && data.len() >= 48
+        && compressed == data.len() - 48
+        && is_zlib(48)
+    {
+        return Some((48, size));
     }
+    None
+}
+
+fn decompress_with_48_bytes_header(data: &[u8]) -> Result<Vec<u8>, Error> {
+    Error::check_len("lod entry", data, 0, 48)?;
     let mut cursor = Cursor::new(data);
     cursor.seek(std::io::SeekFrom::Start(20))?;
     let compressed_size = cursor.read_u32::<LittleEndian>()? as usize;
@@ -56,10 +80,8 @@ fn decompress_with_48_bytes_header(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error
     crate::assets::zlib::decompress(&data[48..], compressed_size, uncompressed_size)
 }
 
-fn decompress_with_8_bytes_header(data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
-    if data.len() < 8 {
-        return Err("data too short for 8-byte header".into());
-    }
+fn decompress_with_8_bytes_header(data: &[u8]) -> Result<Vec<u8>, Error> {
+    Error::check_len("lod entry", data, 0, 8)?;
     let header_compressed_size = u32::from_le_bytes(data[0..=3].try_into()?) as usize;
     let decompressed_size = u32::from_le_bytes(data[4..=7].try_into()?) as usize;
     let payload = &data[8..];
@@ -106,7 +128,7 @@ impl LodData {
         }
     }
 
-    pub fn dump<Q>(&self, path: Q) -> Result<(), Box<dyn Error>>
+    pub fn dump<Q>(&self, path: Q) -> Result<(), Error>
     where
         Q: AsRef<Path>,
     {
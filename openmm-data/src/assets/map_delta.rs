@@ -0,0 +1,878 @@
+//! Map delta sections shared by DDM (outdoor) and DLV (indoor) files.
+//!
+//! Both delta formats store the same run of sections between their
+//! format-specific parts:
+//!   1. MapObjects   — u32 count + count × 0x64 bytes (projectiles / dropped items)
+//!   2. MapSprites   — u32 count + count × 0x1C bytes (decoration instances)
+//!   3. SoundSprites — 10 × i32 (ambient sound positions, no count prefix)
+//!   4. MapChests    — u32 count + count × 0x106C bytes (chest contents)
+//!   5. MapMonsters  — u32 count + count × 0x224 bytes (actors, see [`DdmActor`])
+//!
+//! MM7 drops the MapSprites and SoundSprites sections (decorations are flag
+//! words earlier in the file) and stores the rest in a different order:
+//!   1. MapMonsters  — u32 count + count × 0x344 bytes
+//!   2. MapObjects   — u32 count + count × 0x70 bytes
+//!   3. MapChests    — u32 count + count × 0x14CC bytes
+//!
+//! Struct layouts follow MMExtension's `MapObject`, `MapSprite`, `MapChest`
+//! and `Item` definitions. Every byte, including padding, is kept in a field
+//! so [`MapDelta::write`] reproduces the input exactly.
+
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read};
+
+use crate::Error;
+use crate::assets::blv::Blv;
+use crate::assets::ddm::{ACTOR_SIZE_MM6, ACTOR_SIZE_MM7, Ddm, DdmActor};
+use crate::assets::dlv::{Dlv, DlvLayout};
+use crate::assets::items::{ItemInfo, ItemsTable};
+use crate::utils::MapName;
+use crate::{Assets, LodSerialise, Version};
+
+/// MM6 `Item` struct size (0x1C = 28 bytes).
+pub const ITEM_RECORD_SIZE: usize = 0x1C;
+/// MM7 `Item` struct size (0x24 = 36 bytes).
+pub const ITEM_RECORD_SIZE_MM7: usize = 0x24;
+/// MM6 `MapObject` struct size (0x64 = 100 bytes).
+pub const MAP_OBJECT_SIZE: usize = 0x64;
+/// MM7 `MapObject` struct size (0x70 = 112 bytes).
+pub const MAP_OBJECT_SIZE_MM7: usize = 0x70;
+/// MM6 `MapSprite` struct size (0x1C = 28 bytes).
+pub const MAP_SPRITE_SIZE: usize = 0x1C;
+/// MM6 `MapChest` struct size (0x106C = 4204 bytes).
+pub const MAP_CHEST_SIZE: usize = 0x106C;
+/// MM7 `MapChest` struct size (0x14CC = 5324 bytes).
+pub const MAP_CHEST_SIZE_MM7: usize = 0x14CC;
+/// Item slots per chest.
+pub const CHEST_ITEM_SLOTS: usize = 140;
+/// Number of ambient sound sprite slots.
+pub const SOUND_SPRITE_COUNT: usize = 10;
+/// Size of the MapVars section (event/barrel state bytes).
+pub const MAP_VARS_SIZE: usize = 200;
+/// Size of the last-visit timestamp at the start of the LocationTime block.
+const LAST_VISIT_SIZE: usize = 8;
+/// Size of the MM7 header that opens DDM and DLV files.
+pub const LOCATION_HEADER_SIZE: usize = 40;
+/// [`ItemRecord::flags`] bit: the item has been identified.
+pub const ITEM_FLAG_IDENTIFIED: u32 = 0x01;
+/// [`ItemRecord::flags`] bit: the item is broken.
+pub const ITEM_FLAG_BROKEN: u32 = 0x02;
+
+/// Header that opens MM7 DDM and DLV files (40 bytes). MM6 files have none.
+///
+/// Layout: RespawnCount(4) + LastRespawnDay(4) + Reputation(4) + Alert(4) +
+/// FaceCount(4) + DecorationCount(4) + BModelCount(4) + pad(12)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationHeader {
+    /// Times the map has been refilled. Offset 0x00.
+    pub respawn_count: i32,
+    /// Day of the last refill. Offset 0x04.
+    pub last_respawn_day: i32,
+    /// Local reputation. Offset 0x08.
+    pub reputation: i32,
+    /// Alert state of the map's guards. Offset 0x0C.
+    pub alert: i32,
+    /// Entries in the face attribute section. Offset 0x10.
+    pub face_count: u32,
+    /// Entries in the decoration flag section. Offset 0x14.
+    pub decoration_count: u32,
+    /// Number of BSP models. Offset 0x18.
+    pub bmodel_count: u32,
+    /// Padding. Offset 0x1C.
+    pub _pad0x1c: [i32; 3],
+}
+
+impl LocationHeader {
+    pub fn read(c: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        let mut header = Self {
+            respawn_count: c.read_i32::<LittleEndian>()?,
+            last_respawn_day: c.read_i32::<LittleEndian>()?,
+            reputation: c.read_i32::<LittleEndian>()?,
+            alert: c.read_i32::<LittleEndian>()?,
+            face_count: c.read_u32::<LittleEndian>()?,
+            decoration_count: c.read_u32::<LittleEndian>()?,
+            bmodel_count: c.read_u32::<LittleEndian>()?,
+            ..Default::default()
+        };
+        c.read_i32_into::<LittleEndian>(&mut header._pad0x1c)?;
+        Ok(header)
+    }
+
+    pub fn write(&self, out: &mut Vec<u8>) {
+        out.write_i32::<LittleEndian>(self.respawn_count).unwrap();
+        out.write_i32::<LittleEndian>(self.last_respawn_day).unwrap();
+        out.write_i32::<LittleEndian>(self.reputation).unwrap();
+        out.write_i32::<LittleEndian>(self.alert).unwrap();
+        out.write_u32::<LittleEndian>(self.face_count).unwrap();
+        out.write_u32::<LittleEndian>(self.decoration_count).unwrap();
+        out.write_u32::<LittleEndian>(self.bmodel_count).unwrap();
+        for &p in &self._pad0x1c {
+            out.write_i32::<LittleEndian>(p).unwrap();
+        }
+    }
+}
+
+/// One item instance (28 bytes): chest slot, ground object or inventory entry.
+///
+/// Layout: Number(4) + Bonus(4) + BonusStrength(4) + Bonus2(4) + Charges(4) +
+/// Bits(4) + BodyLocation(1) + MaxCharges(1) + Owner(1) + pad(1)
+///
+/// MM7 appends BonusExpireTime(8), making the record 36 bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemRecord {
+    /// Item id (row in items.txt, 0 = empty slot). Offset 0x00.
+    pub number: i32,
+    /// Standard enchantment id. Offset 0x04.
+    pub bonus: i32,
+    /// Standard enchantment strength. Offset 0x08.
+    pub bonus_strength: i32,
+    /// Special enchantment id. Offset 0x0C.
+    pub bonus2: i32,
+    /// Wand charges or gold amount. Offset 0x10.
+    pub charges: i32,
+    /// Identified/broken/stolen bits. Offset 0x14.
+    pub flags: u32,
+    /// Equipped body slot (0 = not equipped). Offset 0x18.
+    pub body_location: u8,
+    /// Maximum wand charges. Offset 0x19.
+    pub max_charges: u8,
+    /// Owning character index for stolen items. Offset 0x1A.
+    pub owner: u8,
+    /// Padding. Offset 0x1B.
+    pub _pad0x1b: u8,
+    /// Game time a temporary enchantment wears off. Offset 0x1C, MM7+ only.
+    pub expire_time: i64,
+}
+
+impl ItemRecord {
+    pub fn is_empty(&self) -> bool {
+        self.number == 0
+    }
+
+    pub fn is_identified(&self) -> bool {
+        self.flags & ITEM_FLAG_IDENTIFIED != 0
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.flags & ITEM_FLAG_BROKEN != 0
+    }
+
+    /// Read an MM6 item record.
+    pub fn read(c: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        Self::read_version(c, Version::MM6)
+    }
+
+    /// Read an item record laid out for `version`.
+    pub fn read_version(c: &mut Cursor<&[u8]>, version: Version) -> std::io::Result<Self> {
+        Ok(Self {
+            number: c.read_i32::<LittleEndian>()?,
+            bonus: c.read_i32::<LittleEndian>()?,
+            bonus_strength: c.read_i32::<LittleEndian>()?,
+            bonus2: c.read_i32::<LittleEndian>()?,
+            charges: c.read_i32::<LittleEndian>()?,
+            flags: c.read_u32::<LittleEndian>()?,
+            body_location: c.read_u8()?,
+            max_charges: c.read_u8()?,
+            owner: c.read_u8()?,
+            _pad0x1b: c.read_u8()?,
+            expire_time: if version >= Version::MM7 {
+                c.read_i64::<LittleEndian>()?
+            } else {
+                0
+            },
+        })
+    }
+
+    /// Write an MM6 item record.
+    pub fn write(&self, out: &mut Vec<u8>) {
+        self.write_version(out, Version::MM6);
+    }
+
+    /// Write the item record laid out for `version`.
+    pub fn write_version(&self, out: &mut Vec<u8>, version: Version) {
+        out.write_i32::<LittleEndian>(self.number).unwrap();
+        out.write_i32::<LittleEndian>(self.bonus).unwrap();
+        out.write_i32::<LittleEndian>(self.bonus_strength).unwrap();
+        out.write_i32::<LittleEndian>(self.bonus2).unwrap();
+        out.write_i32::<LittleEndian>(self.charges).unwrap();
+        out.write_u32::<LittleEndian>(self.flags).unwrap();
+        out.push(self.body_location);
+        out.push(self.max_charges);
+        out.push(self.owner);
+        out.push(self._pad0x1b);
+        if version >= Version::MM7 {
+            out.write_i64::<LittleEndian>(self.expire_time).unwrap();
+        }
+    }
+}
+
+/// A live map object: projectile, spell effect or item lying on the ground.
+///
+/// Layout (0x64 bytes):
+///   0x00: Type(2), TypeIndex(2), Pos[3](12), Velocity[3](6), Direction(2),
+///   0x18: LookAngle(2), Bits(2), Room(2), Age(2), MaxAge(2), LightMultiplier(2),
+///   0x24: Item(28), 0x40: Spell(4), SpellLevel(4), SpellMastery(4),
+///   0x4C: Owner(4), Target(4), 0x54: Range(1), AttackType(1), pad(2),
+///   0x58: StartPos[3](12)
+///
+/// MM7 (0x70 bytes) has a 36-byte Item and an unknown dword after
+/// SpellMastery, so everything from Spell on sits 8 or 12 bytes later.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapObject {
+    /// Object id (dobjlist.bin). Offset 0x00.
+    pub object_type: i16,
+    /// Index into the object list resolved at load. Offset 0x02.
+    pub type_index: i16,
+    /// Position in MM6 coordinates. Offset 0x04.
+    pub position: [i32; 3],
+    /// Velocity. Offset 0x10.
+    pub velocity: [i16; 3],
+    /// Facing (0-2047). Offset 0x16.
+    pub direction: u16,
+    /// Vertical angle. Offset 0x18.
+    pub look_angle: i16,
+    /// Object state bits. Offset 0x1A.
+    pub bits: u16,
+    /// Indoor room (sector) id. Offset 0x1C.
+    pub room: i16,
+    /// Age in ticks. Offset 0x1E.
+    pub age: i16,
+    /// Lifetime in ticks (0 = permanent). Offset 0x20.
+    pub max_age: i16,
+    /// Light radius multiplier. Offset 0x22.
+    pub light_multiplier: i16,
+    /// Item carried by this object (ground items). Offset 0x24.
+    pub item: ItemRecord,
+    /// Spell id for spell projectiles. Offset 0x40.
+    pub spell: i32,
+    /// Spell level. Offset 0x44.
+    pub spell_level: i32,
+    /// Spell mastery. Offset 0x48.
+    pub spell_mastery: i32,
+    /// Unknown dword after SpellMastery, MM7+ only.
+    pub _unknown_mm7: i32,
+    /// Owner (packed object reference). Offset 0x4C.
+    pub owner: i32,
+    /// Target (packed object reference). Offset 0x50.
+    pub target: i32,
+    /// Range class. Offset 0x54.
+    pub range: u8,
+    /// Attack type. Offset 0x55.
+    pub attack_type: u8,
+    /// Padding. Offset 0x56.
+    pub _pad0x56: u16,
+    /// Launch position. Offset 0x58.
+    pub start_position: [i32; 3],
+}
+
+impl MapObject {
+    pub fn read(c: &mut Cursor<&[u8]>, version: Version) -> std::io::Result<Self> {
+        Ok(Self {
+            object_type: c.read_i16::<LittleEndian>()?,
+            type_index: c.read_i16::<LittleEndian>()?,
+            position: read_i32x3(c)?,
+            velocity: [
+                c.read_i16::<LittleEndian>()?,
+                c.read_i16::<LittleEndian>()?,
+                c.read_i16::<LittleEndian>()?,
+            ],
+            direction: c.read_u16::<LittleEndian>()?,
+            look_angle: c.read_i16::<LittleEndian>()?,
+            bits: c.read_u16::<LittleEndian>()?,
+            room: c.read_i16::<LittleEndian>()?,
+            age: c.read_i16::<LittleEndian>()?,
+            max_age: c.read_i16::<LittleEndian>()?,
+            light_multiplier: c.read_i16::<LittleEndian>()?,
+            item: ItemRecord::read_version(c, version)?,
+            spell: c.read_i32::<LittleEndian>()?,
+            spell_level: c.read_i32::<LittleEndian>()?,
+            spell_mastery: c.read_i32::<LittleEndian>()?,
+            _unknown_mm7: if version >= Version::MM7 {
+                c.read_i32::<LittleEndian>()?
+            } else {
+                0
+            },
+            owner: c.read_i32::<LittleEndian>()?,
+            target: c.read_i32::<LittleEndian>()?,
+            range: c.read_u8()?,
+            attack_type: c.read_u8()?,
+            _pad0x56: c.read_u16::<LittleEndian>()?,
+            start_position: read_i32x3(c)?,
+        })
+    }
+
+    pub fn write(&self, out: &mut Vec<u8>, version: Version) {
+        out.write_i16::<LittleEndian>(self.object_type).unwrap();
+        out.write_i16::<LittleEndian>(self.type_index).unwrap();
+        write_i32x3(out, &self.position);
+        for &v in &self.velocity {
+            out.write_i16::<LittleEndian>(v).unwrap();
+        }
+        out.write_u16::<LittleEndian>(self.direction).unwrap();
+        out.write_i16::<LittleEndian>(self.look_angle).unwrap();
+        out.write_u16::<LittleEndian>(self.bits).unwrap();
+        out.write_i16::<LittleEndian>(self.room).unwrap();
+        out.write_i16::<LittleEndian>(self.age).unwrap();
+        out.write_i16::<LittleEndian>(self.max_age).unwrap();
+        out.write_i16::<LittleEndian>(self.light_multiplier).unwrap();
+        self.item.write_version(out, version);
+        out.write_i32::<LittleEndian>(self.spell).unwrap();
+        out.write_i32::<LittleEndian>(self.spell_level).unwrap();
+        out.write_i32::<LittleEndian>(self.spell_mastery).unwrap();
+        if version >= Version::MM7 {
+            out.write_i32::<LittleEndian>(self._unknown_mm7).unwrap();
+        }
+        out.write_i32::<LittleEndian>(self.owner).unwrap();
+        out.write_i32::<LittleEndian>(self.target).unwrap();
+        out.push(self.range);
+        out.push(self.attack_type);
+        out.write_u16::<LittleEndian>(self._pad0x56).unwrap();
+        write_i32x3(out, &self.start_position);
+    }
+}
+
+/// A decoration instance's mutable state.
+///
+/// Layout (0x1C bytes): DecListId(2) + Bits(2) + Pos[3](12) + Direction(4) +
+/// EventVariable(2) + Event(2) + TriggerRadius(2) + DirectionDegrees(2)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapSprite {
+    /// Row in ddeclist.bin. Offset 0x00.
+    pub declist_id: u16,
+    /// Decoration bits (visible, triggered, ...). Offset 0x02.
+    pub bits: u16,
+    /// Position in MM6 coordinates. Offset 0x04.
+    pub position: [i32; 3],
+    /// Facing (0-2047). Offset 0x10.
+    pub direction: i32,
+    /// Event variable index. Offset 0x14.
+    pub event_variable: i16,
+    /// Event id run on click. Offset 0x16.
+    pub event: i16,
+    /// Touch trigger radius. Offset 0x18.
+    pub trigger_radius: i16,
+    /// Facing in degrees. Offset 0x1A.
+    pub direction_degrees: i16,
+}
+
+impl MapSprite {
+    pub fn read(c: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        Ok(Self {
+            declist_id: c.read_u16::<LittleEndian>()?,
+            bits: c.read_u16::<LittleEndian>()?,
+            position: read_i32x3(c)?,
+            direction: c.read_i32::<LittleEndian>()?,
+            event_variable: c.read_i16::<LittleEndian>()?,
+            event: c.read_i16::<LittleEndian>()?,
+            trigger_radius: c.read_i16::<LittleEndian>()?,
+            direction_degrees: c.read_i16::<LittleEndian>()?,
+        })
+    }
+
+    pub fn write(&self, out: &mut Vec<u8>) {
+        out.write_u16::<LittleEndian>(self.declist_id).unwrap();
+        out.write_u16::<LittleEndian>(self.bits).unwrap();
+        write_i32x3(out, &self.position);
+        out.write_i32::<LittleEndian>(self.direction).unwrap();
+        out.write_i16::<LittleEndian>(self.event_variable).unwrap();
+        out.write_i16::<LittleEndian>(self.event).unwrap();
+        out.write_i16::<LittleEndian>(self.trigger_radius).unwrap();
+        out.write_i16::<LittleEndian>(self.direction_degrees).unwrap();
+    }
+}
+
+bitflags! {
+    /// [`MapChest::bits`] flags.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct ChestFlags: u16 {
+        /// Opening the chest sets off its trap unless the party disarms it.
+        const TRAPPED      = 0x0001;
+        /// Random item placeholders have been rolled into real items.
+        const ITEMS_PLACED = 0x0002;
+        /// The party has opened the chest at least once.
+        const OPENED       = 0x0004;
+    }
+}
+
+/// Number of inventory cells an item icon `pixels` wide (or tall) covers.
+/// Cells are 32 pixels; icons may overhang a cell by up to 14 pixels.
+pub fn item_cell_span(pixels: u32) -> usize {
+    (pixels.max(14) as usize - 14) / 32 + 1
+}
+
+/// Top-left cell of the first free `size` (columns, rows) area in a
+/// row-major `cols` × `rows` item grid, scanning column by column.
+///
+/// Grids use the encoding of [`MapChest::inventory`]: 0 is a free cell.
+pub fn free_grid_cell<T: Copy + Default + PartialEq>(
+    cells: &[T],
+    cols: usize,
+    rows: usize,
+    size: (usize, usize),
+) -> Option<usize> {
+    let (w, h) = size;
+    if w == 0 || h == 0 || w > cols || h > rows || cells.len() < cols * rows {
+        return None;
+    }
+    (0..=cols - w)
+        .flat_map(|x| (0..=rows - h).map(move |y| (x, y)))
+        .find(|&(x, y)| (0..h).all(|dy| (0..w).all(|dx| cells[(y + dy) * cols + x + dx] == T::default())))
+        .map(|(x, y)| y * cols + x)
+}
+
+/// Mark a `size` area starting at `cell` as holding item `slot`: `slot + 1`
+/// in the top-left cell and `-(slot + 1)` in the others.
+pub fn mark_grid_cells<T: Copy + From<i16>>(
+    cells: &mut [T],
+    cols: usize,
+    cell: usize,
+    size: (usize, usize),
+    slot: usize,
+) {
+    let id = slot as i16 + 1;
+    let (x0, y0) = (cell % cols, cell / cols);
+    for y in y0..y0 + size.1 {
+        for x in x0..x0 + size.0 {
+            if let Some(c) = cells.get_mut(y * cols + x) {
+                *c = T::from(if (x, y) == (x0, y0) { id } else { -id });
+            }
+        }
+    }
+}
+
+/// Free every cell holding item `slot`.
+pub fn clear_grid_cells<T: Copy + Default + PartialEq + From<i16>>(cells: &mut [T], slot: usize) {
+    let id = slot as i16 + 1;
+    let (start, covered) = (T::from(id), T::from(-id));
+    for c in cells.iter_mut().filter(|c| **c == start || **c == covered) {
+        *c = T::default();
+    }
+}
+
+/// A chest and its contents.
+///
+/// Layout (0x106C bytes): ChestPicture(2) + Bits(2) + Items[140](28 each) +
+/// Inventory[140](i16 each); 0x14CC bytes in MM7 with its 36-byte items.
+/// `inventory` maps grid cells to item slots:
+/// a positive value `n` means slot `n - 1` starts in that cell, a negative
+/// value marks a cell covered by the item starting in slot `-n - 1`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapChest {
+    /// Row in dchest.bin (picture / grid size). Offset 0x00.
+    pub picture: u16,
+    /// Chest bits (trapped, items placed, opened, ...). Offset 0x02.
+    pub bits: u16,
+    /// Item slots (always 140). Offset 0x04.
+    pub items: Vec<ItemRecord>,
+    /// Grid cell → item slot mapping (always 140). Offset 0xF54.
+    pub inventory: Vec<i16>,
+}
+
+impl Default for MapChest {
+    fn default() -> Self {
+        Self {
+            picture: 0,
+            bits: 0,
+            items: vec![ItemRecord::default(); CHEST_ITEM_SLOTS],
+            inventory: vec![0; CHEST_ITEM_SLOTS],
+        }
+    }
+}
+
+impl MapChest {
+    /// Non-empty item slots with their slot index.
+    pub fn stored_items(&self) -> impl Iterator<Item = (usize, &ItemRecord)> {
+        self.items.iter().enumerate().filter(|(_, item)| !item.is_empty())
+    }
+
+    pub fn flags(&self) -> ChestFlags {
+        ChestFlags::from_bits_retain(self.bits)
+    }
+
+    /// Set (`on`) or clear `flags`, keeping the other bits.
+    pub fn set_flags(&mut self, flags: ChestFlags, on: bool) {
+        let mut bits = self.flags();
+        bits.set(flags, on);
+        self.bits = bits.bits();
+    }
+
+    /// Grid cell where item `slot` starts, if it is placed.
+    pub fn item_cell(&self, slot: usize) -> Option<usize> {
+        let id = slot as i16 + 1;
+        self.inventory.iter().position(|&c| c == id)
+    }
+
+    /// Remove the item in `slot` and free its grid cells.
+    pub fn take_item(&mut self, slot: usize) -> Option<ItemRecord> {
+        let item = self.items.get_mut(slot).filter(|i| !i.is_empty())?;
+        let taken = std::mem::take(item);
+        clear_grid_cells(&mut self.inventory, slot);
+        Some(taken)
+    }
+
+    /// Store `item` in the first free slot and the first free `size` area of
+    /// a `cols` × `rows` grid. Returns the slot, or `None` if it doesn't fit.
+    pub fn place_item(&mut self, item: ItemRecord, size: (usize, usize), cols: usize, rows: usize) -> Option<usize> {
+        let slot = self.items.iter().position(|i| i.is_empty())?;
+        let cell = free_grid_cell(&self.inventory, cols, rows, size)?;
+        self.items[slot] = item;
+        mark_grid_cells(&mut self.inventory, cols, cell, size, slot);
+        Some(slot)
+    }
+
+    /// Roll the chest's contents the first time it is opened.
+    ///
+    /// An item number `-n` is a placeholder for a random item of treasure
+    /// level `n` (levels above 6 count as 6); a chest with no items at all
+    /// gets one to three items of the map's `treasure_level` (none at level
+    /// 0). The rolled items are packed into the `cols` × `rows` grid with
+    /// `cell_size` giving each item's (columns, rows); items that don't fit
+    /// are dropped. Sets [`ChestFlags::ITEMS_PLACED`]; does nothing if it is
+    /// already set. `roll(n)` returns a number in `0..n`.
+    pub fn generate_items(
+        &mut self,
+        treasure_level: u8,
+        table: &ItemsTable,
+        cols: usize,
+        rows: usize,
+        cell_size: impl Fn(&ItemInfo) -> (usize, usize),
+        mut roll: impl FnMut(usize) -> usize,
+    ) {
+        if self.flags().contains(ChestFlags::ITEMS_PLACED) {
+            return;
+        }
+        self.set_flags(ChestFlags::ITEMS_PLACED, true);
+        let mut levels: Vec<Option<u8>> = self
+            .items
+            .iter()
+            .filter(|i| !i.is_empty())
+            .map(|i| (i.number < 0).then(|| i.number.unsigned_abs().min(6) as u8))
+            .collect();
+        if levels.is_empty() && treasure_level > 0 {
+            levels = vec![Some(treasure_level); 1 + roll(3)];
+        }
+        if levels.iter().all(Option::is_none) {
+            return;
+        }
+
+        // Re-pack everything: rolled items rarely match the placeholder's size.
+        let kept: Vec<ItemRecord> = self.items.iter().filter(|i| i.number > 0).copied().collect();
+        let rolled: Vec<ItemRecord> = levels
+            .into_iter()
+            .flatten()
+            .filter_map(|level| table.random_item(level, &mut roll))
+            .map(|info| ItemRecord {
+                number: info.id as i32,
+                ..Default::default()
+            })
+            .collect();
+        self.items.iter_mut().for_each(|i| *i = ItemRecord::default());
+        self.inventory.iter_mut().for_each(|c| *c = 0);
+        for item in kept.into_iter().chain(rolled) {
+            let size = table.get(item.number as u16).map_or((1, 1), &cell_size);
+            if self.place_item(item, size, cols, rows).is_none() {
+                log::warn!("chest full, dropping item {}", item.number);
+            }
+        }
+    }
+
+    pub fn read(c: &mut Cursor<&[u8]>, version: Version) -> std::io::Result<Self> {
+        let picture = c.read_u16::<LittleEndian>()?;
+        let bits = c.read_u16::<LittleEndian>()?;
+        let items = (0..CHEST_ITEM_SLOTS)
+            .map(|_| ItemRecord::read_version(c, version))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let inventory = (0..CHEST_ITEM_SLOTS)
+            .map(|_| c.read_i16::<LittleEndian>())
+            .collect::<std::io::Result<Vec<_>>>()?;
+        Ok(Self {
+            picture,
+            bits,
+            items,
+            inventory,
+        })
+    }
+
+    /// Write the chest, padding or truncating `items`/`inventory` to 140 slots.
+    pub fn write(&self, out: &mut Vec<u8>, version: Version) {
+        out.write_u16::<LittleEndian>(self.picture).unwrap();
+        out.write_u16::<LittleEndian>(self.bits).unwrap();
+        for i in 0..CHEST_ITEM_SLOTS {
+            self.items
+                .get(i)
+                .copied()
+                .unwrap_or_default()
+                .write_version(out, version);
+        }
+        for i in 0..CHEST_ITEM_SLOTS {
+            out.write_i16::<LittleEndian>(self.inventory.get(i).copied().unwrap_or(0))
+                .unwrap();
+        }
+    }
+}
+
+/// The sections shared by DDM and DLV, in file order.
+///
+/// MM7 files have no sprite or sound sprite sections: those stay empty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MapDelta {
+    pub objects: Vec<MapObject>,
+    pub sprites: Vec<MapSprite>,
+    pub sound_sprites: [i32; SOUND_SPRITE_COUNT],
+    pub chests: Vec<MapChest>,
+    pub actors: Vec<DdmActor>,
+}
+
+impl MapDelta {
+    /// Read all shared sections laid out for `version`, starting at the
+    /// cursor position.
+    pub fn read(c: &mut Cursor<&[u8]>, version: Version) -> Result<Self, Error> {
+        if version >= Version::MM7 {
+            let actors = read_actors(c, version)?;
+            let objects = read_objects(c, version)?;
+            let chests = read_chests(c, version)?;
+            return Ok(Self {
+                objects,
+                chests,
+                actors,
+                ..Default::default()
+            });
+        }
+
+        let objects = read_objects(c, version)?;
+
+        let count = read_count(c, MAP_SPRITE_SIZE, "MapSprites")?;
+        let sprites = (0..count)
+            .map(|_| MapSprite::read(c))
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        let mut sound_sprites = [0i32; SOUND_SPRITE_COUNT];
+        c.read_i32_into::<LittleEndian>(&mut sound_sprites)?;
+
+        let chests = read_chests(c, version)?;
+        let actors = read_actors(c, version)?;
+
+        Ok(Self {
+            objects,
+            sprites,
+            sound_sprites,
+            chests,
+            actors,
+        })
+    }
+
+    /// Append all shared sections in the file order of `version`.
+    pub fn write(&self, out: &mut Vec<u8>, version: Version) {
+        if version >= Version::MM7 {
+            self.write_actors(out, version);
+            self.write_objects(out, version);
+            self.write_chests(out, version);
+            return;
+        }
+        self.write_objects(out, version);
+        out.write_u32::<LittleEndian>(self.sprites.len() as u32).unwrap();
+        for sprite in &self.sprites {
+            sprite.write(out);
+        }
+        for &s in &self.sound_sprites {
+            out.write_i32::<LittleEndian>(s).unwrap();
+        }
+        self.write_chests(out, version);
+        self.write_actors(out, version);
+    }
+
+    fn write_objects(&self, out: &mut Vec<u8>, version: Version) {
+        out.write_u32::<LittleEndian>(self.objects.len() as u32).unwrap();
+        for obj in &self.objects {
+            obj.write(out, version);
+        }
+    }
+
+    fn write_chests(&self, out: &mut Vec<u8>, version: Version) {
+        out.write_u32::<LittleEndian>(self.chests.len() as u32).unwrap();
+        for chest in &self.chests {
+            chest.write(out, version);
+        }
+    }
+
+    fn write_actors(&self, out: &mut Vec<u8>, version: Version) {
+        out.write_u32::<LittleEndian>(self.actors.len() as u32).unwrap();
+        for actor in &self.actors {
+            out.extend_from_slice(&actor.to_bytes_version(version));
+        }
+    }
+}
+
+fn read_objects(c: &mut Cursor<&[u8]>, version: Version) -> Result<Vec<MapObject>, Error> {
+    let size = if version >= Version::MM7 {
+        MAP_OBJECT_SIZE_MM7
+    } else {
+        MAP_OBJECT_SIZE
+    };
+    let count = read_count(c, size, "MapObjects")?;
+    Ok((0..count)
+        .map(|_| MapObject::read(c, version))
+        .collect::<std::io::Result<Vec<_>>>()?)
+}
+
+fn read_chests(c: &mut Cursor<&[u8]>, version: Version) -> Result<Vec<MapChest>, Error> {
+    let size = if version >= Version::MM7 {
+        MAP_CHEST_SIZE_MM7
+    } else {
+        MAP_CHEST_SIZE
+    };
+    let count = read_count(c, size, "MapChests")?;
+    Ok((0..count)
+        .map(|_| MapChest::read(c, version))
+        .collect::<std::io::Result<Vec<_>>>()?)
+}
+
+fn read_actors(c: &mut Cursor<&[u8]>, version: Version) -> Result<Vec<DdmActor>, Error> {
+    let size = if version >= Version::MM7 {
+        ACTOR_SIZE_MM7
+    } else {
+        ACTOR_SIZE_MM6
+    };
+    let count = read_count(c, size, "MapMonsters")?;
+    let mut actors = Vec::with_capacity(count);
+    let mut buf = vec![0u8; size];
+    for i in 0..count {
+        c.read_exact(&mut buf)?;
+        let actor = Ddm::read_actor_version(&buf, version)
+            .map_err(|e| Error::invalid("MapMonsters", format!("actor {i} is malformed: {e}")))?;
+        actors.push(actor);
+    }
+    Ok(actors)
+}
+
+/// A map's delta file: DDM for outdoor maps, DLV for indoor ones.
+#[derive(Debug, Clone)]
+pub enum MapDeltaFile {
+    Ddm(Ddm),
+    Dlv(Dlv),
+}
+
+impl MapDeltaFile {
+    /// Load the pristine delta for `map` from `games.lod`.
+    pub fn load(assets: &Assets, map: &MapName) -> Result<Self, Error> {
+        let data = assets.get_decompressed(format!("games/{}", map.delta_filename()))?;
+        Self::parse(assets, map, &data)
+    }
+
+    /// Parse decompressed delta data for `map`.
+    ///
+    /// DLV section sizes are not stored in the file, so indoor maps read
+    /// them from the pristine BLV in `assets`. The layout follows
+    /// [`Assets::version`].
+    pub fn parse(assets: &Assets, map: &MapName, data: &[u8]) -> Result<Self, Error> {
+        match map {
+            MapName::Outdoor(_) => Ok(Self::Ddm(Ddm::parse_version(data, assets.version())?)),
+            MapName::Indoor(_) => {
+                let blv = Blv::load(assets, &map.filename())?;
+                Ok(Self::Dlv(Dlv::parse_version(
+                    data,
+                    &DlvLayout::from_blv(&blv),
+                    assets.version(),
+                )?))
+            }
+        }
+    }
+
+    pub fn delta(&self) -> &MapDelta {
+        match self {
+            Self::Ddm(ddm) => &ddm.delta,
+            Self::Dlv(dlv) => &dlv.delta,
+        }
+    }
+
+    pub fn delta_mut(&mut self) -> &mut MapDelta {
+        match self {
+            Self::Ddm(ddm) => &mut ddm.delta,
+            Self::Dlv(dlv) => &mut dlv.delta,
+        }
+    }
+
+    /// MapVars bytes (200 when present).
+    pub fn map_vars(&self) -> &[u8] {
+        match self {
+            Self::Ddm(ddm) => &ddm.map_vars,
+            Self::Dlv(dlv) => &dlv.map_vars,
+        }
+    }
+
+    /// Overwrite the MapVars section, padding it to its full 200 bytes.
+    pub fn set_map_vars(&mut self, vars: &[u8]) {
+        let map_vars = match self {
+            Self::Ddm(ddm) => &mut ddm.map_vars,
+            Self::Dlv(dlv) => &mut dlv.map_vars,
+        };
+        map_vars.clear();
+        map_vars.extend((0..MAP_VARS_SIZE).map(|i| vars.get(i).copied().unwrap_or(0)));
+    }
+
+    /// Game time of the party's last visit in MM6 ticks (128 per second):
+    /// the first i64 of the LocationTime block that follows the map vars
+    /// (DLV) or the actors (DDM). 0 for pristine `games.lod` deltas.
+    pub fn last_visit(&self) -> i64 {
+        let block = match self {
+            Self::Ddm(ddm) => &ddm.tail,
+            Self::Dlv(dlv) => &dlv.location_time,
+        };
+        block
+            .get(..LAST_VISIT_SIZE)
+            .map_or(0, |b| i64::from_le_bytes(b.try_into().unwrap()))
+    }
+
+    /// Stamp the last visit time, growing a short LocationTime block.
+    pub fn set_last_visit(&mut self, ticks: i64) {
+        let block = match self {
+            Self::Ddm(ddm) => &mut ddm.tail,
+            Self::Dlv(dlv) => {
+                // LocationTime starts right after the map vars, so they must be full size.
+                dlv.map_vars.resize(MAP_VARS_SIZE, 0);
+                &mut dlv.location_time
+            }
+        };
+        if block.len() < LAST_VISIT_SIZE {
+            block.resize(LAST_VISIT_SIZE, 0);
+        }
+        block[..LAST_VISIT_SIZE].copy_from_slice(&ticks.to_le_bytes());
+    }
+}
+
+impl LodSerialise for MapDeltaFile {
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ddm(ddm) => ddm.to_bytes(),
+            Self::Dlv(dlv) => dlv.to_bytes(),
+        }
+    }
+}
+
+/// Read a u32 section count and check that `count × elem_size` bytes remain.
+fn read_count(c: &mut Cursor<&[u8]>, elem_size: usize, section: &'static str) -> Result<usize, Error> {
+    let count = c.read_u32::<LittleEndian>()? as usize;
+    Error::check_len(
+        section,
+        c.get_ref(),
+        c.position() as usize,
+        count.saturating_mul(elem_size),
+    )?;
+    Ok(count)
+}
+
+fn read_i32x3(c: &mut Cursor<&[u8]>) -> std::io::Result<[i32; 3]> {
+    Ok([
+        c.read_i32::<LittleEndian>()?,
+        c.read_i32::<LittleEndian>()?,
+        c.read_i32::<LittleEndian>()?,
+    ])
+}
+
+fn write_i32x3(out: &mut Vec<u8>, v: &[i32; 3]) {
+    for &x in v {
+        out.write_i32::<LittleEndian>(x).unwrap();
+    }
+}
@@ -27,12 +27,14 @@
 
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::Cursor;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
+const MINUTES_PER_DAY: u64 = 24 * 60;
+
 /// Per-map info from mapstats.txt.
 /// From OpenEnroth MapInfo and MMExtension MapStatsItem.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,7 +55,8 @@ pub struct MapInfo {
     pub reset_count: u16,
     /// First visit day.
     pub first_visit_day: u16,
-    /// Respawn interval in days.
+    /// Refill interval in days (RefillDays): after this long away the map
+    /// resets to its pristine state. 0 = never.
     pub respawn_days: u16,
     /// Lock difficulty (0-10, "x5 Lock" from mapstats.txt).
     pub lock: u8,
@@ -80,12 +83,12 @@ pub struct MapStats {
 }
 
 impl MapStats {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/mapstats.txt")?;
         Self::try_from(raw.as_slice())
     }
 
-    pub fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(text: &str) -> Result<Self, Error> {
         let body: String = text.lines().skip(3).collect::<Vec<_>>().join("\n");
         let mut rdr = ReaderBuilder::new()
             .delimiter(b'\t')
@@ -95,7 +98,7 @@ impl MapStats {
 
         let mut maps = Vec::new();
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("mapstats.txt", e))?;
 
             let filename = rec.get(2).unwrap_or("").trim().to_lowercase();
             if filename.is_empty() {
@@ -180,7 +183,7 @@ impl MapStats {
 }
 
 impl TryFrom<&[u8]> for MapStats {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -252,6 +255,21 @@ fn parse_count_range(s: &str) -> (u8, u8) {
 mod tests;
 
 impl MapInfo {
+    /// Time away after which the map resets, in game minutes. `None` for
+    /// maps that never refill.
+    pub fn refill_interval_minutes(&self) -> Option<u64> {
+        (self.respawn_days > 0).then(|| self.respawn_days as u64 * MINUTES_PER_DAY)
+    }
+
+    /// Whether a map last left at `last_visit` (game minutes) has refilled
+    /// by `now`. A map that was never left is never due.
+    pub fn refill_due(&self, last_visit: u64, now: u64) -> bool {
+        last_visit > 0
+            && self
+                .refill_interval_minutes()
+                .is_some_and(|interval| now.saturating_sub(last_visit) >= interval)
+    }
+
     /// Resolve a spawn point's monster_index to (internal_name_prefix, display_name, slot, forced_variant).
     ///
     /// MM6 spawn index mapping (from MMExtension SpawnPoint.Index):
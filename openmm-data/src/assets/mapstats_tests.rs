@@ -22,6 +22,26 @@ fn make_map_info(monster_names: [&str; 3], difficulty: [u8; 3]) -> MapInfo {
     }
 }
 
+#[test]
+fn refill_due_after_refill_days() {
+    let info = make_map_info(["Goblin", "", ""], [1, 1, 1]);
+    let day = 24 * 60;
+    assert_eq!(info.refill_interval_minutes(), Some(7 * day));
+    assert!(!info.refill_due(day, 8 * day - 1));
+    assert!(info.refill_due(day, 8 * day));
+    // Never left, or clock behind the stamp (older save): no reset.
+    assert!(!info.refill_due(0, 100 * day));
+    assert!(!info.refill_due(10 * day, day));
+}
+
+#[test]
+fn refill_never_with_zero_days() {
+    let mut info = make_map_info(["Goblin", "", ""], [1, 1, 1]);
+    info.respawn_days = 0;
+    assert_eq!(info.refill_interval_minutes(), None);
+    assert!(!info.refill_due(1, u64::MAX));
+}
+
 #[test]
 fn parse_count_range_dash_separated() {
     assert_eq!(parse_count_range("2-4"), (2, 4));
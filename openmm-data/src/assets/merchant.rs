@@ -6,9 +6,9 @@
 //!             "good merchant skill", "wrong type of merchant", "Unnecessary"
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One dialogue scenario row from `merchant.txt`.
@@ -30,7 +30,7 @@ pub struct MerchantTable {
 }
 
 impl MerchantTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/merchant.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -74,7 +74,7 @@ fn split_tsv(line: &str) -> Vec<&str> {
 }
 
 impl TryFrom<&[u8]> for MerchantTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
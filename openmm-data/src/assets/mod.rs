@@ -32,10 +32,14 @@ pub mod enums;
 pub mod event_enums;
 pub mod evt;
 pub mod face_enums;
+pub mod flac;
 pub mod font;
+pub mod font_raster;
 pub mod image;
+pub mod image_encode;
 pub mod items;
 pub mod lod_data;
+pub mod map_delta;
 pub mod mapstats;
 pub mod media_cache;
 pub mod merchant;
@@ -54,6 +58,7 @@ pub mod proftext;
 pub mod quests;
 pub mod scroll;
 pub mod smk;
+pub mod smk_encode;
 pub mod snd;
 pub mod sound_enums;
 pub mod spells;
@@ -73,13 +78,14 @@ pub use self::font::Font;
 pub use self::image::{Image, get_atlas};
 pub use self::items::ItemsTable;
 pub use self::lod_data::LodData;
+pub use self::map_delta::{ItemRecord, MapChest, MapDelta, MapObject, MapSprite};
 pub use self::mapstats::MapStats;
 pub use self::media_cache::MediaCache;
 pub use self::odm::{Odm, SpawnPoint};
 pub use self::palette::{Palette, Palettes};
 pub use self::provider::archive::smk::{SmkArchive, SmkWriter};
 pub use self::provider::{Archive, ArchiveEntry, LodArchive, LodWriter, StaticGameData as GameData, Version};
-pub use self::smk::{SmkAudioInfo, SmkDecoder, SmkInfo, parse_smk_info};
+pub use self::smk::{SmkAudioChunk, SmkAudioInfo, SmkDecoder, SmkInfo, SmkPacket, parse_smk_info};
 pub use self::snd::SndArchive;
 pub use self::zlib::*;
 
@@ -90,7 +96,7 @@ pub use self::awards::AwardsTable;
 pub use self::billboard::BillboardData;
 pub use self::bsp_model::BSPModel;
 pub use self::class::ClassTable;
-pub use self::dlv::Dlv;
+pub use self::dlv::{Dlv, DlvLayout};
 pub use self::dmonlist::MonsterList;
 pub use self::dtile::{Dtile, TileTable};
 pub use self::merchant::MerchantTable;
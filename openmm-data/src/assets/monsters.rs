@@ -13,10 +13,10 @@
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::error::Error;
 use std::io::Cursor;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// All per-variant stats for one monster from monsters.txt.
@@ -93,12 +93,12 @@ pub struct MonsterStatsTable {
 }
 
 impl MonsterStatsTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/monsters.txt")?;
         Self::try_from(raw.as_slice())
     }
 
-    fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+    fn parse(text: &str) -> Result<Self, Error> {
         // Skip the 3 header lines (group labels, column names, blank separator).
         let body: String = text.lines().skip(3).collect::<Vec<_>>().join("\n");
 
@@ -111,7 +111,7 @@ impl MonsterStatsTable {
         let mut entries = HashMap::new();
 
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("monsters.txt", e))?;
 
             // Col 1: internal name (e.g. "GoblinA"). Skip blank/category marker rows.
             let internal = rec.get(1).unwrap_or("").trim();
@@ -211,7 +211,7 @@ impl MonsterStatsTable {
 }
 
 impl TryFrom<&[u8]> for MonsterStatsTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
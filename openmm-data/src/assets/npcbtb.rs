@@ -7,9 +7,9 @@
 //! Columns 0-1: Msg# / Notes; columns 2..N: one per NPC personality type.
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// Dialogue support flags and texts for one NPC personality type.
@@ -31,7 +31,7 @@ pub struct NpcBtbTable {
 }
 
 impl NpcBtbTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/npcbtb.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -122,7 +122,7 @@ impl NpcBtbTable {
 }
 
 impl TryFrom<&[u8]> for NpcBtbTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -4,9 +4,9 @@
 //! Either column may be empty if the lists are different lengths.
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// Male and female NPC name pools.
@@ -17,7 +17,7 @@ pub struct NpcNamePools {
 }
 
 impl NpcNamePools {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/npcnames.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -66,7 +66,7 @@ impl NpcNamePools {
 }
 
 impl TryFrom<&[u8]> for NpcNamePools {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -6,10 +6,10 @@
 
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::Cursor;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One NPC news entry from `npcnews.txt`.
@@ -32,12 +32,12 @@ pub struct NpcNewsTable {
 }
 
 impl NpcNewsTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/npcnews.txt")?;
         Self::try_from(raw.as_slice())
     }
 
-    fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+    fn parse(text: &str) -> Result<Self, Error> {
         let body: String = text.lines().skip(2).collect::<Vec<_>>().join("\n");
         let mut rdr = ReaderBuilder::new()
             .delimiter(b'\t')
@@ -47,7 +47,7 @@ impl NpcNewsTable {
 
         let mut items = Vec::new();
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("npcnews.txt", e))?;
 
             let index: u16 = match rec.get(0).unwrap_or("").trim().parse() {
                 Ok(v) => v,
@@ -74,7 +74,7 @@ impl NpcNewsTable {
 }
 
 impl TryFrom<&[u8]> for NpcNewsTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
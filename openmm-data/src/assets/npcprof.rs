@@ -7,10 +7,10 @@
 
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::Cursor;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One NPC profession definition from `npcprof.txt`.
@@ -41,12 +41,12 @@ pub struct NpcProfTable {
 }
 
 impl NpcProfTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/npcprof.txt")?;
         Self::try_from(raw.as_slice())
     }
 
-    fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+    fn parse(text: &str) -> Result<Self, Error> {
         let body: String = text.lines().skip(4).collect::<Vec<_>>().join("\n");
         let mut rdr = ReaderBuilder::new()
             .delimiter(b'\t')
@@ -56,7 +56,7 @@ impl NpcProfTable {
 
         let mut professions = Vec::new();
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("npcprof.txt", e))?;
 
             let id: u16 = match rec.get(0).unwrap_or("").trim().parse() {
                 Ok(v) => v,
@@ -91,7 +91,7 @@ impl NpcProfTable {
 }
 
 impl TryFrom<&[u8]> for NpcProfTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
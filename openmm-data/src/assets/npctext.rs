@@ -4,9 +4,9 @@
 //! Columns: #, Text, Notes
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One NPC dialogue text entry.
@@ -25,7 +25,7 @@ pub struct NpcTextTable {
 }
 
 impl NpcTextTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/npctext.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -63,7 +63,7 @@ impl NpcTextTable {
 }
 
 impl TryFrom<&[u8]> for NpcTextTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -4,9 +4,9 @@
 //! Columns: #, Topic, (Notes)
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One NPC topic entry.
@@ -24,7 +24,7 @@ pub struct NpcTopicTable {
 }
 
 impl NpcTopicTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/npctopic.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -54,7 +54,7 @@ impl NpcTopicTable {
 }
 
 impl TryFrom<&[u8]> for NpcTopicTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -1,8 +1,9 @@
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::{Cursor, Read, Seek};
 
+use crate::Error;
+
 fn default_odm_map() -> [u8; 16384] {
     [0; 16384]
 }
@@ -15,7 +16,7 @@ use crate::assets::{
     dtile::{Dtile, TileTable},
     lod_data::LodData,
 };
-use crate::{Assets, utils::try_read_string_block};
+use crate::{Assets, Version, utils::try_read_string_block};
 
 pub const ODM_SIZE: usize = 128;
 pub const ODM_PLAY_SIZE: usize = 88;
@@ -33,6 +34,17 @@ const TILEMAP_SIZE: usize = ODM_AREA;
 const ATTRIBUTE_MAP_OFFSET: u64 = TILE_MAP_OFFSET + ATTRIBUTE_MAP_SIZE as u64;
 const ATTRIBUTE_MAP_SIZE: usize = ODM_AREA;
 
+/// MM7+ terrain lighting data between the attribute map and the models:
+/// a u32[128×128×2] triangle map and a u16[128×128×2] normal index map,
+/// followed by `normal_count` float normals (12 bytes each).
+const TERRAIN_NORMAL_MAPS_SIZE: i64 = 0x20000 + 0x10000;
+const TERRAIN_NORMAL_SIZE: i64 = 12;
+
+/// Spawn point record size: 20 bytes in MM6, 24 in MM7+ (trailing Group).
+fn spawn_point_size(version: Version) -> usize {
+    if version >= Version::MM7 { 24 } else { 20 }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Odm {
@@ -69,9 +81,9 @@ pub struct Odm {
 
 /// A spawn point for monsters or items in an ODM outdoor map.
 ///
-/// MM6 record: 20 bytes (SpawnPoint struct from MMExtension, no MM7 Group field at 0x14).
+/// MM6 record: 20 bytes (SpawnPoint struct from MMExtension). MM7+ appends a
+/// u32 Group at 0x14 for 24 bytes.
 /// Layout: 0x00: pos[3](i32), 0x0C: radius(i16), 0x0E: kind(i16), 0x10: index(i16), 0x12: bits(u16)
-/// A spawn point for monsters or items in an outdoor map.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SpawnPoint {
     /// Spawn center in MM6 world coordinates (x, y, z). Offset 0x00.
@@ -85,23 +97,36 @@ pub struct SpawnPoint {
     pub monster_index: u16,
     /// Spawn attribute flags (e.g. bit 0 = OnAlertMap). Offset 0x12.
     pub attributes: u16,
+    /// Monster group assigned to spawned actors. MM7+ only, 0 for MM6. Offset 0x14.
+    #[serde(default)]
+    pub group: u32,
 }
 
 impl Odm {
-    pub fn load(assets: &Assets, name: &str) -> Result<Self, Box<dyn Error>> {
-        let raw = assets.get_bytes(format!("games/{}", name))?;
-        Self::try_from(raw.as_slice())
+    pub fn load(assets: &Assets, name: &str) -> Result<Self, Error> {
+        let data = assets.get_entry(format!("games/{}", name))?;
+        Self::parse_version(&data, assets.version()).map_err(|e| e.in_file(name))
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    /// Parse an MM6 outdoor map.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        Self::parse_version(data, Version::MM6)
+    }
+
+    /// Parse an outdoor map laid out for `version`.
+    pub fn parse_version(data: &[u8], version: Version) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
+        Self::read(data, &mut cursor, version).map_err(|e| e.at("odm", cursor.position() as usize))
+    }
+
+    fn read(data: &[u8], cursor: &mut Cursor<&[u8]>, version: Version) -> Result<Self, Error> {
         // 0x00: Name[32] — map display name (e.g. "Harmondale")
-        let name = try_read_string_block(&mut cursor, 32)?;
+        let name = try_read_string_block(cursor, 32)?;
         // 0x20: FileName[32] — file name (e.g. "oute3.odm")
-        let file_name = try_read_string_block(&mut cursor, 32)?;
-        let odm_version = try_read_string_block(&mut cursor, 32)?;
-        let sky_texture = try_read_string_block(&mut cursor, 32)?;
-        let ground_texture = try_read_string_block(&mut cursor, 32)?;
+        let file_name = try_read_string_block(cursor, 32)?;
+        let odm_version = try_read_string_block(cursor, 32)?;
+        let sky_texture = try_read_string_block(cursor, 32)?;
+        let ground_texture = try_read_string_block(cursor, 32)?;
         let tile_data: [u16; 8] = [
             cursor.read_u16::<LittleEndian>()?,
             cursor.read_u16::<LittleEndian>()?,
@@ -125,14 +150,21 @@ impl Odm {
         let mut attribute_map: [u8; ATTRIBUTE_MAP_SIZE] = [0; ATTRIBUTE_MAP_SIZE];
         cursor.read_exact(&mut attribute_map)?;
 
+        if version >= Version::MM7 {
+            let normal_count = cursor.read_u32::<LittleEndian>()? as i64;
+            cursor.seek(std::io::SeekFrom::Current(
+                TERRAIN_NORMAL_MAPS_SIZE + normal_count * TERRAIN_NORMAL_SIZE,
+            ))?;
+        }
+
         let bsp_model_count = cursor.read_u32::<LittleEndian>()? as usize;
-        let bsp_models: Vec<BSPModel> = read_bsp_models(&mut cursor, bsp_model_count)?;
+        let bsp_models: Vec<BSPModel> = read_bsp_models(cursor, bsp_model_count)?;
 
         let billboard_count = cursor.read_u32::<LittleEndian>()? as usize;
-        let billboards: Vec<Billboard> = read_billboards(&mut cursor, billboard_count)?;
+        let billboards: Vec<Billboard> = read_billboards(cursor, billboard_count, version)?;
 
         // Spawn points are at the very end of the ODM file.
-        let spawn_points = Self::read_spawn_points(data);
+        let spawn_points = Self::read_spawn_points(data, spawn_point_size(version));
 
         Ok(Self {
             name,
@@ -152,7 +184,7 @@ impl Odm {
 }
 
 impl TryFrom<&[u8]> for Odm {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
@@ -160,6 +192,7 @@ impl TryFrom<&[u8]> for Odm {
     }
 }
 
+// Always writes the MM6 layout.
 impl LodSerialise for Odm {
     fn to_bytes(&self) -> Vec<u8> {
         use byteorder::{LittleEndian, WriteBytesExt};
@@ -311,12 +344,12 @@ fn write_spawn_point(buf: &mut Vec<u8>, sp: &SpawnPoint) {
 
 impl Odm {
     /// Read spawn points from the end of the ODM data.
-    /// The spawn point array is the last section: u32 count + N × 20-byte records.
-    fn read_spawn_points(data: &[u8]) -> Vec<SpawnPoint> {
+    /// The spawn point array is the last section: u32 count + N × `record`-byte records.
+    fn read_spawn_points(data: &[u8], record: usize) -> Vec<SpawnPoint> {
         // Try reading count from various offsets near the end
-        // The spawn section = count(4) + count*20 bytes, ending at data.len()
+        // The spawn section = count(4) + count*record bytes, ending at data.len()
         for candidate_count in (5..200u32).rev() {
-            let section_size = 4 + candidate_count as usize * 20;
+            let section_size = 4 + candidate_count as usize * record;
             if section_size > data.len() {
                 break;
             }
@@ -345,8 +378,8 @@ impl Odm {
             // Read all spawn points
             let mut spawns = Vec::with_capacity(stored_count as usize);
             for i in 0..stored_count as usize {
-                let off = count_offset + 4 + i * 20;
-                if off + 20 > data.len() {
+                let off = count_offset + 4 + i * record;
+                if off + record > data.len() {
                     break;
                 }
                 spawns.push(SpawnPoint {
@@ -359,6 +392,11 @@ impl Odm {
                     spawn_type: u16::from_le_bytes(data[off + 14..off + 16].try_into().unwrap()),
                     monster_index: u16::from_le_bytes(data[off + 16..off + 18].try_into().unwrap()),
                     attributes: u16::from_le_bytes(data[off + 18..off + 20].try_into().unwrap()),
+                    group: if record >= 24 {
+                        u32::from_le_bytes(data[off + 20..off + 24].try_into().unwrap())
+                    } else {
+                        0
+                    },
                 });
             }
             return spawns;
@@ -370,10 +408,10 @@ impl Odm {
         (ODM_SIZE, ODM_SIZE)
     }
 
-    pub fn tile_table(&self, assets: &Assets) -> Result<TileTable, Box<dyn Error>> {
+    pub fn tile_table(&self, assets: &Assets) -> Result<TileTable, Error> {
         Dtile::load(assets)?
             .table(self.tile_data)
-            .ok_or("could not get the tile table".into())
+            .ok_or_else(|| Error::invalid("odm", "tile data matches no dtile.bin tile set"))
     }
 }
 
@@ -556,4 +594,42 @@ mod tests {
         let map = Odm::load(&assets, "oute3.odm").unwrap();
         assert_eq!(map.bsp_models.len(), 85)
     }
+
+    #[test]
+    fn parses_mm7_layout() {
+        let mut data = vec![0u8; 176];
+        data[..6].copy_from_slice(b"Emerld");
+        data.extend(std::iter::repeat_n(7u8, ODM_AREA * 3));
+        // Terrain normals: two of them after the fixed-size maps.
+        data.extend(2u32.to_le_bytes());
+        data.extend(std::iter::repeat_n(0u8, 0x30000 + 2 * 12));
+        data.extend(0u32.to_le_bytes()); // models
+        data.extend(1u32.to_le_bytes()); // billboards
+        let mut billboard = [0u8; 32];
+        billboard[..2].copy_from_slice(&9u16.to_le_bytes());
+        billboard[28..].fill(0xEE);
+        data.extend(billboard);
+        let mut name = [0u8; 32];
+        name[..5].copy_from_slice(b"Tree1");
+        data.extend(name);
+        data.extend(5u32.to_le_bytes());
+        for i in 0..5u32 {
+            data.extend(100i32.to_le_bytes());
+            data.extend((-100i32).to_le_bytes());
+            data.extend(50i32.to_le_bytes());
+            data.extend([0, 1, 3, 0, 1, 0, 0, 0]);
+            data.extend((i + 1).to_le_bytes());
+        }
+
+        let odm = Odm::parse_version(&data, Version::MM7).unwrap();
+        assert_eq!(odm.name, "Emerld");
+        assert_eq!(odm.attribute_map[0], 7);
+        assert_eq!(odm.billboards.len(), 1);
+        assert_eq!(odm.billboards[0].declist_name, "tree1");
+        assert_eq!(odm.billboards[0].data.declist_id, 9);
+        assert_eq!(odm.spawn_points.len(), 5);
+        assert_eq!(odm.spawn_points[0].position, [100, -100, 50]);
+        assert_eq!(odm.spawn_points[0].spawn_type, 3);
+        assert_eq!(odm.spawn_points[4].group, 5);
+    }
 }
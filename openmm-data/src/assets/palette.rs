@@ -1,6 +1,6 @@
 use crate::Assets;
+use crate::Error;
 use std::collections::HashMap;
-use std::error::Error;
 
 const PALETTE_HEADER_SIZE: usize = 48;
 const PALETTE_SIZE: usize = 768;
@@ -13,11 +13,14 @@ pub struct Palette {
 }
 
 impl TryFrom<&[u8]> for Palette {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         if data.len() != PALETTE_DATA_SIZE {
-            return Err("Malformed palette, expected size is 768B".into());
+            return Err(Error::invalid(
+                "palette",
+                format!("{} bytes, expected {PALETTE_DATA_SIZE}", data.len()),
+            ));
         }
         let palette = &data[PALETTE_HEADER_SIZE..];
         Ok(Self {
@@ -33,12 +36,12 @@ pub struct Palettes {
 }
 
 impl TryFrom<&Assets> for Palettes {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(assets: &Assets) -> Result<Self, Self::Error> {
         let palette_files: Vec<_> = assets
             .files_in("bitmaps")
-            .ok_or("No bitmaps archive found")?
+            .ok_or_else(|| Error::not_found("", "bitmaps.lod"))?
             .into_iter()
             .filter(|f| f.to_lowercase().starts_with("pal") && f.len() == 6)
             .collect();
@@ -61,17 +64,20 @@ impl Palettes {
     }
 }
 
-fn extract_palette_id(s: &str) -> Result<u16, Box<dyn Error>> {
-    if s.len() < 3 {
-        return Err("String is too short to contain a palette id".into());
-    }
-    let num_str = &s[s.len() - 3..];
-    match num_str.parse::<u16>() {
-        Ok(num) => Ok(num),
-        _ => Err("Invalid u16 value".into()),
+impl FromIterator<(u16, Palette)> for Palettes {
+    fn from_iter<I: IntoIterator<Item = (u16, Palette)>>(iter: I) -> Self {
+        Palettes {
+            palettes: iter.into_iter().collect(),
+        }
     }
 }
 
+fn extract_palette_id(s: &str) -> Result<u16, Error> {
+    s.get(s.len().saturating_sub(3)..)
+        .and_then(|num| num.parse::<u16>().ok())
+        .ok_or_else(|| Error::invalid("palette", format!("no palette id in `{s}`")))
+}
+
 #[cfg(test)]
 #[path = "palette_tests.rs"]
 mod tests;
@@ -4,9 +4,9 @@
 //! Columns: Number, Questions, Answers
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One password entry.
@@ -25,7 +25,7 @@ pub struct PasswordsTable {
 }
 
 impl PasswordsTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/passwords.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -56,7 +56,7 @@ impl PasswordsTable {
 }
 
 impl TryFrom<&[u8]> for PasswordsTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
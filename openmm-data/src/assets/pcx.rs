@@ -75,3 +75,79 @@ pub fn decode(data: &[u8]) -> Option<image::DynamicImage> {
     }
     Some(image::DynamicImage::ImageRgba8(img))
 }
+
+/// Encode an image as a 24-bit RGB PCX (3 planes, RLE), the format MM6 uses
+/// for save screenshots. Alpha is dropped.
+pub fn encode(img: &image::RgbaImage) -> Vec<u8> {
+    let (width, height) = img.dimensions();
+    // Scanline planes are padded to an even byte count, as the format requires.
+    let bytes_per_line = (width as usize).div_ceil(2) * 2;
+
+    let mut out = Vec::with_capacity(128 + (width * height * 3) as usize);
+    out.push(0x0A); // manufacturer: ZSoft
+    out.push(5); // version 3.0+
+    out.push(1); // RLE encoding
+    out.push(8); // bits per pixel per plane
+    out.extend_from_slice(&0u16.to_le_bytes()); // x_min
+    out.extend_from_slice(&0u16.to_le_bytes()); // y_min
+    out.extend_from_slice(&(width.saturating_sub(1) as u16).to_le_bytes());
+    out.extend_from_slice(&(height.saturating_sub(1) as u16).to_le_bytes());
+    out.extend_from_slice(&72u16.to_le_bytes()); // horizontal DPI
+    out.extend_from_slice(&72u16.to_le_bytes()); // vertical DPI
+    out.extend_from_slice(&[0u8; 48]); // EGA palette (unused)
+    out.push(0); // reserved
+    out.push(3); // planes: R, G, B
+    out.extend_from_slice(&(bytes_per_line as u16).to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // palette info: colour
+    out.resize(128, 0);
+
+    let mut line = vec![0u8; bytes_per_line];
+    for y in 0..height {
+        for channel in 0..3 {
+            for x in 0..width {
+                line[x as usize] = img.get_pixel(x, y)[channel];
+            }
+            encode_rle_line(&line, &mut out);
+        }
+    }
+    out
+}
+
+/// RLE-encode one plane scanline: runs of up to 63 bytes are stored as
+/// `0xC0 | count, value`; single bytes below `0xC0` are stored literally.
+fn encode_rle_line(line: &[u8], out: &mut Vec<u8>) {
+    let mut i = 0;
+    while i < line.len() {
+        let value = line[i];
+        let run = line[i..].iter().take(0x3F).take_while(|&&b| b == value).count();
+        if run > 1 || value >= 0xC0 {
+            out.push(0xC0 | run as u8);
+        }
+        out.push(value);
+        i += run;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_round_trip() {
+        // Odd width exercises scanline padding; the gradient mixes runs and literals.
+        let img = image::RgbaImage::from_fn(151, 7, |x, y| {
+            let v = if x < 80 { 0xF0 } else { (x * 3 + y) as u8 };
+            image::Rgba([v, (y * 40) as u8, 0xC0, 255])
+        });
+        let decoded = decode(&encode(&img)).expect("decode encoded pcx").to_rgba8();
+        assert_eq!(decoded.dimensions(), img.dimensions());
+        assert_eq!(decoded.as_raw(), img.as_raw());
+    }
+
+    #[test]
+    fn long_runs_are_split() {
+        let mut out = Vec::new();
+        encode_rle_line(&[7u8; 100], &mut out);
+        assert_eq!(out, vec![0xC0 | 63, 7, 0xC0 | 37, 7]);
+    }
+}
@@ -4,9 +4,9 @@
 //! Columns: #, Profession name, then (topic, text) pairs for Sunday..Saturday (7 days × 2 = 14 cols).
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 pub const DAYS: [&str; 7] = [
@@ -50,7 +50,7 @@ pub struct ProfTextTable {
 }
 
 impl ProfTextTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/proftext.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -96,7 +96,7 @@ impl ProfTextTable {
 }
 
 impl TryFrom<&[u8]> for ProfTextTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
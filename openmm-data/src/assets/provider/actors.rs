@@ -5,9 +5,9 @@
 //! no direct LOD queries during spawn.
 
 use crate::Assets;
+use crate::Error;
 use crate::assets::GameData;
 use crate::assets::ddm::{MonsterSchedule, SpellBuff};
-use std::error::Error;
 
 /// Stub for future map-state persistence. When populated, actors whose index
 /// is in `dead_actor_ids` are excluded from the returned roster.
@@ -17,6 +17,9 @@ pub struct MapStateSnapshot {
 
 /// A fully resolved actor from the map's DDM file.
 pub struct Actor {
+    /// Index of the source record in the DDM/DLV actor list, or -1 for
+    /// actors created from spawn points.
+    pub ddm_index: i32,
     pub position: [i32; 3],
     pub standing_sprite: String,
     pub walking_sprite: String,
@@ -94,25 +97,25 @@ impl Actors {
         map_name: &str,
         state: Option<&MapStateSnapshot>,
         game_data: &GameData,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, Error> {
         let ddm = crate::assets::ddm::Ddm::load(assets, map_name)?;
         let street_npcs = game_data.street_npcs.as_ref();
 
         let dead_ids: &[u16] = state.map(|s| s.dead_actor_ids.as_slice()).unwrap_or(&[]);
 
-        let raw_monsters = ddm.actors.iter().filter(|a| a.npc_id == 0).count();
-        let raw_npcs = ddm.actors.iter().filter(|a| a.npc_id > 0).count();
+        let raw_monsters = ddm.delta.actors.iter().filter(|a| a.npc_id == 0).count();
+        let raw_npcs = ddm.delta.actors.iter().filter(|a| a.npc_id > 0).count();
         log::warn!(
             "Actors::new {}: {} raw actors ({} monsters npc_id=0, {} NPCs)",
             map_name,
-            ddm.actors.len(),
+            ddm.delta.actors.len(),
             raw_monsters,
             raw_npcs
         );
 
-        let mut actors = Vec::with_capacity(ddm.actors.len());
+        let mut actors = Vec::with_capacity(ddm.delta.actors.len());
 
-        for (idx, raw) in ddm.actors.iter().enumerate() {
+        for (idx, raw) in ddm.delta.actors.iter().enumerate() {
             if dead_ids.contains(&(idx as u16)) {
                 continue;
             }
@@ -166,6 +169,7 @@ impl Actors {
             };
 
             actors.push(Actor {
+                ddm_index: idx as i32,
                 position: [raw.position[0] as i32, raw.position[1] as i32, raw.position[2] as i32],
                 standing_sprite: entry.standing_sprite,
                 walking_sprite: entry.walking_sprite,
@@ -221,7 +225,7 @@ impl Actors {
         raw_actors: &[crate::assets::ddm::DdmActor],
         state: Option<&MapStateSnapshot>,
         game_data: &GameData,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, Error> {
         let street_npcs = game_data.street_npcs.as_ref();
 
         let dead_ids: &[u16] = state.map(|s| s.dead_actor_ids.as_slice()).unwrap_or(&[]);
@@ -268,6 +272,7 @@ impl Actors {
             };
 
             actors.push(Actor {
+                ddm_index: idx as i32,
                 position: [raw.position[0] as i32, raw.position[1] as i32, raw.position[2] as i32],
                 standing_sprite: entry.standing_sprite,
                 walking_sprite: entry.walking_sprite,
@@ -316,6 +321,7 @@ impl Actors {
             let offset_y = (r * angle.sin()) as i32;
 
             actors.push(Actor {
+                ddm_index: -1,
                 position: [
                     m.spawn_position[0] + offset_x,
                     m.spawn_position[1] + offset_y,
@@ -336,7 +342,7 @@ impl Actors {
                 hp: m.hp,
                 tether_distance: m.radius.saturating_mul(2),
                 npc_id: 0,
-                monlist_id: 0, // not strictly needed for spawn-point conversion
+                monlist_id: m.monlist_id,
                 is_peasant: false,
                 is_female: false,
                 to_hit_radius: m.body_radius,
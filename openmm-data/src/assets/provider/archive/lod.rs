@@ -1,13 +1,17 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
-    error::Error,
     fs::File,
-    io::{BufReader, Cursor, Read, Seek, SeekFrom, Write},
+    io::{BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write},
+    ops::Deref,
     path::Path,
+    sync::Arc,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
+use crate::Error;
+use crate::assets::lod_data::zlib_payload;
 use crate::assets::provider::archive::{Archive, ArchiveEntry};
 
 // ── LOD Archive Reader ────────────────────────────────────────────────────────
@@ -15,8 +19,14 @@ use crate::assets::provider::archive::{Archive, ArchiveEntry};
 const FILE_HEADER_SIZE: usize = 32;
 const FILE_INDEX_OFFSET: u64 = 256;
 
-#[derive(Debug, Clone)]
+/// Game the archive was written for, from its header string.
+///
+/// Parsers that have version-specific record layouts take one of these;
+/// [`Assets::version`](crate::Assets::version) reports the one in use.
+/// Variants are ordered, so `version >= Version::MM7` selects the MM7+ layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Version {
+    #[default]
     MM6,
     MM7,
     MM8,
@@ -45,7 +55,7 @@ impl TryFrom<&str> for Version {
     }
 }
 
-fn try_read_string(reader: &mut impl Read) -> Result<String, Box<dyn Error>> {
+fn try_read_string(reader: &mut impl Read) -> Result<String, Error> {
     let mut buf = [0u8; 256];
     let mut i = 0;
     while i < buf.len() {
@@ -74,7 +84,7 @@ struct FileHeader {
 }
 
 impl TryFrom<&[u8; FILE_HEADER_SIZE]> for FileHeader {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8; FILE_HEADER_SIZE]) -> Result<Self, Self::Error> {
         let first_zero_idx = data.iter().position(|&x| x == 0).unwrap_or(data.len());
@@ -97,10 +107,36 @@ impl TryFrom<&[u8; FILE_HEADER_SIZE]> for FileHeader {
     }
 }
 
+/// Backing bytes of an open archive: read into RAM, or memory-mapped.
+enum LodBytes {
+    Owned(Vec<u8>),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for LodBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Owned(v) => v,
+            #[cfg(feature = "mmap")]
+            Self::Mapped(m) => m,
+        }
+    }
+}
+
 /// Read-only structure of an LOD file.
-/// Entire file is loaded into RAM at open time; reads are zero-copy slices from that buffer.
+///
+/// [`open`](Self::open) reads the whole file into RAM; with the `mmap`
+/// feature, [`open_mmap`](Self::open_mmap) maps it instead. Either way the
+/// bytes sit behind an `Arc`, so clones share them and the archive can be
+/// read from several threads. [`Archive::get_file`] copies an entry out;
+/// [`entry`](Self::entry) borrows it and [`read_entry`](Self::read_entry)
+/// borrows or inflates it without copying the compressed bytes.
+#[derive(Clone)]
 pub struct LodArchive {
-    data: Vec<u8>,
+    data: Arc<LodBytes>,
     pub version: Version,
     entries: Vec<ArchiveEntry>,
     lookup: HashMap<String, usize>,
@@ -108,24 +144,59 @@ pub struct LodArchive {
 }
 
 impl LodArchive {
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
-        let data = std::fs::read(path.as_ref())?;
-        let mut reader = BufReader::new(Cursor::new(&data));
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        std::fs::read(path)
+            .map_err(Error::from)
+            .and_then(Self::from_bytes)
+            .map_err(|e| e.in_file(path.display().to_string()))
+    }
+
+    /// Memory-map the archive instead of reading it. Entries are paged in
+    /// on first access and never copied by [`entry`](Self::entry) or
+    /// [`read_entry`](Self::read_entry).
+    ///
+    /// The file must not be modified while it is mapped.
+    #[cfg(feature = "mmap")]
+    pub fn open_mmap<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        File::open(path)
+            // SAFETY: game archives are only read; a concurrent writer would
+            // be a user error we cannot guard against, as with any mmap.
+            .and_then(|file| unsafe { memmap2::Mmap::map(&file) })
+            .map_err(Error::from)
+            .and_then(|map| Self::from_storage(LodBytes::Mapped(map)))
+            .map_err(|e| e.in_file(path.display().to_string()))
+    }
+
+    /// Parse an archive already read into memory.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, Error> {
+        Self::from_storage(LodBytes::Owned(data))
+    }
+
+    fn from_storage(data: LodBytes) -> Result<Self, Error> {
+        let mut reader = BufReader::new(Cursor::new(&*data));
 
         let magic = try_read_string(&mut reader)?;
         if magic != "LOD" {
-            return Err("Invalid LOD file magic".into());
+            return Err(Error::BadMagic {
+                format: "lod",
+                found: data[..data.len().min(4)].to_vec(),
+            });
         }
 
         let version_str = try_read_string(&mut reader)?;
-        let version = Version::try_from(version_str.as_str())?;
+        let version = Version::try_from(version_str.as_str())
+            .map_err(|e| Error::invalid("lod", format!("{e} '{version_str}'")))?;
 
         log::debug!("LOD Archive: magic={}, version={:?}", magic, version);
 
         // Read sentinel
         reader.seek(SeekFrom::Start(FILE_INDEX_OFFSET))?;
         let mut buf = [0u8; FILE_HEADER_SIZE];
-        reader.read_exact(&mut buf)?;
+        reader
+            .read_exact(&mut buf)
+            .map_err(|e| Error::from(e).at("lod", FILE_INDEX_OFFSET as usize))?;
         let sentinel = FileHeader::try_from(&buf)?;
 
         let initial_offset = sentinel.offset;
@@ -136,7 +207,9 @@ impl LodArchive {
         let mut lookup = HashMap::with_capacity(num_files);
 
         for i in 0..num_files {
-            reader.read_exact(&mut buf)?;
+            reader
+                .read_exact(&mut buf)
+                .map_err(|e| Error::from(e).at("lod", FILE_INDEX_OFFSET as usize + (i + 1) * FILE_HEADER_SIZE))?;
             let mut fh = FileHeader::try_from(&buf)?;
             fh.offset += initial_offset;
 
@@ -188,7 +261,7 @@ impl LodArchive {
         log::debug!("LOD Archive opened: {} entries", entries.len());
 
         Ok(Self {
-            data,
+            data: Arc::new(data),
             version,
             entries,
             lookup,
@@ -196,6 +269,43 @@ impl LodArchive {
         })
     }
 
+    /// Whether the archive is memory-mapped rather than read into RAM.
+    pub fn is_mapped(&self) -> bool {
+        !matches!(*self.data, LodBytes::Owned(_))
+    }
+
+    /// Borrow an entry's stored (possibly compressed) bytes.
+    pub fn entry(&self, name: &str) -> Option<&[u8]> {
+        let idx = *self.lookup.get(&name.to_lowercase())?;
+        self.slice(idx)
+    }
+
+    /// Stream an entry's contents, inflating zlib-packed entries on the fly.
+    pub fn entry_reader(&self, name: &str) -> Result<Box<dyn Read + '_>, Error> {
+        let raw = self.entry(name).ok_or_else(|| Error::not_found("", name))?;
+        Ok(match zlib_payload(raw) {
+            Some((offset, _)) => Box::new(flate2::read::ZlibDecoder::new(&raw[offset..])),
+            None => Box::new(raw),
+        })
+    }
+
+    /// An entry's contents: borrowed when stored uncompressed, inflated
+    /// straight from the archive bytes otherwise. Gives the same bytes as
+    /// [`LodData`](crate::assets::lod_data::LodData) on the stored entry.
+    pub fn read_entry(&self, name: &str) -> Result<Cow<'_, [u8]>, Error> {
+        let raw = self.entry(name).ok_or_else(|| Error::not_found("", name))?;
+        let Some((offset, size)) = zlib_payload(raw) else {
+            return Ok(Cow::Borrowed(raw));
+        };
+        let mut out = Vec::with_capacity(size);
+        let inflated = flate2::read::ZlibDecoder::new(&raw[offset..]).read_to_end(&mut out);
+        if inflated.is_err() || out.len() != size {
+            // Not actually zlib despite the header shape; stored as-is.
+            return Ok(Cow::Borrowed(raw));
+        }
+        Ok(Cow::Owned(out))
+    }
+
     /// Optional explicitly case-insensitive lookup (e.g. for fallback).
     pub fn get_file_case_insensitive(&self, name: &str) -> Option<Vec<u8>> {
         let lower = name.to_lowercase();
@@ -206,6 +316,10 @@ impl LodArchive {
     }
 
     fn read_bytes(&self, index: usize) -> Option<Vec<u8>> {
+        self.slice(index).map(<[u8]>::to_vec)
+    }
+
+    fn slice(&self, index: usize) -> Option<&[u8]> {
         let offset = self._offsets[index];
         let size = self.entries[index].size;
         let end = offset + size;
@@ -219,7 +333,7 @@ impl LodArchive {
             );
             return None;
         }
-        Some(self.data[offset..end].to_vec())
+        Some(&self.data[offset..end])
     }
 }
 
@@ -257,7 +371,7 @@ impl Archive for LodArchive {
 /// Allows extracting from existing `LodArchive` and applying overrides.
 pub struct LodWriter {
     version: Version,
-    entries: Vec<(String, Vec<u8>)>,
+    entries: Vec<(String, Vec<u8>, [u8; 4])>,
 }
 
 impl LodWriter {
@@ -269,21 +383,49 @@ impl LodWriter {
     }
 
     pub fn add_file(&mut self, name: &str, data: Vec<u8>) -> &mut Self {
-        self.entries.push((name.to_string(), data));
+        self.add_file_with_name_tail(name, data, [0; 4])
+    }
+
+    /// Add a file and stamp bytes 12-15 of its directory name field.
+    /// See [`ArchiveEntry::name_tail`] for why MM6 saves care about these.
+    pub fn add_file_with_name_tail(&mut self, name: &str, data: Vec<u8>, name_tail: [u8; 4]) -> &mut Self {
+        self.entries.push((name.to_string(), data, name_tail));
         self
     }
 
-    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
-        let mut file = File::create(path)?;
-        self.write_to(&mut file)
+    /// Write the archive to `path` atomically.
+    ///
+    /// The archive goes to a sibling `*.tmp` file first, is flushed to disk and
+    /// then renamed over `path`, so an interrupted write never leaves a
+    /// truncated file in place of a good one.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let mut tmp_name = path
+            .file_name()
+            .ok_or_else(|| Error::invalid("lod", format!("{} has no file name", path.display())))?
+            .to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = path.with_file_name(tmp_name);
+
+        let result = (|| -> Result<(), Error> {
+            let mut file = BufWriter::new(File::create(&tmp)?);
+            self.write_to(&mut file)?;
+            file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+            std::fs::rename(&tmp, path)?;
+            Ok(())
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+        result
     }
 
-    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<(), Box<dyn Error>> {
+    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<(), Error> {
         let n = self.entries.len();
         let header_area = 256 + 32 * (1 + n);
         let mut data_offsets: Vec<usize> = Vec::with_capacity(n);
         let mut running = 0usize;
-        for (_, data) in &self.entries {
+        for (_, data, _) in &self.entries {
             data_offsets.push(running);
             running += data.len();
         }
@@ -296,14 +438,14 @@ impl LodWriter {
         let pad = 252usize.saturating_sub(ver_bytes.len());
         w.write_all(&vec![0u8; pad])?;
 
-        write_file_header(w, "", sentinel_offset, 0, n as i32)?;
+        write_file_header(w, "", [0; 4], sentinel_offset, 0, n as i32)?;
 
-        for (i, (name, data)) in self.entries.iter().enumerate() {
+        for (i, (name, data, name_tail)) in self.entries.iter().enumerate() {
             let rel_offset = data_offsets[i] as i32;
-            write_file_header(w, name, rel_offset, data.len() as i32, 0)?;
+            write_file_header(w, name, *name_tail, rel_offset, data.len() as i32, 0)?;
         }
 
-        for (_, data) in &self.entries {
+        for (_, data, _) in &self.entries {
             w.write_all(data)?;
         }
 
@@ -311,13 +453,22 @@ impl LodWriter {
     }
 
     /// Open `src` LOD, override named entries, write result to `out`.
-    pub fn patch<P, Q>(src: P, out: Q, overrides: &[(&str, Vec<u8>)]) -> Result<(), Box<dyn Error>>
+    ///
+    /// Directory `name_tail` bytes of copied and overridden entries are preserved.
+    pub fn patch<P, Q>(src: P, out: Q, overrides: &[(&str, Vec<u8>)]) -> Result<(), Error>
     where
         P: AsRef<Path>,
         Q: AsRef<Path>,
     {
         let original = LodArchive::open(src)?;
-        let mut writer = LodWriter::new(original.version.clone());
+        Self::from_archive(&original, overrides).save(out)
+    }
+
+    /// Build a writer holding every entry of `original`, with named entries replaced.
+    ///
+    /// Overrides not present in `original` are appended at the end.
+    pub fn from_archive(original: &LodArchive, overrides: &[(&str, Vec<u8>)]) -> Self {
+        let mut writer = LodWriter::new(original.version);
 
         let override_map: HashMap<String, &Vec<u8>> = overrides
             .iter()
@@ -325,11 +476,11 @@ impl LodWriter {
             .collect();
 
         for (i, entry) in original.entries.iter().enumerate() {
-            if let Some(ov) = override_map.get(&entry.name) {
-                writer.add_file(&entry.name, (*ov).clone());
-            } else {
-                writer.add_file(&entry.name, original.read_bytes(i).unwrap());
-            }
+            let data = match override_map.get(&entry.name) {
+                Some(ov) => (*ov).clone(),
+                None => original.read_bytes(i).unwrap_or_default(),
+            };
+            writer.add_file_with_name_tail(&entry.name, data, entry.name_tail);
         }
 
         let original_keys: std::collections::HashSet<&String> = original.lookup.keys().collect();
@@ -339,21 +490,53 @@ impl LodWriter {
                 writer.add_file(name, data.to_vec());
             }
         }
-        writer.save(out)
+        writer
+    }
+
+    /// Drop every entry whose name matches `name` (case-insensitive).
+    /// Returns `false` if no entry matched.
+    pub fn remove_file(&mut self, name: &str) -> bool {
+        let before = self.entries.len();
+        self.entries
+            .retain(|(entry_name, _, _)| !entry_name.eq_ignore_ascii_case(name));
+        self.entries.len() != before
+    }
+
+    /// Restamp the `name_tail` of every entry whose name matches `name` (case-insensitive).
+    /// Returns `false` if no entry matched.
+    pub fn set_name_tail(&mut self, name: &str, name_tail: [u8; 4]) -> bool {
+        let mut found = false;
+        for (entry_name, _, tail) in &mut self.entries {
+            if entry_name.eq_ignore_ascii_case(name) {
+                *tail = name_tail;
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// `name_tail` values currently assigned to entries, in entry order.
+    pub fn name_tails(&self) -> impl Iterator<Item = [u8; 4]> + '_ {
+        self.entries.iter().map(|(_, _, tail)| *tail)
     }
 }
 
 fn write_file_header<W: Write>(
     w: &mut W,
     name: &str,
+    name_tail: [u8; 4],
     offset: i32,
     size: i32,
     count: i32,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), Error> {
     let mut name_buf = [0u8; 16];
     let bytes = name.as_bytes();
     let copy_len = bytes.len().min(15);
     name_buf[..copy_len].copy_from_slice(&bytes[..copy_len]);
+    // Names of up to 11 chars leave bytes 12-15 free for the save-cycle tag.
+    if copy_len < 12 {
+        name_buf[12..16].copy_from_slice(&name_tail);
+    }
     w.write_all(&name_buf)?;
     w.write_i32::<LittleEndian>(offset)?;
     w.write_i32::<LittleEndian>(size)?;
@@ -361,3 +544,76 @@ fn write_file_header<W: Write>(
     w.write_i32::<LittleEndian>(count)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::lod_data::{CompressionKind, LodData};
+
+    fn archive(files: &[(&str, Vec<u8>)]) -> LodArchive {
+        let mut writer = LodWriter::new(Version::MM6);
+        for (name, data) in files {
+            writer.add_file(name, data.clone());
+        }
+        let mut bytes = Vec::new();
+        writer.write_to(&mut bytes).unwrap();
+        LodArchive::from_bytes(bytes).unwrap()
+    }
+
+    #[test]
+    fn read_entry_matches_lod_data() {
+        let text: Vec<u8> = (0..4000u32).flat_map(|i| (i % 97).to_le_bytes()).collect();
+        let packed = LodData {
+            kind: CompressionKind::Zlib8,
+            data: text.clone(),
+        }
+        .pack();
+        let lod = archive(&[("packed.bin", packed.clone()), ("plain.txt", b"plain".to_vec())]);
+
+        let entry = lod.read_entry("PACKED.BIN").unwrap();
+        assert!(matches!(entry, Cow::Owned(_)));
+        assert_eq!(*entry, *LodData::try_from(packed.as_slice()).unwrap().data);
+        assert_eq!(*entry, *text);
+
+        let mut streamed = Vec::new();
+        lod.entry_reader("packed.bin")
+            .unwrap()
+            .read_to_end(&mut streamed)
+            .unwrap();
+        assert_eq!(streamed, text);
+
+        assert!(matches!(lod.read_entry("plain.txt").unwrap(), Cow::Borrowed(b"plain")));
+        assert_eq!(lod.entry("packed.bin"), Some(packed.as_slice()));
+        assert!(lod.read_entry("missing").unwrap_err().is_not_found());
+    }
+
+    #[test]
+    fn archives_are_shared_across_threads() {
+        fn send_sync<T: Send + Sync>() {}
+        send_sync::<LodArchive>();
+
+        let lod = archive(&[("a.txt", b"shared".to_vec())]);
+        let clone = lod.clone();
+        let from_thread = std::thread::spawn(move || clone.read_entry("a.txt").unwrap().into_owned())
+            .join()
+            .unwrap();
+        assert_eq!(from_thread, b"shared");
+        assert!(!lod.is_mapped());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn mapped_archive_reads_like_owned() {
+        let path = std::env::temp_dir().join(format!("openmm_mmap_{:?}.lod", std::thread::current().id()));
+        let mut writer = LodWriter::new(Version::MM6);
+        writer.add_file("a.txt", b"mapped".to_vec());
+        writer.save(&path).unwrap();
+
+        let lod = LodArchive::open_mmap(&path).unwrap();
+        assert!(lod.is_mapped());
+        assert_eq!(lod.entry("a.txt"), Some(&b"mapped"[..]));
+        assert_eq!(lod.get_file("a.txt").unwrap(), b"mapped");
+        drop(lod);
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -1,5 +1,6 @@
-use std::{collections::HashMap, error::Error, fs, path::Path};
+use std::{collections::HashMap, fs, path::Path};
 
+use crate::Error;
 use crate::assets::provider::archive::{Archive, ArchiveEntry};
 
 const ENTRY_NAME_LEN: usize = 40;
@@ -14,17 +15,12 @@ pub struct SmkArchive {
 }
 
 impl SmkArchive {
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let data = fs::read(path)?;
-        if data.len() < 4 {
-            return Err("SMK archive too short".into());
-        }
+        Error::check_len("smk archive", &data, 0, 4)?;
 
         let num_files = u32::from_le_bytes(data[0..4].try_into()?) as usize;
-        let table_end = 4 + num_files * ENTRY_LEN;
-        if data.len() < table_end {
-            return Err(format!("SMK table truncated: need {table_end}, have {}", data.len()).into());
-        }
+        Error::check_len("smk archive", &data, 4, num_files * ENTRY_LEN)?;
 
         let mut entries = Vec::with_capacity(num_files);
         let mut lookup = HashMap::with_capacity(num_files);
@@ -132,7 +128,7 @@ impl SmkWriter {
         self.entries.push((name.to_string(), data));
     }
 
-    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
         let mut buf = Vec::new();
 
         let count = self.entries.len() as u32;
@@ -1,6 +1,5 @@
 use std::{
     collections::HashMap,
-    error::Error,
     fs,
     io::{Cursor, Read, Write},
     path::Path,
@@ -8,6 +7,7 @@ use std::{
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
+use crate::Error;
 use crate::assets::provider::archive::{Archive, ArchiveEntry};
 
 pub fn try_read_string(data: &[u8]) -> Option<String> {
@@ -27,7 +27,7 @@ pub struct SndArchive {
 }
 
 impl SndArchive {
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let data = fs::read(path)?;
         let mut cursor = Cursor::new(&data);
         let entry_count = cursor.read_u32::<LittleEndian>()?;
@@ -266,7 +266,7 @@ impl Archive for SndArchive {
     }
 }
 
-fn decompress_deflate(data: &[u8], reserve_size: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+fn decompress_deflate(data: &[u8], reserve_size: usize) -> Result<Vec<u8>, Error> {
     use flate2::bufread::DeflateDecoder;
     use std::io::Read;
     let mut z = DeflateDecoder::new(Cursor::new(data));
@@ -291,7 +291,7 @@ impl SndWriter {
         self.entries.push((name.to_string(), data, decompressed_size));
     }
 
-    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
         let mut buf = Vec::new();
         let mut cursor = Cursor::new(&mut buf);
 
@@ -1,10 +1,9 @@
 use flate2::Compression;
 use flate2::bufread::ZlibDecoder;
 use flate2::write::ZlibEncoder;
-use std::{
-    error::Error,
-    io::{BufReader, Cursor, Read, Write},
-};
+use std::io::{BufReader, Cursor, Read, Write};
+
+use crate::Error;
 
 /// Compress `data` with zlib (default level).
 pub fn compress(data: &[u8]) -> Vec<u8> {
@@ -14,24 +13,30 @@ pub fn compress(data: &[u8]) -> Vec<u8> {
 }
 
 /// Decompress `data`. Verifies that data size == compressed_size and uncompressed size == expected.
-pub fn decompress(data: &[u8], compressed_size: usize, uncompressed_size: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+pub fn decompress(data: &[u8], compressed_size: usize, uncompressed_size: usize) -> Result<Vec<u8>, Error> {
     check_size(data.len(), compressed_size)?;
     let uncompressed_data = decompress_zlib(data, uncompressed_size)?;
     check_size(uncompressed_data.len(), uncompressed_size)?;
     Ok(uncompressed_data)
 }
 
-fn decompress_zlib(data: &[u8], reserve_size: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+fn decompress_zlib(data: &[u8], reserve_size: usize) -> Result<Vec<u8>, Error> {
     let reader: BufReader<_> = BufReader::new(Cursor::new(data));
     let mut z = ZlibDecoder::new(reader);
     let mut buf: Vec<u8> = Vec::with_capacity(reserve_size);
-    z.read_to_end(&mut buf)?;
+    z.read_to_end(&mut buf).map_err(|source| Error::Decompress {
+        name: "zlib".into(),
+        source,
+    })?;
     Ok(buf)
 }
 
-fn check_size(size: usize, expected_size: usize) -> Result<(), Box<dyn Error>> {
+fn check_size(size: usize, expected_size: usize) -> Result<(), Error> {
     if size != expected_size {
-        return Err(format!("Expected data size: {}B, actual size: {}B", expected_size, size).into());
+        return Err(Error::invalid(
+            "zlib",
+            format!("expected data size: {}B, actual size: {}B", expected_size, size),
+        ));
     }
     Ok(())
 }
@@ -1,6 +1,5 @@
-use std::error::Error;
-
 use crate::Assets;
+use crate::Error;
 use crate::assets::{billboard::Billboard, blv::BlvDecoration};
 
 /// A single resolved decoration (billboard/spawn point) from an outdoor map.
@@ -71,7 +70,7 @@ impl Decorations {
     /// Filters out invisible, marker, and no-draw decorations.
     /// Resolves sprite names, detects directional sprites, pre-extracts DSFT scale,
     /// and pre-computes world dimensions for non-directional sprites.
-    pub fn load(assets: &Assets, odm_billboards: &[Billboard]) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets, odm_billboards: &[Billboard]) -> Result<Self, Error> {
         let lod = assets.lod();
         let mut entries = Vec::new();
 
@@ -172,7 +171,7 @@ impl Decorations {
     /// BLV decorations use a string name (e.g. "Torch01") rather than a reliable
     /// declist_id (pristine files always store 0). We resolve via name-based lookup.
     /// Markers ("Party Start"), invisible, and no-draw entries are filtered out.
-    pub fn from_blv(assets: &Assets, blv_decorations: &[BlvDecoration]) -> Result<Self, Box<dyn Error>> {
+    pub fn from_blv(assets: &Assets, blv_decorations: &[BlvDecoration]) -> Result<Self, Error> {
         let lod = assets.lod();
         let mut entries = Vec::new();
 
@@ -3,13 +3,18 @@
 //! `Assets` provides raw archive access (bytes, decompression, palette loading).
 //! `LodDecoder` wraps an `Assets` reference and returns decoded, game-ready data:
 //! sprites, bitmaps, icons, fonts, and NPC tables.
+//!
+//! `Assets` is `Sync`, so batches of sprites decode in parallel on the rayon
+//! pool via [`LodDecoder::decode_sprites`].
 
 use super::npc;
+use crate::Error;
 use crate::assets::ddeclist::DDecListItem;
 use crate::assets::dsft::DSFTFrame;
 use crate::assets::font;
 use crate::assets::provider::Assets;
 use image::{DynamicImage, GenericImageView};
+use rayon::prelude::*;
 
 /// High-level LOD decoder: returns game-ready decoded assets from LOD archives.
 /// Constructed via `Assets::game()`.
@@ -17,6 +22,41 @@ pub struct LodDecoder<'a> {
     assets: &'a Assets,
 }
 
+/// Palette a batch-decoded sprite is drawn with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SpritePalette {
+    /// The palette named in the sprite's own header.
+    #[default]
+    Header,
+    /// A specific palette (DSFT variant palettes). Falls back to the header palette.
+    Id(u16),
+    /// The header palette id plus an offset (monster variant swaps).
+    /// Falls back to the header palette.
+    Offset(u16),
+}
+
+/// One entry of a [`LodDecoder::decode_sprite_requests`] batch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpriteRequest {
+    pub name: String,
+    pub palette: SpritePalette,
+}
+
+impl SpriteRequest {
+    pub fn new(name: impl Into<String>, palette: SpritePalette) -> Self {
+        Self {
+            name: name.into(),
+            palette,
+        }
+    }
+}
+
+impl From<&str> for SpriteRequest {
+    fn from(name: &str) -> Self {
+        Self::new(name, SpritePalette::Header)
+    }
+}
+
 pub struct BillboardSprite {
     pub image: DynamicImage,
     pub d_declist_item: DDecListItem,
@@ -47,10 +87,41 @@ impl<'a> LodDecoder<'a> {
 
     /// Load a sprite image from the sprites archive.
     pub fn sprite(&self, name: &str) -> Option<DynamicImage> {
-        let sprite = self.assets.get_bytes(format!("sprites/{}", name.to_lowercase())).ok()?;
-        let palettes = self.assets.palettes().ok()?;
-        let sprite = crate::assets::image::Image::try_from((sprite.as_slice(), palettes)).ok()?;
-        sprite.to_image_buffer().ok()
+        self.decode_sprite(&SpriteRequest::from(name)).ok()
+    }
+
+    /// Decode one sprite with the palette the request asks for.
+    pub fn decode_sprite(&self, request: &SpriteRequest) -> Result<DynamicImage, Error> {
+        use crate::assets::image::{Image, sprite_palette_id};
+
+        let data = self
+            .assets
+            .get_bytes(format!("sprites/{}", request.name.to_lowercase()))?;
+        let palettes = self.assets.palettes()?;
+        let palette_id = match request.palette {
+            SpritePalette::Header => None,
+            SpritePalette::Id(id) => Some(id),
+            SpritePalette::Offset(offset) => sprite_palette_id(&data).and_then(|id| id.checked_add(offset)),
+        };
+        if let Some(id) = palette_id
+            && let Ok(image) = Image::try_from_with_palette(&data, palettes, id).and_then(|s| s.to_image_buffer())
+        {
+            return Ok(image);
+        }
+        Image::try_from((data.as_slice(), palettes))?.to_image_buffer()
+    }
+
+    /// Decode sprites by name in parallel. Results are in input order.
+    pub fn decode_sprites<S: AsRef<str> + Sync>(&self, names: &[S]) -> Vec<Result<DynamicImage, Error>> {
+        names
+            .par_iter()
+            .map(|name| self.decode_sprite(&SpriteRequest::from(name.as_ref())))
+            .collect()
+    }
+
+    /// Decode sprites with per-sprite palettes in parallel. Results are in input order.
+    pub fn decode_sprite_requests(&self, requests: &[SpriteRequest]) -> Vec<Result<DynamicImage, Error>> {
+        requests.par_iter().map(|request| self.decode_sprite(request)).collect()
     }
 
     /// Load a sprite using a specific palette ID (for monster variant palette swaps).
@@ -96,20 +167,24 @@ impl<'a> LodDecoder<'a> {
         font::Font::parse(&data).ok()
     }
 
-    /// List all .fnt font names available in the icons archive.
+    /// List all .fnt font names available in the icons archive and overlays.
     pub fn font_names(&self) -> Vec<String> {
-        self.assets
+        let overlay_files = self.assets.overlays().iter().flat_map(|layer| layer.files());
+        let mut names: Vec<String> = self
+            .assets
             .files_in("icons")
-            .map(|files| {
-                files
-                    .into_iter()
-                    .filter_map(|f: String| {
-                        let lower = f.to_lowercase();
-                        lower.strip_suffix(".fnt").map(|s| s.to_string())
-                    })
-                    .collect()
-            })
             .unwrap_or_default()
+            .into_iter()
+            .chain(overlay_files)
+            .filter_map(|f: String| {
+                let lower = f.to_lowercase();
+                let file = lower.rsplit('/').next().unwrap_or(&lower);
+                file.strip_suffix(".fnt").map(|s| s.to_string())
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names
     }
 
     /// Load and parse the global NPC metadata table from `npcdata.txt`.
@@ -291,3 +366,72 @@ impl<'a> LodDecoder<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::image::Image;
+    use crate::assets::palette::Palette;
+    use crate::assets::provider::{LodWriter, Version};
+    use image::{ImageBuffer, Rgba};
+
+    /// Game dir with palettes 3 (red) and 4 (blue) and two sprites drawn with palette 3.
+    fn game_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("openmm_decoder_{:?}", std::thread::current().id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let solid = |rgb: [u8; 3]| {
+            let mut data = [0u8; 768];
+            data[3..6].copy_from_slice(&rgb);
+            Palette { data }
+        };
+        let mut bitmaps = LodWriter::new(Version::MM6);
+        bitmaps.add_file("pal003", solid([200, 0, 0]).encode(3));
+        bitmaps.add_file("pal004", solid([0, 0, 200]).encode(4));
+        bitmaps.save(dir.join("bitmaps.lod")).unwrap();
+
+        let mut sprites = LodWriter::new(Version::MM6);
+        for (name, w) in [("spra", 3), ("sprb", 5)] {
+            // Decoding keys transparency off the top-left pixel, so leave column 0 clear.
+            let src = DynamicImage::ImageRgba8(ImageBuffer::from_fn(w, 2, |x, _| {
+                Rgba(if x == 0 { [0, 0, 0, 0] } else { [200, 0, 0, 255] })
+            }));
+            let image = Image::from_rgba(&src, Some(&solid([200, 0, 0])), true).unwrap();
+            sprites.add_file(name, image.encode_sprite(name, 3).unwrap());
+        }
+        sprites.save(dir.join("sprites.lod")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn batch_decode_matches_sequential() {
+        fn send_sync<T: Send + Sync>() {}
+        send_sync::<Assets>();
+
+        let assets = Assets::with_overlays(game_dir(), &[]).unwrap();
+        let lod = assets.lod();
+        let decoded = lod.decode_sprites(&["spra", "missing", "SPRB"]);
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].as_ref().unwrap(), &lod.sprite("spra").unwrap());
+        assert!(decoded[1].as_ref().unwrap_err().is_not_found());
+        assert_eq!(decoded[2].as_ref().unwrap().dimensions(), (5, 2));
+    }
+
+    #[test]
+    fn batch_requests_pick_palettes() {
+        let assets = Assets::with_overlays(game_dir(), &[]).unwrap();
+        let requests = [
+            SpriteRequest::from("spra"),
+            SpriteRequest::new("spra", SpritePalette::Id(4)),
+            SpriteRequest::new("spra", SpritePalette::Offset(1)),
+            SpriteRequest::new("spra", SpritePalette::Id(99)),
+        ];
+        let pixel = |r: &Result<DynamicImage, Error>| r.as_ref().unwrap().to_rgba8().get_pixel(1, 0).0;
+        let decoded = assets.lod().decode_sprite_requests(&requests);
+        assert_eq!(pixel(&decoded[0]), [200, 0, 0, 255]);
+        assert_eq!(pixel(&decoded[1]), [0, 0, 200, 255]);
+        assert_eq!(pixel(&decoded[2]), [0, 0, 200, 255]);
+        assert_eq!(pixel(&decoded[3]), [200, 0, 0, 255]);
+    }
+}
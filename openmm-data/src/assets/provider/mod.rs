@@ -1,5 +1,5 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -9,6 +9,7 @@ pub use self::archive::lod::{LodArchive, Version};
 pub use self::archive::smk::SmkArchive;
 pub use self::archive::snd::SndArchive;
 pub use self::archive::{Archive, ArchiveEntry};
+use crate::Error;
 use crate::assets::dsounds::DSounds;
 use crate::assets::image::Image;
 use crate::assets::lod_data::LodData;
@@ -21,8 +22,10 @@ pub mod decorations;
 pub mod lod_decoder;
 pub mod monster;
 pub mod npc;
+pub mod overlay;
 
-pub use lod_decoder::LodDecoder;
+pub use lod_decoder::{LodDecoder, SpritePalette, SpriteRequest};
+pub use overlay::{AssetSource, OverlayLayer};
 
 /// Global game data loaded once at startup — map-independent, shared across all maps.
 pub struct StaticGameData {
@@ -48,6 +51,8 @@ pub struct StaticGameData {
     pub autonotes_table: Option<crate::assets::autonotes::AutonotesTable>,
     /// Item definitions (icons/items.txt).
     pub items_table: Option<crate::assets::items::ItemsTable>,
+    /// Chest pictures and grid sizes (icons/dchest.bin).
+    pub chest_list: Option<crate::assets::dchest::ChestList>,
     /// Spell definitions (icons/spells.txt).
     pub spells_table: Option<crate::assets::spells::SpellsTable>,
     /// Player class descriptions (icons/class.txt).
@@ -75,7 +80,7 @@ pub struct StaticGameData {
 }
 
 impl StaticGameData {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let dsft = crate::assets::dsft::DSFT::load(assets)?;
         let monlist = crate::assets::dmonlist::MonsterList::load(assets)?;
         let monsters_txt = crate::assets::monsters::MonsterStatsTable::load(assets)?;
@@ -90,6 +95,7 @@ impl StaticGameData {
         let awards_table = crate::assets::awards::AwardsTable::load(assets).ok();
         let autonotes_table = crate::assets::autonotes::AutonotesTable::load(assets).ok();
         let items_table = crate::assets::items::ItemsTable::load(assets).ok();
+        let chest_list = crate::assets::dchest::ChestList::load(assets).ok();
         let spells_table = crate::assets::spells::SpellsTable::load(assets).ok();
         let class_table = crate::assets::class::ClassTable::load(assets).ok();
         let npcbtb_table = crate::assets::npcbtb::NpcBtbTable::load(assets).ok();
@@ -116,6 +122,7 @@ impl StaticGameData {
             awards_table,
             autonotes_table,
             items_table,
+            chest_list,
             spells_table,
             class_table,
             npcbtb_table,
@@ -134,7 +141,9 @@ impl StaticGameData {
 
 /// Unified entry point for retrieving any kind of game asset.
 /// Routes requests between LOD archives, SND archives, SMK archives, and loose files.
+/// Override layers (see [`overlay`]) are searched first, in order.
 pub struct Assets {
+    overlays: Vec<OverlayLayer>,
     lods: HashMap<String, LodArchive>,
     snds: HashMap<String, SndArchive>,
     smks: HashMap<String, SmkArchive>,
@@ -146,10 +155,23 @@ pub struct Assets {
 }
 
 impl Assets {
-    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+    /// Open the game data at `path`, layered under any overrides listed in
+    /// `OPENMM_OVERLAYS`.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::with_overlays(path, &crate::get_overlay_paths())
+    }
+
+    /// Open the game data at `path` with `overlays` searched before it.
+    /// Earlier layers win. Each entry is a loose-file directory or a patch LOD.
+    pub fn with_overlays<P: AsRef<Path>>(path: P, overlays: &[PathBuf]) -> Result<Self, Error> {
         let game_dir = path.as_ref().to_path_buf();
+        let overlays = overlays
+            .iter()
+            .map(|p| OverlayLayer::open(p))
+            .collect::<Result<Vec<_>, _>>()?;
 
         let mut assets = Self {
+            overlays,
             lods: HashMap::new(),
             snds: HashMap::new(),
             smks: HashMap::new(),
@@ -166,7 +188,7 @@ impl Assets {
 
     /// Refresh the asset list by scanning the game directory and known sibling
     /// directories (e.g. `Anims/`) for LOD, SND, and VID archives.
-    pub fn refresh(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn refresh(&mut self) -> Result<(), Error> {
         self.scan_dir(&self.game_dir.clone())?;
 
         // Also scan sibling directories of the game data path for archives.
@@ -193,7 +215,7 @@ impl Assets {
     }
 
     /// Scan a single directory for LOD, SND, and VID archives.
-    fn scan_dir(&mut self, dir: &Path) -> Result<(), Box<dyn Error>> {
+    fn scan_dir(&mut self, dir: &Path) -> Result<(), Error> {
         let entries = match fs::read_dir(dir) {
             Ok(e) => e,
             Err(_) => return Ok(()), // directory may not exist
@@ -207,7 +229,11 @@ impl Assets {
 
                 match ext.to_lowercase().as_str() {
                     "lod" => {
-                        if let Ok(lod) = LodArchive::open(&path) {
+                        #[cfg(feature = "mmap")]
+                        let lod = LodArchive::open_mmap(&path);
+                        #[cfg(not(feature = "mmap"))]
+                        let lod = LodArchive::open(&path);
+                        if let Ok(lod) = lod {
                             self.lods.insert(stem, lod);
                         }
                     }
@@ -233,6 +259,16 @@ impl Assets {
         &self.game_dir
     }
 
+    /// Game version of the loaded data, taken from the `games.lod` header
+    /// (any other LOD if there is none). `MM6` when no archive was found.
+    pub fn version(&self) -> Version {
+        self.lods
+            .get("games")
+            .map(|lod| lod.version)
+            .or_else(|| self.lods.values().map(|lod| lod.version).max())
+            .unwrap_or_default()
+    }
+
     /// Access high-level game tables (DSFT, Monsters, MapStats, etc.)
     pub fn data(&self) -> &StaticGameData {
         self.static_data
@@ -245,104 +281,173 @@ impl Assets {
     }
 
     /// Find raw bytes for an asset, searching through applicable archives.
-    pub fn get_bytes<P: AsRef<Path>>(&self, path_or_name: P) -> Result<Vec<u8>, Box<dyn Error>> {
-        let path = path_or_name.as_ref();
+    pub fn get_bytes<P: AsRef<Path>>(&self, path_or_name: P) -> Result<Vec<u8>, Error> {
+        self.find_bytes(path_or_name.as_ref()).map(|(data, _)| data)
+    }
+
+    /// Which layer [`get_bytes`](Self::get_bytes) serves `path_or_name` from,
+    /// or `None` if it isn't found anywhere.
+    pub fn source_of<P: AsRef<Path>>(&self, path_or_name: P) -> Option<AssetSource> {
+        self.find_bytes(path_or_name.as_ref()).ok().map(|(_, source)| source)
+    }
+
+    fn find_bytes(&self, path: &Path) -> Result<(Vec<u8>, AssetSource), Error> {
         let ext = path.extension().and_then(|s| s.to_str()).map(|s| s.to_lowercase());
-        let name = path.file_name().and_then(|s| s.to_str()).ok_or("invalid path")?;
+        let name = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| Error::invalid("asset", format!("bad asset path `{}`", path.display())))?;
+        let archive_name = path.parent().and_then(|p| p.to_str()).filter(|s| !s.is_empty());
+
+        // 0. Override layers: "archive/name", then bare "name"
+        let mut loose = vec![name.to_string()];
+        if let Some(archive_name) = archive_name {
+            loose.insert(0, format!("{}/{}", archive_name, name));
+        }
+        if let Some(hit) = self.find_in_overlays(&loose, Some(name)) {
+            return Ok(hit);
+        }
 
         // 1. If it's a specific path like "icons/dsounds.bin"
-        if let Some(parent) = path.parent()
-            && let Some(archive_name) = parent.to_str().filter(|s| !s.is_empty())
+        if let Some(archive_name) = archive_name
             && let Some(lod) = self.lods.get(&archive_name.to_lowercase())
             && let Some(data) = lod.get_file(name)
         {
-            return Ok(data);
+            return Ok((data, AssetSource::Archive(archive_name.to_lowercase())));
         }
 
         // 2. Route by extension
         match ext.as_deref() {
             Some("wav") | Option::None => {
                 // Try sound routing
-                if let Ok(data) = self.get_sound(name) {
-                    return Ok(data);
+                if let Ok(hit) = self.find_sound(name) {
+                    return Ok(hit);
                 }
             }
             Some("smk") | Some("bik") => {
-                if let Ok(data) = self.get_smk(name) {
-                    return Ok(data);
+                if let Ok(hit) = self.find_smk(name) {
+                    return Ok(hit);
+                }
+            }
+            Some("mp3") => {
+                if let Some(track) = path.file_stem().and_then(|s| s.to_str())
+                    && let Ok(hit) = self.find_music(track)
+                {
+                    return Ok(hit);
                 }
             }
             _ => {}
         }
 
         // 3. Fallback: Search all LODs (expensive, but might be needed for loose lookups)
-        for lod in self.lods.values() {
+        for (stem, lod) in &self.lods {
             if let Some(data) = lod.get_file(name) {
-                return Ok(data);
+                return Ok((data, AssetSource::Archive(stem.clone())));
             }
         }
 
-        Err(format!("Asset not found: {:?}", path).into())
+        Err(Error::not_found(archive_name.unwrap_or_default(), name))
     }
 
     /// Specialized sound retrieval using dsounds.bin routing.
-    pub fn get_sound(&self, name_or_id: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    pub fn get_sound(&self, name_or_id: &str) -> Result<Vec<u8>, Error> {
+        self.find_sound(name_or_id).map(|(data, _)| data)
+    }
+
+    fn find_sound(&self, name_or_id: &str) -> Result<(Vec<u8>, AssetSource), Error> {
         let sound_name = if let Ok(id) = name_or_id.parse::<u32>() {
             self.dsounds
                 .as_ref()
-                .ok_or("dsounds.bin not loaded")?
+                .ok_or_else(|| Error::not_found("icons", "dsounds.bin"))?
                 .get_by_id(id)
-                .ok_or(format!("sound id {} not found", id))?
+                .ok_or_else(|| Error::not_found("dsounds", id.to_string()))?
                 .name()
-                .ok_or("sound name not found")?
+                .ok_or_else(|| Error::invalid("dsounds", format!("sound id {id} has no name")))?
         } else {
             name_or_id.strip_suffix(".wav").unwrap_or(name_or_id).to_string()
         };
 
+        let loose = [format!("{}.wav", sound_name), format!("sounds/{}.wav", sound_name)];
+        if let Some(hit) = self.find_in_overlays(&loose, Some(&sound_name)) {
+            return Ok(hit);
+        }
+
         // Search .snd files
-        for snd in self.snds.values() {
+        for (stem, snd) in &self.snds {
             if let Some(data) = snd.get_file(&sound_name) {
-                return Ok(data);
+                return Ok((data, AssetSource::Archive(stem.clone())));
             }
         }
 
         // Search .lod files (e.g. sounds.lod)
-        for lod in self.lods.values() {
+        for (stem, lod) in &self.lods {
             if let Some(data) = lod.get_file(&sound_name) {
-                return Ok(data);
+                return Ok((data, AssetSource::Archive(stem.clone())));
             }
         }
 
-        Err(format!("Sound not found: {}", sound_name).into())
+        Err(Error::not_found("sounds", sound_name))
     }
 
     /// Specialized Smacker video retrieval.
-    pub fn get_smk(&self, name: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    pub fn get_smk(&self, name: &str) -> Result<Vec<u8>, Error> {
+        self.find_smk(name).map(|(data, _)| data)
+    }
+
+    fn find_smk(&self, name: &str) -> Result<(Vec<u8>, AssetSource), Error> {
         let name = name
             .strip_suffix(".smk")
             .or_else(|| name.strip_suffix(".bik"))
             .unwrap_or(name);
-        for smk in self.smks.values() {
+        let loose = [format!("{}.smk", name), format!("anims/{}.smk", name)];
+        if let Some(hit) = self.find_in_overlays(&loose, None) {
+            return Ok(hit);
+        }
+        for (stem, smk) in &self.smks {
             if let Some(data) = smk.get_file(name) {
-                return Ok(data);
+                return Ok((data, AssetSource::Archive(stem.clone())));
             }
         }
-        Err(format!("Smacker video not found: {}", name).into())
+        Err(Error::not_found("anims", name))
     }
 
     /// Retrieve a music file by track name (e.g. `"13"` → `Music/13.mp3`).
     ///
-    /// Searches for `Music/{track}.mp3` under the game directory's parent,
+    /// Override directories are checked for `Music/{track}.mp3`,
+    /// `Sounds/{track}.mp3` and `{track}.mp3` first. Then searches for
+    /// `Music/{track}.mp3` under the game directory's parent,
     /// case-insensitively (for Linux compatibility with Windows-era paths).
-    pub fn get_music(&self, track: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    pub fn get_music(&self, track: &str) -> Result<Vec<u8>, Error> {
+        self.find_music(track).map(|(data, _)| data)
+    }
+
+    fn find_music(&self, track: &str) -> Result<(Vec<u8>, AssetSource), Error> {
+        let loose = [
+            format!("music/{}.mp3", track),
+            format!("sounds/{}.mp3", track),
+            format!("{}.mp3", track),
+        ];
+        if let Some(hit) = self.find_in_overlays(&loose, None) {
+            return Ok(hit);
+        }
+
         let parent = self.game_dir.parent().unwrap_or(&self.game_dir);
         for dir in &["Music", "Sounds"] {
             let rel = format!("{}/{}.mp3", dir, track);
             if let Some(path) = crate::utils::find_path_case_insensitive(parent, &rel) {
-                return Ok(fs::read(&path)?);
+                return Ok((fs::read(&path)?, AssetSource::GameFile(path)));
             }
         }
-        Err(format!("Music not found: Music/{t}.mp3 or Sounds/{t}.mp3", t = track).into())
+        Err(Error::not_found("music", format!("{track}.mp3")))
+    }
+
+    fn find_in_overlays(&self, loose: &[String], entry: Option<&str>) -> Option<(Vec<u8>, AssetSource)> {
+        self.overlays.iter().find_map(|layer| layer.find(loose, entry))
+    }
+
+    /// Override layers in search order.
+    pub fn overlays(&self) -> &[OverlayLayer] {
+        &self.overlays
     }
 
     /// Decode all audio from an SMK video into a WAV buffer.
@@ -359,16 +464,39 @@ impl Assets {
         self.get_bytes(path).is_ok()
     }
 
-    pub fn get_decompressed<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, Box<dyn Error>> {
+    pub fn get_decompressed<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, Error> {
+        self.get_entry(path).map(Cow::into_owned)
+    }
+
+    /// Like [`get_decompressed`](Self::get_decompressed), but an
+    /// `"archive/name"` entry served by a game LOD is borrowed when stored
+    /// uncompressed and inflated straight from the archive otherwise,
+    /// without copying the packed bytes first.
+    pub fn get_entry<P: AsRef<Path>>(&self, path: P) -> Result<Cow<'_, [u8]>, Error> {
+        let path = path.as_ref();
+        let name = path.file_name().and_then(|s| s.to_str());
+        let archive = path.parent().and_then(|p| p.to_str()).filter(|s| !s.is_empty());
+        if let (Some(name), Some(archive)) = (name, archive)
+            && let Some(lod) = self.lods.get(&archive.to_lowercase())
+            && lod.contains(name)
+            && !self.overlays.iter().any(|layer| {
+                let loose = [format!("{archive}/{name}"), name.to_string()];
+                layer.contains(&loose, Some(name))
+            })
+        {
+            return lod.read_entry(name);
+        }
         let raw = self.get_bytes(path)?;
-        Ok(match LodData::try_from(raw.as_slice()) {
+        Ok(Cow::Owned(match LodData::try_from(raw.as_slice()) {
             Ok(d) => d.data,
             Err(_) => raw,
-        })
+        }))
     }
 
-    pub fn palettes(&self) -> Result<&Palettes, Box<dyn Error>> {
-        self.palettes_cache.as_ref().ok_or_else(|| "palettes not loaded".into())
+    pub fn palettes(&self) -> Result<&Palettes, Error> {
+        self.palettes_cache
+            .as_ref()
+            .ok_or_else(|| Error::not_found("bitmaps", "pal*"))
     }
 
     pub fn archives(&self) -> Vec<String> {
@@ -406,9 +534,15 @@ impl Assets {
         self.dsounds.as_ref()
     }
 
-    /// O(1) check: does `name` exist in the given LOD archive?
-    /// No allocation, no decoding — just a hash lookup.
+    /// O(1) check: does `name` exist in the given LOD archive (or an
+    /// override layer)? No decoding — just hash lookups.
     pub fn lod_contains(&self, archive: &str, name: &str) -> bool {
+        if !self.overlays.is_empty() {
+            let loose = [format!("{}/{}", archive, name), name.to_string()];
+            if self.overlays.iter().any(|layer| layer.contains(&loose, Some(name))) {
+                return true;
+            }
+        }
         self.lods
             .get(&archive.to_lowercase())
             .is_some_and(|lod| lod.contains(name))
@@ -419,8 +553,10 @@ impl Assets {
     }
 
     /// Dump all assets in an archive to disk (useful for debugging).
-    pub fn dump_lod(&self, archive: &str, out_path: &Path) -> Result<(), Box<dyn Error>> {
-        let lod = self.get_lod(archive).ok_or("archive not found")?;
+    pub fn dump_lod(&self, archive: &str, out_path: &Path) -> Result<(), Error> {
+        let lod = self
+            .get_lod(archive)
+            .ok_or_else(|| Error::not_found("", format!("{archive}.lod")))?;
         let palettes = self.palettes()?;
         fs::create_dir_all(out_path)?;
 
@@ -1,8 +1,8 @@
 //! Monster sprite resolution — maps DSFT group names to sprite file roots.
 
 use crate::Assets;
+use crate::Error;
 use crate::assets::GameData;
-use std::error::Error;
 
 /// A single resolved monster spawn entry for a map.
 /// One entry per group member. Spread position is computed by the caller using
@@ -10,6 +10,8 @@ use std::error::Error;
 pub struct Monster {
     /// Display name from mapstats (e.g. "Goblin", "Orc").
     pub name: String,
+    /// 0-based index into dmonlist.bin for the resolved variant.
+    pub monlist_id: u8,
     /// Group center in MM6 coordinates (NOT yet spread). Caller applies angle × radius.
     pub spawn_position: [i32; 3],
     /// Radius from center for position spreading.
@@ -75,7 +77,7 @@ impl Monsters {
     /// Uses pre-loaded `GameData` (MapStats, MonsterList, DSFT) — no per-call LOD reads.
     /// Returns one `Monster` per group member, with `spawn_position` = group center.
     /// Position spreading (angle × radius) is left to the caller.
-    pub fn load(assets: &Assets, map_name: &str, game_data: &GameData) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets, map_name: &str, game_data: &GameData) -> Result<Self, Error> {
         let odm = crate::assets::odm::Odm::load(assets, map_name)?;
         let spawn_points: Vec<SpawnPointRef> = odm
             .spawn_points
@@ -96,7 +98,7 @@ impl Monsters {
         map_name: &str,
         game_data: &GameData,
         assets: &Assets,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, Error> {
         let spawn_points: Vec<SpawnPointRef> = blv_spawn_points
             .iter()
             .map(|sp| SpawnPointRef {
@@ -114,12 +116,12 @@ impl Monsters {
         map_name: &str,
         game_data: &GameData,
         assets: &Assets,
-    ) -> Result<Self, Box<dyn Error>> {
+    ) -> Result<Self, Error> {
         let map_name_lower = map_name.to_lowercase();
         let cfg = game_data
             .mapstats
             .get(&map_name_lower)
-            .ok_or("map not found in mapstats")?;
+            .ok_or_else(|| Error::invalid("mapstats", format!("no row for {map_name}")))?;
 
         let mut entries = Vec::new();
         for sp in spawn_points {
@@ -164,9 +166,10 @@ impl Monsters {
                     cfg.variant_from_roll(slot, roll)
                 };
 
-                let Some(desc) = game_data.monlist.find_by_name(mon_name, variant) else {
+                let Some(monlist_id) = game_data.monlist.find_index_by_name(mon_name, variant) else {
                     continue;
                 };
+                let desc = &game_data.monlist.monsters[monlist_id];
 
                 // Per-variant name from monsters.txt takes priority over the mapstats base name.
                 // e.g. PeasantM2 A="Apprentice Mage", B="Journeyman Mage", C="Mage".
@@ -207,6 +210,7 @@ impl Monsters {
 
                 entries.push(Monster {
                     name: display_name.to_string(),
+                    monlist_id: monlist_id as u8,
                     spawn_position: sp.position,
                     spawn_radius: sp.radius,
                     group_index: g,
@@ -2,12 +2,12 @@
 //! `npcnames.txt` to build sex-split peasant portrait pools.
 
 use std::collections::HashMap;
-use std::error::Error;
 use std::io::Cursor;
 
 use csv::ReaderBuilder;
 
 use crate::Assets;
+use crate::Error;
 use crate::assets::npcnames::NpcNamePools;
 
 /// A name+portrait+profession for a dynamically generated street NPC (peasant).
@@ -65,7 +65,7 @@ pub struct StreetNpcs {
 
 impl StreetNpcs {
     /// Load from assets — reads npcdata.txt and cross-references npcnames.txt for sex classification.
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let name_pool = NpcNamePools::load(assets).ok();
         let data = assets.get_decompressed("icons/npcdata.txt")?;
         Self::parse(&data, name_pool.as_ref())
@@ -76,7 +76,7 @@ impl StreetNpcs {
     /// `name_pool` (from npcnames.txt) is used to classify peasant entries by sex for
     /// sex-appropriate portrait assignment. Entries whose sex can't be determined are
     /// added to both pools as fallback portraits.
-    pub fn parse(data: &[u8], name_pool: Option<&NpcNamePools>) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(data: &[u8], name_pool: Option<&NpcNamePools>) -> Result<Self, Error> {
         // Decode as Latin-1: every byte is a valid Unicode scalar value
         let text: String = data.iter().map(|&b| b as char).collect();
         let mut npcs = HashMap::new();
@@ -90,7 +90,7 @@ impl StreetNpcs {
             .from_reader(Cursor::new(body.as_bytes()));
 
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("npcdata.txt", e))?;
 
             let id: u32 = match rec.get(0).unwrap_or("").trim().parse() {
                 Ok(v) if v > 0 => v,
@@ -0,0 +1,141 @@
+//! Override layers searched before the game's own archives.
+//!
+//! A layer is either a loose-file directory or a "patch" LOD. Directories
+//! mirror the archive layout (`icons/dsounds.bin`, `bitmaps/grastyl`) and may
+//! also hold files at the top level or under `Sounds/`, `Anims/` and
+//! `Music/`. Loose files hold the same bytes the archive entry would, and
+//! names match case-insensitively. A patch LOD is searched by entry name,
+//! whichever archive the caller asked for.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+
+use super::archive::Archive;
+use super::archive::lod::LodArchive;
+
+/// One override layer, see the module docs.
+pub enum OverlayLayer {
+    Dir {
+        root: PathBuf,
+        /// Lowercase `/`-separated path relative to `root` → file on disk.
+        files: HashMap<String, PathBuf>,
+    },
+    Lod {
+        path: PathBuf,
+        lod: LodArchive,
+    },
+}
+
+impl OverlayLayer {
+    /// Open a directory as a loose layer or any other file as a patch LOD.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        if path.is_dir() {
+            let mut files = HashMap::new();
+            index_dir(path, "", &mut files)?;
+            Ok(Self::Dir {
+                root: path.to_path_buf(),
+                files,
+            })
+        } else {
+            let lod = LodArchive::open(path).map_err(|e| e.in_file(path.display().to_string()))?;
+            Ok(Self::Lod {
+                path: path.to_path_buf(),
+                lod,
+            })
+        }
+    }
+
+    /// Directory or LOD file this layer was opened from.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Dir { root, .. } => root,
+            Self::Lod { path, .. } => path,
+        }
+    }
+
+    /// Every file the layer provides: relative paths for a directory,
+    /// entry names for a patch LOD. Sorted.
+    pub fn files(&self) -> Vec<String> {
+        let mut names: Vec<String> = match self {
+            Self::Dir { files, .. } => files.keys().cloned().collect(),
+            Self::Lod { lod, .. } => lod.list_files().iter().map(|e| e.name.clone()).collect(),
+        };
+        names.sort();
+        names
+    }
+
+    /// Whether the layer has any of `loose` (directory) or `entry` (patch LOD).
+    pub(super) fn contains(&self, loose: &[String], entry: Option<&str>) -> bool {
+        match self {
+            Self::Dir { files, .. } => loose.iter().any(|rel| files.contains_key(&rel.to_lowercase())),
+            Self::Lod { lod, .. } => entry.is_some_and(|name| lod.contains(name)),
+        }
+    }
+
+    /// Read the first of `loose` (directory) or `entry` (patch LOD).
+    pub(super) fn find(&self, loose: &[String], entry: Option<&str>) -> Option<(Vec<u8>, AssetSource)> {
+        match self {
+            Self::Dir { root, files } => loose.iter().find_map(|rel| {
+                let file = files.get(&rel.to_lowercase())?;
+                match fs::read(file) {
+                    Ok(data) => Some((data, AssetSource::OverlayDir(root.clone()))),
+                    Err(e) => {
+                        log::warn!("overlay {}: {e}", file.display());
+                        None
+                    }
+                }
+            }),
+            Self::Lod { path, lod } => entry
+                .and_then(|name| lod.get_file(name))
+                .map(|data| (data, AssetSource::PatchLod(path.clone()))),
+        }
+    }
+}
+
+fn index_dir(dir: &Path, prefix: &str, files: &mut HashMap<String, PathBuf>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let rel = format!("{prefix}{}", name.to_lowercase());
+        if path.is_dir() {
+            index_dir(&path, &format!("{rel}/"), files)?;
+        } else {
+            files.insert(rel, path);
+        }
+    }
+    Ok(())
+}
+
+/// Where [`Assets`](super::Assets) found an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetSource {
+    /// Loose file in an override directory.
+    OverlayDir(PathBuf),
+    /// Entry in a patch LOD.
+    PatchLod(PathBuf),
+    /// One of the game's LOD/SND/VID archives, by stem (e.g. `"icons"`).
+    Archive(String),
+    /// Loose file in the game's own `Music/` or `Sounds/` directory.
+    GameFile(PathBuf),
+}
+
+impl fmt::Display for AssetSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OverlayDir(p) => write!(f, "overlay dir {}", p.display()),
+            Self::PatchLod(p) => write!(f, "patch lod {}", p.display()),
+            Self::Archive(name) => write!(f, "archive {name}"),
+            Self::GameFile(p) => write!(f, "game file {}", p.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "overlay_tests.rs"]
+mod tests;
@@ -0,0 +1,138 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::PathBuf;
+
+use super::*;
+use crate::Assets;
+use crate::assets::provider::{LodWriter, Version};
+
+fn scratch(tag: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("openmm_overlay_{tag}_{:?}", std::thread::current().id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_lod(path: &Path, files: &[(&str, &[u8])]) {
+    let mut writer = LodWriter::new(Version::MM6);
+    for (name, data) in files {
+        writer.add_file(name, data.to_vec());
+    }
+    writer.save(path).unwrap();
+}
+
+/// Game dir with `icons.lod`, a loose override dir and a patch LOD.
+fn layered(tag: &str) -> (PathBuf, PathBuf, PathBuf) {
+    let root = scratch(tag);
+    let game = root.join("data");
+    fs::create_dir_all(&game).unwrap();
+    write_lod(
+        &game.join("icons.lod"),
+        &[("a.txt", b"base-a"), ("b.txt", b"base-b"), ("c.txt", b"base-c")],
+    );
+
+    let loose = root.join("mod");
+    fs::create_dir_all(loose.join("Icons")).unwrap();
+    fs::create_dir_all(loose.join("Music")).unwrap();
+    fs::create_dir_all(loose.join("Sounds")).unwrap();
+    fs::write(loose.join("Icons").join("A.TXT"), b"loose-a").unwrap();
+    fs::write(loose.join("Music").join("13.mp3"), b"mp3").unwrap();
+    fs::write(loose.join("Sounds").join("Hit.wav"), b"wav").unwrap();
+
+    let patch = root.join("patch.lod");
+    write_lod(
+        &patch,
+        &[("a.txt", b"patch-a"), ("b.txt", b"patch-b"), ("new.txt", b"patch-new")],
+    );
+    (game, loose, patch)
+}
+
+#[test]
+fn earlier_layers_win_over_later_ones_and_archives() {
+    let (game, loose, patch) = layered("order");
+    let assets = Assets::with_overlays(&game, &[loose.clone(), patch.clone()]).unwrap();
+
+    assert_eq!(assets.get_bytes("icons/a.txt").unwrap(), b"loose-a");
+    assert_eq!(
+        assets.source_of("icons/a.txt"),
+        Some(AssetSource::OverlayDir(loose.clone()))
+    );
+    assert_eq!(assets.get_bytes("icons/b.txt").unwrap(), b"patch-b");
+    assert_eq!(
+        assets.source_of("icons/b.txt"),
+        Some(AssetSource::PatchLod(patch.clone()))
+    );
+    assert_eq!(assets.get_bytes("icons/c.txt").unwrap(), b"base-c");
+    assert_eq!(
+        assets.source_of("icons/c.txt"),
+        Some(AssetSource::Archive("icons".into()))
+    );
+    assert_eq!(assets.source_of("icons/missing.txt"), None);
+
+    let reversed = Assets::with_overlays(&game, &[patch.clone(), loose]).unwrap();
+    assert_eq!(reversed.get_bytes("icons/a.txt").unwrap(), b"patch-a");
+}
+
+#[test]
+fn overlays_cover_sounds_music_and_containment() {
+    let (game, loose, patch) = layered("media");
+    let assets = Assets::with_overlays(&game, &[loose.clone(), patch]).unwrap();
+
+    assert_eq!(assets.get_sound("hit").unwrap(), b"wav");
+    assert_eq!(assets.get_bytes("hit.wav").unwrap(), b"wav");
+    assert_eq!(assets.get_music("13").unwrap(), b"mp3");
+    assert_eq!(assets.source_of("13.mp3"), Some(AssetSource::OverlayDir(loose)));
+    assert!(assets.lod_contains("icons", "new.txt"));
+    assert!(!assets.lod_contains("icons", "nothing.txt"));
+
+    let plain = Assets::with_overlays(&game, &[]).unwrap();
+    assert!(plain.get_sound("hit").is_err());
+    assert!(!plain.lod_contains("icons", "new.txt"));
+}
+
+#[test]
+fn layer_listing_and_bad_layers() {
+    let (game, loose, patch) = layered("list");
+    let dir = OverlayLayer::open(&loose).unwrap();
+    assert_eq!(dir.path(), loose.as_path());
+    assert_eq!(dir.files(), vec!["icons/a.txt", "music/13.mp3", "sounds/hit.wav"]);
+    assert_eq!(
+        OverlayLayer::open(&patch).unwrap().files(),
+        vec!["a.txt", "b.txt", "new.txt"]
+    );
+
+    let not_a_lod = loose.join("Music").join("13.mp3");
+    assert!(Assets::with_overlays(&game, &[not_a_lod]).is_err());
+    assert!(Assets::with_overlays(&game, &[game.join("nope")]).is_err());
+}
+
+#[test]
+fn get_entry_borrows_from_archives_but_honours_overlays() {
+    let (game, loose, patch) = layered("entry");
+    let assets = Assets::with_overlays(&game, &[loose, patch]).unwrap();
+
+    assert!(matches!(
+        assets.get_entry("icons/c.txt").unwrap(),
+        Cow::Borrowed(b"base-c")
+    ));
+    assert_eq!(&*assets.get_entry("icons/a.txt").unwrap(), b"loose-a");
+    assert_eq!(&*assets.get_entry("icons/b.txt").unwrap(), b"patch-b");
+    assert!(assets.get_entry("icons/nothing.txt").unwrap_err().is_not_found());
+}
+
+#[test]
+fn version_comes_from_games_lod() {
+    let game = scratch("version");
+    write_lod(&game.join("icons.lod"), &[("a.txt", b"a")]);
+    assert_eq!(Assets::with_overlays(&game, &[]).unwrap().version(), Version::MM6);
+
+    let mut writer = LodWriter::new(Version::MM7);
+    writer.add_file("out01.ddm", vec![0; 300]);
+    writer.save(game.join("games.lod")).unwrap();
+    let assets = Assets::with_overlays(&game, &[]).unwrap();
+    assert_eq!(assets.version(), Version::MM7);
+
+    // 300 zero bytes hold an empty MM6 DDM but not the MM7 automap section.
+    let e = crate::Ddm::load(&assets, "out01.odm").unwrap_err();
+    assert!(matches!(e.kind(), crate::Error::Truncated { .. }), "{e}");
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// Quest bit name table loaded from `icons/quests.txt`.
@@ -12,7 +12,7 @@ pub struct QuestNames {
 }
 
 impl QuestNames {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/quests.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -83,7 +83,7 @@ impl QuestNames {
 }
 
 impl TryFrom<&[u8]> for QuestNames {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
@@ -4,9 +4,9 @@
 //! Columns: Item#, message text, dungeon #, Notes
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One scroll text entry.
@@ -28,7 +28,7 @@ pub struct ScrollTable {
 }
 
 impl ScrollTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/scroll.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -71,7 +71,7 @@ impl ScrollTable {
 }
 
 impl TryFrom<&[u8]> for ScrollTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
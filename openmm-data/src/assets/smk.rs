@@ -46,8 +46,8 @@ pub fn parse_smk_info(smk: &[u8]) -> Option<SmkInfo> {
     })
 }
 
-/// Audio properties for the active track in an SMK file.
-#[derive(Debug, Clone, Copy)]
+/// Audio properties for one track in an SMK file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SmkAudioInfo {
     pub track: u8,
     pub rate: u32,
@@ -55,7 +55,43 @@ pub struct SmkAudioInfo {
     pub bitdepth: u8,
 }
 
+impl SmkAudioInfo {
+    /// PCM bytes per second of playback, used to timestamp audio chunks.
+    pub fn bytes_per_second(&self) -> u32 {
+        self.rate * self.channels as u32 * (self.bitdepth as u32 / 8)
+    }
+}
+
+/// A chunk of PCM audio decoded alongside one video frame.
+#[derive(Debug, Clone)]
+pub struct SmkAudioChunk {
+    pub track: u8,
+    /// Presentation time of the first sample, in seconds from the start of the video.
+    ///
+    /// Smacker front-loads audio in the first frames, so this runs ahead of the
+    /// frame's own timestamp; it is derived from the bytes decoded so far on the
+    /// track, restarting from the landing frame's time after a seek.
+    pub pts: f64,
+    pub pcm: Vec<u8>,
+}
+
+/// One frame of an SMK stream with the audio interleaved with it.
+#[derive(Debug, Clone)]
+pub struct SmkPacket {
+    pub frame: u32,
+    /// Presentation time of the frame, in seconds from the start of the video.
+    pub pts: f64,
+    /// RGBA pixels (`width * height * 4` bytes).
+    pub rgba: Vec<u8>,
+    /// Audio chunks for every enabled track that carried data in this frame.
+    pub audio: Vec<SmkAudioChunk>,
+}
+
 /// Decodes an SMK video frame by frame, yielding RGBA pixel data.
+///
+/// `next_frame()` returns bare pixels; `next_packet()` streams frames together
+/// with their audio chunks and timestamps, and `seek_keyframe()`/`seek_frame()`/
+/// `seek_time()` reposition the stream.
 pub struct SmkDecoder {
     inner: Smk,
     pub width: u32,
@@ -65,8 +101,19 @@ pub struct SmkDecoder {
     pub fps: f32,
     /// Active audio track and its properties, if any audio is present.
     pub audio: Option<SmkAudioInfo>,
+    /// Every audio track present in the file, in track order.
+    pub tracks: Vec<SmkAudioInfo>,
+    seconds_per_frame: f64,
+    /// Bit per track: audio is decoded and returned in packets.
+    enabled_tracks: u8,
+    /// PCM bytes returned per track since `audio_origin`.
+    audio_bytes: [u64; 7],
+    /// Timestamp the per-track byte counters start from (0 or the last seek target).
+    audio_origin: f64,
     started: bool,
     done: bool,
+    /// A seek already decoded the current frame; hand it out before advancing.
+    pending: bool,
 }
 
 impl SmkDecoder {
@@ -76,26 +123,32 @@ impl SmkDecoder {
 
         let info = inner.info();
         let frame_count = info.frame_count;
-        let fps = if info.microseconds_per_frame > 0.0 {
-            1_000_000.0 / info.microseconds_per_frame as f32
+        let seconds_per_frame = if info.microseconds_per_frame > 0.0 {
+            info.microseconds_per_frame / 1_000_000.0
         } else {
-            10.0
+            0.1
         };
+        let fps = (1.0 / seconds_per_frame) as f32;
 
         let video = inner.info_video();
         let (width, height) = (video.width, video.height);
 
         let ai = inner.info_audio();
-        let audio = (0..7u8).find(|&t| ai.track_mask & (1 << t) != 0).map(|t| SmkAudioInfo {
-            track: t,
-            rate: ai.rate[t as usize],
-            channels: ai.channels[t as usize],
-            bitdepth: ai.bitdepth[t as usize],
-        });
+        let tracks: Vec<SmkAudioInfo> = (0..7u8)
+            .filter(|&t| ai.track_mask & (1 << t) != 0)
+            .map(|t| SmkAudioInfo {
+                track: t,
+                rate: ai.rate[t as usize],
+                channels: ai.channels[t as usize],
+                bitdepth: ai.bitdepth[t as usize],
+            })
+            .collect();
+        let audio = tracks.first().copied();
+        let enabled_tracks = audio.map_or(0, |a| 1 << a.track);
 
         inner.enable_video(true);
         for t in 0..7u8 {
-            inner.enable_audio(t, audio.is_some_and(|a| a.track == t));
+            inner.enable_audio(t, enabled_tracks & (1 << t) != 0);
         }
 
         Ok(Self {
@@ -105,17 +158,144 @@ impl SmkDecoder {
             frame_count,
             fps,
             audio,
+            tracks,
+            seconds_per_frame,
+            enabled_tracks,
+            audio_bytes: [0; 7],
+            audio_origin: 0.0,
             started: false,
             done: false,
+            pending: false,
         })
     }
 
     /// Decode and return the next frame as RGBA pixels (`width * height * 4` bytes).
     /// Returns `None` when all frames are exhausted.
     pub fn next_frame(&mut self) -> Option<Vec<u8>> {
-        if self.done {
+        self.advance().then(|| self.decode_current_frame())
+    }
+
+    /// Decode the next frame together with its audio chunks and timestamps.
+    /// Returns `None` when all frames are exhausted.
+    pub fn next_packet(&mut self) -> Option<SmkPacket> {
+        if !self.advance() {
             return None;
         }
+        let mut audio = Vec::new();
+        for info in &self.tracks {
+            if self.enabled_tracks & (1 << info.track) == 0 {
+                continue;
+            }
+            let pcm = self.inner.audio_data(info.track).unwrap_or_default();
+            if pcm.is_empty() {
+                continue;
+            }
+            let played = &mut self.audio_bytes[info.track as usize];
+            let pts = self.audio_origin + *played as f64 / info.bytes_per_second().max(1) as f64;
+            *played += pcm.len() as u64;
+            audio.push(SmkAudioChunk {
+                track: info.track,
+                pts,
+                pcm: pcm.to_vec(),
+            });
+        }
+        let frame = self.position();
+        Some(SmkPacket {
+            frame,
+            pts: self.frame_pts(frame),
+            rgba: self.decode_current_frame(),
+            audio,
+        })
+    }
+
+    /// Turn decoding of one audio track on or off.  Only the first track is
+    /// enabled by default; unknown tracks are ignored.
+    pub fn enable_track(&mut self, track: u8, enable: bool) {
+        if !self.tracks.iter().any(|t| t.track == track) {
+            return;
+        }
+        if enable {
+            self.enabled_tracks |= 1 << track;
+        } else {
+            self.enabled_tracks &= !(1 << track);
+        }
+        self.inner.enable_audio(track, enable);
+    }
+
+    /// Index of the most recently decoded frame.
+    pub fn position(&self) -> u32 {
+        self.inner.info().current_frame
+    }
+
+    /// Presentation time of `frame`, in seconds.
+    pub fn frame_pts(&self, frame: u32) -> f64 {
+        frame as f64 * self.seconds_per_frame
+    }
+
+    /// Total running time of the video, in seconds.
+    pub fn duration(&self) -> f64 {
+        self.frame_pts(self.frame_count)
+    }
+
+    /// Jump to the keyframe at or before `frame` and return its index.  The
+    /// keyframe is the next thing `next_frame()`/`next_packet()` returns.
+    pub fn seek_keyframe(&mut self, frame: u32) -> Result<u32, SmkError> {
+        if self.frame_count == 0 {
+            return Ok(0);
+        }
+        self.inner.seek_keyframe(frame.min(self.frame_count - 1))?;
+        let landed = self.position();
+        self.landed_at(landed);
+        Ok(landed)
+    }
+
+    /// Jump to exactly `frame` by seeking to the keyframe before it and decoding
+    /// forward.  `frame` is the next thing `next_frame()`/`next_packet()` returns.
+    pub fn seek_frame(&mut self, frame: u32) -> Result<(), SmkError> {
+        if self.frame_count == 0 {
+            return Ok(());
+        }
+        let target = frame.min(self.frame_count - 1);
+        self.inner.seek_keyframe(target)?;
+        while self.position() < target {
+            self.inner.next_frame()?;
+        }
+        self.landed_at(target);
+        Ok(())
+    }
+
+    /// Jump to the frame showing at `seconds` (clamped to the video's length).
+    pub fn seek_time(&mut self, seconds: f64) -> Result<(), SmkError> {
+        let frame = (seconds.max(0.0) / self.seconds_per_frame).floor();
+        self.seek_frame(frame.min(u32::MAX as f64) as u32)
+    }
+
+    /// Restart from the first frame.
+    pub fn rewind(&mut self) {
+        self.started = false;
+        self.done = false;
+        self.pending = false;
+        self.audio_bytes = [0; 7];
+        self.audio_origin = 0.0;
+    }
+
+    /// Reset the stream state after a seek decoded `frame`.
+    fn landed_at(&mut self, frame: u32) {
+        self.started = true;
+        self.done = false;
+        self.pending = true;
+        self.audio_bytes = [0; 7];
+        self.audio_origin = self.frame_pts(frame);
+    }
+
+    /// Move to the next frame, returning `false` once the stream is exhausted.
+    fn advance(&mut self) -> bool {
+        if std::mem::take(&mut self.pending) {
+            return true;
+        }
+        if self.done {
+            return false;
+        }
 
         let result = if !self.started {
             self.started = true;
@@ -127,13 +307,13 @@ impl SmkDecoder {
         match result {
             Err(_) | Ok(FrameStatus::Done) => {
                 self.done = true;
-                None
+                false
             }
             Ok(FrameStatus::Last) => {
                 self.done = true;
-                Some(self.decode_current_frame())
+                true
             }
-            Ok(FrameStatus::More) => Some(self.decode_current_frame()),
+            Ok(FrameStatus::More) => true,
         }
     }
 
@@ -210,6 +390,80 @@ mod tests {
     use crate::assets::provider::archive::Archive;
     use crate::assets::provider::archive::smk::SmkArchive;
 
+    /// Build a 4x4 SMK2 with no Huffman trees and one raw 8-bit mono track.
+    /// Frame `i` carries eight PCM bytes of value `i`; `keyframes` marks frames.
+    fn synthetic_smk(frames: u32, keyframes: &[u32]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(b"SMK2");
+        for v in [4u32, 4, frames, 100, 0] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        for t in 0..7 {
+            out.extend_from_slice(&(if t == 0 { 8u32 } else { 0 }).to_le_bytes());
+        }
+        // Tree chunk size, then the four unpacked tree sizes.
+        for v in [1u32, 0, 0, 0, 0] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+        for t in 0..7 {
+            let rate = if t == 0 { 0x4000_0000u32 | 8000 } else { 0 };
+            out.extend_from_slice(&rate.to_le_bytes());
+        }
+        out.extend_from_slice(&0u32.to_le_bytes());
+        for f in 0..frames {
+            let key = u32::from(keyframes.contains(&f));
+            out.extend_from_slice(&(12 | key).to_le_bytes());
+        }
+        out.extend(std::iter::repeat_n(0x02u8, frames as usize));
+        out.push(0);
+        for f in 0..frames {
+            out.extend_from_slice(&12u32.to_le_bytes());
+            out.extend_from_slice(&[f as u8; 8]);
+        }
+        out
+    }
+
+    #[test]
+    fn packets_interleave_frames_and_audio() {
+        let mut dec = SmkDecoder::new(synthetic_smk(5, &[0, 3])).unwrap();
+        assert_eq!(dec.fps, 10.0);
+        assert_eq!(dec.tracks.len(), 1);
+        assert_eq!(dec.tracks[0].bytes_per_second(), 8000);
+
+        for i in 0..5u32 {
+            let packet = dec.next_packet().expect("packet");
+            assert_eq!(packet.frame, i);
+            assert!((packet.pts - i as f64 * 0.1).abs() < 1e-9);
+            assert_eq!(packet.rgba.len(), 4 * 4 * 4);
+            assert_eq!(packet.audio.len(), 1);
+            assert_eq!(packet.audio[0].pcm, vec![i as u8; 8]);
+            assert!((packet.audio[0].pts - i as f64 * 8.0 / 8000.0).abs() < 1e-9);
+        }
+        assert!(dec.next_packet().is_none());
+    }
+
+    #[test]
+    fn seeks_land_on_keyframes_and_exact_frames() {
+        let mut dec = SmkDecoder::new(synthetic_smk(5, &[0, 3])).unwrap();
+
+        assert_eq!(dec.seek_keyframe(4).unwrap(), 3);
+        assert_eq!(dec.next_packet().unwrap().frame, 3);
+
+        dec.seek_frame(4).unwrap();
+        let packet = dec.next_packet().unwrap();
+        assert_eq!(packet.frame, 4);
+        assert_eq!(packet.audio[0].pcm, vec![4; 8]);
+        assert!((packet.audio[0].pts - 0.4).abs() < 1e-9);
+        assert!(dec.next_packet().is_none());
+
+        dec.seek_time(0.25).unwrap();
+        assert_eq!(dec.next_packet().unwrap().frame, 2);
+        assert_eq!(dec.next_packet().unwrap().frame, 3);
+
+        dec.rewind();
+        assert_eq!(dec.next_packet().unwrap().frame, 0);
+    }
+
     #[test]
     fn smk_decoder_reads_3dologo() {
         let data_path = crate::get_data_path();
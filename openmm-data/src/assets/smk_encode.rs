@@ -0,0 +1,629 @@
+//! RGBA frames + PCM audio → Smacker (SMK2) video, the inverse of the
+//! decoder wrapped by `smk.rs`.
+//!
+//! [`SmkEncoder`] quantizes the whole clip to one 256-colour palette (median
+//! cut over 6-bit colours, since Smacker palettes are 6 bits per channel),
+//! codes each 4x4 block losslessly against that palette and stores audio as
+//! raw PCM. The output plays in `SmkDecoder` and can be packed into a VID
+//! archive with `SmkWriter`.
+//!
+//! File layout (all little endian):
+//!   0x00: "SMK2", width u32, height u32, frames u32, frame rate i32
+//!         (negative: 10 µs units per frame), flags u32,
+//!   0x1C: max audio bytes per frame u32[7], tree chunk size u32,
+//!   0x3C: unpacked tree sizes u32[4] (MMAP, MCLR, FULL, TYPE),
+//!   0x4C: audio rate/flags u32[7], reserved u32
+//!   then frame sizes u32[frames] (bit 0 = keyframe), frame types u8[frames]
+//!   (bit 0 = palette, bit 1+n = audio track n), the Huffman tree bitstream
+//!   and the frame chunks.
+//! Keyframes carry the full palette and no "unchanged" blocks, so seeking
+//! can restart decoding from any of them.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::Error;
+
+use super::image_encode::{Nearest, median_cut};
+
+/// Smacker 6-bit to 8-bit palette expansion (matches the decoder's table).
+const PALMAP: [u8; 64] = [
+    0x00, 0x04, 0x08, 0x0C, 0x10, 0x14, 0x18, 0x1C, 0x20, 0x24, 0x28, 0x2C, 0x30, 0x34, 0x38, 0x3C, 0x41, 0x45, 0x49,
+    0x4D, 0x51, 0x55, 0x59, 0x5D, 0x61, 0x65, 0x69, 0x6D, 0x71, 0x75, 0x79, 0x7D, 0x82, 0x86, 0x8A, 0x8E, 0x92, 0x96,
+    0x9A, 0x9E, 0xA2, 0xA6, 0xAA, 0xAE, 0xB2, 0xB6, 0xBA, 0xBE, 0xC3, 0xC7, 0xCB, 0xCF, 0xD3, 0xD7, 0xDB, 0xDF, 0xE3,
+    0xE7, 0xEB, 0xEF, 0xF3, 0xF7, 0xFB, 0xFF,
+];
+
+/// Block run lengths addressable by the 6-bit size field of a TYPE value.
+const SIZETABLE: [u32; 64] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+    32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59,
+    128, 256, 512, 1024, 2048,
+];
+
+const TREE_MMAP: usize = 0;
+const TREE_MCLR: usize = 1;
+const TREE_FULL: usize = 2;
+const TREE_TYPE: usize = 3;
+
+const BLOCK_MONO: u16 = 0;
+const BLOCK_FULL: u16 = 1;
+const BLOCK_VOID: u16 = 2;
+const BLOCK_SOLID: u16 = 3;
+
+/// Audio track present flag in the header's rate field.
+const AUDIO_EXISTS: u32 = 0x4000_0000;
+const AUDIO_16BIT: u32 = 0x2000_0000;
+const AUDIO_STEREO: u32 = 0x1000_0000;
+
+/// Builds an SMK2 stream from RGBA frames and an optional PCM track.
+///
+/// Frames are buffered until [`SmkEncoder::encode`], which needs the whole
+/// clip to pick the shared palette.
+pub struct SmkEncoder {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f32,
+    /// A keyframe every this many frames (0: only the first frame).
+    pub keyframe_interval: u32,
+    frames: Vec<Vec<u8>>,
+    audio: Option<PcmTrack>,
+}
+
+struct PcmTrack {
+    rate: u32,
+    channels: u8,
+    bitdepth: u8,
+    pcm: Vec<u8>,
+}
+
+/// One 4x4 block of a frame, as the decoder's block types.
+#[derive(Clone, Copy, PartialEq)]
+enum Block {
+    /// Unchanged from the previous frame.
+    Void,
+    Solid(u8),
+    /// Two colours (`hi << 8 | lo`) and a 16-bit map selecting `hi`.
+    Mono {
+        colors: u16,
+        map: u16,
+    },
+    /// Pixel pairs, two per row.
+    Full([u16; 8]),
+}
+
+impl Block {
+    /// TYPE value without the size field; consecutive equal keys form a run.
+    fn key(&self) -> u16 {
+        match *self {
+            Block::Void => BLOCK_VOID,
+            Block::Solid(c) => BLOCK_SOLID | (c as u16) << 8,
+            Block::Mono { .. } => BLOCK_MONO,
+            Block::Full(_) => BLOCK_FULL,
+        }
+    }
+}
+
+impl SmkEncoder {
+    pub fn new(width: u32, height: u32, fps: f32) -> Self {
+        Self {
+            width,
+            height,
+            fps,
+            keyframe_interval: fps.round().max(1.0) as u32,
+            frames: Vec::new(),
+            audio: None,
+        }
+    }
+
+    /// Append a frame of `width * height * 4` RGBA bytes (alpha is ignored).
+    pub fn add_frame(&mut self, rgba: Vec<u8>) -> Result<(), Error> {
+        let expected = self.width as usize * self.height as usize * 4;
+        if rgba.len() != expected {
+            return Err(Error::invalid(
+                "smk",
+                format!("frame has {} bytes, expected {expected}", rgba.len()),
+            ));
+        }
+        self.frames.push(rgba);
+        Ok(())
+    }
+
+    /// Set the audio track: interleaved PCM (unsigned 8-bit or signed 16-bit
+    /// little endian, as in WAV). It is spread evenly over the frames.
+    pub fn set_audio(&mut self, rate: u32, channels: u8, bitdepth: u8, pcm: Vec<u8>) -> Result<(), Error> {
+        if !(1..=2).contains(&channels) || !matches!(bitdepth, 8 | 16) || rate == 0 || rate > 0x00FF_FFFF {
+            return Err(Error::invalid(
+                "smk",
+                format!("unsupported audio format: {rate} Hz, {channels} channels, {bitdepth} bit"),
+            ));
+        }
+        self.audio = Some(PcmTrack {
+            rate,
+            channels,
+            bitdepth,
+            pcm,
+        });
+        Ok(())
+    }
+
+    /// Encode everything added so far into SMK bytes.
+    pub fn encode(&self) -> Result<Vec<u8>, Error> {
+        let (w, h) = (self.width as usize, self.height as usize);
+        if w == 0 || h == 0 || w % 4 != 0 || h % 4 != 0 {
+            return Err(Error::invalid(
+                "smk",
+                format!("frame size {w}x{h} must be a non-zero multiple of 4"),
+            ));
+        }
+        if self.frames.is_empty() {
+            return Err(Error::invalid("smk", "no frames to encode"));
+        }
+        if self.fps.is_nan() || self.fps <= 0.0 {
+            return Err(Error::invalid("smk", format!("invalid frame rate {}", self.fps)));
+        }
+
+        let (palette6, indexed) = self.quantize();
+
+        // Pass 1: classify blocks and gather symbol frequencies for the trees.
+        let mut blocks = Vec::with_capacity(indexed.len());
+        let mut freqs: [HashMap<u16, u64>; 4] = Default::default();
+        for (i, frame) in indexed.iter().enumerate() {
+            let prev = (!self.is_keyframe(i)).then(|| indexed[i - 1].as_slice());
+            let frame_blocks = classify(frame, prev, w, h);
+            for (type_value, run) in runs(&frame_blocks) {
+                *freqs[TREE_TYPE].entry(type_value).or_default() += 1;
+                for block in run {
+                    match *block {
+                        Block::Mono { colors, map } => {
+                            *freqs[TREE_MCLR].entry(colors).or_default() += 1;
+                            *freqs[TREE_MMAP].entry(map).or_default() += 1;
+                        }
+                        Block::Full(pairs) => {
+                            for p in pairs {
+                                *freqs[TREE_FULL].entry(p).or_default() += 1;
+                            }
+                        }
+                        Block::Void | Block::Solid(_) => {}
+                    }
+                }
+            }
+            blocks.push(frame_blocks);
+        }
+
+        let mut tree_bits = BitWriter::default();
+        let mut tree_sizes = [0u32; 4];
+        let mut codes: [HashMap<u16, Vec<bool>>; 4] = Default::default();
+        for t in 0..4 {
+            (tree_sizes[t], codes[t]) = write_huff16(&mut tree_bits, &freqs[t]);
+        }
+        // The decoder's unaligned byte reads look one byte ahead.
+        let mut trees = tree_bits.finish();
+        trees.push(0);
+
+        // Pass 2: frame chunks.
+        let audio_chunks = self.split_audio();
+        let mut chunks = Vec::with_capacity(blocks.len());
+        let mut types = Vec::with_capacity(blocks.len());
+        for (i, frame_blocks) in blocks.iter().enumerate() {
+            let mut chunk = Vec::new();
+            let mut frame_type = 0u8;
+            if self.is_keyframe(i) {
+                frame_type |= 0x01;
+                chunk.push(0);
+                for c in &palette6 {
+                    chunk.extend_from_slice(c);
+                }
+                chunk.resize(chunk.len().next_multiple_of(4), 0);
+                chunk[0] = (chunk.len() / 4) as u8;
+            }
+            if let Some(pcm) = audio_chunks.get(i).filter(|c| !c.is_empty()) {
+                frame_type |= 0x02;
+                chunk.extend_from_slice(&(pcm.len() as u32 + 4).to_le_bytes());
+                chunk.extend_from_slice(pcm);
+            }
+            let mut bits = BitWriter::default();
+            for (type_value, run) in runs(frame_blocks) {
+                bits.code(&codes[TREE_TYPE][&type_value]);
+                for block in run {
+                    match *block {
+                        Block::Mono { colors, map } => {
+                            bits.code(&codes[TREE_MCLR][&colors]);
+                            bits.code(&codes[TREE_MMAP][&map]);
+                        }
+                        Block::Full(pairs) => {
+                            for p in pairs {
+                                bits.code(&codes[TREE_FULL][&p]);
+                            }
+                        }
+                        Block::Void | Block::Solid(_) => {}
+                    }
+                }
+            }
+            chunk.extend_from_slice(&bits.finish());
+            chunk.resize(chunk.len().next_multiple_of(4), 0);
+            chunks.push(chunk);
+            types.push(frame_type);
+        }
+
+        let mut out = Vec::with_capacity(0x68 + chunks.iter().map(Vec::len).sum::<usize>());
+        out.extend_from_slice(b"SMK2");
+        out.extend_from_slice(&self.width.to_le_bytes());
+        out.extend_from_slice(&self.height.to_le_bytes());
+        out.extend_from_slice(&(chunks.len() as u32).to_le_bytes());
+        let rate = -((100_000.0 / self.fps as f64).round().max(1.0) as i32);
+        out.extend_from_slice(&rate.to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        let max_audio = audio_chunks.iter().map(Vec::len).max().unwrap_or(0) as u32;
+        for t in 0..7 {
+            let size = if t == 0 { max_audio } else { 0 };
+            out.extend_from_slice(&size.to_le_bytes());
+        }
+        out.extend_from_slice(&(trees.len() as u32).to_le_bytes());
+        for size in tree_sizes {
+            out.extend_from_slice(&size.to_le_bytes());
+        }
+        for t in 0..7 {
+            let flags = match &self.audio {
+                Some(a) if t == 0 => {
+                    AUDIO_EXISTS
+                        | if a.bitdepth == 16 { AUDIO_16BIT } else { 0 }
+                        | if a.channels == 2 { AUDIO_STEREO } else { 0 }
+                        | a.rate
+                }
+                _ => 0,
+            };
+            out.extend_from_slice(&flags.to_le_bytes());
+        }
+        out.extend_from_slice(&0u32.to_le_bytes());
+        for (i, chunk) in chunks.iter().enumerate() {
+            let size = chunk.len() as u32 | u32::from(self.is_keyframe(i));
+            out.extend_from_slice(&size.to_le_bytes());
+        }
+        out.extend_from_slice(&types);
+        out.extend_from_slice(&trees);
+        for chunk in &chunks {
+            out.extend_from_slice(chunk);
+        }
+        Ok(out)
+    }
+
+    fn is_keyframe(&self, frame: usize) -> bool {
+        frame.is_multiple_of(self.keyframe_interval as usize)
+    }
+
+    /// Shared 6-bit palette for the clip and each frame as palette indices.
+    fn quantize(&self) -> (Vec<[u8; 3]>, Vec<Vec<u8>>) {
+        let snap = |c: &[u8]| [expand(c[0]), expand(c[1]), expand(c[2])];
+        let mut histogram: HashMap<[u8; 3], u64> = HashMap::new();
+        for frame in &self.frames {
+            for p in frame.chunks_exact(4) {
+                *histogram.entry(snap(p)).or_default() += 1;
+            }
+        }
+
+        let mut palette = [0u8; 256 * 3];
+        let mut palette6 = vec![[0u8; 3]; 256];
+        for (i, c) in median_cut(histogram.into_iter().collect(), 256).iter().enumerate() {
+            palette6[i] = c.map(to_6bit);
+            palette[i * 3..i * 3 + 3].copy_from_slice(&palette6[i].map(|v| PALMAP[v as usize]));
+        }
+
+        let mut nearest = Nearest::new(&palette, 0);
+        let indexed = self
+            .frames
+            .iter()
+            .map(|frame| frame.chunks_exact(4).map(|p| nearest.index(snap(p))).collect())
+            .collect();
+        (palette6, indexed)
+    }
+
+    /// PCM bytes per frame, whole sample frames spread evenly over the clip.
+    fn split_audio(&self) -> Vec<Vec<u8>> {
+        let Some(audio) = &self.audio else { return Vec::new() };
+        let align = audio.channels as usize * (audio.bitdepth as usize / 8);
+        let samples = audio.pcm.len() / align;
+        let n = self.frames.len();
+        (0..n)
+            .map(|i| audio.pcm[samples * i / n * align..samples * (i + 1) / n * align].to_vec())
+            .collect()
+    }
+}
+
+/// Nearest 6-bit level for an 8-bit channel value.
+fn to_6bit(c: u8) -> u8 {
+    ((c as u32 * 63 + 127) / 255) as u8
+}
+
+/// Round an 8-bit channel to the nearest value a Smacker palette can hold.
+fn expand(c: u8) -> u8 {
+    PALMAP[to_6bit(c) as usize]
+}
+
+/// Split an indexed frame into blocks, left to right, top to bottom.
+/// With `prev`, blocks identical to the previous frame become `Void`.
+fn classify(frame: &[u8], prev: Option<&[u8]>, w: usize, h: usize) -> Vec<Block> {
+    let mut blocks = Vec::with_capacity(w / 4 * (h / 4));
+    for by in (0..h).step_by(4) {
+        for bx in (0..w).step_by(4) {
+            let mut px = [0u8; 16];
+            for r in 0..4 {
+                let row = (by + r) * w + bx;
+                px[r * 4..r * 4 + 4].copy_from_slice(&frame[row..row + 4]);
+            }
+            let unchanged = prev.is_some_and(|prev| {
+                (0..4).all(|r| {
+                    let row = (by + r) * w + bx;
+                    prev[row..row + 4] == px[r * 4..r * 4 + 4]
+                })
+            });
+            blocks.push(if unchanged { Block::Void } else { code_block(&px) });
+        }
+    }
+    blocks
+}
+
+fn code_block(px: &[u8; 16]) -> Block {
+    let hi = px[0];
+    let Some(&lo) = px.iter().find(|&&p| p != hi) else {
+        return Block::Solid(hi);
+    };
+    if px.iter().all(|&p| p == hi || p == lo) {
+        let map = (0..16).filter(|&i| px[i] == hi).fold(0u16, |m, i| m | 1 << i);
+        return Block::Mono {
+            colors: (hi as u16) << 8 | lo as u16,
+            map,
+        };
+    }
+    let mut pairs = [0u16; 8];
+    for r in 0..4 {
+        let p = &px[r * 4..r * 4 + 4];
+        pairs[r * 2] = (p[3] as u16) << 8 | p[2] as u16;
+        pairs[r * 2 + 1] = (p[1] as u16) << 8 | p[0] as u16;
+    }
+    Block::Full(pairs)
+}
+
+/// Group blocks into runs the TYPE tree can express: each item is the full
+/// TYPE value (key + size index) and the blocks it covers.
+fn runs(blocks: &[Block]) -> Vec<(u16, &[Block])> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    while start < blocks.len() {
+        let key = blocks[start].key();
+        let end = blocks[start..]
+            .iter()
+            .position(|b| b.key() != key)
+            .map_or(blocks.len(), |n| start + n);
+        let mut pos = start;
+        while pos < end {
+            let left = (end - pos) as u32;
+            let size = SIZETABLE.iter().rposition(|&s| s <= left).unwrap_or(0);
+            let len = SIZETABLE[size] as usize;
+            out.push((key | (size as u16) << 2, &blocks[pos..pos + len]));
+            pos += len;
+        }
+        start = end;
+    }
+    out
+}
+
+/// Write one Huff16 tree (with its two Huff8 byte trees) and return the
+/// header's unpacked size and the code of every value.
+fn write_huff16(bits: &mut BitWriter, freqs: &HashMap<u16, u64>) -> (u32, HashMap<u16, Vec<bool>>) {
+    let Some(tree) = huffman(freqs) else {
+        // No tree: the decoder substitutes a single zero leaf.
+        bits.bit(false);
+        bits.bit(false);
+        return (0, HashMap::new());
+    };
+
+    bits.bit(true);
+    let mut low = HashMap::new();
+    let mut high = HashMap::new();
+    for &v in freqs.keys() {
+        *low.entry(v & 0xFF).or_default() += 1;
+        *high.entry(v >> 8).or_default() += 1;
+    }
+    let low_codes = write_huff8(bits, &low);
+    let high_codes = write_huff8(bits, &high);
+
+    // Escape-code cache: values no leaf uses, so every leaf is literal.
+    let mut unused = (0..=u16::MAX).filter(|v| !freqs.contains_key(v));
+    for _ in 0..3 {
+        let v = unused.next().unwrap_or(0);
+        bits.byte(v as u8);
+        bits.byte((v >> 8) as u8);
+    }
+
+    let mut nodes = 0;
+    write_node(bits, &tree, &mut nodes, &mut |bits, v| {
+        bits.code(&low_codes[&(v & 0xFF)]);
+        bits.code(&high_codes[&(v >> 8)]);
+    });
+    bits.bit(false);
+
+    let mut codes = HashMap::new();
+    collect_codes(&tree, &mut Vec::new(), &mut codes);
+    (12 + 4 * nodes, codes)
+}
+
+/// Write a Huff8 byte tree and return the code of every byte.
+fn write_huff8(bits: &mut BitWriter, freqs: &HashMap<u16, u64>) -> HashMap<u16, Vec<bool>> {
+    let tree = huffman(freqs).expect("a Huff16 leaf always has bytes");
+    bits.bit(true);
+    write_node(bits, &tree, &mut 0, &mut |bits, v| bits.byte(v as u8));
+    bits.bit(false);
+    let mut codes = HashMap::new();
+    collect_codes(&tree, &mut Vec::new(), &mut codes);
+    codes
+}
+
+enum Node {
+    Leaf(u16),
+    Branch(Box<Node>, Box<Node>),
+}
+
+/// Huffman tree over the symbols in `freqs`; ties break on symbol order so
+/// the output is deterministic.
+fn huffman(freqs: &HashMap<u16, u64>) -> Option<Node> {
+    let mut symbols: Vec<_> = freqs.iter().map(|(&v, &n)| (v, n)).collect();
+    symbols.sort_unstable();
+    let mut nodes: Vec<Option<Node>> = symbols.iter().map(|&(v, _)| Some(Node::Leaf(v))).collect();
+    let mut heap: BinaryHeap<_> = symbols.iter().enumerate().map(|(i, &(_, n))| Reverse((n, i))).collect();
+    while heap.len() > 1 {
+        let (Some(Reverse((n1, a))), Some(Reverse((n2, b)))) = (heap.pop(), heap.pop()) else {
+            break;
+        };
+        let node = Node::Branch(Box::new(nodes[a].take()?), Box::new(nodes[b].take()?));
+        heap.push(Reverse((n1 + n2, nodes.len())));
+        nodes.push(Some(node));
+    }
+    let Reverse((_, root)) = heap.pop()?;
+    nodes[root].take()
+}
+
+/// Branch: 1, left, right. Leaf: 0, then the value via `leaf`.
+fn write_node(bits: &mut BitWriter, node: &Node, count: &mut u32, leaf: &mut impl FnMut(&mut BitWriter, u16)) {
+    *count += 1;
+    match node {
+        Node::Leaf(v) => {
+            bits.bit(false);
+            leaf(bits, *v);
+        }
+        Node::Branch(left, right) => {
+            bits.bit(true);
+            write_node(bits, left, count, leaf);
+            write_node(bits, right, count, leaf);
+        }
+    }
+}
+
+/// Left is a 0 bit, right a 1 bit; a lone leaf has the empty code.
+fn collect_codes(node: &Node, prefix: &mut Vec<bool>, out: &mut HashMap<u16, Vec<bool>>) {
+    match node {
+        Node::Leaf(v) => {
+            out.insert(*v, prefix.clone());
+        }
+        Node::Branch(left, right) => {
+            for (bit, child) in [(false, left), (true, right)] {
+                prefix.push(bit);
+                collect_codes(child, prefix, out);
+                prefix.pop();
+            }
+        }
+    }
+}
+
+/// LSB-first bit packer matching the decoder's bitstream reader.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    used: u8,
+}
+
+impl BitWriter {
+    fn bit(&mut self, bit: bool) {
+        if self.used == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            *self.bytes.last_mut().expect("pushed above") |= 1 << self.used;
+        }
+        self.used = (self.used + 1) % 8;
+    }
+
+    fn byte(&mut self, byte: u8) {
+        for i in 0..8 {
+            self.bit(byte >> i & 1 != 0);
+        }
+    }
+
+    fn code(&mut self, code: &[bool]) {
+        for &bit in code {
+            self.bit(bit);
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::smk::SmkDecoder;
+
+    /// Colours a Smacker palette holds exactly, so the round trip is lossless.
+    fn color(i: usize) -> [u8; 4] {
+        [PALMAP[i % 64], PALMAP[(i * 7) % 64], PALMAP[(i * 13) % 64], 255]
+    }
+
+    fn frame(w: usize, h: usize, f: impl Fn(usize, usize) -> [u8; 4]) -> Vec<u8> {
+        (0..h)
+            .flat_map(|y| (0..w).map(move |x| (x, y)))
+            .flat_map(|(x, y)| f(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn frames_and_audio_round_trip_through_decoder() {
+        let (w, h) = (16, 8);
+        let frames = [
+            // Solid, two-colour and noisy blocks.
+            frame(w, h, |x, y| match x / 4 {
+                0 => color(1),
+                1 => color(2 + (x + y) % 2),
+                _ => color(x * 3 + y * 5),
+            }),
+            // Only the first block changes.
+            frame(w, h, |x, y| match x / 4 {
+                0 => color(9),
+                1 => color(2 + (x + y) % 2),
+                _ => color(x * 3 + y * 5),
+            }),
+            frame(w, h, |x, y| color(x + y)),
+        ];
+        let pcm: Vec<u8> = (0..600u32).flat_map(|i| (i as i16 * 50).to_le_bytes()).collect();
+
+        let mut encoder = SmkEncoder::new(w as u32, h as u32, 10.0);
+        encoder.keyframe_interval = 2;
+        for f in &frames {
+            encoder.add_frame(f.clone()).unwrap();
+        }
+        encoder.set_audio(22050, 1, 16, pcm.clone()).unwrap();
+        let bytes = encoder.encode().unwrap();
+
+        let mut dec = SmkDecoder::new(bytes).unwrap();
+        assert_eq!((dec.width, dec.height, dec.frame_count), (16, 8, 3));
+        assert_eq!(dec.fps, 10.0);
+        assert_eq!(dec.tracks.len(), 1);
+        assert_eq!(
+            (dec.tracks[0].rate, dec.tracks[0].channels, dec.tracks[0].bitdepth),
+            (22050, 1, 16)
+        );
+
+        let mut audio = Vec::new();
+        for expected in &frames {
+            let packet = dec.next_packet().expect("frame");
+            assert_eq!(&packet.rgba, expected);
+            audio.extend(packet.audio.into_iter().flat_map(|a| a.pcm));
+        }
+        assert!(dec.next_packet().is_none());
+        assert_eq!(audio, pcm);
+
+        // Frame 2 is a keyframe and decodes on its own.
+        assert_eq!(dec.seek_keyframe(2).unwrap(), 2);
+        assert_eq!(dec.next_frame().unwrap(), frames[2]);
+    }
+
+    #[test]
+    fn rejects_sizes_the_block_coder_cannot_express() {
+        let mut encoder = SmkEncoder::new(6, 4, 15.0);
+        encoder.add_frame(vec![0; 6 * 4 * 4]).unwrap();
+        assert!(encoder.encode().is_err());
+        assert!(encoder.add_frame(vec![0; 3]).is_err());
+        assert!(encoder.set_audio(22050, 3, 16, Vec::new()).is_err());
+    }
+}
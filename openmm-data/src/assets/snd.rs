@@ -157,6 +157,9 @@ pub fn ima_adpcm_to_pcm(wav: &[u8]) -> Option<Vec<u8>> {
                 pcm_samples.push(decode_nibble(hi, &mut predictors[0], &mut step_indices[0]));
             }
         } else {
+            // Each group holds 8 samples per channel, stored channel after
+            // channel; the output interleaves them frame by frame.
+            let mut group = vec![0i16; channels * 8];
             let mut i = 0;
             while i + channels * 4 <= payload.len() {
                 for ch in 0..channels {
@@ -164,18 +167,216 @@ pub fn ima_adpcm_to_pcm(wav: &[u8]) -> Option<Vec<u8>> {
                         let byte = payload[i + ch * 4 + b];
                         let lo = byte & 0x0F;
                         let hi = (byte >> 4) & 0x0F;
-                        pcm_samples.push(decode_nibble(lo, &mut predictors[ch], &mut step_indices[ch]));
-                        pcm_samples.push(decode_nibble(hi, &mut predictors[ch], &mut step_indices[ch]));
+                        group[(b * 2) * channels + ch] = decode_nibble(lo, &mut predictors[ch], &mut step_indices[ch]);
+                        group[(b * 2 + 1) * channels + ch] =
+                            decode_nibble(hi, &mut predictors[ch], &mut step_indices[ch]);
                     }
                 }
+                pcm_samples.extend_from_slice(&group);
                 i += channels * 4;
             }
         }
     }
     pcm_samples.truncate(total_samples);
+    // The last block is padded to whole nibble groups; `fact` has the true length.
+    if let Some(fact) = find_chunk(wav, b"fact")
+        && let Some(count) = wav.get(fact + 8..fact + 12)
+    {
+        let frames = u32::from_le_bytes([count[0], count[1], count[2], count[3]]) as usize;
+        pcm_samples.truncate(frames * channels);
+    }
     Some(build_pcm_wav(&pcm_samples, channels as u16, sample_rate))
 }
 
+// ── WAV decoding and re-encoding ────────────────────────────
+
+/// Codec parameters from a WAV's `fmt ` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavFormat {
+    /// 1 = PCM, 17 = IMA-ADPCM.
+    pub tag: u16,
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    pub block_align: u16,
+}
+
+impl WavFormat {
+    pub fn parse(wav: &[u8]) -> Option<Self> {
+        let fmt_offset = find_chunk(wav, b"fmt ")?;
+        let fmt = wav.get(fmt_offset + 8..fmt_offset + 24)?;
+        Some(Self {
+            tag: u16::from_le_bytes([fmt[0], fmt[1]]),
+            channels: u16::from_le_bytes([fmt[2], fmt[3]]),
+            sample_rate: u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]),
+            block_align: u16::from_le_bytes([fmt[12], fmt[13]]),
+            bits_per_sample: u16::from_le_bytes([fmt[14], fmt[15]]),
+        })
+    }
+}
+
+/// Decode a WAV in any codec Audio.snd uses (8/16-bit PCM, IMA-ADPCM) to
+/// interleaved 16-bit samples, channel count and sample rate.
+pub fn wav_to_pcm16(wav: &[u8]) -> Option<(Vec<i16>, u16, u32)> {
+    let format = WavFormat::parse(wav)?;
+    if format.tag == 17 {
+        return pcm_samples_from_wav(&ima_adpcm_to_pcm(wav)?);
+    }
+    if format.tag != 1 || format.channels == 0 {
+        return None;
+    }
+    let data_offset = find_chunk(wav, b"data")?;
+    let data_size = u32::from_le_bytes(wav.get(data_offset + 4..data_offset + 8)?.try_into().ok()?) as usize;
+    let data = &wav[data_offset + 8..(data_offset + 8 + data_size).min(wav.len())];
+    let samples = match format.bits_per_sample {
+        8 => data.iter().map(|&b| ((b as i16) - 128) << 8).collect(),
+        16 => data.chunks_exact(2).map(|c| i16::from_le_bytes([c[0], c[1]])).collect(),
+        _ => return None,
+    };
+    Some((samples, format.channels, format.sample_rate))
+}
+
+/// Encode samples as a WAV in `like`'s codec: IMA-ADPCM with the same block
+/// size, 8-bit PCM, or otherwise 16-bit PCM. Channel count and rate are the
+/// samples' own.
+pub fn encode_wav_like(like: &WavFormat, samples: &[i16], channels: u16, sample_rate: u32) -> Vec<u8> {
+    match (like.tag, like.bits_per_sample) {
+        (17, _) => pcm_to_ima_adpcm(samples, channels, sample_rate, like.block_align),
+        (1, 8) => {
+            let data: Vec<u8> = samples.iter().map(|&s| ((s >> 8) + 128) as u8).collect();
+            build_wav(1, channels, sample_rate, channels, 8, &[], &data)
+        }
+        _ => build_pcm_wav(samples, channels, sample_rate),
+    }
+}
+
+/// Encode 16-bit samples as an IMA-ADPCM WAV (format 17) with
+/// `block_align`-byte blocks, the inverse of [`ima_adpcm_to_pcm`].
+pub fn pcm_to_ima_adpcm(samples: &[i16], channels: u16, sample_rate: u32, block_align: u16) -> Vec<u8> {
+    let ch = channels.max(1) as usize;
+    // Stereo payloads interleave 4-byte (8-sample) groups per channel.
+    let block_align = (block_align as usize).max(ch * 8).next_multiple_of(ch * 4);
+    let samples_per_block = (block_align - ch * 4) * 2 / ch + 1;
+    let frames = samples.len() / ch;
+
+    let mut data = Vec::with_capacity(frames.div_ceil(samples_per_block) * block_align);
+    let mut predictors = vec![0i32; ch];
+    // Block headers carry the step index, so start where the first sample
+    // delta needs it instead of ramping up from the smallest step.
+    let mut step_indices: Vec<i32> = (0..ch)
+        .map(|c| {
+            let delta = match samples.get(ch + c) {
+                Some(&next) => (next as i32 - samples[c] as i32).abs(),
+                None => 0,
+            };
+            STEP_TABLE.iter().position(|&step| step >= delta).unwrap_or(88) as i32
+        })
+        .collect();
+    for start in (0..frames).step_by(samples_per_block) {
+        let len = samples_per_block.min(frames - start);
+        for c in 0..ch {
+            predictors[c] = samples[start * ch + c] as i32;
+            data.extend_from_slice(&(predictors[c] as i16).to_le_bytes());
+            data.push(step_indices[c] as u8);
+            data.push(0);
+        }
+        // Samples after the header one, per channel, padded to whole groups.
+        let group = if ch == 1 { 2 } else { 8 };
+        let coded = (len - 1).next_multiple_of(group);
+        let nibbles: Vec<Vec<u8>> = (0..ch)
+            .map(|c| {
+                (1..=coded)
+                    .map(|i| {
+                        let sample = if i < len { samples[(start + i) * ch + c] } else { 0 };
+                        encode_nibble(sample, &mut predictors[c], &mut step_indices[c])
+                    })
+                    .collect()
+            })
+            .collect();
+        if ch == 1 {
+            data.extend(nibbles[0].chunks_exact(2).map(|p| p[0] | p[1] << 4));
+        } else {
+            for g in (0..coded).step_by(8) {
+                for n in &nibbles {
+                    data.extend(n[g..g + 8].chunks_exact(2).map(|p| p[0] | p[1] << 4));
+                }
+            }
+        }
+    }
+
+    let mut extra = Vec::with_capacity(8);
+    extra.extend_from_slice(&2u16.to_le_bytes());
+    extra.extend_from_slice(&(samples_per_block as u16).to_le_bytes());
+    let mut wav = build_wav(17, channels, sample_rate, block_align as u16, 4, &extra, &data);
+    // `fact` carries the true sample count; decoders may pad to whole blocks.
+    let fact_at = wav.len() - data.len() - 8;
+    let mut fact = Vec::with_capacity(12);
+    fact.extend_from_slice(b"fact");
+    fact.extend_from_slice(&4u32.to_le_bytes());
+    fact.extend_from_slice(&(frames as u32).to_le_bytes());
+    wav.splice(fact_at..fact_at, fact);
+    let riff_size = wav.len() as u32 - 8;
+    wav[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    wav
+}
+
+/// Pick the nibble whose decoded value is closest to `sample` and advance
+/// the decoder state with it, so encoder and decoder stay in step.
+fn encode_nibble(sample: i16, predictor: &mut i32, step_index: &mut i32) -> u8 {
+    let step = STEP_TABLE[*step_index as usize];
+    let mut diff = sample as i32 - *predictor;
+    let mut nibble = 0u8;
+    if diff < 0 {
+        nibble = 8;
+        diff = -diff;
+    }
+    let mut threshold = step;
+    for bit in [4, 2, 1] {
+        if diff >= threshold {
+            nibble |= bit;
+            diff -= threshold;
+        }
+        threshold >>= 1;
+    }
+    decode_nibble(nibble, predictor, step_index);
+    nibble
+}
+
+/// RIFF/WAVE with a `fmt ` chunk (plus `fmt_extra` bytes) and a `data` chunk.
+fn build_wav(
+    tag: u16,
+    channels: u16,
+    sample_rate: u32,
+    block_align: u16,
+    bits: u16,
+    fmt_extra: &[u8],
+    data: &[u8],
+) -> Vec<u8> {
+    let fmt_size = 16 + fmt_extra.len() as u32;
+    let samples_per_block = match fmt_extra {
+        [_, _, lo, hi, ..] => u16::from_le_bytes([*lo, *hi]) as u32,
+        _ => 1,
+    };
+    let byte_rate = sample_rate * block_align as u32 / samples_per_block.max(1);
+    let mut out = Vec::with_capacity(28 + fmt_size as usize + data.len());
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(20 + fmt_size + data.len() as u32).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&fmt_size.to_le_bytes());
+    out.extend_from_slice(&tag.to_le_bytes());
+    out.extend_from_slice(&channels.to_le_bytes());
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&byte_rate.to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&bits.to_le_bytes());
+    out.extend_from_slice(fmt_extra);
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    out
+}
+
 // ── Audio enhancement ───────────────────────────────────────
 
 #[derive(Debug, Clone, Default)]
@@ -252,7 +453,7 @@ fn pcm_samples_from_wav(wav: &[u8]) -> Option<(Vec<i16>, u16, u32)> {
     Some((samples, channels, sample_rate))
 }
 
-fn build_pcm_wav(samples: &[i16], channels: u16, sample_rate: u32) -> Vec<u8> {
+pub fn build_pcm_wav(samples: &[i16], channels: u16, sample_rate: u32) -> Vec<u8> {
     let pcm_data_size = (samples.len() * 2) as u32;
     let byte_rate = sample_rate * channels as u32 * 2;
     let block_align = channels * 2;
@@ -388,3 +589,66 @@ fn dsp_deess(samples: &mut [i16], sample_rate: u32, reduction_db: f32) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(len: usize, channels: usize) -> Vec<i16> {
+        (0..len * channels)
+            .map(|i| ((i / channels) as f32 * 0.07 + (i % channels) as f32).sin() * 12000.0)
+            .map(|s| s as i16)
+            .collect()
+    }
+
+    #[test]
+    fn ima_adpcm_round_trips_within_quantisation_error() {
+        for channels in [1u16, 2] {
+            let samples = tone(2000, channels as usize);
+            let wav = pcm_to_ima_adpcm(&samples, channels, 22050, 512 * channels);
+            let format = WavFormat::parse(&wav).unwrap();
+            assert_eq!(
+                (format.tag, format.channels, format.block_align),
+                (17, channels, 512 * channels)
+            );
+
+            let (decoded, ch, rate) = wav_to_pcm16(&wav).unwrap();
+            assert_eq!((ch, rate), (channels, 22050));
+            assert_eq!(decoded.len(), samples.len());
+            let max_err = decoded
+                .iter()
+                .zip(&samples)
+                .map(|(a, b)| (*a as i32 - *b as i32).abs())
+                .max();
+            assert!(max_err.unwrap() < 1500, "max error {max_err:?}");
+        }
+    }
+
+    #[test]
+    fn encode_wav_like_keeps_the_codec() {
+        let samples = tone(300, 1);
+        let pcm8 = WavFormat {
+            tag: 1,
+            channels: 1,
+            sample_rate: 11025,
+            bits_per_sample: 8,
+            block_align: 1,
+        };
+        let wav = encode_wav_like(&pcm8, &samples, 1, 11025);
+        assert_eq!(WavFormat::parse(&wav).unwrap(), pcm8);
+        let (decoded, _, _) = wav_to_pcm16(&wav).unwrap();
+        assert!(
+            decoded
+                .iter()
+                .zip(&samples)
+                .all(|(a, b)| (*a as i32 - *b as i32).abs() < 256)
+        );
+
+        let pcm16 = WavFormat {
+            bits_per_sample: 16,
+            ..pcm8
+        };
+        let wav = encode_wav_like(&pcm16, &samples, 1, 11025);
+        assert_eq!(wav_to_pcm16(&wav).unwrap().0, samples);
+    }
+}
@@ -8,10 +8,10 @@
 
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::Cursor;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// A single spell definition from `spells.txt`.
@@ -50,12 +50,12 @@ pub struct SpellsTable {
 }
 
 impl SpellsTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/spells.txt")?;
         Self::try_from(raw.as_slice())
     }
 
-    fn parse(text: &str) -> Result<Self, Box<dyn Error>> {
+    fn parse(text: &str) -> Result<Self, Error> {
         let body: String = text.lines().skip(3).collect::<Vec<_>>().join("\n");
         let mut rdr = ReaderBuilder::new()
             .delimiter(b'\t')
@@ -65,7 +65,7 @@ impl SpellsTable {
 
         let mut spells = Vec::new();
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("spells.txt", e))?;
 
             let index: u16 = match rec.get(0).unwrap_or("").trim().parse() {
                 Ok(v) => v,
@@ -113,7 +113,7 @@ impl SpellsTable {
 }
 
 impl TryFrom<&[u8]> for SpellsTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
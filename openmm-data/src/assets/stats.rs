@@ -4,9 +4,9 @@
 //! Columns: stat name, description text
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One stat description entry.
@@ -23,7 +23,7 @@ pub struct StatsTable {
 }
 
 impl StatsTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/stats.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -53,7 +53,7 @@ impl StatsTable {
 }
 
 impl TryFrom<&[u8]> for StatsTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
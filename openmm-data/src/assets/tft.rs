@@ -1,9 +1,9 @@
 use bitflags::bitflags;
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 use std::io::{Cursor, Read};
 
+use crate::Error;
 use crate::LodSerialise;
 use crate::{Assets, assets::lod_data::LodData};
 
@@ -30,12 +30,12 @@ pub struct TextureFrameTable {
 }
 
 impl TextureFrameTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/dtft.bin")?;
         Self::try_from(raw.as_slice())
     }
 
-    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
         let mut cursor = Cursor::new(data);
         let count = cursor.read_u32::<LittleEndian>()? as usize;
         let mut entries = Vec::with_capacity(count);
@@ -78,7 +78,7 @@ impl TextureFrameTable {
 }
 
 impl TryFrom<&[u8]> for TextureFrameTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = LodData::try_from(data)?;
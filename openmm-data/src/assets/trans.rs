@@ -4,9 +4,9 @@
 //! Columns: 2D#, Transition Description
 
 use serde::{Deserialize, Serialize};
-use std::error::Error;
 
 use crate::Assets;
+use crate::Error;
 use crate::LodSerialise;
 
 /// One transition description entry.
@@ -24,7 +24,7 @@ pub struct TransTable {
 }
 
 impl TransTable {
-    pub fn load(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn load(assets: &Assets) -> Result<Self, Error> {
         let raw = assets.get_bytes("icons/trans.txt")?;
         Self::try_from(raw.as_slice())
     }
@@ -54,7 +54,7 @@ impl TransTable {
 }
 
 impl TryFrom<&[u8]> for TransTable {
-    type Error = Box<dyn Error>;
+    type Error = Error;
 
     fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
         let data = match crate::assets::lod_data::LodData::try_from(data) {
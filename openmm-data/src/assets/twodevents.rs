@@ -5,12 +5,12 @@
 //! EVT SpeakInHouse instructions.
 
 use std::collections::HashMap;
-use std::error::Error;
 use std::io::{Cursor, Read};
 
 use csv::ReaderBuilder;
 
 use crate::Assets;
+use crate::Error;
 
 /// A building/house entry from 2devents.txt.
 ///
@@ -76,7 +76,7 @@ pub struct TwoDEvents {
 
 impl TwoDEvents {
     /// Parse 2devents.txt from the LOD archive.
-    pub fn parse(assets: &Assets) -> Result<Self, Box<dyn Error>> {
+    pub fn parse(assets: &Assets) -> Result<Self, Error> {
         let raw = assets
             .get_bytes("icons/2devents.txt")
             .or_else(|_| assets.get_bytes("new/2devents.txt"))?;
@@ -102,7 +102,7 @@ impl TwoDEvents {
 
         let mut houses = HashMap::new();
         for result in rdr.records() {
-            let rec = result?;
+            let rec = result.map_err(|e| Error::csv("2devents.txt", e))?;
 
             let id: u32 = match rec.get(0).unwrap_or("").trim().parse() {
                 Ok(v) => v,
@@ -0,0 +1,36 @@
+/// Show which override layer, patch LOD or game archive each asset comes from.
+///
+/// Usage:
+///   asset_layers                 — list every override layer and its files
+///   asset_layers <asset>...      — where each asset (e.g. `icons/dsounds.bin`) resolves
+///
+/// Layers come from OPENMM_OVERLAYS, game data from OPENMM_PATH_MM6.
+use openmm_data::Assets;
+
+fn main() {
+    let assets = Assets::new(openmm_data::get_data_path()).unwrap_or_else(|e| {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    });
+
+    let names: Vec<String> = std::env::args().skip(1).collect();
+    if names.is_empty() {
+        if assets.overlays().is_empty() {
+            println!("no override layers (set {})", openmm_data::ENV_OPENMM_OVERLAYS);
+        }
+        for (i, layer) in assets.overlays().iter().enumerate() {
+            println!("[{}] {}", i, layer.path().display());
+            for file in layer.files() {
+                println!("    {file}");
+            }
+        }
+        return;
+    }
+
+    for name in &names {
+        match assets.source_of(name) {
+            Some(source) => println!("{name:<24} {source}"),
+            None => println!("{name:<24} not found"),
+        }
+    }
+}
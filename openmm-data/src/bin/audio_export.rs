@@ -0,0 +1,158 @@
+/// Export every Audio.snd sound to 16-bit WAV or FLAC, and rebuild Audio.snd
+/// from a directory of replacements.
+///
+/// Usage:
+///   audio_export export <out_dir> [wav|flac] [gentle|voice]
+///   audio_export import <in_dir> <out.snd>
+///
+/// `export` writes `{name}.wav` (or `.flac`) plus a `{name}.json` sidecar with
+/// the dsounds.bin record (id, name, type, flags) and the original codec.
+/// `gentle`/`voice` apply the matching `AudioEnhance` preset. `import` walks
+/// the game's Audio.snd and, for every entry with a `{name}.wav` or
+/// `{name}.flac` in `in_dir`, re-encodes it to the entry's original codec
+/// (IMA-ADPCM, 8- or 16-bit PCM) and compression; other entries are copied
+/// as they are. The result is written to `out.snd`.
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use openmm_data::assets::flac;
+use openmm_data::assets::provider::archive::zlib;
+use openmm_data::assets::snd::{
+    AudioEnhance, SndExt, SndWriter, WavFormat, build_pcm_wav, encode_wav_like, wav_to_pcm16,
+};
+use openmm_data::{Archive, Assets};
+use serde_json::json;
+
+const USAGE: &str = "usage:
+  audio_export export <out_dir> [wav|flac] [gentle|voice]
+  audio_export import <in_dir> <out.snd>";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["export", out] => export(out, "wav", None),
+        ["export", out, codec] => export(out, codec, None),
+        ["export", out, codec, preset] => export(out, codec, Some(preset)),
+        ["import", dir, out] => import(dir, out),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn export(out: &str, codec: &str, preset: Option<&str>) -> Result<(), Box<dyn Error>> {
+    if codec != "wav" && codec != "flac" {
+        return Err(format!("unknown codec '{codec}' (expected wav or flac)").into());
+    }
+    let enhance = match preset {
+        None => AudioEnhance::default(),
+        Some("gentle") => AudioEnhance::gentle(),
+        Some("voice") => AudioEnhance::voice(),
+        Some(other) => return Err(format!("unknown preset '{other}' (expected gentle or voice)").into()),
+    };
+
+    let assets = Assets::new(openmm_data::get_data_path())?;
+    let snd = assets.get_snd("audio").ok_or("Audio.snd not found")?;
+    let dsounds = assets.dsounds();
+    fs::create_dir_all(out)?;
+
+    let (mut written, mut skipped) = (0, 0);
+    for entry in snd.list_files() {
+        let name = &entry.name;
+        let original = snd.get_file(name);
+        let decoded = snd.get_enhanced(name, &enhance).and_then(|wav| wav_to_pcm16(&wav));
+        let (Some(original), Some((samples, channels, rate))) = (original, decoded) else {
+            eprintln!("{name}: not a decodable WAV, skipped");
+            skipped += 1;
+            continue;
+        };
+
+        let bytes = match codec {
+            "flac" => flac::encode(&samples, channels, rate)?,
+            _ => build_pcm_wav(&samples, channels, rate),
+        };
+        fs::write(Path::new(out).join(format!("{name}.{codec}")), bytes)?;
+
+        let info = dsounds.and_then(|d| d.get_by_name(name));
+        let source = WavFormat::parse(&original);
+        let sidecar = json!({
+            "name": name,
+            "id": info.map(|i| i.sound_id),
+            "type": info.map(|i| i.sound_type),
+            "flags": info.map(|i| format!("0x{:04x}", i.attributes)),
+            "source": source.map(|f| json!({
+                "codec": if f.tag == 17 { "ima_adpcm" } else { "pcm" },
+                "sample_rate": f.sample_rate,
+                "channels": f.channels,
+                "bits_per_sample": f.bits_per_sample,
+                "block_align": f.block_align,
+            })),
+            "zlib": entry.decompressed_size > 0 && entry.decompressed_size != entry.size,
+        });
+        fs::write(
+            Path::new(out).join(format!("{name}.json")),
+            serde_json::to_string_pretty(&sidecar)?,
+        )?;
+        written += 1;
+    }
+    println!("{out}: exported {written} sounds as {codec}, skipped {skipped}");
+    Ok(())
+}
+
+fn import(dir: &str, out: &str) -> Result<(), Box<dyn Error>> {
+    let assets = Assets::new(openmm_data::get_data_path())?;
+    let snd = assets.get_snd("audio").ok_or("Audio.snd not found")?;
+    let replacements: Vec<PathBuf> = fs::read_dir(dir)?.filter_map(|e| e.ok().map(|e| e.path())).collect();
+
+    let mut writer = SndWriter::new();
+    let mut replaced = 0;
+    for entry in snd.list_files() {
+        let name = &entry.name;
+        let Some(path) = find_replacement(&replacements, name) else {
+            let raw = snd.get_file_raw(name).ok_or(format!("{name}: unreadable entry"))?;
+            writer.add(name, raw, entry.decompressed_size);
+            continue;
+        };
+
+        let bytes = fs::read(path)?;
+        let (samples, channels, rate) = if bytes.starts_with(b"fLaC") {
+            flac::decode(&bytes)?
+        } else {
+            wav_to_pcm16(&bytes).ok_or(format!("{}: unsupported WAV", path.display()))?
+        };
+        let wav = match snd.get_file(name).as_deref().and_then(WavFormat::parse) {
+            Some(format) => encode_wav_like(&format, &samples, channels, rate),
+            None => build_pcm_wav(&samples, channels, rate),
+        };
+
+        let compressed = entry.decompressed_size > 0
+            && entry.decompressed_size != entry.size
+            && snd.get_file_raw(name).is_some_and(|raw| raw.starts_with(&[0x78]));
+        let size = wav.len();
+        writer.add(name, if compressed { zlib::compress(&wav) } else { wav }, size);
+        replaced += 1;
+    }
+    writer.save(out)?;
+    println!("{out}: {} entries, {replaced} replaced", snd.list_files().len());
+    Ok(())
+}
+
+/// `{name}.wav` or `{name}.flac` in the replacement directory, any case.
+fn find_replacement<'a>(paths: &'a [PathBuf], name: &str) -> Option<&'a Path> {
+    paths
+        .iter()
+        .find(|p| {
+            let stem = p.file_stem().and_then(|s| s.to_str());
+            let ext = p.extension().and_then(|e| e.to_str());
+            stem.is_some_and(|s| s.eq_ignore_ascii_case(name))
+                && ext.is_some_and(|e| e.eq_ignore_ascii_case("wav") || e.eq_ignore_ascii_case("flac"))
+        })
+        .map(PathBuf::as_path)
+}
@@ -8,7 +8,7 @@ fn main() {
 
     println!("=== DDM actors after 1-indexed fix ===\n");
     let mut seen = std::collections::HashSet::new();
-    for (i, actor) in ddm.actors.iter().enumerate() {
+    for (i, actor) in ddm.delta.actors.iter().enumerate() {
         if !seen.insert(actor.monlist_id) && i > 5 {
             continue;
         }
@@ -0,0 +1,111 @@
+/// Assemble an edited `dump_evt` listing back into a map's .evt and .str.
+///
+/// Usage:
+///   evt_asm build <map> <in.asm> <out.lod>   — write <map>.evt/.str into a patch LOD
+///   evt_asm check                            — round-trip every script in the game data
+///
+/// `out.lod` is created if missing, otherwise the two entries are added or
+/// replaced. Entries are zlib-packed like the originals. List the LOD in
+/// `overlays` (openmm.toml) to play the patched scripts.
+use std::error::Error;
+use std::path::Path;
+
+use openmm_data::LodSerialise;
+use openmm_data::assets::evt::EvtFile;
+use openmm_data::assets::provider::{Archive, LodArchive, LodWriter, Version};
+use openmm_data::lod_data::{CompressionKind, LodData};
+
+const USAGE: &str = "usage:
+  evt_asm build <map> <in.asm> <out.lod>
+  evt_asm check";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["build", map, asm, out] => build(map, asm, out),
+        ["check"] => check(),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn build(map: &str, asm: &str, out: &str) -> Result<(), Box<dyn Error>> {
+    let map = map.trim_end_matches(".evt").to_lowercase();
+    let evt = EvtFile::assemble(&std::fs::read_to_string(asm)?).map_err(|e| format!("{asm}: {e}"))?;
+    let pack = |data: Vec<u8>| {
+        LodData {
+            kind: CompressionKind::Zlib8,
+            data,
+        }
+        .pack()
+    };
+    let entries = [
+        (format!("{map}.evt"), pack(evt.to_bytes())),
+        (format!("{map}.str"), pack(evt.str_bytes())),
+    ];
+
+    let writer = if Path::new(out).exists() {
+        let existing = LodArchive::open(out)?;
+        // Overrides match entry names exactly; reuse the stored spelling.
+        let entries: Vec<(String, Vec<u8>)> = entries
+            .into_iter()
+            .map(|(name, data)| {
+                let stored = existing
+                    .list_files()
+                    .iter()
+                    .find(|e| e.name.eq_ignore_ascii_case(&name))
+                    .map_or(name, |e| e.name.clone());
+                (stored, data)
+            })
+            .collect();
+        let entries: Vec<(&str, Vec<u8>)> = entries.iter().map(|(n, d)| (n.as_str(), d.clone())).collect();
+        LodWriter::from_archive(&existing, &entries)
+    } else {
+        let mut writer = LodWriter::new(Version::MM6);
+        for (name, data) in entries {
+            writer.add_file(&name, data);
+        }
+        writer
+    };
+    writer.save(out)?;
+    println!(
+        "{out}: {map}.evt ({} instructions), {map}.str ({} strings)",
+        evt.records.len(),
+        evt.strings.len()
+    );
+    Ok(())
+}
+
+fn check() -> Result<(), Box<dyn Error>> {
+    let assets = openmm_data::Assets::new(openmm_data::get_data_path())?;
+    let mut failed = 0;
+    let mut checked = 0;
+    for archive in assets.archives() {
+        for name in assets.files_in(&archive).unwrap_or_default() {
+            let Some(map) = name.to_lowercase().strip_suffix(".evt").map(str::to_string) else {
+                continue;
+            };
+            let original = EvtFile::parse(&assets, &map)?;
+            let same = EvtFile::assemble(&original.disassemble()).is_ok_and(|again| {
+                again.to_bytes() == original.to_bytes() && again.str_bytes() == original.str_bytes()
+            });
+            if !same {
+                println!("{map}: MISMATCH");
+                failed += 1;
+            }
+            checked += 1;
+        }
+    }
+    println!("{checked} scripts, {failed} mismatched");
+    if failed > 0 {
+        return Err(format!("{failed} scripts did not round-trip").into());
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+/// Convert PNGs into MM6 bitmap or sprite entries inside a patch LOD.
+///
+/// Usage:
+///   png_to_lod bitmap <in.png> <out.lod> [palette_id]   — texture/UI bitmap
+///   png_to_lod sprite <in.png> <out.lod> <palette_id>   — billboard/monster sprite
+///
+/// The entry is named after the PNG's file stem (lowercased). `out.lod` is
+/// created if missing, otherwise the entry is added or replaced. Without a
+/// palette id a bitmap gets its own median-cut palette; with one (or for
+/// sprites, always) pixels are mapped onto that `palNNN` palette from the
+/// game data found via OPENMM_PATH_MM6. Sprite pixels with alpha < 128 are
+/// transparent. List the result in `overlays` (openmm.toml) to use it.
+use std::error::Error;
+use std::path::Path;
+
+use openmm_data::Image;
+use openmm_data::assets::provider::{Archive, LodArchive, LodWriter, Version};
+
+const USAGE: &str = "usage:
+  png_to_lod bitmap <in.png> <out.lod> [palette_id]
+  png_to_lod sprite <in.png> <out.lod> <palette_id>";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["bitmap", png, out] => convert(png, out, None, false),
+        ["bitmap", png, out, pal] => parse_id(pal).and_then(|id| convert(png, out, Some(id), false)),
+        ["sprite", png, out, pal] => parse_id(pal).and_then(|id| convert(png, out, Some(id), true)),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn parse_id(s: &str) -> Result<u16, Box<dyn Error>> {
+    s.parse().map_err(|_| format!("invalid palette id '{s}'").into())
+}
+
+fn convert(png: &str, out: &str, palette_id: Option<u16>, sprite: bool) -> Result<(), Box<dyn Error>> {
+    let name = Path::new(png)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("PNG path has no file name")?
+        .to_lowercase();
+    let source = image::open(png)?;
+
+    let assets;
+    let palette = match palette_id {
+        Some(id) => {
+            assets = openmm_data::Assets::new(openmm_data::get_data_path())?;
+            Some(assets.palettes()?.get(id).ok_or(format!("palette {id} not found"))?)
+        }
+        None => None,
+    };
+    let image = Image::from_rgba(&source, palette, sprite)?;
+    let bytes = if sprite {
+        image.encode_sprite(&name, palette_id.unwrap_or_default())?
+    } else {
+        image.encode_bitmap(&name, palette_id.unwrap_or_default())?
+    };
+
+    let writer = if Path::new(out).exists() {
+        let existing = LodArchive::open(out)?;
+        // Overrides match entry names exactly; reuse the stored spelling.
+        let entry = existing
+            .list_files()
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(&name))
+            .map_or(name.clone(), |e| e.name.clone());
+        LodWriter::from_archive(&existing, &[(entry.as_str(), bytes)])
+    } else {
+        let mut writer = LodWriter::new(Version::MM6);
+        writer.add_file(&name, bytes);
+        writer
+    };
+    writer.save(out)?;
+    println!(
+        "{out}: {name} ({}x{} {})",
+        image.width,
+        image.height,
+        if sprite { "sprite" } else { "bitmap" }
+    );
+    Ok(())
+}
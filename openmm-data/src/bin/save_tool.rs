@@ -0,0 +1,165 @@
+/// Inspect and edit MM6 save files (.mm6 LOD archives).
+///
+/// Usage:
+///   save_tool list  <save.mm6>                          — files in the archive
+///   save_tool show  <save.mm6>                          — header, party and characters as JSON
+///   save_tool patch <save.mm6> <patch.json> [out.mm6]   — apply a JSON patch (in place by default)
+///
+/// See `openmm_data::save::patch` for the patch format. When a patch moves
+/// the party to a map the save has never visited, the map's pristine delta
+/// is copied in from games.lod (found via OPENMM_PATH_MM6) so the save
+/// still opens on that map.
+use std::error::Error;
+use std::path::Path;
+
+use openmm_data::LodSerialise;
+use openmm_data::lod_data::{CompressionKind, LodData};
+use openmm_data::map_delta::MapDeltaFile;
+use openmm_data::save::character::SaveCharacter;
+use openmm_data::save::patch::SavePatch;
+use openmm_data::save::{SaveFile, SaveHeader, SaveParty};
+use openmm_data::utils::MapName;
+use serde_json::{Value, json};
+
+const USAGE: &str = "usage:
+  save_tool list  <save.mm6>
+  save_tool show  <save.mm6>
+  save_tool patch <save.mm6> <patch.json> [out.mm6]";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["list", save] => list(save),
+        ["show", save] => show(save),
+        ["patch", save, patch_file] => patch(save, patch_file, save),
+        ["patch", save, patch_file, out] => patch(save, patch_file, out),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn list(path: &str) -> Result<(), Box<dyn Error>> {
+    let save = SaveFile::open(path)?;
+    for name in save.list_files() {
+        let size = save.get_file(&name).map_or(0, |d| d.len());
+        println!("{name:<16} {size:>8}");
+    }
+    Ok(())
+}
+
+fn show(path: &str) -> Result<(), Box<dyn Error>> {
+    let save = SaveFile::open(path)?;
+    let out = json!({
+        "header": header_json(&save.header()),
+        "current_map": save.detect_current_map(),
+        "party": party_json(&save.party()),
+    });
+    println!("{}", serde_json::to_string_pretty(&out)?);
+    Ok(())
+}
+
+fn patch(path: &str, patch_path: &str, out: &str) -> Result<(), Box<dyn Error>> {
+    let save = SaveFile::open(path)?;
+    let patch = SavePatch::from_json(&std::fs::read_to_string(patch_path)?)?;
+
+    let mut header = save.header();
+    let mut party = save.party();
+    patch.apply(&mut header, &mut party)?;
+
+    let mut extra = Vec::new();
+    let map = MapName::try_from(header.map_name.as_str())?;
+    if save.map_delta(&map).is_none() {
+        let assets = openmm_data::Assets::new(openmm_data::get_data_path())?;
+        let pristine = MapDeltaFile::load(&assets, &map)?;
+        let packed = LodData {
+            kind: CompressionKind::Zlib8,
+            data: pristine.to_bytes(),
+        }
+        .pack();
+        extra.push((map.delta_filename(), packed));
+    }
+    let extra: Vec<(&str, Vec<u8>)> = extra.iter().map(|(n, d)| (n.as_str(), d.clone())).collect();
+
+    save.write(out, &header, &party, &save.clock(), &extra, &[])?;
+
+    // Re-open to prove the result is a loadable save on the intended map.
+    let written = SaveFile::open(out)?;
+    written.party();
+    let detected = written.detect_current_map();
+    if detected.as_deref() != Some(header.map_stem()) {
+        return Err(format!("wrote {out} but it opens on {detected:?}, not {}", header.map_stem()).into());
+    }
+    println!("wrote {} ({})", Path::new(out).display(), header.map_name);
+    Ok(())
+}
+
+fn header_json(header: &SaveHeader) -> Value {
+    json!({
+        "save_name": header.save_name,
+        "map_name": header.map_name,
+        "playing_time": header.playing_time,
+    })
+}
+
+fn party_json(party: &SaveParty) -> Value {
+    json!({
+        "position": party.position,
+        "direction": party.direction,
+        "look_angle": party.look_angle,
+        "calendar": {
+            "year": party.year,
+            "month": party.month,
+            "week": party.week,
+            "day": party.day,
+            "hour": party.hour,
+            "minute": party.minute,
+            "second": party.second,
+        },
+        "gold": party.gold,
+        "bank_gold": party.bank_gold,
+        "food": party.food,
+        "reputation": party.reputation,
+        "deaths": party.deaths,
+        "quest_bits": party.quest_bits,
+        "autonote_bits": party.autonote_bits,
+        "characters": party.characters.iter().map(character_json).collect::<Vec<_>>(),
+    })
+}
+
+fn character_json(c: &SaveCharacter) -> Value {
+    let stat = |i: usize| json!({ "base": c.base_stats[i], "bonus": c.stat_bonuses[i] });
+    json!({
+        "name": c.name,
+        "face": c.face,
+        "sex": c.sex,
+        "class": c.class,
+        "level": c.level,
+        "experience": c.experience,
+        "skill_points": c.skill_points,
+        "hp": c.hp,
+        "sp": c.sp,
+        "birth_year": c.birth_year,
+        "stats": {
+            "might": stat(0),
+            "intellect": stat(1),
+            "personality": stat(2),
+            "endurance": stat(3),
+            "accuracy": stat(4),
+            "speed": stat(5),
+            "luck": stat(6),
+        },
+        "resistances": c.resistances,
+        "resistance_bonuses": c.resistance_bonuses,
+        "skills": c.skills,
+        "spells": (1..=c.spells.len()).filter(|&id| c.knows_spell(id)).collect::<Vec<_>>(),
+        "conditions": c.conditions,
+        "items": c.items.iter().filter(|i| !i.is_empty()).map(|i| i.number).collect::<Vec<_>>(),
+    })
+}
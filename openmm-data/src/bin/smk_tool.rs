@@ -0,0 +1,166 @@
+/// Export Smacker videos to PNG frames + WAV, and encode frames back into a VID archive.
+///
+/// Usage:
+///   smk_tool export <in.smk|archive.vid> [name] <out_dir>
+///   smk_tool import <frames_dir> <archive.vid> <name> [fps] [audio.wav]
+///
+/// `export` writes `{name}_0000.png`, `{name}_0001.png`, … and `{name}.wav`
+/// (when the video has audio) into `out_dir`. `import` encodes the directory's
+/// PNGs in file name order (all the same size, a multiple of 4) plus an
+/// optional 8/16-bit PCM WAV as a new SMK2 video, then adds it to the archive
+/// or replaces the entry of the same name; the archive is created if missing.
+/// The default frame rate is 15 fps.
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use openmm_data::assets::provider::archive::Archive;
+use openmm_data::assets::smk_encode::SmkEncoder;
+use openmm_data::assets::{SmkArchive, SmkDecoder, SmkWriter};
+
+const USAGE: &str = "usage:
+  smk_tool export <in.smk|archive.vid> [name] <out_dir>
+  smk_tool import <frames_dir> <archive.vid> <name> [fps] [audio.wav]";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["export", smk, out] => export(smk, None, out),
+        ["export", vid, name, out] => export(vid, Some(name), out),
+        ["import", frames, vid, name] => import(frames, vid, name, None, None),
+        ["import", frames, vid, name, fps] => import(frames, vid, name, Some(fps), None),
+        ["import", frames, vid, name, fps, wav] => import(frames, vid, name, Some(fps), Some(wav)),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn export(input: &str, name: Option<&str>, out: &str) -> Result<(), Box<dyn Error>> {
+    let (name, bytes) = match name {
+        Some(name) => {
+            let archive = SmkArchive::open(input)?;
+            let bytes = archive.get_file(name).ok_or(format!("'{name}' not found in {input}"))?;
+            (name.to_string(), bytes)
+        }
+        None => {
+            let stem = Path::new(input)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .ok_or("input path has no file name")?;
+            (stem.to_string(), fs::read(input)?)
+        }
+    };
+
+    fs::create_dir_all(out)?;
+    let mut decoder = SmkDecoder::new(bytes.clone())?;
+    let (w, h) = (decoder.width, decoder.height);
+    let mut count = 0;
+    while let Some(rgba) = decoder.next_frame() {
+        let path = Path::new(out).join(format!("{name}_{count:04}.png"));
+        image::RgbaImage::from_raw(w, h, rgba)
+            .ok_or("decoded frame has the wrong size")?
+            .save(&path)?;
+        count += 1;
+    }
+    let audio = SmkDecoder::extract_audio_wav(&bytes);
+    if let Some(wav) = &audio {
+        fs::write(Path::new(out).join(format!("{name}.wav")), wav)?;
+    }
+    println!(
+        "{out}: {name} {count} frames ({w}x{h}, {:.1} fps){}",
+        decoder.fps,
+        if audio.is_some() { " + audio" } else { "" }
+    );
+    Ok(())
+}
+
+fn import(frames: &str, vid: &str, name: &str, fps: Option<&str>, wav: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let fps: f32 = match fps {
+        Some(s) => s.parse().map_err(|_| format!("invalid frame rate '{s}'"))?,
+        None => 15.0,
+    };
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(frames)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("png")))
+        .collect();
+    paths.sort();
+    let first = paths.first().ok_or(format!("no PNG frames in {frames}"))?;
+    let (w, h) = image::image_dimensions(first)?;
+
+    let mut encoder = SmkEncoder::new(w, h, fps);
+    for path in &paths {
+        let frame = image::open(path)?.to_rgba8();
+        if frame.dimensions() != (w, h) {
+            return Err(format!("{}: expected {w}x{h}, got {:?}", path.display(), frame.dimensions()).into());
+        }
+        encoder.add_frame(frame.into_raw())?;
+    }
+    if let Some(wav) = wav {
+        let (rate, channels, bitdepth, pcm) = read_pcm_wav(&fs::read(wav)?)?;
+        encoder.set_audio(rate, channels, bitdepth, pcm)?;
+    }
+    let smk = encoder.encode()?;
+    let size = smk.len();
+
+    let mut writer = SmkWriter::new();
+    let mut replaced = false;
+    if Path::new(vid).exists() {
+        let existing = SmkArchive::open(vid)?;
+        for entry in existing.list_files() {
+            if entry.name.eq_ignore_ascii_case(name) {
+                writer.add(&entry.name, smk.clone());
+                replaced = true;
+            } else {
+                writer.add(&entry.name, existing.get_file(&entry.name).unwrap_or_default());
+            }
+        }
+    }
+    if !replaced {
+        writer.add(name, smk);
+    }
+    writer.save(vid)?;
+    println!(
+        "{vid}: {} {name} ({} frames, {w}x{h}, {fps} fps, {size} bytes)",
+        if replaced { "replaced" } else { "added" },
+        paths.len()
+    );
+    Ok(())
+}
+
+/// Read an uncompressed PCM WAV: (rate, channels, bits per sample, samples).
+fn read_pcm_wav(wav: &[u8]) -> Result<(u32, u8, u8, Vec<u8>), Box<dyn Error>> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return Err("not a RIFF/WAVE file".into());
+    }
+    let mut format = None;
+    let mut data = None;
+    let mut pos = 12;
+    while pos + 8 <= wav.len() {
+        let size = u32::from_le_bytes(wav[pos + 4..pos + 8].try_into()?) as usize;
+        let body = &wav[pos + 8..(pos + 8 + size).min(wav.len())];
+        match &wav[pos..pos + 4] {
+            b"fmt " if body.len() >= 16 => format = Some(body),
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        pos += 8 + size + size % 2;
+    }
+    let fmt = format.ok_or("WAV has no fmt chunk")?;
+    let data = data.ok_or("WAV has no data chunk")?;
+    let tag = u16::from_le_bytes([fmt[0], fmt[1]]);
+    if tag != 1 {
+        return Err(format!("WAV format {tag} is not PCM").into());
+    }
+    let channels = u16::from_le_bytes([fmt[2], fmt[3]]);
+    let rate = u32::from_le_bytes(fmt[4..8].try_into()?);
+    let bits = u16::from_le_bytes([fmt[14], fmt[15]]);
+    Ok((rate, channels.try_into()?, bits.try_into()?, data.to_vec()))
+}
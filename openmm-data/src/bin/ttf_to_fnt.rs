@@ -0,0 +1,71 @@
+/// Rasterize a TrueType/OpenType font into an MM6 .fnt entry inside a patch LOD.
+///
+/// Usage:
+///   ttf_to_fnt <font.ttf> <height> <name> <out.lod> [cp1252|cp1251]
+///
+/// Writes `<name>.fnt` (e.g. `arrus` to replace the dialogue font) covering
+/// bytes 32..=255 in the given code page (default cp1252, the game's own).
+/// `out.lod` is created if missing, otherwise the entry is added or
+/// replaced. List the LOD in `overlays` (openmm.toml) to use the font.
+use std::error::Error;
+use std::path::Path;
+
+use openmm_data::LodSerialise;
+use openmm_data::assets::font_raster::{Codepage, FontRaster};
+use openmm_data::assets::provider::{Archive, LodArchive, LodWriter, Version};
+use openmm_data::lod_data::{CompressionKind, LodData};
+
+const USAGE: &str = "usage:
+  ttf_to_fnt <font.ttf> <height> <name> <out.lod> [cp1252|cp1251]";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        [ttf, height, name, out] => convert(ttf, height, name, out, Codepage::Windows1252),
+        [ttf, height, name, out, "cp1252"] => convert(ttf, height, name, out, Codepage::Windows1252),
+        [ttf, height, name, out, "cp1251"] => convert(ttf, height, name, out, Codepage::Windows1251),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn convert(ttf: &str, height: &str, name: &str, out: &str, codepage: Codepage) -> Result<(), Box<dyn Error>> {
+    let height: u8 = height.parse().map_err(|_| format!("invalid height '{height}'"))?;
+    let data = std::fs::read(ttf)?;
+    let font = FontRaster::new(&data, height).codepage(codepage).build()?;
+    let glyphs = (font.first_char..=font.last_char)
+        .filter(|&c| font.has_glyph(c))
+        .count();
+
+    let name = format!("{}.fnt", name.trim_end_matches(".fnt").to_lowercase());
+    let bytes = LodData {
+        kind: CompressionKind::Zlib8,
+        data: font.to_bytes(),
+    }
+    .pack();
+
+    let writer = if Path::new(out).exists() {
+        let existing = LodArchive::open(out)?;
+        // Overrides match entry names exactly; reuse the stored spelling.
+        let entry = existing
+            .list_files()
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(&name))
+            .map_or(name.clone(), |e| e.name.clone());
+        LodWriter::from_archive(&existing, &[(entry.as_str(), bytes)])
+    } else {
+        let mut writer = LodWriter::new(Version::MM6);
+        writer.add_file(&name, bytes);
+        writer
+    };
+    writer.save(out)?;
+    println!("{out}: {name} (height {height}, {glyphs} glyphs)");
+    Ok(())
+}
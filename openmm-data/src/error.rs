@@ -0,0 +1,270 @@
+//! Error type shared by every openmm-data parser and loader.
+//!
+//! Variants carry enough context (archive, file, byte offset, CSV line) for
+//! a loading screen or a tool to say what went wrong and where. There is no
+//! conversion from a bare message: a bad value is [`Error::Invalid`] with
+//! its format, so the message always says which data it came from.
+
+use std::fmt;
+use std::io;
+
+/// Result alias used throughout the crate.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Everything that can go wrong reading or writing game data.
+#[derive(Debug)]
+pub enum Error {
+    /// An asset is missing from every archive (and overlay) searched.
+    NotFound {
+        archive: String,
+        name: String,
+    },
+    /// Data ended before a structure was complete.
+    Truncated {
+        format: &'static str,
+        offset: usize,
+        expected: usize,
+    },
+    /// A file did not start with the signature its format requires.
+    BadMagic {
+        format: &'static str,
+        found: Vec<u8>,
+    },
+    /// A compressed payload failed to inflate.
+    Decompress {
+        name: String,
+        source: io::Error,
+    },
+    /// A row in a tab/comma separated table could not be read.
+    CsvRow {
+        file: String,
+        line: u64,
+        message: String,
+    },
+    /// Well-framed data with a value the format does not allow.
+    Invalid {
+        format: &'static str,
+        message: String,
+    },
+    /// Any error annotated with the file it came from.
+    InFile {
+        file: String,
+        source: Box<Error>,
+    },
+    Io(io::Error),
+    /// A library error that only carries a message (JSON, image, number parsing).
+    Other(String),
+}
+
+impl Error {
+    /// Shorthand for [`Error::NotFound`].
+    pub fn not_found(archive: impl Into<String>, name: impl Into<String>) -> Self {
+        Self::NotFound {
+            archive: archive.into(),
+            name: name.into(),
+        }
+    }
+
+    /// Shorthand for [`Error::Invalid`].
+    pub fn invalid(format: &'static str, message: impl Into<String>) -> Self {
+        Self::Invalid {
+            format,
+            message: message.into(),
+        }
+    }
+
+    /// [`Error::CsvRow`] for a bad row in `file`.
+    pub fn csv(file: &str, e: csv::Error) -> Self {
+        match Self::from(e) {
+            Self::CsvRow { line, message, .. } => Self::CsvRow {
+                file: file.to_string(),
+                line,
+                message,
+            },
+            other => other.in_file(file),
+        }
+    }
+
+    /// Fail with [`Error::Truncated`] unless `data` holds `len` bytes at `offset`.
+    pub fn check_len(format: &'static str, data: &[u8], offset: usize, len: usize) -> Result<()> {
+        if data.len() < offset.saturating_add(len) {
+            return Err(Self::Truncated {
+                format,
+                offset,
+                expected: len,
+            });
+        }
+        Ok(())
+    }
+
+    /// Turn a short read at `offset` into [`Error::Truncated`]; other errors pass through.
+    pub fn at(self, format: &'static str, offset: usize) -> Self {
+        match self {
+            Self::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof => Self::Truncated {
+                format,
+                offset,
+                expected: 0,
+            },
+            other => other,
+        }
+    }
+
+    /// Annotate with the file being read. Already annotated errors keep
+    /// their innermost file.
+    pub fn in_file(self, file: impl Into<String>) -> Self {
+        match self {
+            e @ Self::InFile { .. } => e,
+            e => Self::InFile {
+                file: file.into(),
+                source: Box::new(e),
+            },
+        }
+    }
+
+    /// The underlying error, looking through [`Error::InFile`].
+    pub fn kind(&self) -> &Error {
+        match self {
+            Self::InFile { source, .. } => source.kind(),
+            e => e,
+        }
+    }
+
+    /// Whether this is (or wraps) [`Error::NotFound`].
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.kind(), Self::NotFound { .. })
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { archive, name } if archive.is_empty() => write!(f, "asset not found: {name}"),
+            Self::NotFound { archive, name } => write!(f, "asset not found: {archive}/{name}"),
+            Self::Truncated {
+                format,
+                offset,
+                expected: 0,
+            } => write!(f, "{format}: data truncated at offset {offset:#x}"),
+            Self::Truncated {
+                format,
+                offset,
+                expected,
+            } => write!(
+                f,
+                "{format}: data truncated, need {expected} bytes at offset {offset:#x}"
+            ),
+            Self::BadMagic { format, found } => write!(f, "{format}: bad magic {found:02x?}"),
+            Self::Decompress { name, source } => write!(f, "{name}: decompression failed: {source}"),
+            Self::CsvRow { file, line, message } => write!(f, "{file}:{line}: {message}"),
+            Self::Invalid { format, message } => write!(f, "{format}: {message}"),
+            Self::InFile { file, source } => write!(f, "{file}: {source}"),
+            Self::Io(e) => e.fmt(f),
+            Self::Other(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decompress { source, .. } | Self::Io(source) => Some(source),
+            Self::InFile { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Self {
+        match e.position() {
+            Some(pos) => Self::CsvRow {
+                file: String::new(),
+                line: pos.line(),
+                message: e.to_string(),
+            },
+            None => Self::Other(e.to_string()),
+        }
+    }
+}
+
+/// Conversions for library errors that only ever surface as messages.
+macro_rules! from_display {
+    ($($ty:ty),* $(,)?) => {$(
+        impl From<$ty> for Error {
+            fn from(e: $ty) -> Self {
+                Self::Other(e.to_string())
+            }
+        }
+    )*};
+}
+
+from_display!(
+    std::array::TryFromSliceError,
+    std::num::ParseIntError,
+    std::num::ParseFloatError,
+    std::str::Utf8Error,
+    std::string::FromUtf8Error,
+    serde_json::Error,
+    image::ImageError,
+    ab_glyph::InvalidFont,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_carry_context() {
+        assert_eq!(
+            Error::not_found("icons", "foo.fnt").to_string(),
+            "asset not found: icons/foo.fnt"
+        );
+        assert_eq!(
+            Error::check_len("odm", &[0; 4], 2, 8).unwrap_err().to_string(),
+            "odm: data truncated, need 8 bytes at offset 0x2"
+        );
+        let e = Error::from(io::Error::from(io::ErrorKind::UnexpectedEof)).at("blv", 0x88);
+        assert_eq!(
+            e.in_file("d01.blv").to_string(),
+            "d01.blv: blv: data truncated at offset 0x88"
+        );
+    }
+
+    #[test]
+    fn parsers_report_typed_errors() {
+        let e = crate::LodArchive::from_bytes(b"PK\x03\x04 not a lod".to_vec())
+            .err()
+            .unwrap();
+        assert!(matches!(e, Error::BadMagic { format: "lod", ref found } if found == b"PK\x03\x04"));
+
+        let e = crate::Odm::parse(&[0; 40]).err().unwrap();
+        assert!(matches!(e, Error::Truncated { format: "odm", .. }), "{e}");
+    }
+
+    #[test]
+    fn kind_looks_through_file_context() {
+        let e = Error::not_found("games", "oute3.odm")
+            .in_file("oute3.odm")
+            .in_file("ignored");
+        assert!(e.is_not_found());
+        assert!(matches!(e, Error::InFile { ref file, .. } if file == "oute3.odm"));
+        assert!(!Error::invalid("odm", "x").is_not_found());
+    }
+
+    #[test]
+    fn csv_errors_keep_the_line() {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader("a,b\nc\n".as_bytes());
+        let err = reader.records().find_map(|r| r.err()).unwrap();
+        let e = Error::csv("class.txt", err);
+        assert!(matches!(e, Error::CsvRow { line: 2, .. }));
+        assert!(e.to_string().starts_with("class.txt:2: "), "{e}");
+    }
+}
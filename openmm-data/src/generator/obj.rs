@@ -29,8 +29,8 @@
 //! assert!(model.header.faces_count > 0);
 //! ```
 
+use crate::Error;
 use crate::assets::bsp_model::{BSPModel, BSPModelFace, BSPModelHeader, BSPNode, BoundingBox, Plane};
-use std::error::Error;
 
 /// How to convert from the OBJ/exporter coordinate system to MM6 world coords.
 ///
@@ -60,7 +60,7 @@ impl CoordConv {
 /// * `texture` — texture name to assign to every face (max 9 chars for MM6)
 /// * `conv`    — coordinate remapping from exporter space to MM6 space
 /// * `origin`  — world-space position offset applied to every vertex
-pub fn import_obj(data: &[u8], texture: &str, conv: CoordConv, origin: [i32; 3]) -> Result<BSPModel, Box<dyn Error>> {
+pub fn import_obj(data: &[u8], texture: &str, conv: CoordConv, origin: [i32; 3]) -> Result<BSPModel, Error> {
     let text = std::str::from_utf8(data)?;
 
     let mut raw_verts: Vec<[f64; 3]> = Vec::new();
@@ -102,7 +102,7 @@ pub fn import_obj(data: &[u8], texture: &str, conv: CoordConv, origin: [i32; 3])
     }
 
     if raw_verts.is_empty() {
-        return Err("OBJ file contains no vertices".into());
+        return Err(Error::invalid("obj", "no vertices"));
     }
 
     // Convert vertices to MM6 world space (apply coord convention + origin)
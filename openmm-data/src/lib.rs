@@ -6,6 +6,9 @@ pub trait LodSerialise {
     fn to_bytes(&self) -> Vec<u8>;
 }
 
+pub mod error;
+pub use error::{Error, Result};
+
 pub mod assets;
 pub use assets::provider::Assets;
 pub use assets::*;
@@ -16,6 +19,16 @@ pub mod utils;
 pub use utils::find_path_case_insensitive;
 
 pub const ENV_OPENMM_PATH_MM6: &str = "OPENMM_PATH_MM6";
+/// Override layers searched before the game archives, as a platform path
+/// list (`:`-separated on Unix, `;` on Windows). See [`Assets::with_overlays`].
+pub const ENV_OPENMM_OVERLAYS: &str = "OPENMM_OVERLAYS";
+
+/// Override layers from `OPENMM_OVERLAYS`, in search order.
+pub fn get_overlay_paths() -> Vec<PathBuf> {
+    env::var_os(ENV_OPENMM_OVERLAYS)
+        .map(|v| env::split_paths(&v).filter(|p| !p.as_os_str().is_empty()).collect())
+        .unwrap_or_default()
+}
 
 fn has_mm6_lods(path: &Path) -> bool {
     ["games.lod", "Games.lod", "GAMES.LOD"]
@@ -3,11 +3,55 @@
 // Each character occupies 0x161C (5660) bytes inside `party.bin`.
 // Four characters start at offset 0x02C4 in party.bin.
 //
-// Stores a full copy of the raw bytes for round-trip fidelity --
-// fields we don't parse stay intact through parse -> to_bytes cycles.
+// Every byte of the record lives in a field -- regions whose meaning is not
+// known yet are kept as `_unknown*` byte arrays -- so parse -> to_bytes
+// reproduces the input exactly.
+//
+// Layout follows MMExtension's MM6 `Player`:
+//   0x0000: Face, Name[16], Sex, Class, pad, 0x0014: Stats 7 x (base, bonus)
+//   0x0030: ArmorClassBonus, LevelBase, LevelBonus, AgeBonus, 0x0038: ?[40]
+//   0x0060: Skills[31], 0x007F: Awards bits[64], 0x00BF: Spells[99], 0x0122: ?[34]
+//   0x0144: Items[138] (0x1C each), 0x105C: Inventory[126] (i32, 14 x 9 grid)
+//   0x1254: Resistances 5 x (base, bonus), 0x1268: SpellBuffs[17], 0x1378: ?[4]
+//   0x137C: RecoveryDelay, 0x137E: ?[146], 0x1410: SkillPoints, HP, SP,
+//   0x141C: BirthYear, 0x1420: Experience, 0x1428: EquippedItems[16],
+//   0x1468: Conditions[17] (i64 timestamps), 0x14F0: ?[300]
+//
+// MM7 records (0x1B3C bytes) start with the fields MM6 keeps at the end:
+//   0x0000: Conditions[20], 0x00A0: Experience, 0x00A8: Name[16], Sex, Class,
+//   Face, pad, 0x00BC: Stats .. AgeBonus, 0x00E0: ?[40], 0x0108: Skills[37]
+//   (u16 each), 0x0152: Awards bits[64], 0x0192: Spells[99]
+// Those are mapped onto the MM6 fields; the rest of the record is kept as is.
+
+use std::io::Cursor;
+
+use crate::Version;
+use crate::assets::ddm::SpellBuff;
+use crate::assets::map_delta::{ITEM_RECORD_SIZE, ItemRecord};
 
 /// Size of a single character record in bytes.
 pub const CHARACTER_SIZE: usize = 0x161C; // 5660
+/// Size of an MM7 character record in bytes.
+pub const CHARACTER_SIZE_MM7: usize = 0x1B3C; // 6972
+
+/// Number of item instances a character can carry (inventory + equipped).
+pub const ITEM_SLOTS: usize = 138;
+/// Inventory grid width in cells.
+pub const INVENTORY_WIDTH: usize = 14;
+/// Inventory grid height in cells.
+pub const INVENTORY_HEIGHT: usize = 9;
+/// Number of equipment body slots.
+pub const EQUIP_SLOTS: usize = 16;
+/// Number of spells in the spellbook (9 schools x 11).
+pub const SPELL_COUNT: usize = 99;
+/// Size of the awards bitfield in bytes (512 bits).
+pub const AWARDS_LEN: usize = 64;
+/// Number of condition timestamps. Order: Cursed, Weak, Asleep, Afraid,
+/// Drunk, Insane, Poison1, Disease1, Poison2, Disease2, Poison3, Disease3,
+/// Paralyzed, Unconscious, Dead, Stoned, Eradicated.
+pub const CONDITION_COUNT: usize = 17;
+/// Number of temporary spell buffs (Bless, Haste, Heroism, resistances, ...).
+pub const CHARACTER_BUFF_COUNT: usize = 17;
 
 // ── Binary layout offsets ──────────────────────────────────────────
 const FACE_OFFSET: usize = 0x0000;
@@ -15,47 +59,97 @@ const NAME_OFFSET: usize = 0x0001;
 const NAME_LEN: usize = 16; // null-terminated, max 15 chars + null
 const SEX_OFFSET: usize = 0x0011;
 const CLASS_OFFSET: usize = 0x0012;
+const PAD_0X13_OFFSET: usize = 0x0013;
 const STATS_OFFSET: usize = 0x0014; // 7 × (base i16, bonus i16) = 28 bytes
 const STAT_COUNT: usize = 7;
 const STAT_PAIR_SIZE: usize = 4; // base i16 + bonus i16
-const LEVEL_OFFSET: usize = 0x001C;
+const ARMOR_CLASS_BONUS_OFFSET: usize = 0x0030;
+const LEVEL_OFFSET: usize = 0x0032;
+const LEVEL_BONUS_OFFSET: usize = 0x0034;
+const AGE_BONUS_OFFSET: usize = 0x0036;
+const UNKNOWN_0X38_OFFSET: usize = 0x0038;
+const UNKNOWN_0X38_LEN: usize = 40;
 const SKILLS_OFFSET: usize = 0x0060;
 const SKILLS_LEN: usize = 31;
+const MM7_SKILLS_OFFSET: usize = 0x0108;
+const MM7_SKILLS_LEN: usize = 37;
+const AWARDS_OFFSET: usize = 0x007F;
+const SPELLS_OFFSET: usize = 0x00BF;
+const UNKNOWN_0X122_OFFSET: usize = 0x0122;
+const UNKNOWN_0X122_LEN: usize = 34;
+const ITEMS_OFFSET: usize = 0x0144;
+const INVENTORY_OFFSET: usize = 0x105C;
 const RESISTANCES_OFFSET: usize = 0x1254; // 5 × (base i16, bonus i16) = 20 bytes
 const RESISTANCE_COUNT: usize = 5;
+const SPELL_BUFFS_OFFSET: usize = 0x1268;
+const SPELL_BUFF_SIZE: usize = 16;
+const UNKNOWN_0X1378_OFFSET: usize = 0x1378;
+const UNKNOWN_0X1378_LEN: usize = 4;
 const RECOVERY_DELAY_OFFSET: usize = 0x137C;
+const UNKNOWN_0X137E_OFFSET: usize = 0x137E;
+const UNKNOWN_0X137E_LEN: usize = 146;
 const SKILL_POINTS_OFFSET: usize = 0x1410;
 const HP_OFFSET: usize = 0x1414;
 const SP_OFFSET: usize = 0x1418;
 const BIRTH_YEAR_OFFSET: usize = 0x141C;
 const EXPERIENCE_OFFSET: usize = 0x1420;
+const EQUIPPED_OFFSET: usize = 0x1428;
+const CONDITIONS_OFFSET: usize = 0x1468;
+const UNKNOWN_0X14F0_OFFSET: usize = 0x14F0;
+const UNKNOWN_0X14F0_LEN: usize = CHARACTER_SIZE - UNKNOWN_0X14F0_OFFSET; // 300
 
 /// Parsed MM6 character from a save file.
 #[derive(Debug, Clone)]
 pub struct SaveCharacter {
-    /// Raw bytes for round-trip serialization.
-    raw: Vec<u8>,
-
     pub face: u8,
     pub name: String,
+    /// Name field as stored; bytes after the terminator are kept so an
+    /// unchanged name round-trips exactly.
+    name_raw: [u8; NAME_LEN],
     /// 0 = male, 1 = female.
     pub sex: u8,
     /// Class index (e.g. 9 = Paladin, 12 = ?, 3 = ?, 6 = Knight).
     pub class: u8,
-    /// Base character level.
-    pub level: i16,
+    pub _pad0x13: u8,
     /// Base stats: Might, Intellect, Personality, Endurance, Accuracy, Speed, Luck.
     pub base_stats: [i16; STAT_COUNT],
     /// Bonus stats (same order as base_stats).
     pub stat_bonuses: [i16; STAT_COUNT],
-    /// Skill levels (31 skills).
-    pub skills: [u8; SKILLS_LEN],
+    /// Temporary armor class bonus.
+    pub armor_class_bonus: i16,
+    /// Base character level.
+    pub level: i16,
+    /// Temporary level bonus.
+    pub level_bonus: i16,
+    /// Magical aging, added to the calendar age.
+    pub age_bonus: i16,
+    pub _unknown0x38: [u8; UNKNOWN_0X38_LEN],
+    /// Skill words: 31 in MM6, 37 in MM7. The low 6 bits are the level;
+    /// bit 0x40 = expert, 0x80 = master, 0x100 = grandmaster (MM7).
+    pub skills: Vec<u16>,
+    /// Awards bitfield (LSB-first, indexed by awards.txt row).
+    pub awards: [u8; AWARDS_LEN],
+    /// Learned spell flags, one byte per spell (index = spell id - 1).
+    pub spells: [u8; SPELL_COUNT],
+    pub _unknown0x122: [u8; UNKNOWN_0X122_LEN],
+    /// Carried item instances. Inventory cells and equip slots refer to these.
+    pub items: [ItemRecord; ITEM_SLOTS],
+    /// Inventory grid, row-major 14 × 9. A cell holds `item index + 1` at an
+    /// item's top-left corner, `-(item index + 1)` on the other cells it
+    /// covers, and 0 when free.
+    pub inventory: [i32; INVENTORY_WIDTH * INVENTORY_HEIGHT],
     /// Base resistances: Fire, Elec, Cold, Poison, Magic.
     pub resistances: [i16; RESISTANCE_COUNT],
     /// Bonus resistances (same order).
     pub resistance_bonuses: [i16; RESISTANCE_COUNT],
+    /// Temporary spell effects on this character.
+    pub spell_buffs: [SpellBuff; CHARACTER_BUFF_COUNT],
+    pub _unknown0x1378: [u8; UNKNOWN_0X1378_LEN],
     /// Recovery delay in ticks.
     pub recovery_delay: i16,
+    pub _unknown0x137e: [u8; UNKNOWN_0X137E_LEN],
+    /// Unspent skill points.
+    pub skill_points: i32,
     /// Current hit points.
     pub hp: i32,
     /// Current spell points.
@@ -64,8 +158,15 @@ pub struct SaveCharacter {
     pub birth_year: i32,
     /// Total experience points.
     pub experience: i64,
-    /// Unspent skill points.
-    pub skill_points: i32,
+    /// `item index + 1` worn in each body slot, 0 = empty.
+    pub equipped: [i32; EQUIP_SLOTS],
+    /// Game time each condition was inflicted, 0 = not afflicted.
+    pub conditions: [i64; CONDITION_COUNT],
+    pub _unknown0x14f0: [u8; UNKNOWN_0X14F0_LEN],
+    /// Layout the record was read in, and is written back in.
+    version: Version,
+    /// The MM7 record this character was read from, empty for MM6.
+    mm7_raw: Vec<u8>,
 }
 
 /// Read stat or resistance pairs (base i16, bonus i16) from contiguous memory.
@@ -89,6 +190,37 @@ fn write_i16_pairs<const N: usize>(buf: &mut [u8], offset: usize, base: &[i16; N
     }
 }
 
+fn read_i16(data: &[u8], offset: usize) -> i16 {
+    i16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_i32(data: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_i64(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    data[offset..offset + N].try_into().unwrap()
+}
+
+fn read_i32_array<const N: usize>(data: &[u8], offset: usize) -> [i32; N] {
+    std::array::from_fn(|i| read_i32(data, offset + i * 4))
+}
+
+fn write_i32_array(buf: &mut [u8], offset: usize, values: &[i32]) {
+    for (i, v) in values.iter().enumerate() {
+        buf[offset + i * 4..offset + i * 4 + 4].copy_from_slice(&v.to_le_bytes());
+    }
+}
+
+fn decode_name(raw: &[u8; NAME_LEN]) -> String {
+    let end = raw.iter().position(|&b| b == 0).unwrap_or(NAME_LEN);
+    String::from_utf8_lossy(&raw[..end]).into_owned()
+}
+
 impl SaveCharacter {
     /// Parse a character from raw bytes. Panics if `data.len() < CHARACTER_SIZE`.
     pub fn parse(data: &[u8]) -> Self {
@@ -99,75 +231,156 @@ impl SaveCharacter {
             data.len()
         );
 
-        let raw = data[..CHARACTER_SIZE].to_vec();
-
-        let face = data[FACE_OFFSET];
-
         // Name: 16 bytes, null-terminated.
-        let name_bytes = &data[NAME_OFFSET..NAME_OFFSET + NAME_LEN];
-        let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(NAME_LEN);
-        let name = String::from_utf8_lossy(&name_bytes[..name_end]).into_owned();
-
-        let sex = data[SEX_OFFSET];
-        let class = data[CLASS_OFFSET];
-        let level = i16::from_le_bytes([data[LEVEL_OFFSET], data[LEVEL_OFFSET + 1]]);
+        let name_raw = read_bytes::<NAME_LEN>(data, NAME_OFFSET);
+        let name = decode_name(&name_raw);
 
         let (base_stats, stat_bonuses) = read_i16_pairs::<STAT_COUNT>(data, STATS_OFFSET);
-
-        let mut skills = [0u8; SKILLS_LEN];
-        skills.copy_from_slice(&data[SKILLS_OFFSET..SKILLS_OFFSET + SKILLS_LEN]);
-
         let (resistances, resistance_bonuses) = read_i16_pairs::<RESISTANCE_COUNT>(data, RESISTANCES_OFFSET);
 
-        let recovery_delay = i16::from_le_bytes([data[RECOVERY_DELAY_OFFSET], data[RECOVERY_DELAY_OFFSET + 1]]);
-
-        let hp = i32::from_le_bytes(data[HP_OFFSET..HP_OFFSET + 4].try_into().unwrap());
-        let sp = i32::from_le_bytes(data[SP_OFFSET..SP_OFFSET + 4].try_into().unwrap());
-        let birth_year = i32::from_le_bytes(data[BIRTH_YEAR_OFFSET..BIRTH_YEAR_OFFSET + 4].try_into().unwrap());
-        let experience = i64::from_le_bytes(data[EXPERIENCE_OFFSET..EXPERIENCE_OFFSET + 8].try_into().unwrap());
-        let skill_points = i32::from_le_bytes(data[SKILL_POINTS_OFFSET..SKILL_POINTS_OFFSET + 4].try_into().unwrap());
+        let mut cursor = Cursor::new(&data[ITEMS_OFFSET..INVENTORY_OFFSET]);
+        let items = std::array::from_fn(|_| ItemRecord::read(&mut cursor).expect("item slots fit the record"));
+
+        let spell_buffs = std::array::from_fn(|i| {
+            let off = SPELL_BUFFS_OFFSET + i * SPELL_BUFF_SIZE;
+            SpellBuff {
+                expire_time: read_i64(data, off),
+                power: read_i16(data, off + 8),
+                skill: read_i16(data, off + 10),
+                overlay_id: read_i16(data, off + 12),
+                caster: data[off + 14],
+                bits: data[off + 15],
+            }
+        });
 
         Self {
-            raw,
-            face,
+            face: data[FACE_OFFSET],
             name,
-            sex,
-            class,
-            level,
+            name_raw,
+            sex: data[SEX_OFFSET],
+            class: data[CLASS_OFFSET],
+            _pad0x13: data[PAD_0X13_OFFSET],
             base_stats,
             stat_bonuses,
-            skills,
+            armor_class_bonus: read_i16(data, ARMOR_CLASS_BONUS_OFFSET),
+            level: read_i16(data, LEVEL_OFFSET),
+            level_bonus: read_i16(data, LEVEL_BONUS_OFFSET),
+            age_bonus: read_i16(data, AGE_BONUS_OFFSET),
+            _unknown0x38: read_bytes(data, UNKNOWN_0X38_OFFSET),
+            skills: data[SKILLS_OFFSET..SKILLS_OFFSET + SKILLS_LEN]
+                .iter()
+                .map(|&b| b.into())
+                .collect(),
+            awards: read_bytes(data, AWARDS_OFFSET),
+            spells: read_bytes(data, SPELLS_OFFSET),
+            _unknown0x122: read_bytes(data, UNKNOWN_0X122_OFFSET),
+            items,
+            inventory: read_i32_array(data, INVENTORY_OFFSET),
             resistances,
             resistance_bonuses,
-            recovery_delay,
-            hp,
-            sp,
-            birth_year,
-            experience,
-            skill_points,
+            spell_buffs,
+            _unknown0x1378: read_bytes(data, UNKNOWN_0X1378_OFFSET),
+            recovery_delay: read_i16(data, RECOVERY_DELAY_OFFSET),
+            _unknown0x137e: read_bytes(data, UNKNOWN_0X137E_OFFSET),
+            skill_points: read_i32(data, SKILL_POINTS_OFFSET),
+            hp: read_i32(data, HP_OFFSET),
+            sp: read_i32(data, SP_OFFSET),
+            birth_year: read_i32(data, BIRTH_YEAR_OFFSET),
+            experience: read_i64(data, EXPERIENCE_OFFSET),
+            equipped: read_i32_array(data, EQUIPPED_OFFSET),
+            conditions: std::array::from_fn(|i| read_i64(data, CONDITIONS_OFFSET + i * 8)),
+            _unknown0x14f0: read_bytes(data, UNKNOWN_0X14F0_OFFSET),
+            version: Version::MM6,
+            mm7_raw: Vec::new(),
         }
     }
 
-    /// Serialize back to bytes, patching parsed fields into the raw copy.
-    /// Fields we don't parse stay exactly as they were in the original data.
+    /// Parse a character record laid out for `version`. Panics if `data` is
+    /// shorter than the record.
+    ///
+    /// Only the head of an MM7 record is decoded (conditions, experience,
+    /// name, sex, class, face, stats, level, skills, awards and spells). The
+    /// other fields read as zero and their stored bytes are written back
+    /// unchanged by [`to_bytes`](Self::to_bytes).
+    pub fn parse_version(data: &[u8], version: Version) -> Self {
+        if version < Version::MM7 {
+            return Self::parse(data);
+        }
+        assert!(
+            data.len() >= CHARACTER_SIZE_MM7,
+            "SaveCharacter::parse_version: need {} bytes, got {}",
+            CHARACTER_SIZE_MM7,
+            data.len()
+        );
+        let mut c = Self::parse(&mm7_character_as_mm6(data));
+        c.skills = (0..MM7_SKILLS_LEN)
+            .map(|i| u16::from_le_bytes([data[MM7_SKILLS_OFFSET + i * 2], data[MM7_SKILLS_OFFSET + i * 2 + 1]]))
+            .collect();
+        c.version = version;
+        c.mm7_raw = data[..CHARACTER_SIZE_MM7].to_vec();
+        c
+    }
+
+    /// Serialize back to a record in the layout it was read in.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut buf = self.raw.clone();
+        let mm6 = self.to_bytes_mm6();
+        if self.version < Version::MM7 {
+            return mm6;
+        }
+        let mut out = if self.mm7_raw.len() == CHARACTER_SIZE_MM7 {
+            self.mm7_raw.clone()
+        } else {
+            vec![0u8; CHARACTER_SIZE_MM7]
+        };
+        mm6_character_into_mm7(&mm6, &mut out);
+        for (i, &skill) in self.skills.iter().take(MM7_SKILLS_LEN).enumerate() {
+            let off = MM7_SKILLS_OFFSET + i * 2;
+            out[off..off + 2].copy_from_slice(&skill.to_le_bytes());
+        }
+        out
+    }
+
+    /// Serialize to the 0x161C-byte MM6 record.
+    fn to_bytes_mm6(&self) -> Vec<u8> {
+        let mut buf = vec![0u8; CHARACTER_SIZE];
 
         buf[FACE_OFFSET] = self.face;
 
-        // Name: write up to 15 chars, pad with zeroes + null terminator.
-        let name_bytes = self.name.as_bytes();
-        let copy_len = name_bytes.len().min(NAME_LEN - 1);
-        buf[NAME_OFFSET..NAME_OFFSET + NAME_LEN].fill(0);
-        buf[NAME_OFFSET..NAME_OFFSET + copy_len].copy_from_slice(&name_bytes[..copy_len]);
+        // Name: keep the stored bytes while the name is unchanged, otherwise
+        // write up to 15 chars, pad with zeroes + null terminator.
+        if decode_name(&self.name_raw) == self.name {
+            buf[NAME_OFFSET..NAME_OFFSET + NAME_LEN].copy_from_slice(&self.name_raw);
+        } else {
+            let name_bytes = self.name.as_bytes();
+            let copy_len = name_bytes.len().min(NAME_LEN - 1);
+            buf[NAME_OFFSET..NAME_OFFSET + copy_len].copy_from_slice(&name_bytes[..copy_len]);
+        }
 
         buf[SEX_OFFSET] = self.sex;
         buf[CLASS_OFFSET] = self.class;
-        buf[LEVEL_OFFSET..LEVEL_OFFSET + 2].copy_from_slice(&self.level.to_le_bytes());
+        buf[PAD_0X13_OFFSET] = self._pad0x13;
 
         write_i16_pairs(&mut buf, STATS_OFFSET, &self.base_stats, &self.stat_bonuses);
+        buf[ARMOR_CLASS_BONUS_OFFSET..ARMOR_CLASS_BONUS_OFFSET + 2]
+            .copy_from_slice(&self.armor_class_bonus.to_le_bytes());
+        buf[LEVEL_OFFSET..LEVEL_OFFSET + 2].copy_from_slice(&self.level.to_le_bytes());
+        buf[LEVEL_BONUS_OFFSET..LEVEL_BONUS_OFFSET + 2].copy_from_slice(&self.level_bonus.to_le_bytes());
+        buf[AGE_BONUS_OFFSET..AGE_BONUS_OFFSET + 2].copy_from_slice(&self.age_bonus.to_le_bytes());
+        buf[UNKNOWN_0X38_OFFSET..UNKNOWN_0X38_OFFSET + UNKNOWN_0X38_LEN].copy_from_slice(&self._unknown0x38);
 
-        buf[SKILLS_OFFSET..SKILLS_OFFSET + SKILLS_LEN].copy_from_slice(&self.skills);
+        for (i, &skill) in self.skills.iter().take(SKILLS_LEN).enumerate() {
+            buf[SKILLS_OFFSET + i] = skill as u8;
+        }
+        buf[AWARDS_OFFSET..AWARDS_OFFSET + AWARDS_LEN].copy_from_slice(&self.awards);
+        buf[SPELLS_OFFSET..SPELLS_OFFSET + SPELL_COUNT].copy_from_slice(&self.spells);
+        buf[UNKNOWN_0X122_OFFSET..UNKNOWN_0X122_OFFSET + UNKNOWN_0X122_LEN].copy_from_slice(&self._unknown0x122);
+
+        let mut items = Vec::with_capacity(ITEM_SLOTS * ITEM_RECORD_SIZE);
+        for item in &self.items {
+            item.write(&mut items);
+        }
+        buf[ITEMS_OFFSET..INVENTORY_OFFSET].copy_from_slice(&items);
+        write_i32_array(&mut buf, INVENTORY_OFFSET, &self.inventory);
 
         write_i16_pairs(
             &mut buf,
@@ -176,15 +389,102 @@ impl SaveCharacter {
             &self.resistance_bonuses,
         );
 
+        for (i, buff) in self.spell_buffs.iter().enumerate() {
+            let off = SPELL_BUFFS_OFFSET + i * SPELL_BUFF_SIZE;
+            buf[off..off + 8].copy_from_slice(&buff.expire_time.to_le_bytes());
+            buf[off + 8..off + 10].copy_from_slice(&buff.power.to_le_bytes());
+            buf[off + 10..off + 12].copy_from_slice(&buff.skill.to_le_bytes());
+            buf[off + 12..off + 14].copy_from_slice(&buff.overlay_id.to_le_bytes());
+            buf[off + 14] = buff.caster;
+            buf[off + 15] = buff.bits;
+        }
+        buf[UNKNOWN_0X1378_OFFSET..UNKNOWN_0X1378_OFFSET + UNKNOWN_0X1378_LEN].copy_from_slice(&self._unknown0x1378);
+
         buf[RECOVERY_DELAY_OFFSET..RECOVERY_DELAY_OFFSET + 2].copy_from_slice(&self.recovery_delay.to_le_bytes());
+        buf[UNKNOWN_0X137E_OFFSET..UNKNOWN_0X137E_OFFSET + UNKNOWN_0X137E_LEN].copy_from_slice(&self._unknown0x137e);
         buf[SKILL_POINTS_OFFSET..SKILL_POINTS_OFFSET + 4].copy_from_slice(&self.skill_points.to_le_bytes());
         buf[HP_OFFSET..HP_OFFSET + 4].copy_from_slice(&self.hp.to_le_bytes());
         buf[SP_OFFSET..SP_OFFSET + 4].copy_from_slice(&self.sp.to_le_bytes());
         buf[BIRTH_YEAR_OFFSET..BIRTH_YEAR_OFFSET + 4].copy_from_slice(&self.birth_year.to_le_bytes());
         buf[EXPERIENCE_OFFSET..EXPERIENCE_OFFSET + 8].copy_from_slice(&self.experience.to_le_bytes());
+        write_i32_array(&mut buf, EQUIPPED_OFFSET, &self.equipped);
+        for (i, t) in self.conditions.iter().enumerate() {
+            let off = CONDITIONS_OFFSET + i * 8;
+            buf[off..off + 8].copy_from_slice(&t.to_le_bytes());
+        }
+        buf[UNKNOWN_0X14F0_OFFSET..].copy_from_slice(&self._unknown0x14f0);
 
         buf
     }
+
+    /// Age in years at `current_year`, including magical aging.
+    pub fn age(&self, current_year: i32) -> i32 {
+        current_year - self.birth_year + self.age_bonus as i32
+    }
+
+    /// Whether spell `spell_id` (1-based, spells.txt row) is in the spellbook.
+    pub fn knows_spell(&self, spell_id: usize) -> bool {
+        spell_id
+            .checked_sub(1)
+            .and_then(|i| self.spells.get(i))
+            .is_some_and(|&b| b != 0)
+    }
+
+    /// Whether award `index` (awards.txt row) has been earned.
+    pub fn has_award(&self, index: usize) -> bool {
+        self.awards.get(index / 8).is_some_and(|b| b & (1 << (index % 8)) != 0)
+    }
+
+    /// Item whose top-left corner is in inventory cell (`x`, `y`).
+    pub fn inventory_item(&self, x: usize, y: usize) -> Option<&ItemRecord> {
+        if x >= INVENTORY_WIDTH || y >= INVENTORY_HEIGHT {
+            return None;
+        }
+        self.item_ref(self.inventory[y * INVENTORY_WIDTH + x])
+    }
+
+    /// Item worn in body slot `slot`.
+    pub fn equipped_item(&self, slot: usize) -> Option<&ItemRecord> {
+        self.item_ref(*self.equipped.get(slot)?)
+    }
+
+    /// Resolve an `item index + 1` reference into `items`.
+    fn item_ref(&self, reference: i32) -> Option<&ItemRecord> {
+        let index = usize::try_from(reference).ok()?.checked_sub(1)?;
+        self.items.get(index).filter(|item| !item.is_empty())
+    }
+}
+
+/// MM6 and MM7 offsets of the fields both records have, as (MM6, MM7, len).
+/// Skills are left out: MM7 stores more of them, as u16 words.
+const MM7_SHARED_FIELDS: [(usize, usize, usize); 9] = [
+    (FACE_OFFSET, 0x00BA, 1),
+    (NAME_OFFSET, 0x00A8, NAME_LEN),
+    (SEX_OFFSET, 0x00B8, 1),
+    (CLASS_OFFSET, 0x00B9, 1),
+    (PAD_0X13_OFFSET, 0x00BB, 1),
+    // Stats, ArmorClassBonus, LevelBase, LevelBonus, AgeBonus and ?[40].
+    (STATS_OFFSET, 0x00BC, SKILLS_OFFSET - STATS_OFFSET),
+    (AWARDS_OFFSET, 0x0152, AWARDS_LEN + SPELL_COUNT),
+    (EXPERIENCE_OFFSET, 0x00A0, 8),
+    (CONDITIONS_OFFSET, 0x0000, CONDITION_COUNT * 8),
+];
+
+/// Rearrange the fields of an MM7 character record into an MM6 one.
+fn mm7_character_as_mm6(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0u8; CHARACTER_SIZE];
+    for (mm6, mm7, len) in MM7_SHARED_FIELDS {
+        out[mm6..mm6 + len].copy_from_slice(&data[mm7..mm7 + len]);
+    }
+    out
+}
+
+/// Write the shared fields of an MM6 record back into an MM7 one: the
+/// inverse of [`mm7_character_as_mm6`].
+fn mm6_character_into_mm7(mm6: &[u8], out: &mut [u8]) {
+    for (from, to, len) in MM7_SHARED_FIELDS {
+        out[to..to + len].copy_from_slice(&mm6[from..from + len]);
+    }
 }
 
 #[cfg(test)]
@@ -206,6 +506,19 @@ mod tests {
         &party[start..start + CHARACTER_SIZE]
     }
 
+    /// Deterministic pseudo-random record, so every byte is non-trivial.
+    fn noise_record() -> Vec<u8> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        (0..CHARACTER_SIZE)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
     #[test]
     fn parse_roderick() {
         let party = load_party_bin();
@@ -258,4 +571,90 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn round_trip_every_byte() {
+        let original = noise_record();
+        let parsed = SaveCharacter::parse(&original);
+        assert_eq!(parsed.to_bytes(), original);
+    }
+
+    #[test]
+    fn typed_fields_at_layout_offsets() {
+        let mut data = vec![0u8; CHARACTER_SIZE];
+        data[NAME_OFFSET..NAME_OFFSET + 4].copy_from_slice(b"Zed\0");
+        data[LEVEL_OFFSET..LEVEL_OFFSET + 2].copy_from_slice(&12i16.to_le_bytes());
+        data[AGE_BONUS_OFFSET..AGE_BONUS_OFFSET + 2].copy_from_slice(&3i16.to_le_bytes());
+        data[BIRTH_YEAR_OFFSET..BIRTH_YEAR_OFFSET + 4].copy_from_slice(&1147i32.to_le_bytes());
+        data[AWARDS_OFFSET + 1] = 0b0000_0100; // award 10
+        data[SPELLS_OFFSET + 4] = 1; // spell 5
+        // Item 2 (index 1): a broken, identified item with a wand charge.
+        let item = ITEMS_OFFSET + ITEM_RECORD_SIZE;
+        data[item..item + 4].copy_from_slice(&101i32.to_le_bytes());
+        data[item + 0x10..item + 0x14].copy_from_slice(&7i32.to_le_bytes());
+        data[item + 0x14] = 0x03;
+        // It sits at inventory cell (2, 1) and is also worn in slot 5.
+        let cell = INVENTORY_OFFSET + (INVENTORY_WIDTH + 2) * 4;
+        data[cell..cell + 4].copy_from_slice(&2i32.to_le_bytes());
+        data[EQUIPPED_OFFSET + 5 * 4..EQUIPPED_OFFSET + 6 * 4].copy_from_slice(&2i32.to_le_bytes());
+        // Condition 14 (Dead) since tick 5000; buff 7 (Haste) power 4.
+        data[CONDITIONS_OFFSET + 14 * 8..CONDITIONS_OFFSET + 15 * 8].copy_from_slice(&5000i64.to_le_bytes());
+        let buff = SPELL_BUFFS_OFFSET + 7 * SPELL_BUFF_SIZE;
+        data[buff + 8..buff + 10].copy_from_slice(&4i16.to_le_bytes());
+
+        let c = SaveCharacter::parse(&data);
+        assert_eq!(c.name, "Zed");
+        assert_eq!(c.level, 12);
+        assert_eq!(c.age(1165), 21);
+        assert!(c.has_award(10) && !c.has_award(9));
+        assert!(c.knows_spell(5) && !c.knows_spell(4) && !c.knows_spell(0));
+        let carried = c.inventory_item(2, 1).expect("item in cell");
+        assert_eq!(carried.number, 101);
+        assert_eq!(carried.charges, 7);
+        assert!(carried.is_identified() && carried.is_broken());
+        assert_eq!(c.equipped_item(5), Some(carried));
+        assert!(c.equipped_item(0).is_none());
+        assert_eq!(c.conditions[14], 5000);
+        assert_eq!(c.spell_buffs[7].power, 4);
+        assert_eq!(c.to_bytes(), data);
+    }
+
+    #[test]
+    fn mm7_record_maps_head_and_round_trips() {
+        let mut data: Vec<u8> = (0..CHARACTER_SIZE_MM7).map(|i| (i * 13 + 1) as u8).collect();
+        data[0xA0..0xA8].copy_from_slice(&123_456i64.to_le_bytes()); // experience
+        data[0xA8..0xB8].copy_from_slice(b"Zed\0\0\0\0\0\0\0\0\0\0\0\0\0");
+        data[0xB9] = 4; // class
+        data[0xDA..0xDC].copy_from_slice(&9i16.to_le_bytes()); // level
+        data[14 * 8..15 * 8].copy_from_slice(&5000i64.to_le_bytes()); // Dead
+        let skill33 = MM7_SKILLS_OFFSET + 33 * 2;
+        data[skill33..skill33 + 2].copy_from_slice(&0x0105u16.to_le_bytes()); // grandmaster 5
+
+        let mut c = SaveCharacter::parse_version(&data, Version::MM7);
+        assert_eq!(c.name, "Zed");
+        assert_eq!(c.class, 4);
+        assert_eq!(c.level, 9);
+        assert_eq!(c.experience, 123_456);
+        assert_eq!(c.conditions[14], 5000);
+        assert_eq!(c.skills.len(), MM7_SKILLS_LEN);
+        assert_eq!(c.skills[33], 0x0105);
+        assert_eq!(c.to_bytes(), data);
+
+        c.level = 10;
+        c.skills[36] = 0x41;
+        let bytes = c.to_bytes();
+        let skill36 = MM7_SKILLS_OFFSET + 36 * 2;
+        assert_eq!(&bytes[0xDA..0xDC], &10i16.to_le_bytes());
+        assert_eq!(&bytes[skill36..skill36 + 2], &0x41u16.to_le_bytes());
+        assert_eq!(bytes[0x1000..], data[0x1000..], "undecoded bytes are kept");
+    }
+
+    #[test]
+    fn renamed_character_writes_clean_name() {
+        let mut c = SaveCharacter::parse(&noise_record());
+        c.name = "Roderick".to_string();
+        let bytes = c.to_bytes();
+        assert_eq!(&bytes[NAME_OFFSET..NAME_OFFSET + NAME_LEN], b"Roderick\0\0\0\0\0\0\0\0");
+        assert_eq!(SaveCharacter::parse(&bytes).name, "Roderick");
+    }
 }
@@ -1,24 +1,33 @@
 //! High-level wrapper around an MM6 save LOD archive.
 //!
-//! Provides typed accessors for save chunks (header, party, clock)
-//! and a `write_patched` helper for modifying saves.
+//! Provides typed accessors for save chunks (header, party, clock, NPCs,
+//! overlays), a `write` helper for storing updated chunks, and
+//! `write_patched` for raw entry replacement.
 
-use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::Error;
 use crate::assets::provider::archive::Archive;
-use crate::assets::provider::archive::lod::{LodArchive, LodWriter};
+use crate::assets::provider::archive::lod::{LodArchive, LodWriter, Version};
 use image::DynamicImage;
 
 use crate::Assets;
 use crate::assets::GameData;
+use crate::assets::map_delta::MapDeltaFile;
 use crate::assets::provider::actors::{Actors, MapStateSnapshot};
+use crate::utils::MapName;
 
 use super::clock::SaveClock;
 use super::header::SaveHeader;
+use super::npcdata::SaveNpcs;
+use super::overlay::SaveOverlays;
 use super::party::SaveParty;
 
+/// Save screenshot (`image.pcx`) dimensions, matching the load screen preview.
+pub const SCREENSHOT_WIDTH: u32 = 150;
+pub const SCREENSHOT_HEIGHT: u32 = 112;
+
 /// An opened MM6 save file (.mm6 LOD archive).
 pub struct SaveFile {
     lod: LodArchive,
@@ -28,14 +37,25 @@ pub struct SaveFile {
 }
 
 impl SaveFile {
-    /// Open a .mm6 save file from disk.
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+    /// Open a .mm6 (or MM7 .mm7) save file from disk.
+    ///
+    /// MM8 saves are rejected: their party.bin and map deltas use layouts
+    /// these parsers don't read yet.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let path = path.as_ref().to_path_buf();
         let slot = path.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
         let lod = LodArchive::open(&path)?;
+        if lod.version > Version::MM7 {
+            return Err(Error::invalid("save", format!("{:?} saves are not supported yet", lod.version)).in_file(slot));
+        }
         Ok(Self { lod, slot, path })
     }
 
+    /// Game the save was written by.
+    pub fn version(&self) -> Version {
+        self.lod.version
+    }
+
     /// Parse the save header from `header.bin`.
     pub fn header(&self) -> SaveHeader {
         let data = self
@@ -51,7 +71,7 @@ impl SaveFile {
             .lod
             .get_file("party.bin")
             .unwrap_or_else(|| panic!("save '{}' missing party.bin", self.path.display()));
-        SaveParty::parse(&data)
+        SaveParty::parse_version(&data, self.lod.version)
     }
 
     /// Parse the clock data from `clock.bin`.
@@ -63,12 +83,31 @@ impl SaveFile {
             .unwrap_or_else(|| SaveClock::parse(&[]))
     }
 
+    /// Parse the NPC roster from `npcdata.bin`, or `None` if absent.
+    pub fn npcs(&self) -> Option<SaveNpcs> {
+        self.get_file("npcdata.bin").as_deref().map(SaveNpcs::parse)
+    }
+
+    /// Parse the active spell overlays from `overlay.bin`, or `None` if absent.
+    pub fn overlays(&self) -> Option<SaveOverlays> {
+        self.get_file("overlay.bin").as_deref().map(SaveOverlays::parse)
+    }
+
     /// Decode the save screenshot (`image.pcx`), or `None` if absent/corrupt.
     pub fn screenshot(&self) -> Option<DynamicImage> {
         let data = self.lod.get_file("image.pcx")?;
         crate::assets::pcx::decode(&data)
     }
 
+    /// Scale `img` to the save screenshot size and encode it as `image.pcx`
+    /// data, ready to pass to [`write`](Self::write) as an extra entry.
+    pub fn encode_screenshot(img: &DynamicImage) -> Vec<u8> {
+        // `thumbnail_exact` box-filters in integer math: fast enough to run
+        // on a full-resolution frame capture.
+        let thumb = img.thumbnail_exact(SCREENSHOT_WIDTH, SCREENSHOT_HEIGHT);
+        crate::assets::pcx::encode(&thumb.to_rgba8())
+    }
+
     /// Get file data by exact name, decompressing if needed.
     pub fn get_file(&self, name: &str) -> Option<Vec<u8>> {
         self.lod.get_file(name).map(Self::try_decompress)
@@ -91,6 +130,12 @@ impl SaveFile {
         self.lod.list_files().iter().map(|e| e.name.clone()).collect()
     }
 
+    /// Decompressed DDM/DLV delta for `map`, or `None` if the map was never
+    /// saved. Lookup is case-insensitive.
+    pub fn map_delta(&self, map: &MapName) -> Option<Vec<u8>> {
+        self.get_file_ci(&map.delta_filename())
+    }
+
     /// Load resolved actors for a map from the save's DDM/DLV data.
     /// Handles DDM vs DLV lookup (case-insensitive), decompression, and actor resolution.
     /// Returns `None` if no DDM/DLV exists for this map in the save.
@@ -101,33 +146,25 @@ impl SaveFile {
         state: Option<&MapStateSnapshot>,
         game_data: &GameData,
     ) -> Option<Actors> {
-        // Strip .odm/.blv/.ddm/.dlv extension if present — callers may pass full filenames.
+        // Strip .ddm/.dlv extension if present — callers may pass full filenames.
         let stem = map_name
-            .strip_suffix(".odm")
-            .or_else(|| map_name.strip_suffix(".blv"))
-            .or_else(|| map_name.strip_suffix(".ddm"))
+            .strip_suffix(".ddm")
             .or_else(|| map_name.strip_suffix(".dlv"))
             .unwrap_or(map_name);
-        let ddm_name = format!("{}.ddm", stem);
-        let dlv_name = format!("{}.dlv", stem);
-        let (data, is_dlv) = if let Some(d) = self.get_file_ci(&ddm_name) {
-            (d, false)
-        } else if let Some(d) = self.get_file_ci(&dlv_name) {
-            (d, true)
-        } else {
-            return None;
-        };
+        let map = MapName::try_from(stem).ok()?;
+        let data = self.map_delta(&map)?;
 
         log::info!(
             "loaded {} for '{}' from save '{}' ({} bytes)",
-            if is_dlv { "DLV" } else { "DDM" },
+            map.delta_filename(),
             map_name,
             self.slot,
             data.len()
         );
 
-        let raw_actors = crate::assets::ddm::Ddm::parse_from_data(&data).ok()?;
-        Actors::from_raw_actors(assets, &raw_actors, state, game_data).ok()
+        // DLV sections are presized from the BLV, so parsing one needs the pristine BLV.
+        let delta = MapDeltaFile::parse(assets, &map, &data).ok()?;
+        Actors::from_raw_actors(assets, &delta.delta().actors, state, game_data).ok()
     }
 
     /// Detect the current map from the save archive.
@@ -187,18 +224,75 @@ impl SaveFile {
         Some(stem.to_lowercase())
     }
 
+    /// Write this save to `dest` with updated header, party and clock chunks.
+    ///
+    /// Every other entry is copied verbatim; `extra` adds or replaces further
+    /// entries (e.g. map deltas) and entries named in `removed` are left out
+    /// (e.g. the delta of a refilled map). `party.bin` and the DDM/DLV of
+    /// `header.map_name` are stamped with a fresh shared `name_tail` so that
+    /// [`detect_current_map`](Self::detect_current_map) resolves the new map.
+    ///
+    /// `dest` may be the file this save was opened from: the archive is held in memory.
+    pub fn write<P: AsRef<Path>>(
+        &self,
+        dest: P,
+        header: &SaveHeader,
+        party: &SaveParty,
+        clock: &SaveClock,
+        extra: &[(&str, Vec<u8>)],
+        removed: &[&str],
+    ) -> Result<(), Error> {
+        let mut overrides: Vec<(&str, Vec<u8>)> = vec![
+            ("header.bin", header.to_bytes().to_vec()),
+            ("party.bin", party.to_bytes()),
+            ("clock.bin", clock.to_bytes()),
+        ];
+        // Overrides match existing entries by exact name, so reuse the archive's spelling.
+        let names = self.list_files();
+        overrides.extend(extra.iter().map(|(name, data)| {
+            let name = names
+                .iter()
+                .find(|n| n.eq_ignore_ascii_case(name))
+                .map_or(*name, String::as_str);
+            (name, data.clone())
+        }));
+
+        let mut writer = LodWriter::from_archive(&self.lod, &overrides);
+        for name in removed {
+            writer.remove_file(name);
+        }
+
+        let stem = header.map_stem();
+        let tag = fresh_name_tail(writer.name_tails());
+        writer.set_name_tail("party.bin", tag);
+        if !writer.set_name_tail(&format!("{stem}.ddm"), tag) {
+            writer.set_name_tail(&format!("{stem}.dlv"), tag);
+        }
+
+        writer.save(dest)
+    }
+
     /// Write a patched copy of `src` save to `dest`, replacing named entries.
     ///
     /// Entries not in `overrides` are copied verbatim from `src`.
-    pub fn write_patched<P: AsRef<Path>>(
-        src: &Path,
-        dest: P,
-        overrides: &[(&str, Vec<u8>)],
-    ) -> Result<(), Box<dyn Error>> {
+    pub fn write_patched<P: AsRef<Path>>(src: &Path, dest: P, overrides: &[(&str, Vec<u8>)]) -> Result<(), Error> {
         LodWriter::patch(src, dest, overrides)
     }
 }
 
+/// Pick a `name_tail` not used by any existing entry, so only the entries
+/// explicitly stamped with it share it.
+fn fresh_name_tail(used: impl Iterator<Item = [u8; 4]>) -> [u8; 4] {
+    let used: std::collections::HashSet<[u8; 4]> = used.collect();
+    (1..=u16::MAX)
+        .map(|n| {
+            let [lo, hi] = n.to_le_bytes();
+            [b'O', b'M', lo, hi]
+        })
+        .find(|tag| !used.contains(tag))
+        .unwrap_or([b'O', b'M', 0, 0])
+}
+
 /// Open all `.mm6` save files from `dir`, sorted by slot name.
 ///
 /// Sort order: autosave first, then quiksave, then alphabetical.
@@ -233,13 +327,18 @@ pub fn list_saves<P: AsRef<Path>>(dir: P) -> Vec<SaveFile> {
     saves.into_iter().filter_map(|(_, p)| SaveFile::open(&p).ok()).collect()
 }
 
-/// Ordering key: autosave(0), quiksave(1), everything else(2). Alphabetical within tier.
-fn save_slot_order(slot: &str) -> (u8, String) {
-    match slot {
-        s if s.starts_with("autosave") => (0, s.to_string()),
-        s if s.starts_with("quiksave") => (1, s.to_string()),
-        s => (2, s.to_string()),
-    }
+/// Ordering key: autosave(0), quiksave(1), everything else(2). Within a
+/// tier slots sort by name, with a trailing slot number compared as a number
+/// so `autosave2` lists before `autosave10`.
+pub fn save_slot_order(slot: &str) -> (u8, String, u64) {
+    let tier = match slot {
+        s if s.starts_with("autosave") => 0,
+        s if s.starts_with("quiksave") => 1,
+        _ => 2,
+    };
+    let stem = slot.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = slot[stem.len()..].parse().unwrap_or(0);
+    (tier, stem.to_string(), number)
 }
 
 #[cfg(test)]
@@ -289,4 +388,178 @@ mod tests {
         assert!(upper.is_some(), "uppercase DDM lookup should succeed");
         assert_eq!(lower.unwrap().len(), upper.unwrap().len());
     }
+
+    #[test]
+    fn slots_sort_by_number() {
+        let mut slots = vec!["save001", "autosave10", "quiksave", "autosave2", "autosave1"];
+        slots.sort_by_key(|s| save_slot_order(s));
+        assert_eq!(slots, ["autosave1", "autosave2", "autosave10", "quiksave", "save001"]);
+    }
+
+    /// Build a minimal synthetic save at `path` (no game data required).
+    fn write_synthetic_save(path: &Path) {
+        let header = SaveHeader {
+            map_name: "oute3.odm".to_string(),
+            ..Default::default()
+        };
+        let mut lod = LodWriter::new(Version::MM6);
+        lod.add_file("header.bin", header.to_bytes().to_vec())
+            .add_file("party.bin", vec![0u8; super::super::party::PARTY_BIN_SIZE])
+            .add_file("clock.bin", vec![0u8; super::super::clock::CLOCK_SIZE])
+            .add_file("oute3.ddm", vec![1, 2, 3])
+            .add_file("d01.dlv", vec![4, 5, 6]);
+        lod.save(path).expect("write synthetic save");
+    }
+
+    #[test]
+    fn write_updates_chunks_and_current_map() {
+        let tid = std::thread::current().id();
+        let src = std::env::temp_dir().join(format!("openmm_save_src_{:?}.mm6", tid));
+        let dest = std::env::temp_dir().join(format!("openmm_save_dest_{:?}.mm6", tid));
+        write_synthetic_save(&src);
+
+        let save = SaveFile::open(&src).expect("open synthetic save");
+        let mut header = save.header();
+        header.map_name = "d01.blv".to_string();
+        let mut party = save.party();
+        party.gold = 1234;
+        party.position = [100, -200, 30];
+        party.quest_bits = vec![5, 77];
+        party.characters[0].name = "Tester".to_string();
+        party.characters[0].hp = 42;
+        save.write(&dest, &header, &party, &save.clock(), &[("extra.bin", vec![9])], &[])
+            .expect("write save");
+
+        let written = SaveFile::open(&dest).expect("reopen written save");
+        let reparsed = written.party();
+        let map = written.detect_current_map();
+        let ddm = written.get_file("oute3.ddm");
+        let extra = written.get_file("extra.bin");
+        let _ = std::fs::remove_file(&src);
+        let _ = std::fs::remove_file(&dest);
+
+        assert_eq!(written.header().map_name, "d01.blv");
+        assert_eq!(reparsed.gold, 1234);
+        assert_eq!(reparsed.position, [100, -200, 30]);
+        assert_eq!(reparsed.quest_bits, vec![5, 77]);
+        assert_eq!(reparsed.characters[0].name, "Tester");
+        assert_eq!(reparsed.characters[0].hp, 42);
+        assert_eq!(map.as_deref(), Some("d01"));
+        assert_eq!(ddm, Some(vec![1, 2, 3]), "untouched entries are copied verbatim");
+        assert_eq!(extra, Some(vec![9]));
+    }
+
+    #[test]
+    fn write_in_place() {
+        let path = std::env::temp_dir().join(format!("openmm_save_inplace_{:?}.mm6", std::thread::current().id()));
+        write_synthetic_save(&path);
+
+        let save = SaveFile::open(&path).expect("open synthetic save");
+        let mut party = save.party();
+        party.food = 99;
+        save.write(&path, &save.header(), &party, &save.clock(), &[], &[])
+            .expect("overwrite save");
+
+        let food = SaveFile::open(&path).expect("reopen").party().food;
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(food, 99);
+    }
+
+    #[test]
+    fn write_replaces_map_delta_case_insensitively() {
+        let path = std::env::temp_dir().join(format!("openmm_save_delta_{:?}.mm6", std::thread::current().id()));
+        write_synthetic_save(&path);
+
+        let save = SaveFile::open(&path).expect("open synthetic save");
+        save.write(
+            &path,
+            &save.header(),
+            &save.party(),
+            &save.clock(),
+            &[("OUTE3.DDM", vec![7, 8])],
+            &[],
+        )
+        .expect("write delta");
+
+        let written = SaveFile::open(&path).expect("reopen");
+        let ddm = written.map_delta(&MapName::try_from("oute3").unwrap());
+        let ddm_entries = written
+            .list_files()
+            .iter()
+            .filter(|n| n.eq_ignore_ascii_case("oute3.ddm"))
+            .count();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(ddm, Some(vec![7, 8]));
+        assert_eq!(ddm_entries, 1, "the existing entry is replaced, not duplicated");
+    }
+
+    #[test]
+    fn write_drops_removed_map_delta() {
+        let path = std::env::temp_dir().join(format!("openmm_save_drop_{:?}.mm6", std::thread::current().id()));
+        write_synthetic_save(&path);
+
+        let save = SaveFile::open(&path).expect("open synthetic save");
+        save.write(&path, &save.header(), &save.party(), &save.clock(), &[], &["OUTE3.DDM"])
+            .expect("write save");
+
+        let written = SaveFile::open(&path).expect("reopen");
+        let ddm = written.map_delta(&MapName::try_from("oute3").unwrap());
+        let dlv = written.map_delta(&MapName::try_from("d01").unwrap());
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(ddm, None, "a dropped map loads as unvisited");
+        assert_eq!(dlv, Some(vec![4, 5, 6]));
+    }
+
+    #[test]
+    fn failed_write_keeps_previous_save() {
+        let tid = std::thread::current().id();
+        let good = std::env::temp_dir().join(format!("openmm_save_atomic_{:?}.mm6", tid));
+        // A directory in place of the destination makes the final rename fail.
+        let blocked = std::env::temp_dir().join(format!("openmm_save_blocked_{:?}.mm6", tid));
+        write_synthetic_save(&good);
+        let _ = std::fs::create_dir_all(&blocked);
+
+        let save = SaveFile::open(&good).expect("open synthetic save");
+        let before = std::fs::read(&good).expect("read save");
+        let result = save.write(&blocked, &save.header(), &save.party(), &save.clock(), &[], &[]);
+        let tmp_left = blocked
+            .with_file_name(format!("openmm_save_blocked_{:?}.mm6.tmp", tid))
+            .exists();
+        let good_left = good
+            .with_file_name(format!("openmm_save_atomic_{:?}.mm6.tmp", tid))
+            .exists();
+        let after = std::fs::read(&good).expect("read save");
+        let _ = std::fs::remove_dir(&blocked);
+        let _ = std::fs::remove_file(&good);
+
+        assert!(result.is_err());
+        assert!(!tmp_left, "temporary file is cleaned up after a failed write");
+        assert!(!good_left, "successful writes leave no temporary file");
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn write_embeds_screenshot() {
+        let path = std::env::temp_dir().join(format!("openmm_save_shot_{:?}.mm6", std::thread::current().id()));
+        write_synthetic_save(&path);
+
+        let save = SaveFile::open(&path).expect("open synthetic save");
+        let shot = DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(640, 480, image::Rgba([10, 20, 30, 255])));
+        let pcx = SaveFile::encode_screenshot(&shot);
+        save.write(
+            &path,
+            &save.header(),
+            &save.party(),
+            &save.clock(),
+            &[("image.pcx", pcx)],
+            &[],
+        )
+        .expect("write screenshot");
+
+        let decoded = SaveFile::open(&path).expect("reopen").screenshot();
+        let _ = std::fs::remove_file(&path);
+        let decoded = decoded.expect("screenshot decodes").to_rgba8();
+        assert_eq!(decoded.dimensions(), (SCREENSHOT_WIDTH, SCREENSHOT_HEIGHT));
+        assert_eq!(decoded.get_pixel(75, 56), &image::Rgba([10, 20, 30, 255]));
+    }
 }
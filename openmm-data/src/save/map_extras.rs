@@ -0,0 +1,225 @@
+//! `<map>.omx` inside a save LOD: per-map state the map's delta cannot hold.
+//!
+//! An MM6 DLV keeps only the low 16 bits of each face's attributes, no face
+//! textures, no light switches and no EVT timer state, and MM6 chests have
+//! no lock bit, so changes made by `SetFacesBit`, `SetTexture` and
+//! `ToggleIndoorLight`, the map's timers and its script-locked chests are
+//! written here, next to the delta, and read back with it. MM6 looks
+//! entries up by name and never opens this one.
+//!
+//! Layout (little-endian):
+//!   0x00: magic `OMX1`
+//!   u32 attribute count, then per face: face index u32, attributes u32
+//!   u32 texture count, then per face: face index u32, name length u8, name bytes
+//!   u32 light count, then per light: BLV light index u32, on u8
+//!   u32 timer count, then per timer: event id u16, marker step index u16,
+//!     armed at u64, due after u64, enabled u8 (times in game minutes)
+//!   u32 locked chest count, then per chest: MapChests index u32
+//!
+//! An entry that ends before a section has nothing in it.
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use crate::Error;
+use crate::utils::MapName;
+
+const MAGIC: &[u8; 4] = b"OMX1";
+
+/// Parsed `<map>.omx`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MapExtras {
+    /// Face index → full 32-bit attribute word.
+    pub face_attributes: BTreeMap<usize, u32>,
+    /// Face index → texture name.
+    pub face_textures: BTreeMap<usize, String>,
+    /// BLV light index → on, for lights switched away from their BLV state.
+    pub lights: BTreeMap<usize, bool>,
+    /// EVT timers by (event id, index of the timer marker step).
+    pub timers: BTreeMap<(u16, u16), MapTimer>,
+    /// MapChests indices locked by `ToggleChestFlag`.
+    pub locked_chests: BTreeSet<usize>,
+}
+
+/// Saved state of one EVT timer step.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MapTimer {
+    /// Game minute the timer was first armed: the origin of interval timers.
+    pub armed_at: u64,
+    /// Game minute after which its next occurrence is due: when it last
+    /// fired, or was armed or switched back on if it has not fired since.
+    pub due_after: u64,
+    /// Cleared by `EnableDateTimer(event, 0)`.
+    pub enabled: bool,
+}
+
+impl MapExtras {
+    /// Save entry name for `map`'s extras, e.g. `"d01.omx"`.
+    pub fn filename(map: &MapName) -> String {
+        Path::new(&map.delta_filename())
+            .with_extension("omx")
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        Error::check_len("omx", data, 0, MAGIC.len())?;
+        if &data[..MAGIC.len()] != MAGIC {
+            return Err(Error::BadMagic {
+                format: "omx",
+                found: data[..MAGIC.len()].to_vec(),
+            });
+        }
+        let mut c = Cursor::new(data);
+        c.set_position(MAGIC.len() as u64);
+        Self::read(&mut c).map_err(|e| Error::from(e).at("omx", c.position() as usize))
+    }
+
+    fn read(c: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        let mut extras = Self::default();
+        for _ in 0..c.read_u32::<LittleEndian>()? {
+            let face = c.read_u32::<LittleEndian>()? as usize;
+            extras.face_attributes.insert(face, c.read_u32::<LittleEndian>()?);
+        }
+        for _ in 0..c.read_u32::<LittleEndian>()? {
+            let face = c.read_u32::<LittleEndian>()? as usize;
+            let mut name = vec![0; c.read_u8()? as usize];
+            c.read_exact(&mut name)?;
+            extras
+                .face_textures
+                .insert(face, String::from_utf8_lossy(&name).into_owned());
+        }
+        let at_end = |c: &Cursor<&[u8]>| c.position() as usize == c.get_ref().len();
+        if at_end(c) {
+            return Ok(extras);
+        }
+        for _ in 0..c.read_u32::<LittleEndian>()? {
+            let light = c.read_u32::<LittleEndian>()? as usize;
+            extras.lights.insert(light, c.read_u8()? != 0);
+        }
+        if at_end(c) {
+            return Ok(extras);
+        }
+        for _ in 0..c.read_u32::<LittleEndian>()? {
+            let key = (c.read_u16::<LittleEndian>()?, c.read_u16::<LittleEndian>()?);
+            let timer = MapTimer {
+                armed_at: c.read_u64::<LittleEndian>()?,
+                due_after: c.read_u64::<LittleEndian>()?,
+                enabled: c.read_u8()? != 0,
+            };
+            extras.timers.insert(key, timer);
+        }
+        if at_end(c) {
+            return Ok(extras);
+        }
+        for _ in 0..c.read_u32::<LittleEndian>()? {
+            extras.locked_chests.insert(c.read_u32::<LittleEndian>()? as usize);
+        }
+        Ok(extras)
+    }
+
+    /// Serialize back to raw bytes. Texture names are cut to 255 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.write_u32::<LittleEndian>(self.face_attributes.len() as u32)
+            .unwrap();
+        for (&face, &attributes) in &self.face_attributes {
+            out.write_u32::<LittleEndian>(face as u32).unwrap();
+            out.write_u32::<LittleEndian>(attributes).unwrap();
+        }
+        out.write_u32::<LittleEndian>(self.face_textures.len() as u32).unwrap();
+        for (&face, name) in &self.face_textures {
+            let name = &name.as_bytes()[..name.len().min(u8::MAX as usize)];
+            out.write_u32::<LittleEndian>(face as u32).unwrap();
+            out.write_u8(name.len() as u8).unwrap();
+            out.extend_from_slice(name);
+        }
+        out.write_u32::<LittleEndian>(self.lights.len() as u32).unwrap();
+        for (&light, &on) in &self.lights {
+            out.write_u32::<LittleEndian>(light as u32).unwrap();
+            out.write_u8(on as u8).unwrap();
+        }
+        out.write_u32::<LittleEndian>(self.timers.len() as u32).unwrap();
+        for (&(event_id, marker), timer) in &self.timers {
+            out.write_u16::<LittleEndian>(event_id).unwrap();
+            out.write_u16::<LittleEndian>(marker).unwrap();
+            out.write_u64::<LittleEndian>(timer.armed_at).unwrap();
+            out.write_u64::<LittleEndian>(timer.due_after).unwrap();
+            out.write_u8(timer.enabled as u8).unwrap();
+        }
+        out.write_u32::<LittleEndian>(self.locked_chests.len() as u32).unwrap();
+        for &chest in &self.locked_chests {
+            out.write_u32::<LittleEndian>(chest as u32).unwrap();
+        }
+        out
+    }
+
+    /// Whether there is nothing to save.
+    pub fn is_empty(&self) -> bool {
+        self.face_attributes.is_empty()
+            && self.face_textures.is_empty()
+            && self.lights.is_empty()
+            && self.timers.is_empty()
+            && self.locked_chests.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut extras = MapExtras::default();
+        extras.face_attributes.insert(7, 0x2000_0001);
+        extras.face_attributes.insert(3, 0x0000_4000);
+        extras.face_textures.insert(7, "t1swb".to_string());
+        extras.lights.insert(2, false);
+        extras.lights.insert(5, true);
+        extras.timers.insert(
+            (40, 0),
+            MapTimer {
+                armed_at: 600,
+                due_after: 1_440,
+                enabled: false,
+            },
+        );
+        extras.locked_chests.insert(4);
+        let bytes = extras.to_bytes();
+        assert_eq!(MapExtras::parse(&bytes).unwrap(), extras);
+        assert_eq!(MapExtras::parse(&bytes).unwrap().to_bytes(), bytes);
+    }
+
+    #[test]
+    fn trailing_sections_are_optional() {
+        let mut extras = MapExtras::default();
+        extras.face_attributes.insert(1, 0x4000);
+        let bytes = extras.to_bytes();
+        // Dropping the chest, timer and light sections one by one.
+        for sections in 1..=3 {
+            assert_eq!(MapExtras::parse(&bytes[..bytes.len() - 4 * sections]).unwrap(), extras);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_and_short_data() {
+        assert!(matches!(MapExtras::parse(b"DLV1"), Err(Error::BadMagic { .. })));
+        let bytes = MapExtras {
+            face_textures: BTreeMap::from([(1, "wall".to_string())]),
+            ..Default::default()
+        }
+        .to_bytes();
+        assert!(matches!(
+            MapExtras::parse(&bytes[..bytes.len() - 1]),
+            Err(Error::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn filename_sits_next_to_the_delta() {
+        let map = MapName::try_from("d01").unwrap();
+        assert_eq!(MapExtras::filename(&map), "d01.omx");
+    }
+}
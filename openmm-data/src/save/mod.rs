@@ -1,4 +1,4 @@
-//! MM6 native save-file parsers (header, party, character, clock).
+//! MM6 native save-file parsers (header, party, character, clock, NPCs, overlays).
 //!
 //! Each sub-module owns one binary chunk from a `.mm6` LOD save archive
 //! and provides parse + round-trip serialization.
@@ -7,9 +7,16 @@ pub mod character;
 pub mod clock;
 pub mod file;
 pub mod header;
+pub mod map_extras;
+pub mod npcdata;
+pub mod overlay;
 pub mod party;
+pub mod patch;
 
 // Convenience re-exports for common types.
 pub use file::{SaveFile, list_saves};
 pub use header::SaveHeader;
+pub use map_extras::{MapExtras, MapTimer};
+pub use npcdata::SaveNpcs;
+pub use overlay::SaveOverlays;
 pub use party::SaveParty;
@@ -0,0 +1,237 @@
+//! `npcdata.bin` inside an MM6 save LOD: live state of every quest NPC.
+//!
+//! The file is the game's NPC array dumped verbatim, one fixed-size record
+//! per `npcdata.txt` row, indexed by NPC id (record 0 is an unused
+//! placeholder). Event scripts mutate it at runtime: `MoveNPC` rewrites the
+//! house, `SetNPCTopic` an event slot, `SetNPCGreeting` the greeting, and
+//! hiring toggles [`NPC_FLAG_HIRED`].
+//!
+//! Layout (0x3C bytes, all i32):
+//!   0x00: name pointer, portrait, flags, fame, reputation, 0x14: house,
+//!   0x18: profession, greeting, join cost, news, 0x28: events A/B/C,
+//!   0x34: ?[8]
+//!
+//! The first eleven dwords mirror the `npcdata.txt` columns; the greeting
+//! slot and the trailing dwords are inferred from MM7's `NPCData`. Bytes
+//! past the last whole record are kept as-is so parse -> to_bytes
+//! reproduces the input exactly.
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::Cursor;
+
+/// Size of a single NPC record in bytes.
+pub const NPC_RECORD_SIZE: usize = 0x3C;
+/// Number of topic event slots per NPC (events A, B, C).
+pub const NPC_TOPIC_COUNT: usize = 3;
+/// Flag bit: the NPC has joined the party as a hireling.
+pub const NPC_FLAG_HIRED: i32 = 0x80;
+
+/// One NPC record from `npcdata.bin`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SaveNpc {
+    /// Runtime pointer into the game's name table. Offset 0x00. Meaningless
+    /// on disk, preserved for round-trip.
+    pub name_ptr: i32,
+    /// Portrait index (`NPC081` → 81). Offset 0x04.
+    pub portrait: i32,
+    /// State bits, see [`NPC_FLAG_HIRED`]. Offset 0x08.
+    pub flags: i32,
+    /// Offset 0x0C.
+    pub fame: i32,
+    /// Offset 0x10.
+    pub reputation: i32,
+    /// 2D location id of the house the NPC lives in. Offset 0x14.
+    pub house: i32,
+    /// Profession index into `npcprof.txt`. Offset 0x18.
+    pub profession: i32,
+    /// Greeting id into `npcbtb.txt`; 0 = default. Offset 0x1C.
+    pub greeting: i32,
+    /// Hire cost in gold. Offset 0x20.
+    pub join_cost: i32,
+    /// News string id. Offset 0x24.
+    pub news: i32,
+    /// Topic event ids (A, B, C); 0 = empty slot. Offset 0x28.
+    pub topics: [i32; NPC_TOPIC_COUNT],
+    /// Offset 0x34.
+    pub _unknown0x34: [i32; 2],
+}
+
+impl SaveNpc {
+    /// Whether the NPC currently travels with the party.
+    pub fn is_hired(&self) -> bool {
+        self.flags & NPC_FLAG_HIRED != 0
+    }
+
+    /// Set or clear the hired flag.
+    pub fn set_hired(&mut self, hired: bool) {
+        if hired {
+            self.flags |= NPC_FLAG_HIRED;
+        } else {
+            self.flags &= !NPC_FLAG_HIRED;
+        }
+    }
+
+    fn read(cursor: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        let mut dwords = [0i32; NPC_RECORD_SIZE / 4];
+        cursor.read_i32_into::<LittleEndian>(&mut dwords)?;
+        Ok(Self {
+            name_ptr: dwords[0],
+            portrait: dwords[1],
+            flags: dwords[2],
+            fame: dwords[3],
+            reputation: dwords[4],
+            house: dwords[5],
+            profession: dwords[6],
+            greeting: dwords[7],
+            join_cost: dwords[8],
+            news: dwords[9],
+            topics: [dwords[10], dwords[11], dwords[12]],
+            _unknown0x34: [dwords[13], dwords[14]],
+        })
+    }
+
+    fn write(&self, buf: &mut Vec<u8>) {
+        let dwords = [
+            self.name_ptr,
+            self.portrait,
+            self.flags,
+            self.fame,
+            self.reputation,
+            self.house,
+            self.profession,
+            self.greeting,
+            self.join_cost,
+            self.news,
+            self.topics[0],
+            self.topics[1],
+            self.topics[2],
+            self._unknown0x34[0],
+            self._unknown0x34[1],
+        ];
+        for v in dwords {
+            buf.write_i32::<LittleEndian>(v).unwrap();
+        }
+    }
+}
+
+/// Parsed `npcdata.bin`: the NPC roster indexed by NPC id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveNpcs {
+    pub npcs: Vec<SaveNpc>,
+    /// Bytes after the last whole record.
+    tail: Vec<u8>,
+}
+
+impl SaveNpcs {
+    /// Parse an `npcdata.bin` blob. Never fails: a partial trailing record
+    /// is kept as raw bytes.
+    pub fn parse(data: &[u8]) -> Self {
+        let count = data.len() / NPC_RECORD_SIZE;
+        let mut cursor = Cursor::new(data);
+        let npcs = (0..count)
+            .map(|_| SaveNpc::read(&mut cursor).expect("record bounds checked above"))
+            .collect();
+        Self {
+            npcs,
+            tail: data[count * NPC_RECORD_SIZE..].to_vec(),
+        }
+    }
+
+    /// Serialize back to raw bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.npcs.len() * NPC_RECORD_SIZE + self.tail.len());
+        for npc in &self.npcs {
+            npc.write(&mut buf);
+        }
+        buf.extend_from_slice(&self.tail);
+        buf
+    }
+
+    /// Record for `npc_id`, or `None` if out of range.
+    pub fn get(&self, npc_id: i32) -> Option<&SaveNpc> {
+        usize::try_from(npc_id).ok().and_then(|i| self.npcs.get(i))
+    }
+
+    /// Mutable record for `npc_id`, or `None` if out of range.
+    pub fn get_mut(&mut self, npc_id: i32) -> Option<&mut SaveNpc> {
+        usize::try_from(npc_id).ok().and_then(|i| self.npcs.get_mut(i))
+    }
+
+    /// Ids of all NPCs currently hired, in ascending order.
+    pub fn hired(&self) -> impl Iterator<Item = i32> + '_ {
+        self.npcs
+            .iter()
+            .enumerate()
+            .filter(|(_, npc)| npc.is_hired())
+            .map(|(i, _)| i as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_npc(seed: i32) -> SaveNpc {
+        SaveNpc {
+            name_ptr: 0x0056_0000 + seed,
+            portrait: seed,
+            flags: 0,
+            fame: seed * 2,
+            reputation: -seed,
+            house: 100 + seed,
+            profession: seed % 80,
+            greeting: 0,
+            join_cost: 250,
+            news: seed,
+            topics: [seed + 1, 0, seed + 3],
+            _unknown0x34: [7, -1],
+        }
+    }
+
+    #[test]
+    fn round_trip_with_partial_tail() {
+        let npcs = SaveNpcs {
+            npcs: (0..4).map(sample_npc).collect(),
+            tail: vec![1, 2, 3],
+        };
+        let bytes = npcs.to_bytes();
+        assert_eq!(bytes.len(), 4 * NPC_RECORD_SIZE + 3);
+        assert_eq!(SaveNpcs::parse(&bytes), npcs);
+        assert_eq!(SaveNpcs::parse(&bytes).to_bytes(), bytes);
+    }
+
+    #[test]
+    fn fields_at_layout_offsets() {
+        let mut npc = sample_npc(5);
+        npc.greeting = 9;
+        npc.set_hired(true);
+        let mut buf = Vec::new();
+        npc.write(&mut buf);
+        let at = |off: usize| i32::from_le_bytes(buf[off..off + 4].try_into().unwrap());
+        assert_eq!(at(0x08), NPC_FLAG_HIRED);
+        assert_eq!(at(0x14), 105);
+        assert_eq!(at(0x1C), 9);
+        assert_eq!(at(0x28), 6);
+        assert_eq!(at(0x30), 8);
+    }
+
+    #[test]
+    fn hired_flag_and_lookup() {
+        let mut npcs = SaveNpcs::parse(&vec![0u8; 5 * NPC_RECORD_SIZE]);
+        npcs.get_mut(2).unwrap().set_hired(true);
+        npcs.get_mut(4).unwrap().set_hired(true);
+        npcs.get_mut(4).unwrap().set_hired(false);
+        assert_eq!(npcs.hired().collect::<Vec<_>>(), vec![2]);
+        assert!(npcs.get(-1).is_none());
+        assert!(npcs.get(5).is_none());
+    }
+
+    #[test]
+    fn parse_new_game_save() {
+        let save = crate::save::file::SaveFile::open("../data/mm6/data/new.lod").expect("failed to open new.lod");
+        let data = save.get_file("npcdata.bin").expect("npcdata.bin missing");
+        let npcs = SaveNpcs::parse(&data);
+        assert!(!npcs.npcs.is_empty());
+        assert_eq!(npcs.to_bytes(), data);
+    }
+}
@@ -0,0 +1,204 @@
+//! `overlay.bin` inside an MM6 save LOD: the active spell overlay list.
+//!
+//! Overlays are the animated effects drawn over the party portraits and the
+//! viewport while a spell or buff is running (e.g. the Bless sparkle). The
+//! file is a fixed table of [`OVERLAY_SLOTS`] records followed by the list's
+//! own bookkeeping dwords.
+//!
+//! Record layout (0x14 bytes):
+//!   0x00: kind i16, 0x02: ? i16, 0x04: sprite frame time i16,
+//!   0x06: overlay index i16 (1-based into `doverlay.bin`, 0 = free slot),
+//!   0x08: screen x i16, screen y i16, 0x0C: pid i16, projectile size i16,
+//!   0x10: damage modifier i32
+//!
+//! Bytes past the table are kept as-is so parse -> to_bytes reproduces the
+//! input exactly.
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::Cursor;
+
+/// Size of a single overlay record in bytes.
+pub const OVERLAY_RECORD_SIZE: usize = 0x14;
+/// Number of overlay slots in the table.
+pub const OVERLAY_SLOTS: usize = 50;
+
+/// One overlay slot from `overlay.bin`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SaveOverlay {
+    /// Offset 0x00.
+    pub kind: i16,
+    /// Offset 0x02.
+    pub _unknown0x02: i16,
+    /// Elapsed animation time in ticks. Offset 0x04.
+    pub sprite_frame_time: i16,
+    /// 1-based index into `doverlay.bin`; 0 marks a free slot. Offset 0x06.
+    pub overlay_index: i16,
+    /// Offset 0x08.
+    pub screen_x: i16,
+    /// Offset 0x0A.
+    pub screen_y: i16,
+    /// Packed id of the object the overlay is attached to. Offset 0x0C.
+    pub pid: i16,
+    /// Offset 0x0E.
+    pub projectile_size: i16,
+    /// Offset 0x10.
+    pub damage_mod: i32,
+}
+
+impl SaveOverlay {
+    /// Whether this slot holds a running overlay.
+    pub fn is_active(&self) -> bool {
+        self.overlay_index > 0
+    }
+
+    fn read(cursor: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        let mut words = [0i16; 8];
+        cursor.read_i16_into::<LittleEndian>(&mut words)?;
+        Ok(Self {
+            kind: words[0],
+            _unknown0x02: words[1],
+            sprite_frame_time: words[2],
+            overlay_index: words[3],
+            screen_x: words[4],
+            screen_y: words[5],
+            pid: words[6],
+            projectile_size: words[7],
+            damage_mod: cursor.read_i32::<LittleEndian>()?,
+        })
+    }
+
+    fn write(&self, buf: &mut Vec<u8>) {
+        let words = [
+            self.kind,
+            self._unknown0x02,
+            self.sprite_frame_time,
+            self.overlay_index,
+            self.screen_x,
+            self.screen_y,
+            self.pid,
+            self.projectile_size,
+        ];
+        for v in words {
+            buf.write_i16::<LittleEndian>(v).unwrap();
+        }
+        buf.write_i32::<LittleEndian>(self.damage_mod).unwrap();
+    }
+}
+
+/// Parsed `overlay.bin`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveOverlays {
+    pub slots: [SaveOverlay; OVERLAY_SLOTS],
+    /// Bytes after the slot table (list bookkeeping).
+    tail: Vec<u8>,
+}
+
+impl Default for SaveOverlays {
+    fn default() -> Self {
+        Self {
+            slots: [SaveOverlay::default(); OVERLAY_SLOTS],
+            tail: Vec::new(),
+        }
+    }
+}
+
+impl SaveOverlays {
+    /// Parse an `overlay.bin` blob. A short table is zero-padded.
+    pub fn parse(data: &[u8]) -> Self {
+        let table_len = OVERLAY_SLOTS * OVERLAY_RECORD_SIZE;
+        let mut table = vec![0u8; table_len];
+        let len = data.len().min(table_len);
+        table[..len].copy_from_slice(&data[..len]);
+
+        let mut cursor = Cursor::new(table.as_slice());
+        let slots =
+            std::array::from_fn(|_| SaveOverlay::read(&mut cursor).expect("table padded to OVERLAY_SLOTS records"));
+        Self {
+            slots,
+            tail: data.get(table_len..).unwrap_or_default().to_vec(),
+        }
+    }
+
+    /// Serialize back to raw bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(OVERLAY_SLOTS * OVERLAY_RECORD_SIZE + self.tail.len());
+        for slot in &self.slots {
+            slot.write(&mut buf);
+        }
+        buf.extend_from_slice(&self.tail);
+        buf
+    }
+
+    /// Running overlays, in slot order.
+    pub fn active(&self) -> impl Iterator<Item = &SaveOverlay> {
+        self.slots.iter().filter(|o| o.is_active())
+    }
+
+    /// Replace the table with `overlays`, freeing every other slot.
+    /// Overlays beyond [`OVERLAY_SLOTS`] are dropped.
+    pub fn set_active(&mut self, overlays: &[SaveOverlay]) {
+        self.slots = [SaveOverlay::default(); OVERLAY_SLOTS];
+        for (slot, overlay) in self.slots.iter_mut().zip(overlays) {
+            *slot = *overlay;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bless() -> SaveOverlay {
+        SaveOverlay {
+            kind: 1,
+            _unknown0x02: 0,
+            sprite_frame_time: 64,
+            overlay_index: 12,
+            screen_x: 320,
+            screen_y: -40,
+            pid: 0x1234,
+            projectile_size: 0,
+            damage_mod: 0x10000,
+        }
+    }
+
+    #[test]
+    fn round_trip_with_tail() {
+        let mut overlays = SaveOverlays::default();
+        overlays.slots[3] = bless();
+        overlays.tail = vec![1, 0, 0, 0, 5, 0, 0, 0];
+        let bytes = overlays.to_bytes();
+        assert_eq!(bytes.len(), OVERLAY_SLOTS * OVERLAY_RECORD_SIZE + 8);
+        assert_eq!(SaveOverlays::parse(&bytes), overlays);
+        assert_eq!(SaveOverlays::parse(&bytes).to_bytes(), bytes);
+    }
+
+    #[test]
+    fn fields_at_layout_offsets() {
+        let mut buf = Vec::new();
+        bless().write(&mut buf);
+        assert_eq!(buf.len(), OVERLAY_RECORD_SIZE);
+        assert_eq!(i16::from_le_bytes([buf[6], buf[7]]), 12);
+        assert_eq!(i16::from_le_bytes([buf[0x0A], buf[0x0B]]), -40);
+        assert_eq!(i32::from_le_bytes(buf[0x10..0x14].try_into().unwrap()), 0x10000);
+    }
+
+    #[test]
+    fn set_active_compacts_and_frees() {
+        let mut overlays = SaveOverlays::parse(&[]);
+        assert_eq!(overlays.active().count(), 0);
+        overlays.slots[40] = bless();
+        let running: Vec<_> = overlays.active().copied().collect();
+        overlays.set_active(&running);
+        assert_eq!(overlays.slots[0], bless());
+        assert!(!overlays.slots[40].is_active());
+        assert_eq!(overlays.active().count(), 1);
+    }
+
+    #[test]
+    fn parse_new_game_save() {
+        let save = crate::save::file::SaveFile::open("../data/mm6/data/new.lod").expect("failed to open new.lod");
+        let data = save.get_file("overlay.bin").expect("overlay.bin missing");
+        assert_eq!(SaveOverlays::parse(&data).to_bytes(), data);
+    }
+}
@@ -2,15 +2,22 @@
 //
 // Contains party position, calendar, gold/food, quest bits, autonotes,
 // and 4 character records. Keeps raw bytes for round-trip fidelity.
+//
+// Of an MM7 party.bin only the character records are decoded.
 
-use super::character::{CHARACTER_SIZE, SaveCharacter};
+use super::character::{CHARACTER_SIZE, CHARACTER_SIZE_MM7, SaveCharacter};
+use crate::Version;
 
 /// Total size of party.bin in bytes.
 pub const PARTY_BIN_SIZE: usize = 64720;
+/// Number of quest bits stored in party.bin.
+pub const QUEST_BIT_COUNT: usize = QUEST_BITS_LEN * 8;
 
 // ── Binary layout offsets ──────────────────────────────────────────
 /// Offset where the 4 character records begin.
 const PLAYERS_OFFSET: usize = 0x02C4;
+/// Offset of the character records in an MM7 party.bin.
+const PLAYERS_OFFSET_MM7: usize = 0x0944;
 
 // Position / orientation
 const POSITION_X_OFFSET: usize = 0x0028;
@@ -82,6 +89,8 @@ pub struct SaveParty {
     pub characters: [SaveCharacter; 4],
     /// Raw bytes for round-trip serialization.
     raw: Vec<u8>,
+    /// Layout the data was read in, and is written back in.
+    version: Version,
 }
 
 /// Read an i32 (little-endian) from a byte slice at the given offset.
@@ -182,12 +191,68 @@ impl SaveParty {
             autonote_bits,
             characters,
             raw,
+            version: Version::MM6,
         }
     }
 
+    /// Parse party.bin laid out for `version`. Panics if data is too short.
+    ///
+    /// For MM7 only the characters are decoded: the party fields read as
+    /// zero (empty bit lists) and their stored bytes are written back as is.
+    pub fn parse_version(data: &[u8], version: Version) -> Self {
+        if version < Version::MM7 {
+            return Self::parse(data);
+        }
+        let end = PLAYERS_OFFSET_MM7 + 4 * CHARACTER_SIZE_MM7;
+        assert!(
+            data.len() >= end,
+            "party.bin too short: {} bytes, need {}",
+            data.len(),
+            end,
+        );
+        let characters = std::array::from_fn(|i| {
+            let start = PLAYERS_OFFSET_MM7 + i * CHARACTER_SIZE_MM7;
+            SaveCharacter::parse_version(&data[start..start + CHARACTER_SIZE_MM7], version)
+        });
+        Self {
+            position: [0; 3],
+            direction: 0,
+            look_angle: 0,
+            year: 0,
+            month: 0,
+            week: 0,
+            day: 0,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            gold: 0,
+            bank_gold: 0,
+            food: 0,
+            reputation: 0,
+            deaths: 0,
+            quest_bits: Vec::new(),
+            autonote_bits: Vec::new(),
+            characters,
+            raw: data.to_vec(),
+            version,
+        }
+    }
+
+    /// Game the party.bin layout belongs to.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
     /// Serialize back to bytes, patching parsed fields into the raw copy.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut buf = self.raw.clone();
+        if self.version >= Version::MM7 {
+            for (i, character) in self.characters.iter().enumerate() {
+                let start = PLAYERS_OFFSET_MM7 + i * CHARACTER_SIZE_MM7;
+                buf[start..start + CHARACTER_SIZE_MM7].copy_from_slice(&character.to_bytes());
+            }
+            return buf;
+        }
 
         write_i32(&mut buf, POSITION_X_OFFSET, self.position[0]);
         write_i32(&mut buf, POSITION_Y_OFFSET, self.position[1]);
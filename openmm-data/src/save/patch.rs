@@ -0,0 +1,268 @@
+//! JSON edits for hand-crafting saves (used by the `save_tool` binary).
+//!
+//! A [`SavePatch`] names only the fields to change; everything else in the
+//! save is left as it was. Example:
+//!
+//! ```json
+//! {
+//!   "gold": 5000,
+//!   "map": "d01",
+//!   "position": [-1024, 256, 0],
+//!   "quest_bits": { "set": [12, 40], "clear": [7] },
+//!   "characters": [
+//!     { "index": 0, "level": 10, "stats": { "might": 30 }, "skills": { "0": 7 } }
+//!   ]
+//! }
+//! ```
+
+use serde::Deserialize;
+
+use super::character::SaveCharacter;
+use super::header::SaveHeader;
+use super::party::{QUEST_BIT_COUNT, SaveParty};
+use crate::utils::MapName;
+use crate::{Error, Version};
+
+/// Edits to apply to a save's header and party.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SavePatch {
+    pub gold: Option<i32>,
+    pub bank_gold: Option<i32>,
+    pub food: Option<i32>,
+    pub reputation: Option<i32>,
+    /// Party position in MM6 coordinates (x, y, z).
+    pub position: Option<[i32; 3]>,
+    /// Facing, 0-2047 (0 = east, 512 = north).
+    pub direction: Option<i32>,
+    /// Map to start on, e.g. `"oute3"` or `"d01.blv"`.
+    pub map: Option<String>,
+    pub quest_bits: Option<BitsPatch>,
+    #[serde(default)]
+    pub characters: Vec<CharacterPatch>,
+}
+
+/// Bit indices to set and clear.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BitsPatch {
+    #[serde(default)]
+    pub set: Vec<i32>,
+    #[serde(default)]
+    pub clear: Vec<i32>,
+}
+
+/// Edits to one party member, selected by `index` (0-3).
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CharacterPatch {
+    pub index: usize,
+    pub name: Option<String>,
+    pub level: Option<i16>,
+    pub hp: Option<i32>,
+    pub sp: Option<i32>,
+    pub experience: Option<i64>,
+    pub skill_points: Option<i32>,
+    pub stats: Option<StatsPatch>,
+    /// Skill index → skill word (0x40 = expert, 0x80 = master and, for
+    /// MM7, 0x100 = grandmaster bits included).
+    #[serde(default)]
+    pub skills: std::collections::BTreeMap<usize, u16>,
+}
+
+/// Base stat values, in [`SaveCharacter::base_stats`] order.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatsPatch {
+    pub might: Option<i16>,
+    pub intellect: Option<i16>,
+    pub personality: Option<i16>,
+    pub endurance: Option<i16>,
+    pub accuracy: Option<i16>,
+    pub speed: Option<i16>,
+    pub luck: Option<i16>,
+}
+
+impl SavePatch {
+    /// Parse a patch from JSON text.
+    pub fn from_json(text: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// Apply the patch. Validates everything before changing anything, so
+    /// a rejected patch leaves `header` and `party` untouched.
+    pub fn apply(&self, header: &mut SaveHeader, party: &mut SaveParty) -> Result<(), Error> {
+        let map = self
+            .map
+            .as_deref()
+            .map(MapName::try_from)
+            .transpose()
+            .map_err(|e| Error::invalid("save patch", e))?;
+        if let Some(bits) = &self.quest_bits
+            && let Some(&bad) = bits
+                .set
+                .iter()
+                .chain(&bits.clear)
+                .find(|&&b| !(0..QUEST_BIT_COUNT as i32).contains(&b))
+        {
+            return Err(Error::invalid(
+                "save patch",
+                format!("quest bit {bad} out of range 0..{QUEST_BIT_COUNT}"),
+            ));
+        }
+        if party.version() >= Version::MM7 && self.edits_undecoded_mm7_fields() {
+            return Err(Error::invalid(
+                "save patch",
+                "MM7 saves only take map, character name, level, experience, stats and skills edits",
+            ));
+        }
+        for cp in &self.characters {
+            let Some(character) = party.characters.get(cp.index) else {
+                return Err(Error::invalid(
+                    "save patch",
+                    format!("character index {} out of range 0..4", cp.index),
+                ));
+            };
+            if let Some(&skill) = cp.skills.keys().find(|&&s| s >= character.skills.len()) {
+                return Err(Error::invalid(
+                    "save patch",
+                    format!("skill index {skill} out of range 0..{}", character.skills.len()),
+                ));
+            }
+        }
+
+        if let Some(map) = map {
+            header.map_name = map.filename();
+        }
+        set(&mut party.gold, self.gold);
+        set(&mut party.bank_gold, self.bank_gold);
+        set(&mut party.food, self.food);
+        set(&mut party.reputation, self.reputation);
+        set(&mut party.position, self.position);
+        set(&mut party.direction, self.direction);
+
+        if let Some(bits) = &self.quest_bits {
+            party.quest_bits.extend(&bits.set);
+            party.quest_bits.retain(|b| !bits.clear.contains(b));
+            party.quest_bits.sort_unstable();
+            party.quest_bits.dedup();
+        }
+
+        for cp in &self.characters {
+            cp.apply(&mut party.characters[cp.index]);
+        }
+        Ok(())
+    }
+
+    /// Whether the patch sets a field an MM7 party.bin is not decoded for.
+    fn edits_undecoded_mm7_fields(&self) -> bool {
+        self.gold.is_some()
+            || self.bank_gold.is_some()
+            || self.food.is_some()
+            || self.reputation.is_some()
+            || self.position.is_some()
+            || self.direction.is_some()
+            || self.quest_bits.is_some()
+            || self
+                .characters
+                .iter()
+                .any(|c| c.hp.is_some() || c.sp.is_some() || c.skill_points.is_some())
+    }
+}
+
+impl CharacterPatch {
+    fn apply(&self, c: &mut SaveCharacter) {
+        set(&mut c.name, self.name.clone());
+        set(&mut c.level, self.level);
+        set(&mut c.hp, self.hp);
+        set(&mut c.sp, self.sp);
+        set(&mut c.experience, self.experience);
+        set(&mut c.skill_points, self.skill_points);
+        if let Some(stats) = &self.stats {
+            let values = [
+                stats.might,
+                stats.intellect,
+                stats.personality,
+                stats.endurance,
+                stats.accuracy,
+                stats.speed,
+                stats.luck,
+            ];
+            for (stat, value) in c.base_stats.iter_mut().zip(values) {
+                set(stat, value);
+            }
+        }
+        for (&skill, &level) in &self.skills {
+            c.skills[skill] = level;
+        }
+    }
+}
+
+fn set<T>(field: &mut T, value: Option<T>) {
+    if let Some(v) = value {
+        *field = v;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::party::PARTY_BIN_SIZE;
+
+    fn blank() -> (SaveHeader, SaveParty) {
+        (SaveHeader::default(), SaveParty::parse(&vec![0u8; PARTY_BIN_SIZE]))
+    }
+
+    #[test]
+    fn applies_party_and_character_edits() {
+        let (mut header, mut party) = blank();
+        party.quest_bits = vec![3, 7];
+        let patch = SavePatch::from_json(
+            r#"{
+                "gold": 5000,
+                "map": "D01",
+                "position": [1, 2, 3],
+                "quest_bits": { "set": [12, 3], "clear": [7] },
+                "characters": [
+                    { "index": 2, "level": 10, "stats": { "might": 30, "luck": 5 }, "skills": { "4": 71 } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        patch.apply(&mut header, &mut party).unwrap();
+
+        assert_eq!(header.map_name, "d01.blv");
+        assert_eq!(party.gold, 5000);
+        assert_eq!(party.position, [1, 2, 3]);
+        assert_eq!(party.quest_bits, vec![3, 12]);
+        let c = &party.characters[2];
+        assert_eq!(c.level, 10);
+        assert_eq!(c.base_stats, [30, 0, 0, 0, 0, 0, 5]);
+        assert_eq!(c.skills[4], 71);
+
+        let reparsed = SaveParty::parse(&party.to_bytes());
+        assert_eq!(reparsed.gold, 5000);
+        assert_eq!(reparsed.quest_bits, vec![3, 12]);
+        assert_eq!(reparsed.characters[2].level, 10);
+    }
+
+    #[test]
+    fn rejected_patch_changes_nothing() {
+        for json in [
+            r#"{ "gold": 1, "characters": [ { "index": 4 } ] }"#,
+            r#"{ "gold": 1, "characters": [ { "index": 0, "skills": { "31": 1 } } ] }"#,
+            r#"{ "gold": 1, "quest_bits": { "set": [512] } }"#,
+        ] {
+            let (mut header, mut party) = blank();
+            let patch = SavePatch::from_json(json).unwrap();
+            assert!(patch.apply(&mut header, &mut party).is_err(), "{json}");
+            assert_eq!(party.gold, 0, "{json}");
+        }
+    }
+
+    #[test]
+    fn unknown_fields_are_errors() {
+        assert!(SavePatch::from_json(r#"{ "gld": 1 }"#).is_err());
+        assert!(SavePatch::from_json(r#"{ "characters": [ { "index": 0, "stats": { "str": 1 } } ] }"#).is_err());
+    }
+}
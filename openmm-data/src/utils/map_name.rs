@@ -18,6 +18,14 @@ impl MapName {
         }
     }
 
+    /// Returns the filename of this map's delta file (e.g. "oute3.ddm" or "d01.dlv").
+    pub fn delta_filename(&self) -> String {
+        match self {
+            MapName::Outdoor(odm) => format!("out{}{}.ddm", odm.x, odm.y),
+            MapName::Indoor(name) => format!("{}.dlv", name),
+        }
+    }
+
     pub fn is_indoor(&self) -> bool {
         matches!(self, MapName::Indoor(_))
     }
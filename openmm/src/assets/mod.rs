@@ -11,8 +11,8 @@ pub struct GameAssets {
 }
 
 impl GameAssets {
-    pub fn new(path: std::path::PathBuf) -> Result<Self, Box<dyn Error>> {
-        let assets = Assets::new(&*path.to_string_lossy())?;
+    pub fn new(path: std::path::PathBuf, overlays: &[std::path::PathBuf]) -> Result<Self, Box<dyn Error>> {
+        let assets = Assets::with_overlays(&*path.to_string_lossy(), overlays)?;
         Ok(Self {
             assets,
             media: std::sync::RwLock::new(openmm_data::assets::MediaCache::new()),
@@ -67,6 +67,14 @@ impl GameAssets {
         self.assets.data().merchant_table.as_ref()
     }
 
+    pub fn items(&self) -> Option<&openmm_data::items::ItemsTable> {
+        self.assets.data().items_table.as_ref()
+    }
+
+    pub fn chests(&self) -> Option<&openmm_data::dchest::ChestList> {
+        self.assets.data().chest_list.as_ref()
+    }
+
     /// Game-engine API: decoded, game-ready assets (sprites, bitmaps, icons, fonts, NPC tables).
     pub fn lod(&self) -> openmm_data::assets::LodDecoder<'_> {
         self.assets.lod()
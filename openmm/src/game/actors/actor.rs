@@ -69,6 +69,8 @@ pub struct Actor {
     /// Index in the DDM actors array (0-based). -1 for non-DDM actors (ODM spawn groups).
     /// Used by ToggleActorFlag to target a specific actor.
     pub ddm_id: i32,
+    /// 0-based dmonlist.bin index. Lets spawn-point monsters be written to the map delta.
+    pub monlist_id: u8,
     /// Faction group ID from DDM (0 = none). Used by ToggleActorGroupFlag / ChangeGroup.
     pub group_id: i32,
     /// Aggro detection radius in world units. 0 = passive (won't aggro).
@@ -109,6 +111,7 @@ pub struct ActorParams {
     pub tether_distance: f32,
     pub attack_range: f32,
     pub ddm_id: i32,
+    pub monlist_id: u8,
     pub group_id: i32,
     pub aggro_range: f32,
     pub recovery_secs: f32,
@@ -143,6 +146,7 @@ impl Actor {
             attack_timer: (pos.x * 0.007 + pos.z * 0.023).abs().fract() * 3.0 + 1.0,
             attack_anim_remaining: 0.0,
             ddm_id: p.ddm_id,
+            monlist_id: p.monlist_id,
             group_id: p.group_id,
             aggro_range: p.aggro_range,
             recovery_secs: p.recovery_secs,
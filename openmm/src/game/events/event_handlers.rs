@@ -14,6 +14,7 @@ use crate::assets::GameAssets;
 use crate::game::actors::npc_dialogue;
 use crate::game::map::coords::{mm6_binary_angle_to_radians, mm6_position_to_bevy};
 use crate::game::optional::OptionalWrite;
+use crate::game::player::party::Party;
 use crate::game::sound::SoundManager;
 use crate::game::sound::effects::PlayUiSoundEvent;
 use crate::game::sprites::material::SpriteMaterial;
@@ -104,10 +105,46 @@ pub(super) fn handle_speak_in_house(
     }
 }
 
-/// Handle OpenChest: load chest screen, play sound.
-pub(super) fn handle_open_chest(_id: u8, _audio: &mut AudioParams) {
-    debug!("OpenChest(id={})", _id);
-    // Load chest screen via the screen system (handled in scripting.rs)
+/// Handle OpenChest: spring or disarm the chest's trap and roll its contents.
+/// Returns true when the chest opened and its window should be shown.
+pub(super) fn handle_open_chest(
+    id: u8,
+    audio: &mut AudioParams,
+    party: &mut Party,
+    game_assets: &GameAssets,
+    map_name: &MapName,
+    ui: &mut UiState,
+    time_secs: f64,
+) -> bool {
+    use crate::game::map::chests::{self, ChestOpening, TrapOutcome};
+
+    let Some(ref mut map_chests) = audio.chests else {
+        warn!("OpenChest({}): no chests on this map", id);
+        return false;
+    };
+    let map = game_assets.data().mapstats.get(&map_name.filename());
+    match chests::open_chest(map_chests, id as usize, party, map, game_assets) {
+        ChestOpening::Missing => {
+            warn!("OpenChest({}): no such chest", id);
+            false
+        }
+        ChestOpening::Locked => {
+            ui.footer.set_status("It's locked!", 2.0, time_secs);
+            false
+        }
+        ChestOpening::Opened { trap } => {
+            match trap {
+                Some(TrapOutcome::Disarmed) => ui.footer.set_status("You disarm the trap.", 2.0, time_secs),
+                Some(TrapOutcome::Exploded(damage)) => {
+                    info!("OpenChest({}): trap exploded for {} damage", id, damage);
+                    ui.footer.set_status("The chest explodes!", 2.0, time_secs);
+                }
+                None => {}
+            }
+            debug!("OpenChest(id={})", id);
+            true
+        }
+    }
 }
 
 /// Handle MoveToMap: same-map teleport or cross-map transition.
@@ -187,8 +224,7 @@ pub(super) fn handle_move_to_map(
     });
     transition.game_state.set(GameState::Loading);
 
-    // Reset map vars on map transition
-    world_state.game_vars.map_vars = [0; 100];
+    // Map vars are snapshotted on exit and restored for the new map by loading.
     event_queue.clear();
 }
 
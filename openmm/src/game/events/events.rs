@@ -126,9 +126,13 @@ pub fn resolve_building_image(
 pub fn load_map_events(commands: &mut Commands, game_assets: &GameAssets, map_base: &str, indoor: bool) {
     let mut evt = match openmm_data::EvtFile::parse(game_assets.assets(), map_base) {
         Ok(e) => {
-            info!("Loaded {}.evt: {} events", map_base, e.events.len());
+            info!("Loaded {}.evt: {} events", map_base, e.events().len());
             Some(e)
         }
+        Err(e) if e.is_not_found() => {
+            debug!("No {}.evt: {}", map_base, e);
+            None
+        }
         Err(e) => {
             warn!("Failed to load {}.evt: {}", map_base, e);
             None
@@ -139,36 +143,38 @@ pub fn load_map_events(commands: &mut Commands, game_assets: &GameAssets, map_ba
     if !indoor {
         match openmm_data::EvtFile::parse(game_assets.assets(), "out") {
             Ok(out) => {
-                info!("Loaded out.evt: {} events", out.events.len());
+                info!("Loaded out.evt: {} events", out.events().len());
                 if let Some(ref mut map_evt) = evt {
-                    for (id, actions) in out.events {
-                        map_evt.events.entry(id).or_default().extend(actions);
-                    }
+                    map_evt.merge_events(out);
                 } else {
                     evt = Some(out);
                 }
             }
-            Err(e) => {
+            Err(e) if e.is_not_found() => {
                 debug!("No out.evt: {}", e);
             }
+            Err(e) => {
+                warn!("Failed to load out.evt: {}", e);
+            }
         }
     }
 
     // Merge global.evt events (map-independent global events)
     match openmm_data::EvtFile::parse(game_assets.assets(), "global") {
         Ok(global) => {
-            info!("Loaded global.evt: {} events", global.events.len());
+            info!("Loaded global.evt: {} events", global.events().len());
             if let Some(ref mut map_evt) = evt {
-                for (id, actions) in global.events {
-                    map_evt.events.entry(id).or_default().extend(actions);
-                }
+                map_evt.merge_events(global);
             } else {
                 evt = Some(global);
             }
         }
-        Err(e) => {
+        Err(e) if e.is_not_found() => {
             debug!("No global.evt: {}", e);
         }
+        Err(e) => {
+            warn!("Failed to load global.evt: {}", e);
+        }
     }
     let houses = if indoor {
         None
@@ -185,11 +191,11 @@ pub fn load_map_events(commands: &mut Commands, game_assets: &GameAssets, map_ba
         }
     };
     if let Some(ref e) = evt {
-        let mut ids: Vec<_> = e.events.keys().collect();
+        let mut ids: Vec<_> = e.events().keys().collect();
         ids.sort();
         debug!("EVT event_ids: {:?}", ids);
         for &id in &ids {
-            if let Some(actions) = e.events.get(id) {
+            if let Some(actions) = e.events().get(id) {
                 debug!("  event[{}]: {:?}", id, actions);
             }
         }
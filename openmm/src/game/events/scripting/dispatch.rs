@@ -9,6 +9,7 @@ use crate::GameState;
 use crate::assets::GameAssets;
 use crate::game::actors::Actor;
 use crate::game::interaction::DecorationInfo;
+use crate::game::map::indoor::ApplyIndoorFaces;
 use crate::game::map::outdoor::ApplyTextureOutdoors;
 use crate::game::optional::OptionalWrite;
 use crate::game::player::party::Party;
@@ -16,10 +17,10 @@ use crate::game::sound::SoundManager;
 use crate::game::sound::effects::PlayUiSoundEvent;
 use crate::game::sprites::material::SpriteMaterial;
 use crate::game::state::variables;
-use crate::game::ui::UiState;
+use crate::game::ui::{UiMode, UiState};
 
 use super::control_flow::{execute_conditional_jump, log_tail_unreachable};
-use super::queue::EventQueue;
+use super::queue::{EventQueue, EvtWait};
 use crate::game::events::event_handlers;
 use crate::game::events::events::MapEvents;
 
@@ -50,15 +51,19 @@ pub(crate) struct MapEntityParams<'w, 's> {
     pub player_settings: Res<'w, crate::game::player::PlayerSettings>,
 }
 
-/// Bundles audio + mesh assets + game_time to stay within Bevy's 16-param limit.
+/// Bundles audio + mesh assets + game_time + timers to stay within Bevy's 16-param limit.
 #[derive(SystemParam)]
 pub(crate) struct AudioParams<'w> {
     pub ui_sound: Option<bevy::ecs::message::MessageWriter<'w, PlayUiSoundEvent>>,
     pub texture_outdoors: bevy::ecs::message::MessageWriter<'w, ApplyTextureOutdoors>,
+    pub indoor_faces: bevy::ecs::message::MessageWriter<'w, ApplyIndoorFaces>,
+    pub indoor_lights: Option<ResMut<'w, crate::game::map::indoor::IndoorLights>>,
     pub sound_manager: Option<Res<'w, SoundManager>>,
     pub game_time: Option<Res<'w, crate::game::state::GameTime>>,
     pub registry: Option<Res<'w, crate::screens::PropertyRegistry>>,
     pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub timers: ResMut<'w, super::timers::EvtTimers>,
+    pub chests: Option<ResMut<'w, crate::game::map::chests::MapChests>>,
 }
 
 /// Macro for concise stub logging in event arms.
@@ -93,7 +98,7 @@ pub(crate) fn dispatch_on_map_reload(map_events: Option<Res<MapEvents>>, mut eve
     let Some(evt) = me.evt.as_ref() else { return };
 
     let mut ids: Vec<u16> = evt
-        .events
+        .events()
         .iter()
         .filter(|(_, steps)| steps.iter().any(|s| matches!(s.event, GameEvent::OnMapReload)))
         .map(|(id, _)| *id)
@@ -101,7 +106,7 @@ pub(crate) fn dispatch_on_map_reload(map_events: Option<Res<MapEvents>>, mut eve
     ids.sort();
 
     for id in ids {
-        if let Some(steps) = evt.events.get(&id) {
+        if let Some(steps) = evt.events().get(&id) {
             let Some(reload_idx) = steps.iter().position(|s| matches!(s.event, GameEvent::OnMapReload)) else {
                 continue;
             };
@@ -113,8 +118,34 @@ pub(crate) fn dispatch_on_map_reload(map_events: Option<Res<MapEvents>>, mut eve
     }
 }
 
-/// Process one event sequence per frame from the EventQueue.
-/// Each sequence is executed as a script with control flow (Compare/Jmp/RandomGoTo).
+/// Clear the running sequence's wait once the thing it waits for has happened.
+/// Runs before `process_events`, so a key press that started a script cannot
+/// also satisfy a PressAnyKey in it.
+pub(crate) fn resume_waiting_events(
+    mut event_queue: ResMut<EventQueue>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    ui: Res<UiState>,
+) {
+    let Some(wait) = event_queue.waiting() else {
+        return;
+    };
+    let ready = match wait {
+        EvtWait::KeyPress => keys.get_just_pressed().next().is_some() || mouse.get_just_pressed().next().is_some(),
+        EvtWait::DialogClosed => matches!(ui.mode, UiMode::World),
+        // Resumed by the `evt:VideoEnded()` screen action.
+        EvtWait::VideoEnded => false,
+    };
+    if ready {
+        debug!("Event resumed after {:?}", wait);
+        event_queue.resume();
+    }
+}
+
+/// Run one event sequence per frame from the EventQueue.
+/// Each sequence is executed as a script with control flow (Compare/Jmp/RandomGoTo)
+/// until it ends or reaches a step that waits for the player, where it is suspended
+/// with its program counter and resumed by `resume_waiting_events`.
 pub(crate) fn process_events(
     mut event_queue: ResMut<EventQueue>,
     map_events: Option<Res<MapEvents>>,
@@ -134,14 +165,14 @@ pub(crate) fn process_events(
     mut screen_actions: Option<bevy::ecs::message::MessageWriter<crate::screens::runtime::ScreenActions>>,
 ) {
     // When a UI overlay is active, process sound events but keep everything else queued.
-    if !matches!(ui.mode, crate::game::ui::UiMode::World) {
+    if !matches!(ui.mode, UiMode::World) {
         if let Some(ref mut sound_writer) = audio.ui_sound {
             event_queue.drain_sounds(sound_writer);
         }
         return;
     }
 
-    let Some(sequence) = event_queue.pop() else {
+    let Some(mut sequence) = event_queue.next_runnable() else {
         return;
     };
 
@@ -150,9 +181,14 @@ pub(crate) fn process_events(
         .event_id
         .map(|id| format!(" event_id={}", id))
         .unwrap_or_default();
-    info!("── Event{} ({} steps) ──", id_str, steps.len());
+    if sequence.pc == 0 {
+        info!("── Event{} ({} steps) ──", id_str, steps.len());
+    } else {
+        info!("── Event{} resumed at step index {} ──", id_str, sequence.pc);
+    }
     let qb = game_assets.quests();
-    let mut pc = 0usize; // program counter (index into steps vec)
+    let mut pc = sequence.pc; // program counter (index into steps vec)
+    let mut wait = None;
     let mut iterations = 0u32;
     const MAX_ITERATIONS: u32 = 500;
 
@@ -197,6 +233,7 @@ pub(crate) fn process_events(
             }
             GameEvent::ShowMessage { str_id, text } => {
                 debug!("ShowMessage(id={}): {}", str_id, text);
+                // Only shown: the script carries on without waiting for the player.
                 ui.footer.set_status(text, 4.0, time.elapsed_secs_f64());
             }
             GameEvent::ShowFace { player, expression } => {
@@ -208,12 +245,19 @@ pub(crate) fn process_events(
                     player, anim_id
                 );
             }
-            GameEvent::PlayVideo { name, skippable: _ } => {
-                // TODO: wire PlayVideo through the screen system (InlineVideo).
-                warn!("PlayVideo('{}') — not yet wired to screen runtime", name);
+            GameEvent::PlayVideo { name, skippable } => {
+                if let Some(ref mut sa) = screen_actions {
+                    sa.write(crate::screens::runtime::ScreenActions {
+                        actions: vec![format!("PlayVideo(\"{}\", {})", name, *skippable as u8)],
+                    });
+                    wait = Some(EvtWait::VideoEnded);
+                    break;
+                }
+                warn!("PlayVideo('{}') — no screen runtime, skipped", name);
             }
             GameEvent::PressAnyKey => {
-                debug!("Marker: PressAnyKey");
+                wait = Some(EvtWait::KeyPress);
+                break;
             }
 
             // ── Sound ────────────────────────────────────────────────
@@ -233,12 +277,24 @@ pub(crate) fn process_events(
                     &mut cursor_query,
                     &time,
                 );
+                if !matches!(ui.mode, UiMode::World) {
+                    wait = Some(EvtWait::DialogClosed);
+                    break;
+                }
             }
             GameEvent::OpenChest { id } => {
-                event_handlers::handle_open_chest(*id, &mut audio);
-                if let Some(ref mut sa) = screen_actions {
+                let opened = event_handlers::handle_open_chest(
+                    *id,
+                    &mut audio,
+                    &mut party,
+                    &game_assets,
+                    &world_state.map.name,
+                    &mut ui,
+                    time.elapsed_secs_f64(),
+                );
+                if opened && let Some(ref mut sa) = screen_actions {
                     sa.write(crate::screens::runtime::ScreenActions {
-                        actions: vec!["ShowScreen(\"chest\")".to_string()],
+                        actions: vec!["ShowChest()".to_string()],
                     });
                 }
             }
@@ -388,11 +444,19 @@ pub(crate) fn process_events(
             GameEvent::SetSnow { on } => {
                 info!("SetSnow: on={} (no weather system)", on);
             }
+            // `face_id` is a cog number: both act on every face of the group.
             GameEvent::SetFacesBit { face_id, bit, on } => {
-                stub_event!("SetFacesBit", "face={} bit=0x{:x} on={}", face_id, bit, on);
+                audio.indoor_faces.write(ApplyIndoorFaces::Bits {
+                    cog: *face_id,
+                    bits: *bit as u32,
+                    on: *on != 0,
+                });
             }
             GameEvent::SetTexture { face_id, texture_name } => {
-                stub_event!("SetTexture", "face={} tex='{}'", face_id, texture_name);
+                audio.indoor_faces.write(ApplyIndoorFaces::Texture {
+                    cog: *face_id,
+                    texture_name: texture_name.clone(),
+                });
             }
             GameEvent::SetSprite {
                 decoration_id,
@@ -424,10 +488,26 @@ pub(crate) fn process_events(
                 });
             }
             GameEvent::ToggleIndoorLight { light_id, on } => {
-                stub_event!("ToggleIndoorLight", "light={} on={}", light_id, on);
+                let switched = audio
+                    .indoor_lights
+                    .as_mut()
+                    .is_some_and(|lights| lights.set_enabled(*light_id as usize, *on != 0));
+                if switched {
+                    info!("ToggleIndoorLight: light={} on={}", light_id, on);
+                } else {
+                    warn!("ToggleIndoorLight: no light {} on this map", light_id);
+                }
             }
             GameEvent::ToggleChestFlag { chest_id, flag, on } => {
-                stub_event!("ToggleChestFlag", "chest={} flag=0x{:x} on={}", chest_id, flag, on);
+                let toggled = audio
+                    .chests
+                    .as_mut()
+                    .is_some_and(|chests| chests.set_flags(*chest_id as usize, *flag as u16, *on != 0));
+                if toggled {
+                    info!("ToggleChestFlag: chest={} flag=0x{:x} on={}", chest_id, flag, on);
+                } else {
+                    warn!("ToggleChestFlag: no chest {} on this map", chest_id);
+                }
             }
             GameEvent::StopAnimation { decoration_id } => {
                 stub_event!("StopAnimation", "deco={}", decoration_id);
@@ -446,16 +526,25 @@ pub(crate) fn process_events(
                     &audio,
                     &world_state,
                 );
+                if !matches!(ui.mode, UiMode::World) {
+                    wait = Some(EvtWait::DialogClosed);
+                    break;
+                }
             }
             GameEvent::SetNPCTopic {
                 npc_id,
                 topic_index,
                 event_id,
             } => {
-                stub_event!("SetNPCTopic", "npc={} topic={} event={}", npc_id, topic_index, event_id);
+                info!("SetNPCTopic: npc={} topic={} event={}", npc_id, topic_index, event_id);
+                world_state
+                    .game_vars
+                    .npc_topics
+                    .insert((*npc_id, *topic_index as i32), *event_id);
             }
             GameEvent::MoveNPC { npc_id, map_id } => {
-                stub_event!("MoveNPC", "npc={} map_id={}", npc_id, map_id);
+                info!("MoveNPC: npc={} map_id={}", npc_id, map_id);
+                world_state.game_vars.npc_locations.insert(*npc_id, *map_id);
             }
             GameEvent::SetNPCGreeting { npc_id, greeting_id } => {
                 info!("SetNPCGreeting: npc={} greeting={}", npc_id, greeting_id);
@@ -608,33 +697,16 @@ pub(crate) fn process_events(
             GameEvent::OnMapLeave => {
                 debug!("Marker: OnMapLeave");
             }
-            GameEvent::OnTimer {
-                year,
-                month,
-                week,
-                day,
-                hour,
-                minute,
-            } => {
-                stub_event!(
-                    "OnTimer trigger",
-                    "{:04}-{:02}-{:02} (week {}) {:02}:{:02}",
-                    year,
-                    month,
-                    day,
-                    week,
-                    hour,
-                    minute
-                );
-            }
-            GameEvent::OnLongTimer { timer_data } => {
-                stub_event!("OnLongTimer trigger", "data={:02x?}", timer_data);
-            }
-            GameEvent::OnDateTimer { timer_data } => {
-                stub_event!("OnDateTimer", "data={:02x?}", timer_data);
+            GameEvent::OnTimer { .. } | GameEvent::OnLongTimer { .. } | GameEvent::OnDateTimer { .. } => {
+                // Armed by `register_map_timers`; reaching one inline is a no-op.
+                debug!("Marker: {}", event);
             }
             GameEvent::EnableDateTimer { timer_id, on } => {
-                stub_event!("EnableDateTimer", "id={} on={}", timer_id, on);
+                info!("EnableDateTimer: event={} on={}", timer_id, on);
+                let now = audio.game_time.as_ref().map(|gt| gt.total_minutes()).unwrap_or(0);
+                audio.timers.set_enabled(*timer_id as u16, *on != 0, now);
+                let map_key = world_state.map.name.to_string();
+                world_state.game_vars.evt_timers.insert(map_key, audio.timers.record());
             }
 
             // ── Dialogue markers ─────────────────────────────────────
@@ -659,7 +731,10 @@ pub(crate) fn process_events(
                 stub_event!("ChangeEvent", "target={} event={}", target, new_event_id);
             }
             GameEvent::InputString { params } => {
-                stub_event!("InputString", "params={:02x?}", params);
+                // No text entry UI yet: pause until the player acknowledges.
+                stub_event!("InputString", "params={:02x?} (waiting for a key press)", params);
+                wait = Some(EvtWait::KeyPress);
+                break;
             }
             GameEvent::Unhandled {
                 opcode,
@@ -673,4 +748,10 @@ pub(crate) fn process_events(
             }
         }
     }
+
+    if let Some(wait) = wait {
+        debug!("  ⏸ waiting for {:?}", wait);
+        sequence.pc = pc;
+        event_queue.suspend(sequence, wait);
+    }
 }
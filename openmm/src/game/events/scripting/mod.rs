@@ -1,10 +1,12 @@
-//! EVT scripting engine: event queue, control flow, and event dispatch.
+//! EVT scripting engine: event queue, control flow, timers, and event dispatch.
 
 mod control_flow;
 pub(crate) mod dispatch;
 mod queue;
+mod timers;
 
-pub use queue::EventQueue;
+pub use queue::{EventQueue, EvtWait};
+pub use timers::EvtTimers;
 
 use crate::GameState;
 use bevy::prelude::*;
@@ -14,7 +16,20 @@ pub struct EventDispatchPlugin;
 impl Plugin for EventDispatchPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EventQueue>()
-            .add_systems(OnEnter(GameState::Game), dispatch::dispatch_on_map_reload)
-            .add_systems(Update, dispatch::process_events.run_if(in_state(GameState::Game)));
+            .init_resource::<EvtTimers>()
+            .add_systems(
+                OnEnter(GameState::Game),
+                (dispatch::dispatch_on_map_reload, timers::register_map_timers),
+            )
+            .add_systems(
+                Update,
+                (
+                    timers::fire_due_timers,
+                    dispatch::resume_waiting_events,
+                    dispatch::process_events,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Game)),
+            );
     }
 }
@@ -4,36 +4,80 @@ use openmm_data::evt::{EvtFile, EvtStep, GameEvent};
 
 use crate::game::sound::effects::PlayUiSoundEvent;
 
+/// What a suspended sequence is waiting for before it resumes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvtWait {
+    /// Any key or mouse button (PressAnyKey, InputString).
+    KeyPress,
+    /// The UI returning to `UiMode::World` (SpeakNPC, SpeakInHouse).
+    DialogClosed,
+    /// The `evt:VideoEnded()` screen action sent when a PlayVideo clip ends.
+    VideoEnded,
+}
+
 /// An event sequence — a list of steps from one event_id, executed as a script.
 #[derive(Clone)]
 pub(crate) struct EventSequence {
     pub event_id: Option<u16>,
     pub steps: Vec<EvtStep>,
+    /// Index of the next step to execute; kept across suspensions.
+    pub pc: usize,
 }
 
-/// Queue of event sequences waiting to be processed.
-/// Each sequence is executed in full (with control flow) in one frame.
+/// Queue of event sequences waiting to be processed, plus the one running.
+///
+/// Sequences run one at a time, like MM6's script VM: the running sequence
+/// executes until it ends or hits a wait (`EvtWait`), and while it waits it
+/// keeps its program counter and blocks the sequences queued behind it.
 #[derive(bevy::prelude::Resource, Default)]
 pub struct EventQueue {
     sequences: VecDeque<EventSequence>,
+    running: Option<(EventSequence, Option<EvtWait>)>,
 }
 
 impl EventQueue {
     /// Enqueue all steps for a given event_id from the EvtFile as a single sequence.
     pub fn push_all(&mut self, event_id: u16, evt: &EvtFile) {
-        if let Some(steps) = evt.events.get(&event_id)
+        if let Some(steps) = evt.events().get(&event_id)
             && !steps.is_empty()
         {
             self.sequences.push_back(EventSequence {
                 event_id: Some(event_id),
                 steps: steps.clone(),
+                pc: 0,
             });
         }
     }
 
-    /// Pop the next sequence from the front.
-    pub(crate) fn pop(&mut self) -> Option<EventSequence> {
-        self.sequences.pop_front()
+    /// Take the sequence to run this frame: the suspended one once its wait
+    /// has been cleared, otherwise the next queued one. `None` while waiting.
+    pub(crate) fn next_runnable(&mut self) -> Option<EventSequence> {
+        match self.running.take() {
+            Some((sequence, None)) => Some(sequence),
+            Some(waiting) => {
+                self.running = Some(waiting);
+                None
+            }
+            None => self.sequences.pop_front(),
+        }
+    }
+
+    /// Park `sequence` (its `pc` at the step after the waiting one) until
+    /// [`resume`](Self::resume) is called.
+    pub(crate) fn suspend(&mut self, sequence: EventSequence, wait: EvtWait) {
+        self.running = Some((sequence, Some(wait)));
+    }
+
+    /// What the running sequence is waiting for, if it is suspended.
+    pub fn waiting(&self) -> Option<EvtWait> {
+        self.running.as_ref().and_then(|(_, wait)| *wait)
+    }
+
+    /// Clear the running sequence's wait; it continues on the next frame.
+    pub fn resume(&mut self) {
+        if let Some((_, wait)) = &mut self.running {
+            *wait = None;
+        }
     }
 
     /// Enqueue a single synthesized event (not from an EvtFile).
@@ -41,29 +85,39 @@ impl EventQueue {
         self.sequences.push_back(EventSequence {
             event_id: None,
             steps: vec![EvtStep { step: 0, event }],
+            pc: 0,
         });
     }
 
     /// Enqueue steps from index `start` onward (used to skip lifecycle marker steps).
     pub fn push_from(&mut self, event_id: u16, evt: &EvtFile, start: usize) {
-        if let Some(steps) = evt.events.get(&event_id) {
+        if let Some(steps) = evt.events().get(&event_id) {
             let tail: Vec<_> = steps[start.min(steps.len())..].to_vec();
             if !tail.is_empty() {
                 self.sequences.push_back(EventSequence {
                     event_id: Some(event_id),
                     steps: tail,
+                    pc: 0,
                 });
             }
         }
     }
 
-    /// Clear all pending sequences.
+    /// Clear all pending sequences and abort the running one.
     pub fn clear(&mut self) {
         self.sequences.clear();
+        self.running = None;
+    }
+
+    /// Clear pending sequences but keep the running one, so a script that
+    /// opened a dialog carries on once the dialog is dismissed.
+    pub fn clear_pending(&mut self) {
+        self.sequences.clear();
     }
 
     /// Extract and play all PlaySound events, keeping everything else queued.
     /// Used during UI overlays so sounds play but other events survive.
+    /// The running sequence is left alone: removing steps would shift its `pc`.
     pub fn drain_sounds(&mut self, ui_sound: &mut bevy::ecs::message::MessageWriter<PlayUiSoundEvent>) {
         for seq in &mut self.sequences {
             seq.steps.retain(|step| {
@@ -79,3 +133,68 @@ impl EventQueue {
         self.sequences.retain(|seq| !seq.steps.is_empty());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hint(step: u8) -> EvtStep {
+        EvtStep {
+            step,
+            event: GameEvent::Hint {
+                str_id: 0,
+                text: String::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn suspended_sequence_blocks_the_queue_until_resumed() {
+        let mut queue = EventQueue::default();
+        queue.push_single(GameEvent::PressAnyKey);
+        queue.push_single(GameEvent::Exit);
+
+        let mut first = queue.next_runnable().unwrap();
+        first.pc = 1;
+        queue.suspend(first, EvtWait::KeyPress);
+        assert_eq!(queue.waiting(), Some(EvtWait::KeyPress));
+        assert!(queue.next_runnable().is_none());
+
+        queue.resume();
+        assert_eq!(queue.waiting(), None);
+        let resumed = queue.next_runnable().unwrap();
+        assert_eq!(resumed.pc, 1);
+        assert!(matches!(resumed.steps[0].event, GameEvent::PressAnyKey));
+        assert!(matches!(queue.next_runnable().unwrap().steps[0].event, GameEvent::Exit));
+    }
+
+    #[test]
+    fn clear_pending_keeps_the_waiting_sequence() {
+        let mut queue = EventQueue::default();
+        queue.sequences.push_back(EventSequence {
+            event_id: Some(7),
+            steps: vec![hint(0), hint(1)],
+            pc: 0,
+        });
+        queue.push_single(GameEvent::PressAnyKey);
+
+        let sequence = queue.next_runnable().unwrap();
+        queue.suspend(sequence, EvtWait::DialogClosed);
+        queue.clear_pending();
+        queue.resume();
+        assert_eq!(queue.next_runnable().unwrap().event_id, Some(7));
+        assert!(queue.next_runnable().is_none());
+
+        queue.suspend(
+            EventSequence {
+                event_id: None,
+                steps: vec![],
+                pc: 0,
+            },
+            EvtWait::VideoEnded,
+        );
+        queue.clear();
+        assert_eq!(queue.waiting(), None);
+        assert!(queue.next_runnable().is_none());
+    }
+}
@@ -0,0 +1,271 @@
+//! EVT timer scheduler: `OnTimer`/`OnLongTimer`/`OnDateTimer` steps armed on
+//! map entry and fired through the `EventQueue` as `GameTime` passes them.
+//!
+//! Each map's timer state is recorded in `game_vars.evt_timers` whenever it
+//! changes and saved in the map's extras, so a timer keeps its interval grid,
+//! its on/off switch and its missed occurrences across visits.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use openmm_data::evt::{EvtFile, TimerSchedule};
+use openmm_data::save::map_extras::MapTimer;
+
+use super::queue::EventQueue;
+use crate::game::events::events::MapEvents;
+use crate::game::state::{GameTime, WorldState};
+
+/// One armed timer: the event runs from the step after its timer marker.
+#[derive(Debug, Clone)]
+pub(crate) struct EvtTimer {
+    pub event_id: u16,
+    /// Index of the timer marker step in the event's step list.
+    pub marker: usize,
+    pub schedule: TimerSchedule,
+    /// Game minute the timer was first armed at (origin of interval timers).
+    pub armed_at: u64,
+    /// Game minute after which `next_due` was scheduled: the last fire, or
+    /// the arming or re-enabling if it has not fired since.
+    pub due_after: u64,
+    pub next_due: u64,
+    /// `OnDateTimer`s can be switched off and on by `EnableDateTimer`.
+    pub enabled: bool,
+}
+
+/// Timers of the current map, rebuilt on every map entry.
+#[derive(Resource, Default)]
+pub struct EvtTimers {
+    pub(crate) timers: Vec<EvtTimer>,
+}
+
+impl EvtTimers {
+    /// Arm a timer for each timer step in `evt`. Timers in `saved` (this
+    /// map's record from an earlier visit) resume where they were: an
+    /// occurrence that passed while the party was away is due at once.
+    /// New timers are armed at `now`.
+    pub(crate) fn arm(evt: &EvtFile, saved: Option<&BTreeMap<(u16, u16), MapTimer>>, now: u64) -> Self {
+        let mut ids: Vec<u16> = evt.events().keys().copied().collect();
+        ids.sort();
+        let mut timers = Vec::new();
+        for event_id in ids {
+            for (marker, step) in evt.events()[&event_id].iter().enumerate() {
+                let Some(schedule) = TimerSchedule::of(&step.event) else {
+                    continue;
+                };
+                let state = saved
+                    .and_then(|s| s.get(&(event_id, marker as u16)))
+                    .copied()
+                    .unwrap_or(MapTimer {
+                        armed_at: now,
+                        due_after: now,
+                        enabled: true,
+                    });
+                timers.push(EvtTimer {
+                    event_id,
+                    marker,
+                    schedule,
+                    armed_at: state.armed_at,
+                    due_after: state.due_after,
+                    next_due: schedule.next_after(state.due_after, state.armed_at),
+                    enabled: state.enabled,
+                });
+            }
+        }
+        Self { timers }
+    }
+
+    /// The state to keep for this map, keyed like [`MapExtras::timers`](openmm_data::save::MapExtras).
+    pub(crate) fn record(&self) -> BTreeMap<(u16, u16), MapTimer> {
+        self.timers
+            .iter()
+            .map(|t| {
+                let state = MapTimer {
+                    armed_at: t.armed_at,
+                    due_after: t.due_after,
+                    enabled: t.enabled,
+                };
+                ((t.event_id, t.marker as u16), state)
+            })
+            .collect()
+    }
+
+    /// `EnableDateTimer`: switch the timers of `event_id` off or on.
+    /// Re-enabling schedules the next occurrence after `now`, so a timer that
+    /// was off while its time passed does not fire late.
+    pub fn set_enabled(&mut self, event_id: u16, on: bool, now: u64) {
+        for timer in self.timers.iter_mut().filter(|t| t.event_id == event_id) {
+            if on && !timer.enabled {
+                timer.due_after = now;
+                timer.next_due = timer.schedule.next_after(now, timer.armed_at);
+            }
+            timer.enabled = on;
+        }
+    }
+
+    /// Timers due at `now`, each returned once however many occurrences a
+    /// time jump skipped, and rescheduled to their next occurrence after `now`.
+    pub(crate) fn take_due(&mut self, now: u64) -> Vec<(u16, usize)> {
+        let mut due = Vec::new();
+        for timer in self.timers.iter_mut().filter(|t| t.enabled && t.next_due <= now) {
+            due.push((timer.event_id, timer.marker));
+            timer.due_after = now;
+            timer.next_due = timer.schedule.next_after(now, timer.armed_at);
+        }
+        due
+    }
+}
+
+/// On every map entry, arm the timers of the map's events, resuming the ones
+/// recorded on an earlier visit.
+pub(crate) fn register_map_timers(
+    map_events: Option<Res<MapEvents>>,
+    game_time: Res<GameTime>,
+    mut world_state: ResMut<WorldState>,
+    mut timers: ResMut<EvtTimers>,
+) {
+    let Some(evt) = map_events.as_ref().and_then(|me| me.evt.as_ref()) else {
+        timers.timers.clear();
+        return;
+    };
+    let map_key = world_state.map.name.to_string();
+    *timers = EvtTimers::arm(
+        evt,
+        world_state.game_vars.evt_timers.get(&map_key),
+        game_time.total_minutes(),
+    );
+    for timer in &timers.timers {
+        info!("EVT timer: event {} {:?}", timer.event_id, timer.schedule);
+    }
+    world_state.game_vars.evt_timers.insert(map_key, timers.record());
+}
+
+/// Queue the events of every timer that came due, after resting or
+/// `time add` as well as in normal play.
+pub(crate) fn fire_due_timers(
+    map_events: Option<Res<MapEvents>>,
+    game_time: Res<GameTime>,
+    mut world_state: ResMut<WorldState>,
+    mut timers: ResMut<EvtTimers>,
+    mut event_queue: ResMut<EventQueue>,
+) {
+    let Some(evt) = map_events.as_ref().and_then(|me| me.evt.as_ref()) else {
+        return;
+    };
+    let due = timers.take_due(game_time.total_minutes());
+    if due.is_empty() {
+        return;
+    }
+    for (event_id, marker) in due {
+        info!("EVT timer fired: event {}", event_id);
+        event_queue.push_from(event_id, evt, marker + 1);
+    }
+    let map_key = world_state.map.name.to_string();
+    world_state.game_vars.evt_timers.insert(map_key, timers.record());
+}
+
+#[cfg(test)]
+mod tests {
+    use openmm_data::enums::EvtOpcode;
+    use openmm_data::evt::EvtRecord;
+    use openmm_data::save::MapExtras;
+    use openmm_data::utils::time::MINS_PER_DAY;
+
+    use super::*;
+
+    const HOUR: u64 = 60;
+
+    fn hourly(event_id: u16, now: u64) -> EvtTimer {
+        let schedule = TimerSchedule::Interval { minutes: 60 };
+        EvtTimer {
+            event_id,
+            marker: 0,
+            schedule,
+            armed_at: now,
+            due_after: now,
+            next_due: schedule.next_after(now, now),
+            enabled: true,
+        }
+    }
+
+    /// A map whose only event runs the timer step `opcode` with `params`.
+    fn timer_map(event_id: u16, opcode: EvtOpcode, params: Vec<u8>) -> EvtFile {
+        let marker = EvtRecord {
+            event_id,
+            step: 0,
+            opcode: opcode as u8,
+            params,
+        };
+        EvtFile::from_records(vec![marker], Vec::new(), Vec::new())
+    }
+
+    /// A map whose event 7 runs every day at 06:00.
+    fn daily_at_six() -> EvtFile {
+        timer_map(7, EvtOpcode::OnLongTimer, vec![0, 0, 0, 6, 0, 0, 0, 0])
+    }
+
+    /// Leave the map: its record goes through the save's map extras.
+    fn leave(timers: &EvtTimers) -> BTreeMap<(u16, u16), MapTimer> {
+        let extras = MapExtras {
+            timers: timers.record(),
+            ..Default::default()
+        };
+        MapExtras::parse(&extras.to_bytes()).unwrap().timers
+    }
+
+    #[test]
+    fn fires_once_per_jump_and_honours_enable() {
+        let mut timers = EvtTimers {
+            timers: vec![hourly(5, 0), hourly(6, 0)],
+        };
+        assert!(timers.take_due(59).is_empty());
+        assert_eq!(timers.take_due(60), vec![(5, 0), (6, 0)]);
+
+        // A 10 hour rest fires each timer once and keeps the hourly grid.
+        assert_eq!(timers.take_due(11 * 60 + 30), vec![(5, 0), (6, 0)]);
+        assert_eq!(timers.timers[0].next_due, 12 * 60);
+
+        timers.set_enabled(6, false, 11 * 60 + 30);
+        assert_eq!(timers.take_due(13 * 60), vec![(5, 0)]);
+        timers.set_enabled(6, true, 13 * 60);
+        assert_eq!(timers.timers[1].next_due, 14 * 60);
+    }
+
+    #[test]
+    fn switched_off_timer_stays_off_after_reentry() {
+        let evt = daily_at_six();
+        let mut timers = EvtTimers::arm(&evt, None, 0);
+        timers.set_enabled(7, false, HOUR);
+
+        let saved = leave(&timers);
+        let mut timers = EvtTimers::arm(&evt, Some(&saved), 2 * MINS_PER_DAY);
+        assert!(!timers.timers[0].enabled);
+        assert!(timers.take_due(3 * MINS_PER_DAY).is_empty());
+    }
+
+    #[test]
+    fn missed_occurrence_fires_once_on_reentry() {
+        let evt = daily_at_six();
+        // Armed at 08:00 on day 0 and left before it fired.
+        let timers = EvtTimers::arm(&evt, None, 8 * HOUR);
+        let saved = leave(&timers);
+
+        // Back three days later at 01:00: three 06:00s passed, one fire.
+        let now = 3 * MINS_PER_DAY + HOUR;
+        let mut timers = EvtTimers::arm(&evt, Some(&saved), now);
+        assert_eq!(timers.take_due(now), vec![(7, 0)]);
+        assert_eq!(timers.timers[0].next_due, 3 * MINS_PER_DAY + 6 * HOUR);
+        assert!(timers.take_due(now + HOUR).is_empty());
+    }
+
+    #[test]
+    fn interval_grid_survives_reentry() {
+        // Every 90 half-minutes: 45 minutes.
+        let evt = timer_map(9, EvtOpcode::OnTimer, vec![0, 0, 0, 0, 0, 0, 90, 0]);
+        let timers = EvtTimers::arm(&evt, None, 10);
+        let saved = leave(&timers);
+        let timers = EvtTimers::arm(&evt, Some(&saved), 30);
+        // Still on the grid from minute 10, not restarted at 30.
+        assert_eq!(timers.timers[0].next_due, 55);
+    }
+}
@@ -167,7 +167,8 @@ fn facing_rotation(cam_origin: Vec3, cam_forward: Vec3, center: Vec3) -> Quat {
 // --- Systems ---
 
 /// Handle exit input when an overlay UI is active.
-/// Clears the EventQueue to discard any events that were queued alongside the now-dismissed UI.
+/// Clears the EventQueue to discard any events that were queued alongside the now-dismissed UI;
+/// a script suspended on the dialog keeps running.
 fn interaction_input(
     keys: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
@@ -177,7 +178,7 @@ fn interaction_input(
     mut event_queue: ResMut<EventQueue>,
 ) {
     if check_exit_input(&keys, &gamepads) {
-        event_queue.clear();
+        event_queue.clear_pending();
         commands.remove_resource::<OverlayImage>();
         commands.remove_resource::<crate::game::actors::npc_dialogue::NpcPortrait>();
         commands.remove_resource::<crate::game::actors::npc_dialogue::NpcProfile>();
@@ -8,7 +8,7 @@ use crate::game::sprites::loading::AlphaMask;
 /// the EVT step order determines priority, not event type.
 /// Recognised events: Hint, StatusText, LocationName, SpeakInHouse, OpenChest, MoveToMap.
 pub fn resolve_event_name_from_evt(event_id: u16, evt: &openmm_data::evt::EvtFile) -> Option<String> {
-    let steps = evt.events.get(&event_id)?;
+    let steps = evt.events().get(&event_id)?;
     for s in steps {
         let text = match &s.event {
             openmm_data::evt::GameEvent::Hint { text, .. } if !text.is_empty() => text.clone(),
@@ -33,7 +33,7 @@ pub fn resolve_event_name(event_id: u16, map_events: &Option<Res<MapEvents>>) ->
     let evt = me.evt.as_ref()?;
 
     // For SpeakInHouse, prefer the loaded house name over the generic "Building #N"
-    if let Some(steps) = evt.events.get(&event_id) {
+    if let Some(steps) = evt.events().get(&event_id) {
         for s in steps {
             if let openmm_data::evt::GameEvent::SpeakInHouse { house_id } = &s.event {
                 if let Some(houses) = me.houses.as_ref()
@@ -158,6 +158,9 @@ pub fn billboard_hit_test(
 
 #[cfg(test)]
 mod tests {
+    use openmm_data::enums::EvtOpcode;
+    use openmm_data::evt::{EvtFile, EvtRecord};
+
     use super::*;
 
     #[test]
@@ -178,79 +181,43 @@ mod tests {
         assert!(t.is_none());
     }
 
+    /// An .evt of `(event, step, opcode, str id)` records over `strings`.
+    fn evt_with_text(records: &[(u16, u8, EvtOpcode, u8)], strings: &[&str]) -> EvtFile {
+        let records = records
+            .iter()
+            .map(|&(event_id, step, opcode, str_id)| EvtRecord {
+                event_id,
+                step,
+                opcode: opcode as u8,
+                params: vec![str_id],
+            })
+            .collect();
+        let strings = strings.iter().map(|s| s.to_string()).collect();
+        EvtFile::from_records(records, strings, Vec::new())
+    }
+
     #[test]
     fn resolve_event_name_first_match_wins() {
-        use openmm_data::evt::{EvtFile, EvtStep, GameEvent};
-        use std::collections::HashMap;
-        let mut events: HashMap<u16, Vec<EvtStep>> = HashMap::new();
-        events.insert(
-            1,
-            vec![
-                EvtStep {
-                    step: 0,
-                    event: GameEvent::StatusText {
-                        str_id: 0,
-                        text: "status".into(),
-                    },
-                },
-                EvtStep {
-                    step: 1,
-                    event: GameEvent::Hint {
-                        str_id: 0,
-                        text: "hint".into(),
-                    },
-                },
-            ],
+        let evt = evt_with_text(
+            &[(1, 0, EvtOpcode::StatusText, 0), (1, 1, EvtOpcode::MouseOver, 1)],
+            &["status", "hint"],
         );
-        let evt = EvtFile { events };
         // First non-empty match wins in step order
         assert_eq!(resolve_event_name_from_evt(1, &evt), Some("status".to_string()));
     }
 
     #[test]
     fn resolve_event_name_hint_only() {
-        use openmm_data::evt::{EvtFile, EvtStep, GameEvent};
-        use std::collections::HashMap;
-        let mut events: HashMap<u16, Vec<EvtStep>> = HashMap::new();
-        events.insert(
-            2,
-            vec![EvtStep {
-                step: 0,
-                event: GameEvent::Hint {
-                    str_id: 0,
-                    text: "hint".into(),
-                },
-            }],
-        );
-        let evt = EvtFile { events };
+        let evt = evt_with_text(&[(2, 0, EvtOpcode::MouseOver, 0)], &["hint"]);
         assert_eq!(resolve_event_name_from_evt(2, &evt), Some("hint".to_string()));
     }
 
     #[test]
     fn resolve_event_name_empty_text_skipped() {
-        use openmm_data::evt::{EvtFile, EvtStep, GameEvent};
-        use std::collections::HashMap;
-        let mut events: HashMap<u16, Vec<EvtStep>> = HashMap::new();
-        events.insert(
-            3,
-            vec![
-                EvtStep {
-                    step: 0,
-                    event: GameEvent::Hint {
-                        str_id: 0,
-                        text: "".into(),
-                    },
-                },
-                EvtStep {
-                    step: 1,
-                    event: GameEvent::StatusText {
-                        str_id: 0,
-                        text: "real".into(),
-                    },
-                },
-            ],
+        let evt = evt_with_text(
+            &[(3, 0, EvtOpcode::MouseOver, 0), (3, 1, EvtOpcode::StatusText, 1)],
+            &["", "real"],
         );
-        let evt = EvtFile { events };
         assert_eq!(resolve_event_name_from_evt(3, &evt), Some("real".to_string()));
     }
 
@@ -0,0 +1,394 @@
+//! Map chests, opened by EVT `OpenChest` and shown on the `chest` screen.
+//!
+//! Chest contents come from the MapChests section of the map's DDM/DLV and
+//! are written back into it on snapshot, so looted chests stay looted until
+//! the map refills. Random item placeholders are rolled on first open. A
+//! trapped chest is disarmed by the party's best Disarm Trap skill against
+//! the map's mapstats lock, or goes off for `trap_d20_count` d20 damage.
+//! Chests locked by scripts are kept apart from the chest records, in the
+//! map's save extras, since MM6 chests have no lock bit.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use bevy::ecs::message::{MessageReader, MessageWriter};
+use bevy::prelude::*;
+
+use openmm_data::assets::map_delta::{ChestFlags, ItemRecord, MapChest, item_cell_span};
+use openmm_data::dchest::ChestDesc;
+use openmm_data::enums::{EvtTargetCharacter, EvtVariable};
+use openmm_data::items::ItemInfo;
+use openmm_data::mapstats::MapInfo;
+
+use crate::GameState;
+use crate::assets::GameAssets;
+use crate::game::player::party::Party;
+use crate::game::player::party::creation::SplitMix64;
+use crate::game::state::WorldState;
+use crate::game::ui::UiState;
+use crate::screens::runtime::{ScreenActionEvent, ScreenActions};
+use crate::screens::{ElementState, ImageElement, Screen, ScreenElement};
+
+/// Screen definition the chest window is built from.
+const CHEST_SCREEN: &str = "chest";
+/// Element id of the chest picture in `chest.ron`, swapped per chest.
+const BACKGROUND_ID: &str = "icons/CHEST01";
+/// Top-left corner of the chest picture on screen.
+const CHEST_WINDOW: (f32, f32) = (8.0, 8.0);
+/// First grid cell of each chest picture, relative to the picture.
+const GRID_ORIGINS: [(f32, f32); 8] = [
+    (42.0, 34.0),
+    (18.0, 30.0),
+    (18.0, 30.0),
+    (42.0, 34.0),
+    (42.0, 34.0),
+    (42.0, 34.0),
+    (18.0, 30.0),
+    (42.0, 34.0),
+];
+/// Inventory cell size in pixels.
+const CELL: f32 = 32.0;
+/// Grid size used when dchest.bin has no row for a chest.
+const DEFAULT_GRID: (usize, usize) = (9, 9);
+/// `ToggleChestFlag` bit that locks a chest. It is not a [`ChestFlags`] bit
+/// and never reaches the DDM/DLV record.
+pub const LOCK_FLAG: u16 = 0x0010;
+
+/// The current map's chests, indexed by the `OpenChest` chest id.
+#[derive(Resource, Default)]
+pub struct MapChests {
+    chests: Vec<MapChest>,
+    /// Indices of chests locked by scripts, saved in the map's extras.
+    locked: BTreeSet<usize>,
+    /// Chest shown on the chest screen.
+    open: Option<usize>,
+}
+
+impl MapChests {
+    pub(crate) fn new(chests: Vec<MapChest>, locked: BTreeSet<usize>) -> Self {
+        Self {
+            chests,
+            locked,
+            open: None,
+        }
+    }
+
+    pub(crate) fn chests(&self) -> &[MapChest] {
+        &self.chests
+    }
+
+    pub(crate) fn locked(&self) -> &BTreeSet<usize> {
+        &self.locked
+    }
+
+    /// `ToggleChestFlag`: set or clear `flag` on chest `id`; [`LOCK_FLAG`]
+    /// locks or unlocks it, other bits go to its [`ChestFlags`]. Returns
+    /// false for an id the map has no chest for.
+    pub fn set_flags(&mut self, id: usize, flag: u16, on: bool) -> bool {
+        let Some(chest) = self.chests.get_mut(id) else {
+            return false;
+        };
+        chest.set_flags(ChestFlags::from_bits_retain(flag & !LOCK_FLAG), on);
+        if flag & LOCK_FLAG != 0 {
+            if on {
+                self.locked.insert(id);
+            } else {
+                self.locked.remove(&id);
+            }
+        }
+        true
+    }
+}
+
+/// What the party ran into opening a chest.
+pub(crate) enum ChestOpening {
+    /// The map has no chest with that id.
+    Missing,
+    /// The chest is locked shut.
+    Locked,
+    /// The chest is open; `trap` says how its trap, if any, went.
+    Opened { trap: Option<TrapOutcome> },
+}
+
+pub(crate) enum TrapOutcome {
+    Disarmed,
+    /// The trap went off, hitting every party member for this much damage.
+    Exploded(i32),
+}
+
+/// Whether a raw Disarm Trap skill byte (level in the low 6 bits, 0x40 expert,
+/// 0x80 master) beats a mapstats lock of `lock` (0-10, ×5 difficulty).
+pub(crate) fn disarm_succeeds(skill: u8, lock: u8) -> bool {
+    let level = (skill & 0x3F) as u32;
+    let multiplier = if skill & 0x80 != 0 {
+        3
+    } else if skill & 0x40 != 0 {
+        2
+    } else {
+        1
+    };
+    level > 0 && level * multiplier >= lock as u32 * 5
+}
+
+/// Open chest `id`: spring or disarm its trap, roll its contents on first
+/// open, and remember it as the chest the chest screen shows.
+pub(crate) fn open_chest(
+    chests: &mut MapChests,
+    id: usize,
+    party: &mut Party,
+    map: Option<&MapInfo>,
+    game_assets: &GameAssets,
+) -> ChestOpening {
+    if chests.locked.contains(&id) && id < chests.chests.len() {
+        return ChestOpening::Locked;
+    }
+    let Some(chest) = chests.chests.get_mut(id) else {
+        return ChestOpening::Missing;
+    };
+    let flags = chest.flags();
+
+    let mut rng = SplitMix64::seeded();
+    let trap = flags.contains(ChestFlags::TRAPPED).then(|| {
+        chest.set_flags(ChestFlags::TRAPPED, false);
+        let skill = party.max_skill(EvtTargetCharacter::Party, EvtVariable::SKILL_DISARM_TRAP);
+        if disarm_succeeds(skill, map.map_or(0, |m| m.lock)) {
+            return TrapOutcome::Disarmed;
+        }
+        let dice = map.map_or(0, |m| m.trap_d20_count);
+        let damage: i32 = (0..dice).map(|_| rng.index(20) as i32 + 1).sum();
+        for member in party.members.iter_mut().filter(|m| !m.is_dead()) {
+            member.receive_damage(damage);
+        }
+        TrapOutcome::Exploded(damage)
+    });
+
+    if let Some(table) = game_assets.items() {
+        let (cols, rows) = grid_size(chest_desc(chest, game_assets));
+        chest.generate_items(
+            map.map_or(0, |m| m.treasure_level),
+            table,
+            cols,
+            rows,
+            |info| item_size(info, game_assets),
+            |n| rng.index(n),
+        );
+    }
+    chest.set_flags(ChestFlags::OPENED, true);
+    chests.open = Some(id);
+    ChestOpening::Opened { trap }
+}
+
+fn chest_desc<'a>(chest: &MapChest, game_assets: &'a GameAssets) -> Option<&'a ChestDesc> {
+    game_assets.chests()?.chests.get(chest.picture as usize)
+}
+
+fn grid_size(desc: Option<&ChestDesc>) -> (usize, usize) {
+    desc.map_or(DEFAULT_GRID, |d| (d.width as usize, d.height as usize))
+}
+
+/// Icon size of an item in pixels.
+fn icon_pixels(info: &ItemInfo, game_assets: &GameAssets) -> (u32, u32) {
+    game_assets
+        .lod()
+        .icon(&info.pic_file)
+        .map_or((CELL as u32, CELL as u32), |img| (img.width(), img.height()))
+}
+
+/// Inventory cells (columns, rows) an item covers.
+pub(crate) fn item_size(info: &ItemInfo, game_assets: &GameAssets) -> (usize, usize) {
+    let (w, h) = icon_pixels(info, game_assets);
+    (item_cell_span(w), item_cell_span(h))
+}
+
+fn item_element_id(slot: usize) -> String {
+    format!("chest_item_{slot}")
+}
+
+fn item_name(item: &ItemRecord, info: &ItemInfo) -> String {
+    if item.is_identified() || info.is_gold() {
+        info.name.clone()
+    } else {
+        info.not_identified_name.clone()
+    }
+}
+
+/// The chest screen for the open chest: `chest.ron` with the chest's picture
+/// and one clickable element per stored item.
+pub(crate) fn chest_screen(chests: &MapChests, game_assets: &GameAssets) -> Option<Screen> {
+    let chest = chests.open.and_then(|id| chests.chests.get(id))?;
+    let mut screen = crate::screens::load_screen(CHEST_SCREEN)
+        .inspect_err(|e| warn!("chest screen: {e}"))
+        .ok()?;
+    let desc = chest_desc(chest, game_assets);
+    let picture = desc.map_or(0, |d| d.image_index.max(0) as usize);
+    if let Some(ScreenElement::Image(background)) = screen.elements.iter_mut().find(|e| e.id() == BACKGROUND_ID)
+        && let Some(state) = background.states.get_mut("default")
+    {
+        state.texture = format!("icons/CHEST{:02}", picture + 1);
+    }
+
+    let (cols, _) = grid_size(desc);
+    let origin = GRID_ORIGINS.get(picture).copied().unwrap_or(GRID_ORIGINS[0]);
+    let items = game_assets.items()?;
+    for (slot, item) in chest.stored_items() {
+        let (Some(cell), Some(info)) = (chest.item_cell(slot), items.get(item.number as u16)) else {
+            continue;
+        };
+        let (w, h) = icon_pixels(info, game_assets);
+        let (cw, ch) = (item_cell_span(w) as f32, item_cell_span(h) as f32);
+        let x = CHEST_WINDOW.0 + origin.0 + (cell % cols) as f32 * CELL + (cw * CELL - w as f32) / 2.0;
+        let y = CHEST_WINDOW.1 + origin.1 + (cell / cols) as f32 * CELL + (ch * CELL - h as f32) / 2.0;
+        let texture = format!("icons/{}", info.pic_file);
+        screen.elements.push(ScreenElement::Image(ImageElement {
+            id: item_element_id(slot),
+            position: (x, y),
+            size: (w as f32, h as f32),
+            z: 12,
+            states: BTreeMap::from([(
+                "default".to_string(),
+                ElementState {
+                    texture,
+                    condition: String::new(),
+                    transparent_color: String::new(),
+                    animation: None,
+                },
+            )]),
+            on_click: vec![format!("TakeChestItem(\"{slot}\")")],
+            on_hover: vec![format!("evt:Hint(\"{}\")", item_name(item, info))],
+            bindings: BTreeMap::new(),
+            hidden: false,
+            animation: None,
+            transparent_color: "cyan".to_string(),
+            crop_w: 0.0,
+            crop_h: 0.0,
+            crop: false,
+            click_sound: String::new(),
+        }));
+    }
+    Some(screen)
+}
+
+/// Move item `slot` of `chest` to the party: gold into the purse, anything
+/// else into the first backpack with room. Returns the item's name, or
+/// `None` if nobody has room (the item stays in the chest).
+fn take_item(
+    chest: &mut MapChest,
+    slot: usize,
+    party: &mut Party,
+    world_state: &mut WorldState,
+    game_assets: &GameAssets,
+) -> Option<String> {
+    let item = *chest.items.get(slot).filter(|i| !i.is_empty())?;
+    let info = game_assets.items()?.get(item.number as u16)?;
+    if info.is_gold() {
+        world_state.game_vars.gold += item.charges;
+    } else {
+        let size = item_size(info, game_assets);
+        if !party.members.iter_mut().any(|m| m.add_item(item, size)) {
+            return None;
+        }
+    }
+    chest.take_item(slot);
+    Some(item_name(&item, info))
+}
+
+/// Handle `TakeChestItem("slot")` and `TakeAllChestItems()` from the chest screen.
+fn handle_chest_actions(
+    mut events: MessageReader<ScreenActionEvent>,
+    chests: Option<ResMut<MapChests>>,
+    mut party: ResMut<Party>,
+    mut world_state: ResMut<WorldState>,
+    mut ui: ResMut<UiState>,
+    mut actions: MessageWriter<ScreenActions>,
+    game_assets: Res<GameAssets>,
+    time: Res<Time>,
+) {
+    use crate::screens::scripting::parse_string_arg;
+
+    let Some(mut chests) = chests else {
+        events.clear();
+        return;
+    };
+    let Some(chest) = chests.open.and_then(|id| chests.chests.get_mut(id)) else {
+        events.clear();
+        return;
+    };
+
+    for ScreenActionEvent(action) in events.read() {
+        let s = action.trim();
+        let slots: Vec<usize> = if let Some(slot) = parse_string_arg(s, "TakeChestItem").and_then(|n| n.parse().ok()) {
+            vec![slot]
+        } else if s == "TakeAllChestItems()" {
+            chest.stored_items().map(|(slot, _)| slot).collect()
+        } else {
+            continue;
+        };
+
+        let mut hidden = Vec::new();
+        let mut last_taken = None;
+        for slot in slots {
+            match take_item(chest, slot, &mut party, &mut world_state, &game_assets) {
+                Some(name) => {
+                    hidden.push(format!("HideSprite(\"{}\")", item_element_id(slot)));
+                    last_taken = Some(name);
+                }
+                None => {
+                    ui.footer
+                        .set_status("Your packs are full!", 2.0, time.elapsed_secs_f64());
+                    break;
+                }
+            }
+        }
+        if let Some(name) = last_taken {
+            info!("chest: took {} item(s), last '{}'", hidden.len(), name);
+            if hidden.len() == 1 {
+                ui.footer.set_status(&name, 2.0, time.elapsed_secs_f64());
+            }
+        }
+        if !hidden.is_empty() {
+            actions.write(ScreenActions { actions: hidden });
+        }
+    }
+}
+
+pub struct ChestsPlugin;
+
+impl Plugin for ChestsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, handle_chest_actions.run_if(in_state(GameState::Game)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disarm_scales_with_mastery() {
+        // Lock 2 needs an effective skill of 10.
+        assert!(!disarm_succeeds(9, 2));
+        assert!(disarm_succeeds(10, 2));
+        assert!(disarm_succeeds(0x40 | 5, 2));
+        assert!(disarm_succeeds(0x80 | 4, 2));
+        // No skill never disarms, even an unlocked trap.
+        assert!(!disarm_succeeds(0, 0));
+        assert!(disarm_succeeds(1, 0));
+    }
+
+    #[test]
+    fn trap_damage_knocks_out_then_kills() {
+        use crate::game::player::party::member::{Class, PartyMember};
+        use crate::game::player::party::portrait::PortraitId;
+
+        let mut member = PartyMember::new("Zoltan", Class::default(), PortraitId::MaleA, 1);
+        member.hp = 10;
+        member.base_attrs[3] = 15; // Endurance
+        member.receive_damage(12);
+        assert_eq!(member.hp, -2);
+        assert_eq!(member.get_var(EvtVariable::COND_UNCONSCIOUS), 1);
+        assert!(!member.is_dead());
+
+        member.receive_damage(13);
+        assert_eq!(member.hp, -15);
+        assert!(member.is_dead());
+    }
+}
@@ -0,0 +1,205 @@
+//! Runtime face changes for indoor maps, driven by EVT `SetFacesBit` and `SetTexture`.
+//!
+//! The map's BLV stays loaded in [`IndoorFaces`]. A change edits it, records
+//! the face in `GameVariables` (re-applied when the map is rebuilt, and
+//! written into the DLV on snapshot), then rebuilds what the change touches:
+//! the static meshes of the affected textures, door face entities, collision
+//! and the clickable / touch / occluder face sets.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::ecs::message::{Message, MessageReader};
+use bevy::prelude::*;
+
+use openmm_data::blv::Blv;
+
+use crate::assets::GameAssets;
+use crate::game::map::CurrentMap;
+use crate::game::state::WorldState;
+use crate::game::state::state::GameVariables;
+use crate::prepare::build_indoor::{
+    extract_blv_collision, extract_clickable_faces, extract_occluder_faces, extract_touch_trigger_faces,
+};
+use crate::prepare::loading::{indoor_face_material, prepare_sub_mesh};
+
+use super::spawn::{build_clickable_faces, build_occluder_faces, build_touch_triggers, spawn_static_sub_mesh};
+use super::types::{DoorFace, IndoorSubMesh, TouchTriggerFaces};
+
+/// The loaded BLV with its live face attributes and textures.
+#[derive(Resource)]
+pub struct IndoorFaces {
+    pub(crate) blv: Blv,
+    /// Faces spawned as animated door entities rather than static meshes.
+    door_faces: HashSet<usize>,
+    /// Texture pixel sizes for UV generation, grown as textures are swapped in.
+    texture_sizes: HashMap<String, (u32, u32)>,
+}
+
+impl IndoorFaces {
+    pub(crate) fn new(blv: Blv, door_faces: HashSet<usize>, texture_sizes: HashMap<String, (u32, u32)>) -> Self {
+        Self {
+            blv,
+            door_faces,
+            texture_sizes,
+        }
+    }
+}
+
+/// Message to change the faces of an indoor cog group at runtime.
+#[derive(Message)]
+pub enum ApplyIndoorFaces {
+    /// `SetFacesBit`: set (`on`) or clear attribute `bits` on every face of `cog`.
+    Bits { cog: i32, bits: u32, on: bool },
+    /// `SetTexture`: draw every face of `cog` with `texture_name`.
+    Texture { cog: i32, texture_name: String },
+}
+
+/// Re-apply the recorded face changes for `map_key` to a freshly loaded BLV.
+pub(crate) fn apply_face_overrides(blv: &mut Blv, vars: &GameVariables, map_key: &str) {
+    for (&face, &attributes) in vars.face_attributes.get(map_key).into_iter().flatten() {
+        if let Some(f) = blv.faces.get_mut(face) {
+            f.attributes = attributes;
+        }
+    }
+    for (&face, texture) in vars.face_textures.get(map_key).into_iter().flatten() {
+        blv.set_face_texture(face, texture);
+    }
+}
+
+/// Handle `ApplyIndoorFaces` messages: edit the BLV, record the change and
+/// rebuild the affected render, collision and interaction data.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn apply_indoor_faces(
+    mut messages: MessageReader<ApplyIndoorFaces>,
+    faces: Option<ResMut<IndoorFaces>>,
+    current: Option<Res<CurrentMap>>,
+    mut world_state: ResMut<WorldState>,
+    mut commands: Commands,
+    sub_meshes: Query<(Entity, &IndoorSubMesh)>,
+    mut door_entities: Query<(&DoorFace, &mut MeshMaterial3d<StandardMaterial>, &mut Visibility)>,
+    touch_triggers: Option<Res<TouchTriggerFaces>>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    game_assets: Res<GameAssets>,
+    cfg: Res<crate::system::config::GameConfig>,
+) {
+    let Some(mut faces) = faces else {
+        messages.clear();
+        return;
+    };
+    let faces = &mut *faces;
+    let map_key = current.map(|c| c.0.to_string()).unwrap_or_default();
+    let vars = &mut world_state.game_vars;
+
+    // Texture groups to re-mesh, door faces to refresh, and whether the
+    // attribute-derived collision / interaction sets need rebuilding.
+    let mut groups: HashSet<String> = HashSet::new();
+    let mut doors: HashSet<usize> = HashSet::new();
+    let mut attributes_changed = false;
+
+    for msg in messages.read() {
+        match msg {
+            ApplyIndoorFaces::Bits { cog, bits, on } => {
+                let cog_faces = faces.blv.cog_faces(*cog);
+                if cog_faces.is_empty() {
+                    warn!("SetFacesBit: no faces in cog {}", cog);
+                }
+                for face in cog_faces {
+                    if !faces.blv.set_face_bits(face, *bits, *on) {
+                        continue;
+                    }
+                    let attributes = faces.blv.faces[face].attributes;
+                    vars.face_attributes
+                        .entry(map_key.clone())
+                        .or_default()
+                        .insert(face, attributes);
+                    groups.insert(faces.blv.face_texture(face).to_string());
+                    if faces.door_faces.contains(&face) {
+                        doors.insert(face);
+                    }
+                    attributes_changed = true;
+                }
+                info!("SetFacesBit: cog={} bits=0x{:x} on={}", cog, bits, on);
+            }
+            ApplyIndoorFaces::Texture { cog, texture_name } => {
+                if !faces.texture_sizes.contains_key(texture_name) {
+                    let Some(img) = game_assets.lod().bitmap(texture_name) else {
+                        warn!("SetTexture: texture '{}' not found in LOD", texture_name);
+                        continue;
+                    };
+                    faces
+                        .texture_sizes
+                        .insert(texture_name.clone(), (img.width(), img.height()));
+                }
+                let cog_faces = faces.blv.cog_faces(*cog);
+                if cog_faces.is_empty() {
+                    warn!("SetTexture: no faces in cog {}", cog);
+                }
+                for face in cog_faces {
+                    let old = faces.blv.face_texture(face).to_string();
+                    if !faces.blv.set_face_texture(face, texture_name) {
+                        continue;
+                    }
+                    vars.face_textures
+                        .entry(map_key.clone())
+                        .or_default()
+                        .insert(face, texture_name.clone());
+                    groups.insert(old);
+                    groups.insert(texture_name.clone());
+                    if faces.door_faces.contains(&face) {
+                        doors.insert(face);
+                    }
+                }
+                info!("SetTexture: cog={} → '{}'", cog, texture_name);
+            }
+        }
+    }
+
+    groups.remove("");
+    if !groups.is_empty() {
+        for (entity, sub) in &sub_meshes {
+            if groups.contains(&sub.texture_name) {
+                commands.entity(entity).despawn();
+            }
+        }
+        let sampler = crate::assets::sampler_for_filtering(&cfg.models_filtering);
+        for tm in faces.blv.textured_meshes(&faces.texture_sizes, &faces.door_faces) {
+            if !groups.contains(&tm.texture_name) {
+                continue;
+            }
+            let sub = prepare_sub_mesh(tm, &game_assets);
+            spawn_static_sub_mesh(&sub, &sampler, &mut commands, &mut images, &mut meshes, &mut materials);
+        }
+    }
+
+    for (door_face, mut material, mut visibility) in &mut door_entities {
+        if !doors.contains(&door_face.face_index) {
+            continue;
+        }
+        let face = &faces.blv.faces[door_face.face_index];
+        *visibility = if face.is_invisible() || face.is_portal() {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        let (mut mat, texture) = indoor_face_material(faces.blv.face_texture(door_face.face_index), &game_assets);
+        mat.base_color_texture = texture.map(|img| images.add(img));
+        material.0 = materials.add(mat);
+    }
+
+    if attributes_changed {
+        let blv = &faces.blv;
+        let (walls, floors, ceilings) = extract_blv_collision(blv, &faces.door_faces);
+        commands.insert_resource(crate::game::map::collision::build_indoor_colliders(
+            walls, floors, ceilings,
+        ));
+        commands.insert_resource(build_clickable_faces(&extract_clickable_faces(blv)));
+        commands.insert_resource(build_occluder_faces(&extract_occluder_faces(blv, &faces.door_faces)));
+        let mut touch = build_touch_triggers(&extract_touch_trigger_faces(blv));
+        if let Some(old) = touch_triggers {
+            touch.fired = old.fired.clone();
+        }
+        commands.insert_resource(touch);
+    }
+}
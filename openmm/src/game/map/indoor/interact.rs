@@ -77,7 +77,7 @@ pub(crate) fn indoor_interact_system(
             warn!("Indoor interact: no EVT file loaded");
             return;
         };
-        if let Some(steps) = evt.events.get(&event_id) {
+        if let Some(steps) = evt.events().get(&event_id) {
             info!(
                 "Indoor interact: dispatching {} steps for event_id={}",
                 steps.len(),
@@ -0,0 +1,152 @@
+//! Indoor BLV point lights: one entity per light, switched by EVT `ToggleIndoorLight`.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::game::map::CurrentMap;
+use crate::game::state::WorldState;
+use crate::prepare::loading::PreparedLight;
+
+/// Sector ambient (0–255 scale) gained or lost per unit of brightness of a
+/// switched light. brightness=64 (small torch) is ~3; brightness=640 (campfire) 32.
+const AMBIENT_PER_BRIGHTNESS: f32 = 0.05;
+/// Upper bound on the ambient a sector's switched lights can add or remove.
+const MAX_LIGHT_AMBIENT: f32 = 120.0;
+
+/// Marker on each BLV point light entity.
+#[derive(Component)]
+pub struct IndoorLight {
+    /// Index into the BLV light list.
+    pub light_id: usize,
+}
+
+struct LightState {
+    brightness: u16,
+    /// State in the BLV, before any script toggled it.
+    initially_on: bool,
+    on: bool,
+}
+
+/// On/off state of the current map's BLV lights, keyed by light id.
+#[derive(Resource, Default)]
+pub struct IndoorLights {
+    lights: HashMap<usize, LightState>,
+}
+
+impl IndoorLights {
+    pub(crate) fn new(lights: &[PreparedLight]) -> Self {
+        let lights = lights
+            .iter()
+            .map(|l| {
+                let state = LightState {
+                    brightness: l.brightness,
+                    initially_on: l.initially_on,
+                    on: l.enabled,
+                };
+                (l.light_id, state)
+            })
+            .collect();
+        Self { lights }
+    }
+
+    /// `ToggleIndoorLight`: switch light `light_id` on or off. Returns false
+    /// for an id the map has no light for.
+    pub fn set_enabled(&mut self, light_id: usize, on: bool) -> bool {
+        match self.lights.get_mut(&light_id) {
+            Some(light) => {
+                light.on = on;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_enabled(&self, light_id: usize) -> bool {
+        self.lights.get(&light_id).is_some_and(|l| l.on)
+    }
+
+    /// Change to a sector's ambient from its lights switched away from their
+    /// BLV state, on the 0–255 `min_ambient` scale. The sector's `min_ambient`
+    /// already includes the lights the BLV has on, so a light switched off
+    /// subtracts and one switched on adds.
+    pub fn sector_ambient(&self, light_ids: &[usize]) -> f32 {
+        let brightness: f32 = light_ids
+            .iter()
+            .filter_map(|id| self.lights.get(id))
+            .filter(|l| l.on != l.initially_on)
+            .map(|l| {
+                if l.on {
+                    l.brightness as f32
+                } else {
+                    -(l.brightness as f32)
+                }
+            })
+            .sum();
+        (brightness * AMBIENT_PER_BRIGHTNESS).clamp(-MAX_LIGHT_AMBIENT, MAX_LIGHT_AMBIENT)
+    }
+}
+
+/// Show or hide light entities after a toggle and record the switches that
+/// differ from the BLV, so they are restored when the map is entered again.
+pub(super) fn sync_indoor_lights(
+    lights: Option<Res<IndoorLights>>,
+    current: Option<Res<CurrentMap>>,
+    mut world_state: ResMut<WorldState>,
+    mut query: Query<(&IndoorLight, &mut Visibility)>,
+) {
+    let Some(lights) = lights else { return };
+    if !lights.is_changed() {
+        return;
+    }
+    for (light, mut visibility) in &mut query {
+        *visibility = if lights.is_enabled(light.light_id) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    let Some(map) = current else { return };
+    let switched: HashMap<usize, bool> = lights
+        .lights
+        .iter()
+        .filter(|(_, l)| l.on != l.initially_on)
+        .map(|(&id, l)| (id, l.on))
+        .collect();
+    let overrides = &mut world_state.game_vars.indoor_lights;
+    if switched.is_empty() {
+        overrides.remove(&map.0.to_string());
+    } else {
+        overrides.insert(map.0.to_string(), switched);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn light(light_id: usize, brightness: u16, on: bool) -> PreparedLight {
+        PreparedLight {
+            light_id,
+            position: Vec3::ZERO,
+            brightness,
+            enabled: on,
+            initially_on: on,
+        }
+    }
+
+    #[test]
+    fn toggling_changes_sector_ambient() {
+        let mut lights = IndoorLights::new(&[light(0, 640, true), light(3, 64, false)]);
+        // The BLV's own lights are already in `min_ambient`.
+        assert_eq!(lights.sector_ambient(&[0, 3]), 0.0);
+        assert!(lights.set_enabled(3, true));
+        assert!((lights.sector_ambient(&[0, 3]) - 3.2).abs() < 1e-3);
+        assert!(lights.set_enabled(0, false));
+        assert!((lights.sector_ambient(&[0, 3]) + 28.8).abs() < 1e-3);
+        // Unknown ids are ignored.
+        assert!(!lights.set_enabled(9, true));
+        assert_eq!(lights.sector_ambient(&[9]), 0.0);
+    }
+}
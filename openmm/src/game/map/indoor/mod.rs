@@ -1,17 +1,22 @@
-//! Indoor map (BLV) plugin — door animation, interaction, and world spawning.
+//! Indoor map (BLV) plugin — door animation, interaction, face and light changes, and world spawning.
 use bevy::prelude::*;
 
 use crate::GameState;
 use crate::game::map::is_indoor;
 
 mod doors;
+mod faces;
 mod interact;
+mod lights;
 mod spawn;
 mod types;
 
 pub(crate) use doors::door_animation_system;
 pub use doors::trigger_door;
+pub(crate) use faces::apply_face_overrides;
+pub use faces::{ApplyIndoorFaces, IndoorFaces};
 pub(crate) use interact::{indoor_interact_system, indoor_touch_trigger_system};
+pub use lights::IndoorLights;
 pub(crate) use spawn::spawn_indoor_world;
 pub use types::{BlvDoors, DoorColliders, OccluderFaceInfo, OccluderFaces, TouchTriggerFaces};
 
@@ -19,7 +24,16 @@ pub struct BlvPlugin;
 
 impl Plugin for BlvPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Game), spawn_indoor_world.run_if(is_indoor))
+        app.add_message::<ApplyIndoorFaces>()
+            .add_systems(OnEnter(GameState::Game), spawn_indoor_world.run_if(is_indoor))
+            // Face and light changes may come from scripts run behind a
+            // dialog, so they are applied in every UI mode.
+            .add_systems(
+                Update,
+                (faces::apply_indoor_faces, lights::sync_indoor_lights)
+                    .run_if(in_state(GameState::Game))
+                    .run_if(is_indoor),
+            )
             .add_systems(
                 Update,
                 (
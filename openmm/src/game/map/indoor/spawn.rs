@@ -8,10 +8,13 @@ use crate::game::spawn::SpawnCtx;
 use crate::game::spawn::actor::{ActorKind, ActorSpawnParams, spawn_actor};
 use crate::game::sprites::loading as sprites;
 use crate::game::sprites::material::SpriteMaterial;
-use crate::prepare::loading::PreparedIndoorWorld;
+use crate::prepare::loading::{
+    ClickableFaceData, OccluderFaceData, PreparedIndoorWorld, PreparedSubMesh, TouchTriggerFaceData,
+};
 
+use super::lights::{IndoorLight, IndoorLights};
 use super::types::{
-    BlvDoors, DoorColliders, DoorCollisionFace, DoorFace, DoorRuntime, OccluderFaceInfo, OccluderFaces,
+    BlvDoors, DoorColliders, DoorCollisionFace, DoorFace, DoorRuntime, IndoorSubMesh, OccluderFaceInfo, OccluderFaces,
     TouchTriggerFaces, TouchTriggerInfo,
 };
 
@@ -30,9 +33,9 @@ pub(crate) fn spawn_indoor_world(
     spawn_door_faces(&prepared, &mut commands, &mut images, &mut meshes, &mut materials);
     commands.insert_resource(build_blv_doors(&prepared));
     commands.insert_resource(build_door_colliders(&prepared));
-    commands.insert_resource(build_clickable_faces(&prepared));
-    commands.insert_resource(build_occluder_faces(&prepared));
-    commands.insert_resource(build_touch_triggers(&prepared));
+    commands.insert_resource(build_clickable_faces(&prepared.clickable_faces));
+    commands.insert_resource(build_occluder_faces(&prepared.occluder_faces));
+    commands.insert_resource(build_touch_triggers(&prepared.touch_trigger_faces));
     spawn_ambient_light(&mut commands);
     spawn_decorations(
         &prepared,
@@ -67,22 +70,37 @@ fn spawn_static_meshes(
     let model_sampler = crate::assets::sampler_for_filtering(&cfg.models_filtering);
     for model in &prepared.models {
         for sub in &model.sub_meshes {
-            let mut mat = sub.material.clone();
-            if let Some(ref tex) = sub.texture {
-                let mut img = tex.clone();
-                img.sampler = model_sampler.clone();
-                let tex_handle = images.add(img);
-                mat.base_color_texture = Some(tex_handle);
-            }
-            commands.spawn((
-                Mesh3d(meshes.add(sub.mesh.clone())),
-                MeshMaterial3d(materials.add(mat)),
-                InGame,
-            ));
+            spawn_static_sub_mesh(sub, &model_sampler, commands, images, meshes, materials);
         }
     }
 }
 
+/// Spawn one per-texture static mesh, tagged with its texture for later rebuilds.
+pub(super) fn spawn_static_sub_mesh(
+    sub: &PreparedSubMesh,
+    sampler: &bevy::image::ImageSampler,
+    commands: &mut Commands,
+    images: &mut Assets<Image>,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    let mut mat = sub.material.clone();
+    if let Some(ref tex) = sub.texture {
+        let mut img = tex.clone();
+        img.sampler = sampler.clone();
+        let tex_handle = images.add(img);
+        mat.base_color_texture = Some(tex_handle);
+    }
+    commands.spawn((
+        Mesh3d(meshes.add(sub.mesh.clone())),
+        MeshMaterial3d(materials.add(mat)),
+        IndoorSubMesh {
+            texture_name: sub.texture_name.clone(),
+        },
+        InGame,
+    ));
+}
+
 /// Spawn door face entities individually (for animation).
 fn spawn_door_faces(
     prepared: &PreparedIndoorWorld,
@@ -180,9 +198,8 @@ fn build_door_colliders(prepared: &PreparedIndoorWorld) -> DoorColliders {
 }
 
 /// Build clickable face resource for indoor interaction raycasts.
-fn build_clickable_faces(prepared: &PreparedIndoorWorld) -> crate::game::interaction::clickable::Faces {
-    let faces: Vec<crate::game::interaction::clickable::FaceInfo> = prepared
-        .clickable_faces
+pub(super) fn build_clickable_faces(clickable: &[ClickableFaceData]) -> crate::game::interaction::clickable::Faces {
+    let faces: Vec<crate::game::interaction::clickable::FaceInfo> = clickable
         .iter()
         .map(|cf| crate::game::interaction::clickable::FaceInfo {
             face_index: cf.face_index,
@@ -196,9 +213,8 @@ fn build_clickable_faces(prepared: &PreparedIndoorWorld) -> crate::game::interac
 }
 
 /// Build OccluderFaces resource — all solid indoor geometry for ray occlusion.
-fn build_occluder_faces(prepared: &PreparedIndoorWorld) -> OccluderFaces {
-    let occ_faces: Vec<OccluderFaceInfo> = prepared
-        .occluder_faces
+pub(super) fn build_occluder_faces(occluders: &[OccluderFaceData]) -> OccluderFaces {
+    let occ_faces: Vec<OccluderFaceInfo> = occluders
         .iter()
         .map(|f| OccluderFaceInfo {
             normal: f.normal,
@@ -210,9 +226,8 @@ fn build_occluder_faces(prepared: &PreparedIndoorWorld) -> OccluderFaces {
 }
 
 /// Build TouchTriggerFaces resource for proximity-based event dispatch.
-fn build_touch_triggers(prepared: &PreparedIndoorWorld) -> TouchTriggerFaces {
-    let touch_faces: Vec<TouchTriggerInfo> = prepared
-        .touch_trigger_faces
+pub(super) fn build_touch_triggers(touch: &[TouchTriggerFaceData]) -> TouchTriggerFaces {
+    let touch_faces: Vec<TouchTriggerInfo> = touch
         .iter()
         .map(|tf| TouchTriggerInfo {
             event_id: tf.event_id,
@@ -272,7 +287,8 @@ fn spawn_decorations(
     }
 }
 
-/// Spawn BLV static point lights (designer-placed lights for campfires, cauldrons, etc.).
+/// Spawn BLV static point lights (designer-placed lights for campfires, cauldrons, etc.),
+/// one `IndoorLight` entity per BLV light id so scripts can switch them.
 /// radius field is always 0 in MM6 data — brightness alone drives the falloff.
 fn spawn_blv_lights(prepared: &PreparedIndoorWorld, commands: &mut Commands) {
     // Range and intensity are decoupled: range scales linearly so small lights don't get
@@ -280,8 +296,8 @@ fn spawn_blv_lights(prepared: &PreparedIndoorWorld, commands: &mut Commands) {
     // brightness=64 -> range~960 (small torch); brightness=640 -> range~9600 (campfire room-fill).
     const BLV_LIGHT_RANGE_SCALE: f32 = 5.0;
     const BLV_LIGHT_INTENSITY_SCALE: f32 = 300.0;
-    for &(pos, brightness) in &prepared.blv_lights {
-        let b = brightness as f32;
+    for light in &prepared.blv_lights {
+        let b = light.brightness as f32;
         let range = b * BLV_LIGHT_RANGE_SCALE;
         let intensity = b * b * BLV_LIGHT_INTENSITY_SCALE;
         commands.spawn((
@@ -292,10 +308,19 @@ fn spawn_blv_lights(prepared: &PreparedIndoorWorld, commands: &mut Commands) {
                 shadows_enabled: false, //cfg.shadows,
                 ..default()
             },
-            Transform::from_translation(pos),
+            Transform::from_translation(light.position),
+            if light.enabled {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            },
+            IndoorLight {
+                light_id: light.light_id,
+            },
             InGame,
         ));
     }
+    commands.insert_resource(IndoorLights::new(&prepared.blv_lights));
 }
 
 /// Spawn BLV monsters from spawn_points (same pipeline as ODM).
@@ -327,7 +352,7 @@ fn spawn_indoor_monsters(
     // NOTE: Group spread (golden angle) is now pre-applied in the data layer
     // during Monster -> Actor conversion (see Actors::from_monsters) or
     // preserved from individual positions in the saved DLV.
-    for (idx, mon) in monsters.get_actors().iter().enumerate() {
+    for mon in monsters.get_actors() {
         let [bx, by, bz] = mm6_position_to_bevy(mon.position[0], mon.position[1], mon.position[2]);
         let ground_pos = Vec3::new(bx, by, bz);
 
@@ -350,7 +375,8 @@ fn spawn_indoor_monsters(
             recovery_secs: mon.recovery_secs,
             can_fly: mon.can_fly,
             ai_type: &mon.ai_type,
-            ddm_id: idx as i32,
+            ddm_id: mon.ddm_index,
+            monlist_id: mon.monlist_id,
             group_id: 0,
             hostile: true,
         };
@@ -26,6 +26,13 @@ pub struct DoorFace {
     pub moves_by_door: bool,
 }
 
+/// Marker on each static indoor mesh entity — the texture all its faces share.
+/// Static faces are batched per texture, so a face change rebuilds whole groups.
+#[derive(Component)]
+pub struct IndoorSubMesh {
+    pub texture_name: String,
+}
+
 // --- Resources ---
 
 /// Runtime state for a single door.
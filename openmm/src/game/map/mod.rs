@@ -4,6 +4,7 @@
 use bevy::prelude::*;
 use openmm_data::utils::MapName;
 
+pub(crate) mod chests;
 pub mod collision;
 pub mod coords;
 pub(crate) mod indoor;
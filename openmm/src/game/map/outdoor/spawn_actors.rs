@@ -64,7 +64,8 @@ pub(super) fn spawn_npc_actors(
                 recovery_secs: actor.recovery_secs,
                 can_fly: actor.can_fly,
                 ai_type: &actor.ai_type,
-                ddm_id: i as i32,
+                ddm_id: actor.ddm_index,
+                monlist_id: actor.monlist_id,
                 group_id: actor.group,
                 hostile: true,
             };
@@ -144,7 +145,8 @@ pub(super) fn spawn_npc_actors(
             recovery_secs: actor.recovery_secs,
             can_fly: actor.can_fly,
             ai_type: &actor.ai_type,
-            ddm_id: i as i32,
+            ddm_id: actor.ddm_index,
+            monlist_id: actor.monlist_id,
             group_id: actor.group,
             hostile: false,
         };
@@ -206,6 +208,7 @@ pub(super) fn spawn_odm_monsters(
             can_fly: mon.can_fly,
             ai_type: &mon.ai_type,
             ddm_id: -1,
+            monlist_id: mon.monlist_id,
             group_id: 0,
             hostile: true,
         };
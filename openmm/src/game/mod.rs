@@ -69,6 +69,7 @@ impl Plugin for MapPlugin {
             map::indoor::BlvPlugin,
             player::physics::PhysicsPlugin,
             map::spatial_index::SpatialIndexPlugin,
+            map::chests::ChestsPlugin,
             sprites::SpritesPlugin,
         ));
     }
@@ -78,7 +79,12 @@ impl Plugin for MapPlugin {
 struct GameplayPlugin;
 impl Plugin for GameplayPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((player::PlayerPlugin, actors::ActorsPlugin, player::party::PartyPlugin));
+        app.add_plugins((
+            player::PlayerPlugin,
+            actors::ActorsPlugin,
+            player::party::PartyPlugin,
+            save::SavePlugin,
+        ));
     }
 }
 
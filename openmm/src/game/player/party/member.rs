@@ -1,4 +1,6 @@
+use openmm_data::assets::map_delta::{ItemRecord, free_grid_cell, mark_grid_cells};
 use openmm_data::enums::EvtVariable;
+use openmm_data::save::character::{INVENTORY_HEIGHT, INVENTORY_WIDTH, ITEM_SLOTS};
 
 pub const SKILL_COUNT: usize = 31; // EvtVariable::SKILL_STAFF..=EvtVariable::SKILL_MISC
 
@@ -53,6 +55,13 @@ pub struct PartyMember {
     /// Bitmask of active conditions.
     /// Bit N corresponds to EvtVariable(EvtVariable::COND_CURSED.0 + N): bit 0=Cursed, 1=Weak, …, 17=CondMain.
     pub conditions: u32,
+
+    // ── Inventory ───────────────────────────────────────────────────────
+    /// Carried item instances; inventory cells and equip slots refer to these.
+    pub items: [ItemRecord; ITEM_SLOTS],
+    /// Inventory grid, row-major `INVENTORY_WIDTH` × `INVENTORY_HEIGHT`, encoded
+    /// like the save: `n` where item `n - 1` starts, `-n` on its other cells.
+    pub inventory: [i32; INVENTORY_WIDTH * INVENTORY_HEIGHT],
 }
 
 impl PartyMember {
@@ -78,9 +87,25 @@ impl PartyMember {
             awards: 0,
             skill_points: 0,
             conditions: 0,
+            items: [ItemRecord::default(); ITEM_SLOTS],
+            inventory: [0; INVENTORY_WIDTH * INVENTORY_HEIGHT],
         }
     }
 
+    /// Put `item` into the backpack where a `size` (columns, rows) area is
+    /// free. Returns false if there is no room or no free item slot.
+    pub fn add_item(&mut self, item: ItemRecord, size: (usize, usize)) -> bool {
+        let Some(slot) = self.items.iter().position(|i| i.is_empty()) else {
+            return false;
+        };
+        let Some(cell) = free_grid_cell(&self.inventory, INVENTORY_WIDTH, INVENTORY_HEIGHT, size) else {
+            return false;
+        };
+        self.items[slot] = item;
+        mark_grid_cells(&mut self.inventory, INVENTORY_WIDTH, cell, size, slot);
+        true
+    }
+
     /// Set a skill by its EvtVariable. No-op if variable is not a skill.
     pub fn set_skill(&mut self, var: EvtVariable, level: u8) {
         if let Some(idx) = var.skill_index() {
@@ -188,6 +213,30 @@ impl PartyMember {
         }
     }
 
+    /// Whether the member is out of play: dead, petrified or eradicated.
+    pub fn is_dead(&self) -> bool {
+        [
+            EvtVariable::COND_DEAD,
+            EvtVariable::COND_PETRIFIED,
+            EvtVariable::COND_ERADICATED,
+        ]
+        .into_iter()
+        .any(|cond| self.get_var(cond) != 0)
+    }
+
+    /// Lose `damage` hit points. At 0 HP or below the member falls
+    /// unconscious, and dies once HP reaches minus their base Endurance.
+    pub fn receive_damage(&mut self, damage: i32) {
+        self.hp = self.hp.saturating_sub(damage.clamp(0, i16::MAX as i32) as i16);
+        if self.hp > 0 {
+            return;
+        }
+        self.set_var(EvtVariable::COND_UNCONSCIOUS, 1);
+        if self.hp as i32 + self.get_var(EvtVariable::BASE_ENDURANCE) <= 0 {
+            self.set_var(EvtVariable::COND_DEAD, 1);
+        }
+    }
+
     /// Add delta to a per-character EvtVariable.
     pub fn add_var(&mut self, var: EvtVariable, delta: i32) {
         match var {
@@ -267,6 +267,7 @@ fn animate_day_cycle(
     mut tint_buffers: ResMut<SpriteTintBuffers>,
     map: Res<CurrentMap>,
     indoor: Option<Res<crate::prepare::loading::PreparedIndoorWorld>>,
+    indoor_lights: Option<Res<crate::game::map::indoor::IndoorLights>>,
     // Non-billboard (terrain, BSP models) — toggled between lit/unlit on mode change.
     model_query: Query<&MeshMaterial3d<StandardMaterial>, Without<Billboard>>,
     mut sun_query: Query<(&mut Transform, &mut DirectionalLight), Without<Player>>,
@@ -290,6 +291,7 @@ fn animate_day_cycle(
         &cfg,
         is_indoor,
         indoor.as_deref(),
+        indoor_lights.as_deref(),
         &mut lighting_state,
         &mut sun_query,
         &mut ambient_query,
@@ -358,6 +360,7 @@ fn update_sun_and_ambient(
     cfg: &GameConfig,
     is_indoor: bool,
     indoor: Option<&crate::prepare::loading::PreparedIndoorWorld>,
+    indoor_lights: Option<&crate::game::map::indoor::IndoorLights>,
     lighting_state: &mut LightingState,
     sun_query: &mut Query<(&mut Transform, &mut DirectionalLight), Without<Player>>,
     ambient_query: &mut Query<&mut AmbientLight, With<AmbientMarker>>,
@@ -386,7 +389,7 @@ fn update_sun_and_ambient(
     }
 
     if is_indoor {
-        let ambient_brightness = indoor_sector_ambient_brightness(indoor, player_query, lighting_state);
+        let ambient_brightness = indoor_sector_ambient_brightness(indoor, indoor_lights, player_query, lighting_state);
         for mut ambient in ambient_query.iter_mut() {
             ambient.color = Color::srgb(0.85, 0.80, 0.70); // warm stone
             ambient.brightness = ambient_brightness;
@@ -408,13 +411,15 @@ fn update_sun_and_ambient(
 }
 
 /// Look up the sector containing the player and return an ambient brightness based
-/// on its `min_ambient_light` value. Indoor-only; falls back to the first sector
-/// or a small default if the player is outside every bbox.
+/// on its `min_ambient_light` value, raised or lowered by the sector's BLV lights
+/// that scripts switched on or off. Indoor-only; falls back to the first sector or a small default if the
+/// player is outside every bbox.
 ///
 /// Uses `LightingState::last_sector_index` to short-circuit the common case
 /// where the player is still inside the previously-found sector.
 fn indoor_sector_ambient_brightness(
     indoor: Option<&crate::prepare::loading::PreparedIndoorWorld>,
+    indoor_lights: Option<&crate::game::map::indoor::IndoorLights>,
     player_query: &Query<&Transform, With<Player>>,
     lighting_state: &mut LightingState,
 ) -> f32 {
@@ -435,13 +440,18 @@ fn indoor_sector_ambient_brightness(
             && pos.z >= s.bbox_min.z
             && pos.z <= s.bbox_max.z
     };
+    let brightness = |s: &crate::prepare::loading::SectorAmbient| {
+        // `min_ambient` already counts the lights the BLV has on.
+        let switched = indoor_lights.map_or(0.0, |l| l.sector_ambient(&s.light_ids));
+        (s.min_ambient as f32 + switched).clamp(0.0, 255.0) * 0.8 + 25.0
+    };
 
     // Fast path: player is still inside the cached sector's bbox.
     if let Some(idx) = lighting_state.last_sector_index
         && let Some(sector) = indoor_data.sector_ambients.get(idx)
         && contains(sector)
     {
-        return sector.min_ambient as f32 * 0.8 + 25.0;
+        return brightness(sector);
     }
 
     // Slow path: linear scan. Fall back to the first sector if the player is
@@ -455,16 +465,13 @@ fn indoor_sector_ambient_brightness(
     match found {
         Some((idx, s)) => {
             lighting_state.last_sector_index = Some(idx);
-            s.min_ambient as f32 * 0.8 + 25.0
+            brightness(s)
         }
         None => {
             // Don't cache fallbacks — keep re-scanning until the player is
             // inside a real sector again.
             lighting_state.last_sector_index = None;
-            indoor_data
-                .sector_ambients
-                .first()
-                .map_or(25.0, |s| s.min_ambient as f32 * 0.8 + 25.0)
+            indoor_data.sector_ambients.first().map_or(25.0, brightness)
         }
     }
 }
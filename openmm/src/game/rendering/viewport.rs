@@ -289,8 +289,11 @@ pub(crate) fn update_viewport(
         let mut image = Image::default();
         image.texture_descriptor.size = size;
         image.texture_descriptor.format = TextureFormat::Bgra8UnormSrgb;
-        image.texture_descriptor.usage =
-            TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+        // COPY_SRC lets save thumbnails read the 3D scene back without the HUD.
+        image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_DST
+            | TextureUsages::COPY_SRC
+            | TextureUsages::RENDER_ATTACHMENT;
         image.resize(size);
         let image_handle = images.add(image);
 
@@ -1,16 +1,22 @@
 //! Centralized state population from a loaded save file.
 
-use super::ActiveSave;
+use bevy::prelude::*;
+use openmm_data::assets::map_delta::MapDeltaFile;
+use openmm_data::mapstats::MapStats;
+use openmm_data::utils::MapName;
+
+use super::{ActiveSave, MM6_TICKS_PER_MINUTE};
 use crate::game::player::party::Party;
-use crate::game::player::party::member::{ATTR_COUNT, Class, PartyMember, RESIST_COUNT, SKILL_COUNT};
+use crate::game::player::party::member::{ATTR_COUNT, Class, PartyMember, RESIST_COUNT};
 use crate::game::player::party::portrait::PortraitId;
 use crate::game::state::state::WorldState;
 use crate::game::state::time::GameTime;
 
 /// Populate all live game state from an ActiveSave.
 ///
-/// Syncs position, map, gold/food, quest bits, autonotes, calendar, and
-/// party members. Called once after loading a save file.
+/// Syncs position, map, gold/food, quest bits, autonotes, calendar, NPC
+/// roster, spell overlays and party members. Called once after loading a
+/// save file.
 pub fn populate_state_from_save(
     save: &ActiveSave,
     world_state: &mut WorldState,
@@ -37,6 +43,7 @@ pub fn populate_state_from_save(
 
     // ── Gold, food, reputation ──────────────────────────────────────────
     world_state.game_vars.gold = sp.gold;
+    world_state.game_vars.bank_gold = sp.bank_gold;
     world_state.game_vars.food = sp.food;
     world_state.game_vars.reputation = sp.reputation;
 
@@ -52,6 +59,46 @@ pub fn populate_state_from_save(
         world_state.game_vars.autonotes.insert(note);
     }
 
+    // ── NPC roster ──────────────────────────────────────────────────────
+    let vars = &mut world_state.game_vars;
+    vars.npc_greetings.clear();
+    vars.npc_topics.clear();
+    vars.npc_locations.clear();
+    vars.hired_npcs.clear();
+    if let Some(npcs) = &save.npcs {
+        // Record 0 is a placeholder; NPC ids start at 1.
+        for (id, npc) in npcs.npcs.iter().enumerate().skip(1) {
+            let id = id as i32;
+            if npc.greeting != 0 {
+                vars.npc_greetings.insert(id, npc.greeting);
+            }
+            for (slot, &event_id) in npc.topics.iter().enumerate() {
+                if event_id != 0 {
+                    vars.npc_topics.insert((id, slot as i32), event_id);
+                }
+            }
+            if npc.house != 0 {
+                vars.npc_locations.insert(id, npc.house);
+            }
+        }
+        vars.hired_npcs.extend(npcs.hired());
+        vars.npcs_in_party = vars.hired_npcs.len() as i32;
+    }
+
+    // ── Per-map script state ────────────────────────────────────────────
+    // None of it may leak from the game being left. Face and light changes
+    // come back from the save's map extras as each map is entered.
+    vars.face_attributes.clear();
+    vars.face_textures.clear();
+    vars.indoor_lights.clear();
+
+    // ── Spell overlays ──────────────────────────────────────────────────
+    vars.spell_overlays = save
+        .overlays
+        .as_ref()
+        .map(|o| o.active().copied().collect())
+        .unwrap_or_default();
+
     // ── Calendar -> GameTime ────────────────────────────────────────────
     *game_time = GameTime::from_calendar(
         sp.year as u32,
@@ -83,15 +130,120 @@ pub fn populate_state_from_save(
         member.base_attrs[..attr_len].copy_from_slice(&sc.base_stats[..attr_len]);
         member.attr_bonuses[..attr_len].copy_from_slice(&sc.stat_bonuses[..attr_len]);
 
-        // Skills (both have 31 entries).
-        let skill_len = sc.skills.len().min(SKILL_COUNT);
-        member.skills[..skill_len].copy_from_slice(&sc.skills[..skill_len]);
+        // Skills (both have 31 entries; MM6 skill words fit a byte).
+        for (level, &saved) in member.skills.iter_mut().zip(&sc.skills) {
+            *level = saved as u8;
+        }
 
         // Resistances (both have 5 entries: Fire, Elec, Cold, Poison, Magic).
         let res_len = sc.resistances.len().min(RESIST_COUNT);
         member.resistances[..res_len].copy_from_slice(&sc.resistances[..res_len]);
         member.resistance_bonuses[..res_len].copy_from_slice(&sc.resistance_bonuses[..res_len]);
 
+        member.items = sc.items;
+        member.inventory = sc.inventory;
+
         party.members[i] = member;
     }
 }
+
+/// Reset `map` to its pristine `games.lod` state if the party has been away
+/// longer than its mapstats refill period, bringing back killed monsters and
+/// looted chests. Call on map entry, before anything reads the delta.
+///
+/// The map's delta is dropped from the save, so the map loads as on a first
+/// visit: actors come from the pristine delta plus fresh spawn-point monsters.
+/// Returns whether the map was reset.
+pub fn refill_map_if_due(
+    save: &mut ActiveSave,
+    map: &MapName,
+    assets: &openmm_data::Assets,
+    mapstats: &MapStats,
+    world_state: &mut WorldState,
+    game_time: &GameTime,
+) -> bool {
+    let Some(info) = mapstats.get(&map.filename()) else {
+        return false;
+    };
+    let Some(saved) = save
+        .map_delta(map)
+        .and_then(|data| MapDeltaFile::parse(assets, map, &data).ok())
+    else {
+        return false;
+    };
+    let last_visit = (saved.last_visit() / MM6_TICKS_PER_MINUTE).max(0) as u64;
+    let now = game_time.total_minutes();
+    if !info.refill_due(last_visit, now) {
+        return false;
+    }
+    info!(
+        "refilling '{}': {} days since last visit (refill every {})",
+        map,
+        (now - last_visit) / (24 * 60),
+        info.respawn_days
+    );
+    save.drop_map_delta(map);
+    world_state.game_vars.dead_actor_ids.remove(&map.to_string());
+    true
+}
+
+/// Map vars for `map` from its saved delta, or all zero for a map not visited yet.
+pub fn map_vars_from_save(save: &ActiveSave, map: &MapName, assets: &openmm_data::Assets) -> [i32; 100] {
+    let mut vars = [0; 100];
+    let delta = save
+        .map_delta(map)
+        .and_then(|data| MapDeltaFile::parse(assets, map, &data).ok());
+    if let Some(delta) = delta {
+        for (var, &byte) in vars.iter_mut().zip(delta.map_vars()) {
+            *var = byte as i32;
+        }
+    }
+    vars
+}
+
+/// Restore `map`'s face changes (full attribute words and textures), light
+/// switches and EVT timers from the extras saved beside its delta, replacing
+/// the live record for that map.
+pub fn map_extras_from_save(save: &ActiveSave, map: &MapName, world_state: &mut WorldState) {
+    let extras = save.map_extras(map);
+    let vars = &mut world_state.game_vars;
+    let key = map.to_string();
+    vars.face_attributes.remove(&key);
+    vars.face_textures.remove(&key);
+    vars.indoor_lights.remove(&key);
+    vars.evt_timers.remove(&key);
+    if !extras.timers.is_empty() {
+        vars.evt_timers.insert(key.clone(), extras.timers);
+    }
+    if !extras.face_attributes.is_empty() {
+        vars.face_attributes
+            .insert(key.clone(), extras.face_attributes.into_iter().collect());
+    }
+    if !extras.face_textures.is_empty() {
+        vars.face_textures
+            .insert(key.clone(), extras.face_textures.into_iter().collect());
+    }
+    if !extras.lights.is_empty() {
+        vars.indoor_lights.insert(key, extras.lights.into_iter().collect());
+    }
+}
+
+/// Chests for `map` from its saved delta, or as the map ships for a map not
+/// visited yet.
+pub fn map_chests_from_save(
+    save: &ActiveSave,
+    map: &MapName,
+    assets: &openmm_data::Assets,
+) -> Vec<openmm_data::assets::map_delta::MapChest> {
+    let delta = match save.map_delta(map) {
+        Some(data) => MapDeltaFile::parse(assets, map, &data),
+        None => MapDeltaFile::load(assets, map),
+    };
+    match delta {
+        Ok(delta) => delta.delta().chests.clone(),
+        Err(e) => {
+            warn!("chests for {}: {}", map, e);
+            Vec::new()
+        }
+    }
+}
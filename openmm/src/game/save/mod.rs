@@ -2,14 +2,26 @@
 
 pub mod load;
 pub mod slots;
+pub mod store;
+pub mod thumbnail;
 
 use bevy::prelude::*;
+use openmm_data::lod_data::{CompressionKind, LodData};
 use openmm_data::save::file::SaveFile;
 use openmm_data::save::header::SaveHeader;
+use openmm_data::save::map_extras::MapExtras;
+use openmm_data::save::npcdata::SaveNpcs;
+use openmm_data::save::overlay::SaveOverlays;
 use openmm_data::save::party::SaveParty;
 use openmm_data::utils::MapName;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::game::state::state::WorldState;
+
+/// MM6 ticks per second (128). 1 game minute = 128 * 60 = 7680 ticks.
+pub(crate) const MM6_TICKS_PER_MINUTE: i64 = 7680;
 
 /// MM6 direction range: 0-2047 maps to a full circle (TAU radians).
 const MM6_DIRECTION_RANGE: f32 = 2048.0;
@@ -38,26 +50,59 @@ fn bevy_to_mm6_direction(yaw: f32) -> i32 {
 }
 
 /// Live save state loaded from a `.mm6` archive.
-/// Holds parsed header + party data and the converted Bevy-space spawn point.
+/// Holds parsed header, party, NPC and overlay data and the converted
+/// Bevy-space spawn point.
 #[derive(Resource)]
 pub struct ActiveSave {
     pub path: PathBuf,
+    /// The archive at `path`, kept open so map loads can read its deltas
+    /// without re-reading the file.
+    file: SaveFile,
     pub header: SaveHeader,
     pub party: SaveParty,
+    /// NPC roster from `npcdata.bin`; `None` if the save has none.
+    pub npcs: Option<SaveNpcs>,
+    /// Spell overlay table from `overlay.bin`; `None` if the save has none.
+    pub overlays: Option<SaveOverlays>,
     /// Spawn position in Bevy coordinates (converted from MM6).
     pub spawn_position: Vec3,
     /// Spawn yaw in Bevy radians.
     pub spawn_yaw: f32,
     /// Current map parsed from the header.
     pub map_name: MapName,
+    /// Whether live game state has been entered from this save. A freshly
+    /// opened save is not yet in play, so leaving the game must not
+    /// overwrite it with the previous session's state.
+    pub in_play: bool,
+    /// Map deltas and their [`MapExtras`] snapshotted on map exit and not yet
+    /// written to disk, keyed by lowercase entry name (e.g. `"oute3.ddm"`,
+    /// `"d01.dlv"`, `"d01.omx"`).
+    pub map_deltas: HashMap<String, Vec<u8>>,
+    /// Entries dropped by a map refill (or extras with nothing left in
+    /// them), left out of the next write so the map loads as on a first
+    /// visit (spawn points and all).
+    pub dropped_deltas: HashSet<String>,
+    /// Latest viewport capture, PCX-encoded at save screenshot size. Written
+    /// as `image.pcx` on the next save; `None` keeps the archive's image.
+    pub thumbnail: Option<Vec<u8>>,
+    /// Destinations of saves waiting for the thumbnail capture, in request
+    /// order; all are written by the capture's observer (see [`thumbnail`]).
+    /// A quicksave and an autosave requested before one capture lands are
+    /// both kept.
+    pub pending_writes: Vec<PathBuf>,
+    /// A viewport capture has been requested and not yet read back.
+    pub capturing: bool,
 }
 
 impl ActiveSave {
-    /// Open a `.mm6` save file, parse header + party, convert coords to Bevy space.
+    /// Open a `.mm6` save file, parse header, party, NPCs and overlays, convert
+    /// coords to Bevy space.
     pub fn from_file(path: PathBuf) -> Result<Self, Box<dyn Error>> {
         let save_file = SaveFile::open(&path)?;
         let header = save_file.header();
         let party = save_file.party();
+        let npcs = save_file.npcs();
+        let overlays = save_file.overlays();
 
         let spawn_position = mm6_to_bevy_position(&party.position);
         let spawn_yaw = mm6_to_bevy_yaw(party.direction);
@@ -74,26 +119,153 @@ impl ActiveSave {
 
         Ok(Self {
             path,
+            file: save_file,
             header,
             party,
+            npcs,
+            overlays,
             spawn_position,
             spawn_yaw,
             map_name,
+            in_play: false,
+            map_deltas: HashMap::new(),
+            dropped_deltas: HashSet::new(),
+            thumbnail: None,
+            pending_writes: Vec::new(),
+            capturing: false,
         })
     }
 
-    /// Sync spawn position from a Bevy transform back to MM6 coordinates.
-    pub fn update_from_transform(&mut self, transform: &Transform) {
-        let pos = transform.translation;
+    /// Sync position and facing from the live player state back to MM6 coordinates.
+    /// `world_state.player.position` is at eye level; the save stores the party's feet.
+    pub fn update_from_player(&mut self, world_state: &WorldState, eye_height: f32) {
+        let pos = world_state.player.position - Vec3::Y * eye_height;
+        let yaw = world_state.player.yaw;
         self.party.position = bevy_to_mm6_position(pos);
-
-        let (_, yaw, _) = transform.rotation.to_euler(EulerRot::YXZ);
         self.party.direction = bevy_to_mm6_direction(yaw);
 
         self.spawn_position = pos;
         self.spawn_yaw = yaw;
     }
 
+    /// Write header, party, NPCs, overlays, pending map deltas and the
+    /// thumbnail to disk at `dest`, keeping every other entry of the current
+    /// save file. On success the save points at `dest`.
+    pub fn write_to(&mut self, dest: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut extra: Vec<(&str, Vec<u8>)> = self
+            .map_deltas
+            .iter()
+            .map(|(name, data)| {
+                let packed = LodData {
+                    kind: CompressionKind::Zlib8,
+                    data: data.clone(),
+                }
+                .pack();
+                (name.as_str(), packed)
+            })
+            .collect();
+        if let Some(npcs) = &self.npcs {
+            extra.push(("npcdata.bin", npcs.to_bytes()));
+        }
+        if let Some(overlays) = &self.overlays {
+            extra.push(("overlay.bin", overlays.to_bytes()));
+        }
+        if let Some(thumbnail) = &self.thumbnail {
+            extra.push(("image.pcx", thumbnail.clone()));
+        }
+        let removed: Vec<&str> = self.dropped_deltas.iter().map(String::as_str).collect();
+        self.file
+            .write(dest, &self.header, &self.party, &self.file.clock(), &extra, &removed)?;
+        self.file = SaveFile::open(dest)?;
+        self.path = dest.to_path_buf();
+        self.map_deltas.clear();
+        self.dropped_deltas.clear();
+        Ok(())
+    }
+
+    /// Queue a write to `dest` for when the thumbnail capture lands.
+    pub fn queue_write(&mut self, dest: PathBuf) {
+        if !self.pending_writes.contains(&dest) {
+            self.pending_writes.push(dest);
+        }
+    }
+
+    /// Write every queued save, in request order. Failures are logged and
+    /// do not stop the writes after them.
+    pub fn write_pending(&mut self) {
+        for dest in std::mem::take(&mut self.pending_writes) {
+            match self.write_to(&dest) {
+                Ok(()) => info!("Save written to {}", dest.display()),
+                Err(e) => error!("Save to {} failed: {e}", dest.display()),
+            }
+        }
+    }
+
+    /// Decompressed DDM/DLV delta for `map`: the pending snapshot if there
+    /// is one, else the copy in the save file. `None` for unvisited and
+    /// dropped maps.
+    pub fn map_delta(&self, map: &MapName) -> Option<Vec<u8>> {
+        let name = map.delta_filename();
+        if let Some(data) = self.map_deltas.get(&name) {
+            return Some(data.clone());
+        }
+        if self.dropped_deltas.contains(&name) {
+            return None;
+        }
+        self.file.map_delta(map)
+    }
+
+    /// Store a snapshotted delta for `map` until the next [`write_to`](Self::write_to).
+    pub fn set_map_delta(&mut self, map: &MapName, data: Vec<u8>) {
+        let name = map.delta_filename();
+        self.dropped_deltas.remove(&name);
+        self.map_deltas.insert(name, data);
+    }
+
+    /// Forget `map`'s delta and extras, pending and saved, so the map loads
+    /// as unvisited until it is snapshotted again.
+    pub fn drop_map_delta(&mut self, map: &MapName) {
+        for name in [map.delta_filename(), MapExtras::filename(map)] {
+            self.map_deltas.remove(&name);
+            self.dropped_deltas.insert(name);
+        }
+    }
+
+    /// Face changes and light switches saved for `map` beside its delta: the pending snapshot if
+    /// there is one, else the save file's. Empty for maps without any, and
+    /// for a malformed entry (logged).
+    pub fn map_extras(&self, map: &MapName) -> MapExtras {
+        let name = MapExtras::filename(map);
+        let data = match self.map_deltas.get(&name) {
+            Some(data) => Some(data.clone()),
+            None if self.dropped_deltas.contains(&name) => None,
+            None => self.file.get_file_ci(&name),
+        };
+        data.map(|data| {
+            MapExtras::parse(&data).unwrap_or_else(|e| {
+                warn!("ignoring {name}: {e}");
+                MapExtras::default()
+            })
+        })
+        .unwrap_or_default()
+    }
+
+    /// Store `map`'s extras until the next [`write_to`](Self::write_to);
+    /// empty extras remove the entry.
+    pub fn set_map_extras(&mut self, map: &MapName, extras: &MapExtras) {
+        let name = MapExtras::filename(map);
+        if extras.is_empty() {
+            self.map_deltas.remove(&name);
+            self.dropped_deltas.insert(name);
+        } else {
+            self.dropped_deltas.remove(&name);
+            self.map_deltas.insert(name, extras.to_bytes());
+        }
+    }
+
     /// Update the map name in both header and local state.
     pub fn update_map(&mut self, map_name: &MapName) {
         self.map_name = map_name.clone();
@@ -105,12 +277,165 @@ impl ActiveSave {
     }
 }
 
+/// Keys for saving and loading the quicksave slot.
+#[derive(Resource)]
+pub struct SaveKeyBindings {
+    pub quicksave: KeyCode,
+    pub quickload: KeyCode,
+}
+
+impl Default for SaveKeyBindings {
+    fn default() -> Self {
+        Self {
+            quicksave: KeyCode::F5,
+            quickload: KeyCode::F9,
+        }
+    }
+}
+
+/// Slot written by quicksave and read by quickload (MM6 spelling).
+pub const QUICKSAVE_SLOT: &str = "quiksave";
+
+/// Keeps [`ActiveSave`] in step with live state across map transitions,
+/// autosaves on every transition and handles quicksave/quickload keys.
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveKeyBindings>()
+            .add_plugins(thumbnail::ThumbnailPlugin)
+            .add_systems(OnEnter(crate::GameState::Game), |mut save: ResMut<ActiveSave>| {
+                save.in_play = true;
+            })
+            .add_systems(
+                OnExit(crate::GameState::Game),
+                (snapshot_map_on_exit, sync_save_on_exit, autosave_on_transition).chain(),
+            )
+            .add_systems(
+                Update,
+                (quicksave, quickload)
+                    .run_if(in_state(crate::GameState::Game))
+                    .run_if(crate::game::ui::game_input_active),
+            );
+    }
+}
+
+/// Capture live state into the ActiveSave when leaving the game state.
+///
+/// Loading re-populates state from the ActiveSave on every map load, so
+/// without this gold, quest bits, party stats etc. would revert on transitions.
+/// Position is left to the transition, which already set the new spawn point.
+fn sync_save_on_exit(
+    mut save: ResMut<ActiveSave>,
+    world_state: Res<WorldState>,
+    party: Res<crate::game::player::party::Party>,
+    game_time: Res<crate::game::state::time::GameTime>,
+) {
+    if !save.in_play {
+        return;
+    }
+    store::capture_state_into_save(&mut save, &world_state, &party, &game_time);
+}
+
+/// Snapshot the map being left into the ActiveSave, so re-entering it
+/// (or reloading the save once written) restores actors, doors and map vars.
+fn snapshot_map_on_exit(mut save: ResMut<ActiveSave>, world_state: Res<WorldState>, mut live_map: store::LiveMapState) {
+    if !save.in_play {
+        return;
+    }
+    live_map.capture(&mut save, &world_state);
+}
+
+/// Write the ActiveSave to the next rotating autosave slot when leaving
+/// the game for another map. Runs after the map snapshot and state sync, so
+/// the autosave reloads into the destination map with the left map intact.
+/// If a thumbnail capture is still in flight the write waits for it, after
+/// any save already waiting; otherwise waiting saves are written with it.
+fn autosave_on_transition(
+    mut save: ResMut<ActiveSave>,
+    load_request: Option<Res<crate::prepare::loading::LoadRequest>>,
+    cfg: Res<crate::system::config::GameConfig>,
+) {
+    if !save.in_play || load_request.is_none() {
+        return;
+    }
+    let Some(slot) = slots::next_autosave_slot(cfg.autosave_slots) else {
+        return;
+    };
+    queue_autosave(&mut save, slots::local_slot_path(&slot));
+}
+
+/// Queue the autosave to `dest` behind any waiting save, and write them all
+/// now unless a thumbnail capture is in flight to write them.
+fn queue_autosave(save: &mut ActiveSave, dest: PathBuf) {
+    if save.capturing {
+        info!("Autosave: {} waits for its thumbnail", dest.display());
+    }
+    save.queue_write(dest);
+    if !save.capturing {
+        save.write_pending();
+    }
+}
+
+/// Save the running game to the quicksave slot once its thumbnail is captured.
+#[allow(clippy::too_many_arguments)]
+fn quicksave(
+    keys: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<SaveKeyBindings>,
+    world_state: Res<WorldState>,
+    party: Res<crate::game::player::party::Party>,
+    game_time: Res<crate::game::state::time::GameTime>,
+    settings: Res<crate::game::player::PlayerSettings>,
+    mut active_save: ResMut<ActiveSave>,
+    mut live_map: store::LiveMapState,
+) {
+    if !keys.just_pressed(key_bindings.quicksave) {
+        return;
+    }
+    let dest = slots::local_slot_path(QUICKSAVE_SLOT);
+    info!("Quicksave: saving to {}", dest.display());
+    store::queue_save(
+        &mut active_save,
+        dest,
+        &world_state,
+        &party,
+        &game_time,
+        &mut live_map,
+        settings.eye_height,
+    );
+}
+
+/// Reload the quicksave slot, discarding the running game.
+fn quickload(mut commands: Commands, keys: Res<ButtonInput<KeyCode>>, key_bindings: Res<SaveKeyBindings>) {
+    if !keys.just_pressed(key_bindings.quickload) {
+        return;
+    }
+    let path = slots::slot_path(QUICKSAVE_SLOT);
+    if !path.exists() {
+        warn!("Quickload: no quicksave at {}", path.display());
+        return;
+    }
+    try_load_save(&mut commands, path);
+}
+
 /// Try to load a save file and transition to the loading state.
 /// Returns true on success, false on error (logged).
+///
+/// Scripts queued or suspended in the running game are dropped so none of
+/// them resumes against the loaded save, and its EVT timer record is
+/// forgotten: the loaded save's own comes back from its map extras.
 pub fn try_load_save(commands: &mut Commands, path: PathBuf) -> bool {
     match ActiveSave::from_file(path) {
         Ok(save) => {
             commands.insert_resource(save);
+            commands.queue(|world: &mut World| {
+                if let Some(mut event_queue) = world.get_resource_mut::<crate::game::events::EventQueue>() {
+                    event_queue.clear();
+                }
+                if let Some(mut world_state) = world.get_resource_mut::<WorldState>() {
+                    world_state.game_vars.evt_timers.clear();
+                }
+            });
             commands.set_state(crate::GameState::Loading);
             true
         }
@@ -120,3 +445,105 @@ pub fn try_load_save(commands: &mut Commands, path: PathBuf) -> bool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openmm_data::save::clock::CLOCK_SIZE;
+    use openmm_data::save::party::PARTY_BIN_SIZE;
+    use openmm_data::{LodWriter, Version};
+
+    /// A save standing in `oute3` with a snapshot of it, no game data needed.
+    fn write_visited_save(path: &Path) {
+        let header = SaveHeader {
+            map_name: "oute3.odm".to_string(),
+            ..Default::default()
+        };
+        let tail = *b"OM01";
+        let mut lod = LodWriter::new(Version::MM6);
+        lod.add_file("header.bin", header.to_bytes().to_vec())
+            .add_file_with_name_tail("party.bin", vec![0; PARTY_BIN_SIZE], tail)
+            .add_file("clock.bin", vec![0; CLOCK_SIZE])
+            .add_file_with_name_tail("oute3.ddm", vec![1, 2, 3], tail);
+        lod.save(path).expect("write synthetic save");
+    }
+
+    #[test]
+    fn refilled_map_loads_from_spawn_points() {
+        let path = std::env::temp_dir().join(format!("openmm_refill_{:?}.mm6", std::thread::current().id()));
+        write_visited_save(&path);
+        let map = MapName::try_from("oute3").unwrap();
+
+        let mut save = ActiveSave::from_file(path.clone()).expect("open synthetic save");
+        assert!(save.map_delta(&map).is_some());
+
+        // With no delta the loaders take the first-visit path: pristine
+        // actors plus spawn-point monsters.
+        save.drop_map_delta(&map);
+        let dropped = save.map_delta(&map);
+        save.write_to(&path).expect("write save");
+        let reopened = ActiveSave::from_file(path.clone()).expect("reopen").map_delta(&map);
+
+        // Snapshotting the refilled map brings its delta back.
+        save.set_map_delta(&map, vec![4, 5]);
+        let snapshotted = save.map_delta(&map);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(dropped, None);
+        assert_eq!(reopened, None, "the written save has no delta for the refilled map");
+        assert_eq!(snapshotted, Some(vec![4, 5]));
+    }
+
+    #[test]
+    fn quicksave_and_transition_in_one_frame_both_write() {
+        let dir = std::env::temp_dir().join(format!("openmm_pending_{:?}", std::thread::current().id()));
+        let src = dir.join("src.mm6");
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        write_visited_save(&src);
+        let (quick, auto) = (dir.join("quiksave.mm6"), dir.join("autosave1.mm6"));
+
+        let mut save = ActiveSave::from_file(src).expect("open synthetic save");
+        // F5 queued the quicksave and its capture is in flight when the
+        // transition's autosave comes in.
+        save.queue_write(quick.clone());
+        save.capturing = true;
+        queue_autosave(&mut save, auto.clone());
+        let queued = save.pending_writes.clone();
+
+        // The capture's observer lands.
+        save.capturing = false;
+        save.write_pending();
+        let written = (quick.exists(), auto.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(queued, vec![quick.clone(), auto.clone()]);
+        assert_eq!(written, (true, true));
+        assert!(save.pending_writes.is_empty());
+        assert_eq!(save.path, auto);
+    }
+
+    #[test]
+    fn map_extras_are_written_and_dropped_with_the_delta() {
+        let path = std::env::temp_dir().join(format!("openmm_extras_{:?}.mm6", std::thread::current().id()));
+        write_visited_save(&path);
+        let map = MapName::try_from("oute3").unwrap();
+        let mut extras = MapExtras::default();
+        extras.face_attributes.insert(4, 0x2000_0001);
+        extras.face_textures.insert(4, "t1swb".to_string());
+        extras.lights.insert(2, false);
+
+        let mut save = ActiveSave::from_file(path.clone()).expect("open synthetic save");
+        save.set_map_extras(&map, &extras);
+        save.write_to(&path).expect("write save");
+        let mut reopened = ActiveSave::from_file(path.clone()).expect("reopen");
+        let saved = reopened.map_extras(&map);
+
+        reopened.drop_map_delta(&map);
+        reopened.write_to(&path).expect("write refilled save");
+        let refilled = ActiveSave::from_file(path.clone()).expect("reopen").map_extras(&map);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(saved, extras);
+        assert_eq!(refilled, MapExtras::default());
+    }
+}
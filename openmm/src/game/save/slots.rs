@@ -1,18 +1,17 @@
 //! Save slot helpers: directory paths, new-game template, slot naming.
 
 use bevy::prelude::*;
-use openmm_data::save::file::{SaveFile, list_saves};
+use openmm_data::save::file::{SaveFile, list_saves, save_slot_order};
 use std::error::Error;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
+use super::MM6_TICKS_PER_MINUTE;
 use crate::screens::PropertySource;
 
 /// Number of save slots visible at once in the load/save UI.
 const VISIBLE_SLOTS: usize = 7;
 
-/// MM6 ticks per second (128). 1 game minute = 128 * 60 = 7680 ticks.
-const MM6_TICKS_PER_MINUTE: i64 = 7680;
-
 /// Directory where OpenMM-specific save files are stored.
 pub fn local_saves_dir() -> PathBuf {
     PathBuf::from("data/Saves")
@@ -50,6 +49,47 @@ pub fn create_new_game_save() -> Result<PathBuf, Box<dyn Error>> {
     Ok(dest)
 }
 
+/// Path a named slot is written to. Always under [`local_saves_dir`], so
+/// saves found in the original MM6 directory are never overwritten.
+pub fn local_slot_path(slot: &str) -> PathBuf {
+    local_saves_dir().join(format!("{slot}.mm6"))
+}
+
+/// Slot name of the `n`th rotating autosave (1-based), e.g. `"autosave2"`.
+pub fn autosave_slot(n: u32) -> String {
+    format!("autosave{n}")
+}
+
+/// Slot the next map-transition autosave goes to, rotating through
+/// `autosave1..=autosave{count}`. `None` when autosaving is disabled.
+pub fn next_autosave_slot(count: u32) -> Option<String> {
+    let modified: Vec<Option<SystemTime>> = (1..=count)
+        .map(|n| modified_time(&local_slot_path(&autosave_slot(n))))
+        .collect();
+    pick_autosave_index(&modified).map(|i| autosave_slot(i as u32 + 1))
+}
+
+/// Index of the autosave slot to overwrite: the first one not written yet,
+/// else the one written longest ago.
+fn pick_autosave_index(modified: &[Option<SystemTime>]) -> Option<usize> {
+    if let Some(free) = modified.iter().position(Option::is_none) {
+        return Some(free);
+    }
+    modified.iter().enumerate().min_by_key(|(_, t)| **t).map(|(i, _)| i)
+}
+
+/// Last-modified time of a save file, if it exists.
+pub fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Format a save's last-modified time for the load screen, e.g. `"2024-03-09 18:42"`.
+fn format_saved_at(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Local>::from(time)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
 /// Full path for a named save slot, searching multiple locations.
 /// Prioritizes local saves in data/Saves/.
 pub fn slot_path(slot: &str) -> PathBuf {
@@ -72,6 +112,8 @@ pub fn slot_path(slot: &str) -> PathBuf {
 pub struct SaveManager {
     pub saves: Vec<SaveFile>,
     pub headers: Vec<openmm_data::save::header::SaveHeader>,
+    /// Last-modified time of each entry in `saves`.
+    pub modified: Vec<Option<SystemTime>>,
     pub offset: usize,
     pub absolute_selected: Option<usize>,
 }
@@ -90,15 +132,9 @@ impl SaveManager {
         }
         self.saves = all_saves;
         // Re-sort the combined list to ensure consistent ordering across directories
-        self.saves.sort_by_key(|s| {
-            let slot = &s.slot;
-            match slot.as_str() {
-                s if s.starts_with("autosave") => (0, s.to_string()),
-                s if s.starts_with("quiksave") => (1, s.to_string()),
-                s => (2, s.to_string()),
-            }
-        });
+        self.saves.sort_by_key(|s| save_slot_order(&s.slot));
         self.headers = self.saves.iter().map(|s| s.header()).collect();
+        self.modified = self.saves.iter().map(|s| modified_time(&s.path)).collect();
     }
 
     pub fn scroll_up(&mut self) {
@@ -138,6 +174,7 @@ struct SaveSlotSource {
     selected_preview: Option<String>,
     selected_location: String,
     selected_time: String,
+    selected_saved_at: String,
     absolute_selected: Option<usize>,
     offset: usize,
 }
@@ -152,6 +189,7 @@ impl PropertySource for SaveSlotSource {
             "preview" => self.selected_preview.clone(),
             "location" => Some(self.selected_location.clone()),
             "time" => Some(self.selected_time.clone()),
+            "saved" => Some(self.selected_saved_at.clone()),
             _ => {
                 // Handle saveslot[idx] and saveslot[idx].color
                 let (idx_str, sub) = if let Some((idx_part, sub_part)) = path.split_once('.') {
@@ -191,6 +229,7 @@ pub fn update_save_registry(
     let mut selected_preview = None;
     let mut selected_location = String::new();
     let mut selected_time = String::new();
+    let mut selected_saved_at = String::new();
 
     if let Some(idx) = save_manager.absolute_selected {
         let actual_idx = idx;
@@ -221,6 +260,9 @@ pub fn update_save_registry(
 
             let total_minutes = (header.playing_time / MM6_TICKS_PER_MINUTE) as u64;
             selected_time = openmm_data::utils::time::format(total_minutes);
+            if let Some(Some(modified)) = save_manager.modified.get(actual_idx) {
+                selected_saved_at = format_saved_at(*modified);
+            }
         }
     }
 
@@ -229,7 +271,28 @@ pub fn update_save_registry(
         selected_preview,
         selected_location,
         selected_time,
+        selected_saved_at,
         absolute_selected: save_manager.absolute_selected,
         offset: save_manager.offset,
     }));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn autosave_fills_free_slots_first() {
+        let t = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        assert_eq!(pick_autosave_index(&[Some(t), None, None]), Some(1));
+        assert_eq!(pick_autosave_index(&[None, Some(t)]), Some(0));
+    }
+
+    #[test]
+    fn autosave_overwrites_oldest_slot() {
+        let at = |s| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(s));
+        assert_eq!(pick_autosave_index(&[at(300), at(100), at(200)]), Some(1));
+        assert_eq!(pick_autosave_index(&[]), None);
+    }
+}
@@ -0,0 +1,305 @@
+//! Centralized state capture from live runtime state back into a save file.
+//!
+//! Counterpart of [`super::load`]: everything `populate_state_from_save`
+//! reads from the save is written back here, and [`LiveMapState`] turns the
+//! live map (actors, doors, chests, map vars) back into a DDM/DLV delta.
+
+use std::path::PathBuf;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use openmm_data::LodSerialise;
+use openmm_data::assets::AIState;
+use openmm_data::assets::ddm::DdmActor;
+use openmm_data::assets::map_delta::MapDeltaFile;
+use openmm_data::blv::DoorState;
+use openmm_data::dmonlist::MonsterList;
+use openmm_data::save::map_extras::MapExtras;
+
+use super::{ActiveSave, MM6_TICKS_PER_MINUTE, bevy_to_mm6_direction, bevy_to_mm6_position};
+use crate::GameAssets;
+use crate::game::actors::Actor;
+use crate::game::map::CurrentMap;
+use crate::game::map::chests::MapChests;
+use crate::game::map::indoor::BlvDoors;
+use crate::game::player::party::Party;
+use crate::game::player::party::portrait::PortraitId;
+use crate::game::state::state::WorldState;
+use crate::game::state::time::GameTime;
+
+/// Copy live game state into an ActiveSave.
+///
+/// Syncs calendar, gold/bank/food, reputation, quest bits, autonotes, NPC
+/// roster, spell overlays and party members. The party position is taken from `spawn_position`, so
+/// call [`ActiveSave::update_from_player`] first when saving mid-map.
+pub fn capture_state_into_save(save: &mut ActiveSave, world_state: &WorldState, party: &Party, game_time: &GameTime) {
+    let sp = &mut save.party;
+
+    // ── Position ────────────────────────────────────────────────────────
+    sp.position = bevy_to_mm6_position(save.spawn_position);
+    sp.direction = bevy_to_mm6_direction(save.spawn_yaw);
+
+    // ── GameTime -> Calendar ────────────────────────────────────────────
+    let (year, month, day, hour, minute) = game_time.to_calendar();
+    sp.year = year as i32;
+    sp.month = month as i32;
+    sp.week = (day / 7) as i32;
+    sp.day = day as i32;
+    sp.hour = hour as i32;
+    sp.minute = minute as i32;
+    sp.second = 0;
+    save.header.playing_time = game_time.total_minutes() as i64 * MM6_TICKS_PER_MINUTE;
+
+    // ── Gold, food, reputation ──────────────────────────────────────────
+    let vars = &world_state.game_vars;
+    sp.gold = vars.gold;
+    sp.bank_gold = vars.bank_gold;
+    sp.food = vars.food;
+    sp.reputation = vars.reputation;
+
+    // ── Quest bits / autonotes ──────────────────────────────────────────
+    sp.quest_bits = sorted(&vars.quest_bits);
+    sp.autonote_bits = sorted(&vars.autonotes);
+
+    // ── NPC roster / spell overlays ─────────────────────────────────────
+    if let Some(npcs) = &mut save.npcs {
+        for (id, npc) in npcs.npcs.iter_mut().enumerate().skip(1) {
+            let id = id as i32;
+            if let Some(&greeting) = vars.npc_greetings.get(&id) {
+                npc.greeting = greeting;
+            }
+            for (slot, event_id) in npc.topics.iter_mut().enumerate() {
+                if let Some(&event) = vars.npc_topics.get(&(id, slot as i32)) {
+                    *event_id = event;
+                }
+            }
+            if let Some(&house) = vars.npc_locations.get(&id) {
+                npc.house = house;
+            }
+            npc.set_hired(vars.hired_npcs.contains(&id));
+        }
+    }
+    if let Some(overlays) = &mut save.overlays {
+        overlays.set_active(&vars.spell_overlays);
+    }
+
+    // ── Party members -> Characters ─────────────────────────────────────
+    for (sc, member) in sp.characters.iter_mut().zip(party.members.iter()) {
+        sc.name = member.name.clone();
+        sc.class = member.class.id();
+        sc.face = PortraitId::ALL.iter().position(|&p| p == member.portrait).unwrap_or(0) as u8;
+        sc.level = member.level as i16;
+        sc.hp = member.hp as i32;
+        sc.sp = member.sp as i32;
+        sc.experience = member.experience;
+        sc.skill_points = member.skill_points;
+
+        let attr_len = sc.base_stats.len().min(member.base_attrs.len());
+        sc.base_stats[..attr_len].copy_from_slice(&member.base_attrs[..attr_len]);
+        sc.stat_bonuses[..attr_len].copy_from_slice(&member.attr_bonuses[..attr_len]);
+
+        for (saved, &level) in sc.skills.iter_mut().zip(&member.skills) {
+            *saved = level.into();
+        }
+
+        let res_len = sc.resistances.len().min(member.resistances.len());
+        sc.resistances[..res_len].copy_from_slice(&member.resistances[..res_len]);
+        sc.resistance_bonuses[..res_len].copy_from_slice(&member.resistance_bonuses[..res_len]);
+
+        sc.items = member.items;
+        sc.inventory = member.inventory;
+    }
+}
+
+/// Capture live state and queue the active save to be written to `dest`.
+///
+/// The player position is taken from `world_state` (eye level) and lowered
+/// by `eye_height` to the party's feet. The current map is snapshotted too,
+/// so the save restores it as left. The file is written once the viewport
+/// thumbnail has been captured, after which the save points at `dest`.
+pub fn queue_save(
+    save: &mut ActiveSave,
+    dest: PathBuf,
+    world_state: &WorldState,
+    party: &Party,
+    game_time: &GameTime,
+    live_map: &mut LiveMapState,
+    eye_height: f32,
+) {
+    save.update_from_player(world_state, eye_height);
+    capture_state_into_save(save, world_state, party, game_time);
+    live_map.capture(save, world_state);
+    save.queue_write(dest);
+}
+
+/// Live map entities and resources that make up a map's delta file.
+#[derive(SystemParam)]
+pub struct LiveMapState<'w, 's> {
+    current: Option<Res<'w, CurrentMap>>,
+    game_assets: Res<'w, GameAssets>,
+    actors: Query<'w, 's, (&'static mut Actor, &'static GlobalTransform)>,
+    doors: Option<Res<'w, BlvDoors>>,
+    chests: Option<Res<'w, MapChests>>,
+    game_time: Res<'w, GameTime>,
+}
+
+impl LiveMapState<'_, '_> {
+    /// Snapshot the loaded map into a DDM/DLV and store it as a pending
+    /// delta in `save`.
+    ///
+    /// Starts from the map's last saved delta (or the pristine one from
+    /// `games.lod`) and overwrites actor HP, position and AI state, door
+    /// states, face attributes (the full words, textures and light switches go
+    /// into the map's [`MapExtras`]), chest contents and map vars, and stamps the visit time that map refills are
+    /// measured from. Spawn-point monsters are appended as new actor
+    /// records and adopt their record index, so later snapshots update them
+    /// in place. Objects and sprites are carried over unchanged.
+    pub fn capture(&mut self, save: &mut ActiveSave, world_state: &WorldState) {
+        let Some(map) = self.current.as_ref().map(|c| c.0.clone()) else {
+            return;
+        };
+        let assets = self.game_assets.assets();
+        let base = match save.map_delta(&map) {
+            Some(data) => MapDeltaFile::parse(assets, &map, &data),
+            None => MapDeltaFile::load(assets, &map),
+        };
+        let mut delta = match base {
+            Ok(delta) => delta,
+            Err(e) => {
+                warn!("map delta snapshot for '{}' skipped: {e}", map);
+                return;
+            }
+        };
+
+        let mut actors: Vec<_> = self.actors.iter_mut().collect();
+        // Stable record order for spawn-point monsters across snapshots.
+        actors.sort_by(|a, b| {
+            let (pa, pb) = (a.0.initial_position, b.0.initial_position);
+            (pa.x, pa.z)
+                .partial_cmp(&(pb.x, pb.z))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        for (actor, transform) in &mut actors {
+            let feet = transform.translation() - Vec3::Y * actor.sprite_half_height;
+            write_actor(&mut delta, actor, feet, &self.game_assets.data().monlist);
+        }
+
+        if let (MapDeltaFile::Dlv(dlv), Some(doors)) = (&mut delta, self.doors.as_deref()) {
+            for (i, door) in doors.doors.iter().enumerate() {
+                // Moving doors are saved at the end of their travel.
+                let state = match door.state {
+                    DoorState::Opening => DoorState::Open,
+                    DoorState::Closing => DoorState::Closed,
+                    settled => settled,
+                };
+                if let Some(header) = dlv.door_headers.get_mut(i) {
+                    header.set_door_state(state);
+                    header.time_since_triggered = 0;
+                }
+                if let Some(d) = dlv.doors.get_mut(i) {
+                    d.state = state;
+                }
+            }
+        }
+
+        // Faces changed by SetFacesBit and SetTexture. An MM6 DLV keeps only the
+        // low half of each face's attributes, so the full words, the textures,
+        // the ToggleIndoorLight switches, the EVT timers and the locked chests
+        // go into the map's extras beside it.
+        let map_key = map.to_string();
+        if let MapDeltaFile::Dlv(dlv) = &mut delta {
+            let changed = world_state.game_vars.face_attributes.get(&map_key);
+            for (&face, &attributes) in changed.into_iter().flatten() {
+                dlv.set_face_attributes(face, attributes);
+            }
+        }
+        let extras = MapExtras {
+            face_attributes: world_state
+                .game_vars
+                .face_attributes
+                .get(&map_key)
+                .map(|faces| faces.clone().into_iter().collect())
+                .unwrap_or_default(),
+            face_textures: world_state
+                .game_vars
+                .face_textures
+                .get(&map_key)
+                .map(|faces| faces.clone().into_iter().collect())
+                .unwrap_or_default(),
+            lights: world_state
+                .game_vars
+                .indoor_lights
+                .get(&map_key)
+                .map(|lights| lights.clone().into_iter().collect())
+                .unwrap_or_default(),
+            timers: world_state
+                .game_vars
+                .evt_timers
+                .get(&map_key)
+                .cloned()
+                .unwrap_or_default(),
+            locked_chests: self.chests.as_deref().map(|c| c.locked().clone()).unwrap_or_default(),
+        };
+        save.set_map_extras(&map, &extras);
+
+        if let Some(chests) = self.chests.as_deref() {
+            delta.delta_mut().chests = chests.chests().to_vec();
+        }
+
+        let mut vars = delta.map_vars().to_vec();
+        vars.resize(vars.len().max(world_state.game_vars.map_vars.len()), 0);
+        for (byte, &value) in vars.iter_mut().zip(world_state.game_vars.map_vars.iter()) {
+            *byte = value as u8;
+        }
+        delta.set_map_vars(&vars);
+        delta.set_last_visit(self.game_time.total_minutes() as i64 * MM6_TICKS_PER_MINUTE);
+
+        info!(
+            "snapshotted '{}': {} actors, {} map vars set",
+            map.delta_filename(),
+            delta.delta().actors.len(),
+            world_state.game_vars.map_vars.iter().filter(|&&v| v != 0).count()
+        );
+        save.set_map_delta(&map, delta.to_bytes());
+    }
+}
+
+/// Write one live actor into `delta`, appending a record for spawn-point monsters.
+fn write_actor(delta: &mut MapDeltaFile, actor: &mut Actor, feet: Vec3, monlist: &MonsterList) {
+    let actors = &mut delta.delta_mut().actors;
+    let index = match usize::try_from(actor.ddm_id) {
+        Ok(i) if i < actors.len() => i,
+        _ => {
+            let Some(desc) = monlist.monsters.get(actor.monlist_id as usize) else {
+                return;
+            };
+            let mut raw = DdmActor::spawned(&actor.name, actor.monlist_id, desc);
+            raw.initial_position = mm6_actor_position(actor.initial_position);
+            raw.guarding_position = mm6_actor_position(actor.guarding_position);
+            raw.tether_distance = actor.tether_distance as u16;
+            actors.push(raw);
+            actor.ddm_id = (actors.len() - 1) as i32;
+            actors.len() - 1
+        }
+    };
+    let raw = &mut actors[index];
+    raw.hp = actor.hp.max(0);
+    raw.position = mm6_actor_position(feet);
+    raw.ai_state = if actor.hp <= 0 {
+        AIState::Dead as u16
+    } else if raw.ai_state == AIState::Dead as u16 || raw.ai_state == AIState::Dying as u16 {
+        AIState::Standing as u16
+    } else {
+        raw.ai_state
+    };
+}
+
+fn mm6_actor_position(pos: Vec3) -> [i16; 3] {
+    bevy_to_mm6_position(pos).map(|v| v.clamp(i16::MIN as i32, i16::MAX as i32) as i16)
+}
+
+fn sorted(set: &std::collections::HashSet<i32>) -> Vec<i32> {
+    let mut v: Vec<i32> = set.iter().copied().collect();
+    v.sort_unstable();
+    v
+}
@@ -0,0 +1,109 @@
+//! Save screenshots: capture the 3D viewport (no HUD) into the ActiveSave.
+//!
+//! Screenshots are read back asynchronously, so a save cannot wait for one
+//! in the frame it is requested. Instead the save is queued in
+//! [`ActiveSave::pending_writes`]; the viewport is captured once and the
+//! capture's observer embeds it as `image.pcx` and writes the file. Autosaves
+//! are written while the next map loads, so their capture is taken in the
+//! frame the transition is requested, while the map being left still renders.
+
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use openmm_data::save::file::SaveFile;
+
+use super::ActiveSave;
+use crate::GameState;
+use crate::game::player::PlayerCamera;
+use crate::prepare::loading::LoadRequest;
+
+pub struct ThumbnailPlugin;
+
+impl Plugin for ThumbnailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                capture_for_pending_save,
+                capture_before_transition.run_if(resource_added::<LoadRequest>),
+            )
+                .run_if(in_state(GameState::Game)),
+        );
+    }
+}
+
+/// Capture the viewport for the saves queued in `pending_writes`.
+fn capture_for_pending_save(
+    mut commands: Commands,
+    save: Option<ResMut<ActiveSave>>,
+    cameras: Query<(&Camera, Option<&RenderTarget>), With<PlayerCamera>>,
+) {
+    let Some(mut save) = save else {
+        return;
+    };
+    if save.pending_writes.is_empty() || save.capturing {
+        return;
+    }
+    if !capture_viewport(&mut commands, &cameras) {
+        // Nothing to capture: write with the archive's old image.
+        save.write_pending();
+        return;
+    }
+    save.capturing = true;
+}
+
+/// Capture the map being left, for the autosave written on the transition.
+fn capture_before_transition(
+    mut commands: Commands,
+    save: Option<ResMut<ActiveSave>>,
+    cameras: Query<(&Camera, Option<&RenderTarget>), With<PlayerCamera>>,
+) {
+    let Some(mut save) = save else {
+        return;
+    };
+    if save.in_play && !save.capturing && capture_viewport(&mut commands, &cameras) {
+        save.capturing = true;
+    }
+}
+
+/// Request a screenshot of the player camera's output. Returns false when
+/// there is no player camera to capture.
+///
+/// With `render_scale < 1` the camera renders to an offscreen image that
+/// holds only the 3D scene. Otherwise it draws straight into the window, so
+/// the window capture is cropped to the camera viewport to leave the HUD out.
+fn capture_viewport(
+    commands: &mut Commands,
+    cameras: &Query<(&Camera, Option<&RenderTarget>), With<PlayerCamera>>,
+) -> bool {
+    let Ok((camera, target)) = cameras.single() else {
+        return false;
+    };
+    let (screenshot, crop) = match target {
+        Some(RenderTarget::Image(image)) => (Screenshot::image(image.handle.clone()), None),
+        _ => (
+            Screenshot::primary_window(),
+            camera.viewport.as_ref().map(|v| (v.physical_position, v.physical_size)),
+        ),
+    };
+
+    commands.spawn(screenshot).observe(
+        move |captured: On<ScreenshotCaptured>, save: Option<ResMut<ActiveSave>>| {
+            let Some(mut save) = save else {
+                return;
+            };
+            save.capturing = false;
+            match captured.image.clone().try_into_dynamic() {
+                Ok(mut img) => {
+                    if let Some((pos, size)) = crop {
+                        img = img.crop_imm(pos.x, pos.y, size.x, size.y);
+                    }
+                    save.thumbnail = Some(SaveFile::encode_screenshot(&img));
+                }
+                Err(e) => warn!("save thumbnail capture unusable: {e}"),
+            }
+            save.write_pending();
+        },
+    );
+    true
+}
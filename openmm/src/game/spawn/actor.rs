@@ -49,6 +49,7 @@ pub struct ActorSpawnParams<'a> {
     pub can_fly: bool,
     pub ai_type: &'a str,
     pub ddm_id: i32,
+    pub monlist_id: u8,
     pub group_id: i32,
     pub hostile: bool,
 }
@@ -124,6 +125,7 @@ pub fn spawn_actor(
                 tether_distance: params.tether_distance,
                 attack_range: params.attack_range,
                 ddm_id: params.ddm_id,
+                monlist_id: params.monlist_id,
                 group_id: params.group_id,
                 aggro_range: params.aggro_range,
                 recovery_secs: params.recovery_secs,
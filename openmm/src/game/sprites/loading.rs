@@ -15,6 +15,7 @@ use crate::game::player::PlayerCamera;
 use crate::game::sprites::material::{SpriteMaterial, unlit_billboard_material};
 use crate::game::sprites::{AnimationState, FacingYaw, billboard_face_yaw, quantize_billboard_yaw};
 use openmm_data::Assets as DataAssets;
+use openmm_data::assets::provider::{SpritePalette, SpriteRequest};
 
 /// Pad an RGBA image to target dimensions, centered horizontally and bottom-aligned.
 fn pad_sprite_image(rgba: image::RgbaImage, target_w: u32, target_h: u32) -> image::RgbaImage {
@@ -78,8 +79,11 @@ pub struct SpriteCache {
 }
 
 impl SpriteCache {
-    /// Pre-decode a list of (sprite_root, variant) pairs into the cache.
+    /// Pre-decode a list of (sprite_root, variant, palette_id) triples into the cache.
     /// Call during loading screen to avoid decoding during gameplay.
+    ///
+    /// Every root's frames are decoded together in one parallel batch; only
+    /// texture and material creation runs on the calling thread.
     pub fn preload(
         &mut self,
         roots: &[(&str, u8, u16)],
@@ -88,19 +92,44 @@ impl SpriteCache {
         materials: &mut Assets<SpriteMaterial>,
         selflit: bool,
     ) {
+        let mut pending: Vec<(String, &str, u8, u16, FramePlan)> = Vec::new();
+        let mut requests = Vec::new();
         for &(root, variant, palette_id) in roots {
-            load_sprite_frames(
-                root,
-                assets,
-                images,
-                materials,
-                &mut Some(self),
-                variant,
-                0,
-                0,
-                palette_id,
-                selflit,
-            );
+            let root = root.trim_end_matches(|c: char| c.is_ascii_digit());
+            let key = cache_key(root, variant, 0, 0, palette_id);
+            if self.dimensions.contains_key(&key) || pending.iter().any(|(k, ..)| *k == key) {
+                continue;
+            }
+            let mut plan = FramePlan::new(root, assets, variant, palette_id);
+            requests.append(&mut plan.requests);
+            pending.push((key, root, variant, palette_id, plan));
+        }
+
+        let mut decoded = assets
+            .lod()
+            .decode_sprite_requests(&requests)
+            .into_iter()
+            .map(Result::ok);
+        for (key, root, variant, palette_id, plan) in pending {
+            let (frames, masks, w, h) =
+                build_sprite_materials(plan.fill(&mut decoded), 0, 0, images, materials, selflit);
+            if frames.is_empty() {
+                // Nothing under the full root: let the sequential path try shorter names.
+                load_sprite_frames(
+                    root,
+                    assets,
+                    images,
+                    materials,
+                    &mut Some(self),
+                    variant,
+                    0,
+                    0,
+                    palette_id,
+                    selflit,
+                );
+            } else {
+                store_in_cache(&key, &frames, &masks, w, h, &mut Some(self));
+            }
         }
     }
 }
@@ -386,88 +415,127 @@ fn rebuild_from_cache(key: &str, cache: &SpriteCache) -> (Vec<[Handle<SpriteMate
     (frames, mask_frames)
 }
 
-/// Decode sprite frames from the LOD, apply variant tinting, and pad to uniform size.
-/// When `palette_id > 0` and `variant > 1`, uses the DSFT palette directly
-/// (sprite file header palettes use a different numbering system).
-fn decode_sprite_frames(
-    root: &str,
-    assets: &DataAssets,
-    images: &mut Assets<Image>,
-    materials: &mut Assets<SpriteMaterial>,
-    variant: u8,
-    min_w: u32,
-    min_h: u32,
-    palette_id: u16,
-    selflit: bool,
-) -> (Vec<[Handle<SpriteMaterial>; 5]>, Vec<[Arc<AlphaMask>; 5]>, f32, f32) {
-    // First pass: collect all raw sprites and find max dimensions.
-    let mut raw_sprites: Vec<Vec<Option<DynamicImage>>> = Vec::new();
-    let mut max_w = min_w;
-    let mut max_h = min_h;
-
-    // Some dying sprites are stored as a single image with no frame/direction
-    // suffix (e.g. "arc1diq" — the DSFT sprite_name IS the file name). Detect
-    // this by attempting to load the root itself before the frame-letter loop.
-    let root_lower = root.to_lowercase();
-    let root_img = assets.lod().sprite(root);
-    let single_frame_root = root_img.is_some()
-        && !assets.lod_contains("sprites", &format!("{}a0", root_lower))
-        && !assets.lod_contains("sprites", &format!("{}a", root_lower));
-
-    if single_frame_root {
-        // Load the single image for all 5 directional slots.
-        let img = root_img;
-        if let Some(ref i) = img {
-            max_w = max_w.max(i.width());
-            max_h = max_h.max(i.height());
+/// LOD sprites filling each (frame, direction) slot of a sprite root, resolved
+/// from archive lookups alone so every frame can be decoded in one parallel batch.
+struct FramePlan {
+    /// Sprites to decode, in slot order.
+    requests: Vec<SpriteRequest>,
+    /// Per frame, which of the 5 direction slots has an entry in `requests`.
+    slots: Vec<[bool; 5]>,
+}
+
+impl FramePlan {
+    /// When `palette_id > 0` and `variant > 1`, uses the DSFT palette directly
+    /// (sprite file header palettes use a different numbering system).
+    fn new(root: &str, assets: &DataAssets, variant: u8, palette_id: u16) -> Self {
+        let mut plan = Self {
+            requests: Vec::new(),
+            slots: Vec::new(),
+        };
+
+        // Some dying sprites are stored as a single image with no frame/direction
+        // suffix (e.g. "arc1diq" — the DSFT sprite_name IS the file name). Use
+        // that image for all 5 directional slots.
+        let root_lower = root.to_lowercase();
+        let single_frame_root = assets.lod_contains("sprites", &root_lower)
+            && !assets.lod_contains("sprites", &format!("{}a0", root_lower))
+            && !assets.lod_contains("sprites", &format!("{}a", root_lower));
+        if single_frame_root {
+            plan.requests.push(SpriteRequest::from(root));
+            plan.slots.push([true, false, false, false, false]);
+            return plan;
         }
-        raw_sprites.push(vec![img, None, None, None, None]);
-    } else {
+
+        let palette = if variant > 1 && palette_id > 0 {
+            SpritePalette::Id(palette_id)
+        } else if variant > 1 {
+            SpritePalette::Offset((variant - 1) as u16)
+        } else {
+            SpritePalette::Header
+        };
         for frame_char in b'a'..=b'f' {
             let frame_letter = frame_char as char;
             let test0 = format!("{}{}0", root, frame_letter);
             let test_nodir = format!("{}{}", root, frame_letter);
 
-            let has_frame = assets.lod_contains("sprites", &test0) || assets.lod_contains("sprites", &test_nodir);
-            if !has_frame {
+            let has_nodir = assets.lod_contains("sprites", &test_nodir);
+            if !assets.lod_contains("sprites", &test0) && !has_nodir {
                 break;
             }
 
-            let mut dir_imgs: Vec<Option<DynamicImage>> = Vec::with_capacity(5);
-            for dir in 0..5u8 {
+            let mut slots = [false; 5];
+            for (dir, slot) in slots.iter_mut().enumerate() {
                 let name = format!("{}{}{}", root, frame_letter, dir);
-                let img = if variant > 1 && palette_id > 0 {
-                    // Use DSFT palette directly (sprite header palettes differ in numbering)
-                    let sprite_name = if assets.lod_contains("sprites", &name.to_lowercase()) {
-                        &name
-                    } else {
-                        &test_nodir
-                    };
-                    assets
-                        .lod()
-                        .sprite_with_palette(sprite_name, palette_id)
-                        .or_else(|| assets.lod().sprite(sprite_name))
-                } else if variant > 1 {
-                    let pal_offset = (variant - 1) as u16;
-                    load_sprite_with_palette_offset(assets, &name, &test_nodir, pal_offset)
+                let sprite_name = if assets.lod_contains("sprites", &name.to_lowercase()) {
+                    name
+                } else if has_nodir {
+                    test_nodir.clone()
                 } else {
-                    assets.lod().sprite(&name).or_else(|| assets.lod().sprite(&test_nodir))
+                    continue;
                 };
-                if let Some(ref i) = img {
-                    max_w = max_w.max(i.width());
-                    max_h = max_h.max(i.height());
-                }
-                dir_imgs.push(img);
+                plan.requests.push(SpriteRequest::new(sprite_name, palette));
+                *slot = true;
             }
-            raw_sprites.push(dir_imgs);
+            plan.slots.push(slots);
         }
-    } // end else (multi-frame sprites)
+        plan
+    }
 
+    /// Lay decoded images (in request order) out per frame and direction.
+    fn fill(&self, decoded: &mut impl Iterator<Item = Option<DynamicImage>>) -> Vec<Vec<Option<DynamicImage>>> {
+        self.slots
+            .iter()
+            .map(|slots| {
+                slots
+                    .iter()
+                    .map(|&used| if used { decoded.next().flatten() } else { None })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Decode sprite frames from the LOD, apply variant tinting, and pad to uniform size.
+fn decode_sprite_frames(
+    root: &str,
+    assets: &DataAssets,
+    images: &mut Assets<Image>,
+    materials: &mut Assets<SpriteMaterial>,
+    variant: u8,
+    min_w: u32,
+    min_h: u32,
+    palette_id: u16,
+    selflit: bool,
+) -> (Vec<[Handle<SpriteMaterial>; 5]>, Vec<[Arc<AlphaMask>; 5]>, f32, f32) {
+    let mut plan = FramePlan::new(root, assets, variant, palette_id);
+    let requests = std::mem::take(&mut plan.requests);
+    let mut decoded = assets
+        .lod()
+        .decode_sprite_requests(&requests)
+        .into_iter()
+        .map(Result::ok);
+    build_sprite_materials(plan.fill(&mut decoded), min_w, min_h, images, materials, selflit)
+}
+
+/// Pad decoded frames to uniform size (at least `min_w` × `min_h`), build
+/// alpha masks, and create materials.
+fn build_sprite_materials(
+    raw_sprites: Vec<Vec<Option<DynamicImage>>>,
+    min_w: u32,
+    min_h: u32,
+    images: &mut Assets<Image>,
+    materials: &mut Assets<SpriteMaterial>,
+    selflit: bool,
+) -> (Vec<[Handle<SpriteMaterial>; 5]>, Vec<[Arc<AlphaMask>; 5]>, f32, f32) {
+    let (max_w, max_h) = raw_sprites
+        .iter()
+        .flatten()
+        .flatten()
+        .fold((min_w, min_h), |(w, h), img| (w.max(img.width()), h.max(img.height())));
     if raw_sprites.is_empty() || max_w == 0 {
         return (Vec::new(), Vec::new(), 0.0, 0.0);
     }
 
-    // Second pass: pad to uniform size, build alpha masks, and create materials.
     let mut frames = Vec::new();
     let mut frame_masks: Vec<[Arc<AlphaMask>; 5]> = Vec::new();
     let fallback_mask = Arc::new(AlphaMask {
@@ -496,39 +564,6 @@ fn decode_sprite_frames(
     (frames, frame_masks, max_w as f32, max_h as f32)
 }
 
-/// Load a sprite with a palette offset applied (for monster variant palette swaps).
-/// Reads the base sprite's palette_id from its header, adds the offset, and decodes
-/// with the variant palette. Falls back to normal sprite() if palette not found.
-fn load_sprite_with_palette_offset(
-    assets: &DataAssets,
-    name: &str,
-    fallback: &str,
-    palette_offset: u16,
-) -> Option<DynamicImage> {
-    // Try the primary name first, then the fallback (no-direction variant).
-    // Use O(1) lod_contains to pick the name, then fetch bytes exactly once.
-    let sprite_name = if assets.lod_contains("sprites", &name.to_lowercase()) {
-        name
-    } else if assets.lod_contains("sprites", &fallback.to_lowercase()) {
-        fallback
-    } else {
-        return None;
-    };
-
-    // Read the base palette_id from the sprite header via openmm-data parser
-    let sprite_data = assets
-        .get_bytes(format!("sprites/{}", sprite_name.to_lowercase()))
-        .ok()?;
-    let base_palette_id = openmm_data::assets::image::sprite_palette_id(&sprite_data)?;
-    let variant_palette_id = base_palette_id + palette_offset;
-
-    // Try with variant palette, fall back to normal decode
-    assets
-        .lod()
-        .sprite_with_palette(sprite_name, variant_palette_id)
-        .or_else(|| assets.lod().sprite(sprite_name))
-}
-
 /// Update sprite sheets based on camera angle, entity facing, and animation state.
 /// Works with Actor entities (NPCs/monsters) and directional decorations (FacingYaw).
 /// Only processes visible entities within draw distance. Skips material swap when
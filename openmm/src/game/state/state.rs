@@ -24,6 +24,8 @@ pub struct GameVariables {
     pub quest_bits: std::collections::HashSet<i32>,
     /// Party gold.
     pub gold: i32,
+    /// Gold deposited in the bank.
+    pub bank_gold: i32,
     /// Party food rations.
     pub food: i32,
     /// Party reputation (signed — negative is good, positive is bad in MM6).
@@ -38,14 +40,16 @@ pub struct GameVariables {
     pub npcs_in_party: i32,
     /// Total circus prize accumulated (EvtVariable 0xE0).
     pub total_circus_prize: i32,
-    /// NPC topic overrides: npc_id → event_id (set by SetNPCTopic).
-    pub npc_topics: std::collections::HashMap<i32, i32>,
+    /// NPC topic overrides: (npc_id, topic_index) → event_id (set by SetNPCTopic).
+    pub npc_topics: std::collections::HashMap<(i32, i32), i32>,
     /// Party item counts: item_id → count. Backing store for CheckItemsCount / RemoveItems.
     pub items: std::collections::HashMap<i32, i32>,
     /// NPC greeting overrides: npc_id → greeting_id (set by SetNPCGreeting).
     pub npc_greetings: std::collections::HashMap<i32, i32>,
     /// NPC location overrides: npc_id → map_id (set by MoveNPC).
     pub npc_locations: std::collections::HashMap<i32, i32>,
+    /// Quest NPCs hired into the party (the hired bit in `npcdata.bin`).
+    pub hired_npcs: std::collections::HashSet<i32>,
     /// NPC group news overrides: npc_group → news_id (set by SetNPCGroupNews).
     pub npc_group_news: std::collections::HashMap<i32, i32>,
     /// Chest flag bitmasks: chest_id → flags (set by ToggleChestFlag).
@@ -62,9 +66,27 @@ pub struct GameVariables {
     pub actor_flags: std::collections::HashMap<i32, u32>,
     /// Kill counts by faction group: group_id → killed count (incremented when actor HP → 0).
     pub killed_groups: std::collections::HashMap<i32, u32>,
+    /// Running spell overlays (buff effects) as stored in `overlay.bin`.
+    /// Restored on load and written back on save; not animated yet.
+    pub spell_overlays: Vec<openmm_data::save::overlay::SaveOverlay>,
     /// Dead actor DDM IDs per map: map_name_string → set of ddm_id.
     /// Actors in this set are excluded from spawn on map (re)load.
     pub dead_actor_ids: std::collections::HashMap<String, std::collections::HashSet<i32>>,
+    /// Indoor face attributes per map: map_name_string → face index → attributes.
+    /// Set by SetFacesBit and re-applied when the map is rebuilt.
+    pub face_attributes: std::collections::HashMap<String, std::collections::HashMap<usize, u32>>,
+    /// Indoor face textures per map: map_name_string → face index → texture name (set by SetTexture).
+    pub face_textures: std::collections::HashMap<String, std::collections::HashMap<usize, String>>,
+    /// Indoor light on/off overrides per map: map_name_string → BLV light index → on
+    /// (set by ToggleIndoorLight). Saved in the map's extras beside its DLV.
+    pub indoor_lights: std::collections::HashMap<String, std::collections::HashMap<usize, bool>>,
+    /// EVT timer state per map: map_name_string → (event id, marker step) → armed time,
+    /// last fire and EnableDateTimer switch. Resumed when the map's timers are armed on
+    /// entry; saved in the map's extras beside its delta.
+    pub evt_timers: std::collections::HashMap<
+        String,
+        std::collections::BTreeMap<(u16, u16), openmm_data::save::map_extras::MapTimer>,
+    >,
 }
 
 pub struct PlayerRuntimeState {
@@ -125,6 +147,7 @@ impl Default for GameVariables {
             map_vars: [0; 100],
             quest_bits: std::collections::HashSet::new(),
             gold: 200, // Starting gold in MM6
+            bank_gold: 0,
             food: 7,
             reputation: 0,
             autonotes: std::collections::HashSet::new(),
@@ -136,6 +159,7 @@ impl Default for GameVariables {
             items: std::collections::HashMap::new(),
             npc_greetings: std::collections::HashMap::new(),
             npc_locations: std::collections::HashMap::new(),
+            hired_npcs: std::collections::HashSet::new(),
             npc_group_news: std::collections::HashMap::new(),
             chest_flags: std::collections::HashMap::new(),
             stopped_decorations: std::collections::HashSet::new(),
@@ -144,7 +168,12 @@ impl Default for GameVariables {
             event_overrides: std::collections::HashMap::new(),
             actor_flags: std::collections::HashMap::new(),
             killed_groups: std::collections::HashMap::new(),
+            spell_overlays: Vec::new(),
             dead_actor_ids: std::collections::HashMap::new(),
+            face_attributes: std::collections::HashMap::new(),
+            face_textures: std::collections::HashMap::new(),
+            indoor_lights: std::collections::HashMap::new(),
+            evt_timers: std::collections::HashMap::new(),
         }
     }
 }
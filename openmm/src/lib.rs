@@ -35,7 +35,17 @@ impl Plugin for GamePlugin {
         unsafe {
             std::env::set_var(openmm_data::ENV_OPENMM_PATH_MM6, &cfg.path_mm6);
         }
-        let game_assets = GameAssets::new(openmm_data::get_data_path().into()).expect("unable to load game data files");
+        let mut overlays = openmm_data::get_overlay_paths();
+        overlays.extend(cfg.overlays.iter().map(std::path::PathBuf::from));
+        overlays.retain(|p| {
+            let found = p.exists();
+            if !found {
+                bevy::log::warn!("overlay {} not found — skipping", p.display());
+            }
+            found
+        });
+        let game_assets =
+            GameAssets::new(openmm_data::get_data_path().into(), &overlays).expect("unable to load game data files");
         let game_fonts = screens::fonts::GameFonts::load(&game_assets);
         let active_save = if let Some(ref save_name) = cfg.save {
             let path = slots::slot_path(save_name);
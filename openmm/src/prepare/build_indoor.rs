@@ -1,10 +1,14 @@
-//! BLV collision geometry extraction (walls, floors, ceilings) for the indoor loading pipeline.
+//! BLV collision geometry extraction (walls, floors, ceilings) and clickable,
+//! touch-trigger and occluder face sets for the indoor loading pipeline.
+//! Also re-run at runtime when EVT scripts change face attributes.
 
 use bevy::prelude::*;
 
 use crate::game::map::coords::mm6_position_to_bevy;
 use openmm_data::blv::Blv;
 
+use super::prepared::{ClickableFaceData, OccluderFaceData, TouchTriggerFaceData};
+
 /// Convert a BLV face normal from MM6 fixed-point to Bevy world-space.
 pub(crate) fn blv_face_normal(face: &openmm_data::blv::BlvFace) -> Vec3 {
     let n = face.normal_f32();
@@ -41,7 +45,7 @@ pub(crate) fn extract_blv_collision(
     let mut ceilings = Vec::new();
 
     for (face_idx, face) in blv.faces.iter().enumerate() {
-        if face.num_vertices < 3 || face.is_invisible() || face.is_portal() {
+        if face.num_vertices < 3 || face.is_invisible() || face.is_portal() || face.is_untouchable() {
             continue;
         }
 
@@ -93,3 +97,78 @@ pub(crate) fn extract_blv_collision(
 
     (walls, floors, ceilings)
 }
+
+/// Collect clickable faces (CLICKABLE flag with an event) for interaction raycasts.
+pub(crate) fn extract_clickable_faces(blv: &Blv) -> Vec<ClickableFaceData> {
+    blv.faces
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| f.is_clickable() && f.event_id != 0 && f.num_vertices >= 3)
+        .filter_map(|(i, face)| {
+            let verts = blv_face_verts(face, blv);
+            if verts.len() < 3 {
+                return None;
+            }
+            let normal = blv_face_normal(face);
+            let plane_dist = normal.dot(verts[0]);
+            Some(ClickableFaceData {
+                face_index: i,
+                event_id: face.event_id,
+                normal,
+                plane_dist,
+                vertices: verts,
+            })
+        })
+        .collect()
+}
+
+/// Collect touch-triggered faces (EVENT_BY_TOUCH flag) for proximity events.
+pub(crate) fn extract_touch_trigger_faces(blv: &Blv) -> Vec<TouchTriggerFaceData> {
+    blv.faces
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| f.is_touch_trigger() && f.event_id != 0 && f.num_vertices >= 3)
+        .filter_map(|(i, face)| {
+            let verts = blv_face_verts(face, blv);
+            if verts.len() < 3 {
+                return None;
+            }
+            let center = verts.iter().copied().sum::<Vec3>() / verts.len() as f32;
+            // Use half bounding box diagonal as trigger radius
+            let min = verts.iter().copied().reduce(|a, b| a.min(b))?;
+            let max = verts.iter().copied().reduce(|a, b| a.max(b))?;
+            let radius = (max - min).length() * 0.5;
+            Some(TouchTriggerFaceData {
+                face_index: i,
+                event_id: face.event_id,
+                center,
+                radius: radius.max(128.0), // minimum trigger radius
+            })
+        })
+        .collect()
+}
+
+/// Collect all solid faces for ray occlusion (wall/floor/ceiling, no portals, no door faces).
+pub(crate) fn extract_occluder_faces(
+    blv: &Blv,
+    door_faces: &std::collections::HashSet<usize>,
+) -> Vec<OccluderFaceData> {
+    blv.faces
+        .iter()
+        .enumerate()
+        .filter(|(i, f)| !f.is_invisible() && !f.is_portal() && f.num_vertices >= 3 && !door_faces.contains(i))
+        .filter_map(|(_, face)| {
+            let verts = blv_face_verts(face, blv);
+            if verts.len() < 3 {
+                return None;
+            }
+            let normal = blv_face_normal(face);
+            let plane_dist = normal.dot(verts[0]);
+            Some(OccluderFaceData {
+                normal,
+                plane_dist,
+                vertices: verts,
+            })
+        })
+        .collect()
+}
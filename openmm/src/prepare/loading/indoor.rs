@@ -3,10 +3,13 @@ use bevy::{asset::RenderAssetUsages, mesh::PrimitiveTopology, prelude::*};
 use openmm_data::{blv::Blv, utils::OdmName};
 
 use super::{
-    ClickableFaceData, LoadRequest, LoadingProgress, OccluderFaceData, PreparedDoorCollision, PreparedDoorFace,
-    PreparedIndoorWorld, PreparedModel, PreparedSubMesh, SectorAmbient, StartPoint, TouchTriggerFaceData, helpers,
+    LoadRequest, LoadingProgress, PreparedDoorCollision, PreparedDoorFace, PreparedIndoorWorld, PreparedLight,
+    PreparedModel, PreparedSubMesh, SectorAmbient, StartPoint, helpers,
+};
+use crate::prepare::build_indoor::{
+    blv_face_normal, blv_face_verts, extract_blv_collision, extract_clickable_faces, extract_occluder_faces,
+    extract_touch_trigger_faces,
 };
-use crate::prepare::build_indoor::{blv_face_normal, blv_face_verts, extract_blv_collision};
 use crate::{
     GameState,
     assets::GameAssets,
@@ -24,7 +27,7 @@ pub(super) fn step_build_models_indoor(
     active_save: &crate::game::save::ActiveSave,
     world_state: Option<&crate::game::state::WorldState>,
 ) {
-    let blv = progress.blv.as_ref().unwrap();
+    let mut blv = progress.blv.take().unwrap();
     let map_key = load_request.map_name.to_string();
     let snapshot = world_state.and_then(|ws| {
         ws.game_vars
@@ -36,29 +39,51 @@ pub(super) fn step_build_models_indoor(
     });
 
     // Try loading from save first, then fall back to BLV spawn points.
-    let resolved_actors = super::try_load_actors_from_save(active_save, &map_key, snapshot.as_ref(), game_assets)
-        .or_else(|| {
-            openmm_data::assets::Monsters::load_for_blv(
-                &blv.spawn_points,
-                &map_key,
-                game_assets.data(),
-                game_assets.assets(),
-            )
-            .ok()
-            .map(|m| openmm_data::assets::Actors::from_monsters(&m))
-        });
-    let blv = progress.blv.as_ref().unwrap();
+    let resolved_actors =
+        super::try_load_actors_from_save(active_save, &load_request.map_name, snapshot.as_ref(), game_assets).or_else(
+            || {
+                openmm_data::assets::Monsters::load_for_blv(
+                    &blv.spawn_points,
+                    &map_key,
+                    game_assets.data(),
+                    game_assets.assets(),
+                )
+                .ok()
+                .map(|m| openmm_data::assets::Actors::from_monsters(&m))
+            },
+        );
+
+    // Load DLV to get door data — the saved delta keeps doors as the party left them.
+    let saved_delta = active_save.map_delta(&load_request.map_name);
+    let dlv_result = match &saved_delta {
+        Some(data) => openmm_data::dlv::Dlv::parse_version(
+            data,
+            &openmm_data::dlv::DlvLayout::from_blv(&blv),
+            game_assets.assets().version(),
+        ),
+        None => openmm_data::dlv::Dlv::new(
+            game_assets.assets(),
+            &load_request.map_name.to_string(),
+            blv.door_count,
+            blv.doors_data_size,
+        ),
+    };
+    let mut dlv_doors = dlv_result.as_ref().map(|d| d.doors.clone()).unwrap_or_default();
+
+    // Face changes made by EVT scripts: the saved delta's face attributes,
+    // then the recorded overrides (restored from the save's map extras),
+    // which carry the full attribute words and textures.
+    if let (Some(_), Ok(dlv)) = (&saved_delta, &dlv_result) {
+        for (i, face) in blv.faces.iter_mut().enumerate() {
+            face.attributes = dlv.face_attributes_over(i, face.attributes);
+        }
+    }
+    if let Some(ws) = world_state {
+        crate::game::map::indoor::apply_face_overrides(&mut blv, &ws.game_vars, &map_key);
+    }
 
     // Indoor: build meshes from BLV faces
     let texture_sizes = helpers::collect_texture_sizes(&blv.texture_names, game_assets);
-    // Load DLV to get door data
-    let dlv_result = openmm_data::dlv::Dlv::new(
-        game_assets.assets(),
-        &load_request.map_name.to_string(),
-        blv.door_count,
-        blv.doors_data_size,
-    );
-    let mut dlv_doors = dlv_result.as_ref().map(|d| d.doors.clone()).unwrap_or_default();
 
     // Fill in any doors missing face/vertex data from BLV geometry.
     // Some DLV files have fully populated door data; others need
@@ -87,16 +112,12 @@ pub(super) fn step_build_models_indoor(
             mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, dfm.uvs);
             // Skip generate_tangents — door vertices are animated and
             // tangents would become stale. Not needed for flat surfaces.
-            let texture = game_assets.lod().bitmap(&dfm.texture_name).map(|img| {
-                let mut image = crate::assets::dynamic_to_bevy_image(img);
-                image.sampler = crate::assets::repeat_sampler();
-                image
-            });
+            let (material, texture) = indoor_face_material(&dfm.texture_name, game_assets);
             PreparedDoorFace {
                 face_index: dfm.face_index,
                 door_index: dfm.door_index,
                 mesh,
-                material: helpers::indoor_material(&dfm.texture_name),
+                material,
                 texture,
                 is_moving_vertex: dfm.is_moving,
                 base_positions,
@@ -132,7 +153,7 @@ pub(super) fn step_build_models_indoor(
                 let door = dlv_doors.get(door_index)?;
                 let moving_vids: std::collections::HashSet<u16> = door.vertex_ids.iter().copied().collect();
 
-                let verts = blv_face_verts(face, blv);
+                let verts = blv_face_verts(face, &blv);
                 if verts.len() < 3 {
                     return None;
                 }
@@ -162,93 +183,14 @@ pub(super) fn step_build_models_indoor(
             .collect()
     };
 
-    // Collect clickable faces
-    let clickable_faces: Vec<ClickableFaceData> = blv
-        .faces
-        .iter()
-        .enumerate()
-        .filter(|(_, f)| f.is_clickable() && f.event_id != 0 && f.num_vertices >= 3)
-        .filter_map(|(i, face)| {
-            let verts = blv_face_verts(face, blv);
-            if verts.len() < 3 {
-                return None;
-            }
-            let normal = blv_face_normal(face);
-            let plane_dist = normal.dot(verts[0]);
-            Some(ClickableFaceData {
-                face_index: i,
-                event_id: face.event_id,
-                normal,
-                plane_dist,
-                vertices: verts,
-            })
-        })
-        .collect();
-
-    // Collect touch-triggered faces (EVENT_BY_TOUCH flag)
-    let touch_trigger_faces: Vec<TouchTriggerFaceData> = blv
-        .faces
-        .iter()
-        .enumerate()
-        .filter(|(_, f)| f.is_touch_trigger() && f.event_id != 0 && f.num_vertices >= 3)
-        .filter_map(|(i, face)| {
-            let verts = blv_face_verts(face, blv);
-            if verts.len() < 3 {
-                return None;
-            }
-            let center = verts.iter().copied().sum::<Vec3>() / verts.len() as f32;
-            // Use half bounding box diagonal as trigger radius
-            let min = verts.iter().copied().reduce(|a, b| a.min(b))?;
-            let max = verts.iter().copied().reduce(|a, b| a.max(b))?;
-            let radius = (max - min).length() * 0.5;
-            Some(TouchTriggerFaceData {
-                face_index: i,
-                event_id: face.event_id,
-                center,
-                radius: radius.max(128.0), // minimum trigger radius
-            })
-        })
-        .collect();
-
-    // Collect all solid faces for ray occlusion (wall/floor/ceiling, no portals, no door faces).
-    let occluder_faces: Vec<OccluderFaceData> = blv
-        .faces
-        .iter()
-        .enumerate()
-        .filter(|(i, f)| !f.is_invisible() && !f.is_portal() && f.num_vertices >= 3 && !door_faces.contains(i))
-        .filter_map(|(_, face)| {
-            let verts = blv_face_verts(face, blv);
-            if verts.len() < 3 {
-                return None;
-            }
-            let normal = blv_face_normal(face);
-            let plane_dist = normal.dot(verts[0]);
-            Some(OccluderFaceData {
-                normal,
-                plane_dist,
-                vertices: verts,
-            })
-        })
-        .collect();
+    let clickable_faces = extract_clickable_faces(&blv);
+    let touch_trigger_faces = extract_touch_trigger_faces(&blv);
+    let occluder_faces = extract_occluder_faces(&blv, &door_faces);
 
     let models = vec![PreparedModel {
         sub_meshes: textured
             .into_iter()
-            .map(|tm| {
-                let mesh = helpers::build_textured_mesh(tm.positions, tm.normals, tm.uvs);
-                let texture = game_assets.lod().bitmap(&tm.texture_name).map(|img| {
-                    let mut image = crate::assets::dynamic_to_bevy_image(img);
-                    image.sampler = crate::assets::repeat_sampler();
-                    image
-                });
-                PreparedSubMesh {
-                    mesh,
-                    material: helpers::indoor_material(&tm.texture_name),
-                    texture,
-                    texture_name: tm.texture_name.clone(),
-                    face_indices: tm.face_indices.clone(),
-                }
-            })
+            .map(|tm| prepare_sub_mesh(tm, game_assets))
             .collect(),
         name: "blv_faces".to_string(),
         position: Vec3::ZERO,
@@ -261,7 +203,7 @@ pub(super) fn step_build_models_indoor(
         info!("Indoor spawn from MoveToMap event: pos={:?}", pos);
         (Vec3::from(pos), load_request.spawn_yaw.unwrap_or(0.0))
     } else {
-        resolve_indoor_spawn(blv, load_request, game_assets)
+        resolve_indoor_spawn(&blv, load_request, game_assets)
     };
     let start_points = vec![StartPoint {
         name: "indoor_start".to_string(),
@@ -271,7 +213,7 @@ pub(super) fn step_build_models_indoor(
     // Extract collision geometry from BLV faces, excluding animated door faces.
     // Door face geometry is animated separately; their collision would block
     // the player even after a door opens.
-    let (collision_walls, collision_floors, collision_ceilings) = extract_blv_collision(blv, &door_faces);
+    let (collision_walls, collision_floors, collision_ceilings) = extract_blv_collision(&blv, &door_faces);
 
     // Build collision resources during loading so they're available at spawn time.
     commands.insert_resource(crate::game::map::collision::build_indoor_colliders(
@@ -303,25 +245,51 @@ pub(super) fn step_build_models_indoor(
             let [x1, y1, z1] = s.bbox_max.map(|v| v as i32);
             let bmin = Vec3::from(mm6_position_to_bevy(x0, y0, z0));
             let bmax = Vec3::from(mm6_position_to_bevy(x1, y1, z1));
+            // Sector light lists come from the BLV; if the blob didn't decode,
+            // fall back to the lights inside the sector's bounds.
+            let light_ids = if s.light_ids.is_empty() {
+                blv.lights
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, l)| (0..3).all(|a| (s.bbox_min[a]..=s.bbox_max[a]).contains(&l.position[a])))
+                    .map(|(i, _)| i)
+                    .collect()
+            } else {
+                s.light_ids.iter().map(|&id| id as usize).collect()
+            };
             // mm6_position_to_bevy flips Y/Z, so ensure min ≤ max on all axes.
             SectorAmbient {
                 bbox_min: bmin.min(bmax),
                 bbox_max: bmin.max(bmax),
                 min_ambient: s.min_ambient_light.clamp(0, 255) as u8,
+                light_ids,
             }
         })
         .collect();
 
     // Collect BLV static lights (designer-placed — campfires, braziers, etc.).
     // radius is always 0 in MM6 data; brightness drives range + intensity.
-    let blv_lights: Vec<(Vec3, u16)> = blv
+    // Lights switched by ToggleIndoorLight (this session or in the save) keep
+    // their state: the switch is applied to the BLV light's flags.
+    let switched = world_state.and_then(|ws| ws.game_vars.indoor_lights.get(&map_key));
+    let blv_lights: Vec<PreparedLight> = blv
         .lights
-        .iter()
-        .filter(|l| l.brightness > 0)
-        .map(|l| {
+        .iter_mut()
+        .enumerate()
+        .filter(|(_, l)| l.brightness > 0)
+        .map(|(light_id, l)| {
+            let initially_on = l.is_enabled();
+            if let Some(&on) = switched.and_then(|s| s.get(&light_id)) {
+                l.set_enabled(on);
+            }
             let [x, y, z] = l.position;
-            let pos = Vec3::from(mm6_position_to_bevy(x as i32, y as i32, z as i32));
-            (pos, l.brightness)
+            PreparedLight {
+                light_id,
+                position: Vec3::from(mm6_position_to_bevy(x as i32, y as i32, z as i32)),
+                brightness: l.brightness,
+                enabled: l.is_enabled(),
+                initially_on,
+            }
         })
         .collect();
 
@@ -344,12 +312,40 @@ pub(super) fn step_build_models_indoor(
         blv_lights,
         sector_ambients,
     });
+    commands.insert_resource(crate::game::map::indoor::IndoorFaces::new(
+        blv,
+        door_faces,
+        texture_sizes,
+    ));
     commands.insert_resource(crate::game::map::CurrentMap(load_request.map_name.clone()));
     commands.remove_resource::<LoadingProgress>();
     commands.remove_resource::<LoadRequest>();
     game_state.set(GameState::Game);
 }
 
+/// Build one per-texture static mesh, with its texture image and material.
+pub(crate) fn prepare_sub_mesh(tm: openmm_data::blv::BlvTexturedMesh, game_assets: &GameAssets) -> PreparedSubMesh {
+    let mesh = helpers::build_textured_mesh(tm.positions, tm.normals, tm.uvs);
+    let (material, texture) = indoor_face_material(&tm.texture_name, game_assets);
+    PreparedSubMesh {
+        mesh,
+        material,
+        texture,
+        texture_name: tm.texture_name,
+        face_indices: tm.face_indices,
+    }
+}
+
+/// Material and repeat-sampled texture image for an indoor face texture.
+pub(crate) fn indoor_face_material(texture_name: &str, game_assets: &GameAssets) -> (StandardMaterial, Option<Image>) {
+    let texture = game_assets.lod().bitmap(texture_name).map(|img| {
+        let mut image = crate::assets::dynamic_to_bevy_image(img);
+        image.sampler = crate::assets::repeat_sampler();
+        image
+    });
+    (helpers::indoor_material(texture_name), texture)
+}
+
 /// Search outdoor EVT files for a MoveToMap targeting this BLV, or fall back
 /// to sector center. Returns (position, yaw) in Bevy coords.
 fn resolve_indoor_spawn(blv: &Blv, load_request: &LoadRequest, game_assets: &GameAssets) -> (Vec3, f32) {
@@ -363,7 +359,7 @@ fn resolve_indoor_spawn(blv: &Blv, load_request: &LoadRequest, game_assets: &Gam
         openmm_data::evt::EvtFile::parse(game_assets.assets(), base)
             .ok()
             .and_then(|evt| {
-                evt.events.values().flatten().find_map(|s| {
+                evt.events().values().flatten().find_map(|s| {
                     if let openmm_data::evt::GameEvent::MoveToMap {
                         x,
                         y,
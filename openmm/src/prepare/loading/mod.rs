@@ -3,6 +3,8 @@ mod helpers;
 mod indoor;
 mod outdoor;
 
+pub(crate) use indoor::{indoor_face_material, prepare_sub_mesh};
+
 use bevy::prelude::*;
 
 use crate::{GameState, assets::GameAssets, despawn_all, game::map::CurrentMap, system::config::GameConfig};
@@ -15,8 +17,8 @@ use openmm_data::{
 
 // Re-export types so existing `crate::states::loading::X` paths keep working.
 pub use super::prepared::{
-    ClickableFaceData, OccluderFaceData, PreparedDoorCollision, PreparedDoorFace, PreparedIndoorWorld, PreparedModel,
-    PreparedSubMesh, PreparedWorld, SectorAmbient, StartPoint, TouchTriggerFaceData, texture_emissive,
+    ClickableFaceData, OccluderFaceData, PreparedDoorCollision, PreparedDoorFace, PreparedIndoorWorld, PreparedLight,
+    PreparedModel, PreparedSubMesh, PreparedWorld, SectorAmbient, StartPoint, TouchTriggerFaceData, texture_emissive,
 };
 
 pub struct LoadingPlugin;
@@ -152,7 +154,8 @@ impl LoadingStep {
 fn loading_setup(
     mut commands: Commands,
     load_request: Option<Res<LoadRequest>>,
-    active_save: Res<crate::game::save::ActiveSave>,
+    mut active_save: ResMut<crate::game::save::ActiveSave>,
+    game_assets: Res<GameAssets>,
     cfg: Res<GameConfig>,
     mut world_state: ResMut<crate::game::state::WorldState>,
     mut party: ResMut<crate::game::player::party::Party>,
@@ -166,6 +169,8 @@ fn loading_setup(
     commands.remove_resource::<crate::game::map::indoor::DoorColliders>();
     commands.remove_resource::<crate::game::map::indoor::TouchTriggerFaces>();
     commands.remove_resource::<crate::game::map::indoor::OccluderFaces>();
+    commands.remove_resource::<crate::game::map::indoor::IndoorLights>();
+    commands.remove_resource::<crate::game::map::indoor::IndoorFaces>();
     commands.remove_resource::<crate::game::ui::MapOverviewImage>();
     commands.remove_resource::<crate::game::map::collision::BuildingColliders>();
     commands.remove_resource::<crate::game::map::collision::TerrainHeightMap>();
@@ -191,6 +196,25 @@ fn loading_setup(
     // Populate live game state from the save file
     crate::game::save::load::populate_state_from_save(&active_save, &mut world_state, &mut party, &mut game_time);
 
+    // A map left long enough ago comes back pristine (mapstats RefillDays).
+    crate::game::save::load::refill_map_if_due(
+        &mut active_save,
+        &map_name,
+        game_assets.assets(),
+        &game_assets.data().mapstats,
+        &mut world_state,
+        &game_time,
+    );
+
+    // Map vars belong to the map being entered: restore them from its saved delta.
+    world_state.game_vars.map_vars =
+        crate::game::save::load::map_vars_from_save(&active_save, &map_name, game_assets.assets());
+    crate::game::save::load::map_extras_from_save(&active_save, &map_name, &mut world_state);
+    commands.insert_resource(crate::game::map::chests::MapChests::new(
+        crate::game::save::load::map_chests_from_save(&active_save, &map_name, game_assets.assets()),
+        active_save.map_extras(&map_name).locked_chests,
+    ));
+
     // Keep world_state in sync so spawn_world sees the correct map name
     world_state.map.name = map_name.clone();
     if let MapName::Outdoor(ref odm) = map_name {
@@ -322,7 +346,7 @@ fn step_parse_map(
                 progress.step = LoadingStep::BuildModels;
             }
             Err(e) => {
-                error!("Failed to parse indoor map {}: {}", map_name, e);
+                error!("Failed to {} indoor map {}: {}", load_verb(&e), map_name, e);
                 commands.remove_resource::<LoadRequest>();
                 game_state.set(GameState::Menu);
             }
@@ -352,13 +376,18 @@ fn step_parse_map(
             }
         }
         Err(e) => {
-            error!("Failed to parse map {}: {}", map_name, e);
+            error!("Failed to {} map {}: {}", load_verb(&e), map_name, e);
             commands.remove_resource::<LoadRequest>();
             game_state.set(GameState::Menu);
         }
     }
 }
 
+/// "find" for missing files, "parse" for broken ones.
+fn load_verb(e: &openmm_data::Error) -> &'static str {
+    if e.is_not_found() { "find" } else { "parse" }
+}
+
 fn step_build_models(
     progress: &mut LoadingProgress,
     game_assets: &GameAssets,
@@ -398,6 +427,8 @@ fn step_preload_sprites(
     // Time-budgeted sprite preloading: process a batch each frame so
     // the window event loop keeps running and GNOME doesn't flag us.
     const PRELOAD_BUDGET_MS: f32 = 8.0;
+    // Sprite roots decoded per parallel batch; small enough to land near the budget.
+    const PRELOAD_BATCH: usize = 8;
     let frame_start = std::time::Instant::now();
 
     // First frame: build the preload queue from map-specific data
@@ -424,15 +455,13 @@ fn step_preload_sprites(
             if frame_start.elapsed().as_secs_f32() * 1000.0 > PRELOAD_BUDGET_MS {
                 break;
             }
-            let (root, variant, palette_id) = &queue.sprite_roots[queue.sprite_idx];
-            cache.preload(
-                &[(root.as_str(), *variant, *palette_id)],
-                game_assets.assets(),
-                images,
-                sprite_materials,
-                false,
-            );
-            queue.sprite_idx += 1;
+            let end = (queue.sprite_idx + PRELOAD_BATCH).min(queue.sprite_roots.len());
+            let batch: Vec<(&str, u8, u16)> = queue.sprite_roots[queue.sprite_idx..end]
+                .iter()
+                .map(|(root, variant, palette_id)| (root.as_str(), *variant, *palette_id))
+                .collect();
+            cache.preload(&batch, game_assets.assets(), images, sprite_materials, false);
+            queue.sprite_idx = end;
         }
         progress.sprite_cache = Some(cache);
     } else {
@@ -493,16 +522,21 @@ fn step_preload_sprites(
     }
 }
 
-/// Try loading DDM actors from the active save file. Returns `Some(Actors)` if the save
-/// contains a DDM for this map (i.e. the map was previously visited and saved), `None` otherwise.
+/// Try loading DDM/DLV actors from the active save. Returns `Some(Actors)` if the save
+/// holds a delta for this map (a pending snapshot or one written to the save file),
+/// `None` for maps not visited yet.
 pub(super) fn try_load_actors_from_save(
     active_save: &crate::game::save::ActiveSave,
-    map_name: &str,
+    map: &MapName,
     state: Option<&openmm_data::assets::provider::actors::MapStateSnapshot>,
     game_assets: &GameAssets,
 ) -> Option<openmm_data::assets::Actors> {
-    let save_file = openmm_data::save::SaveFile::open(&active_save.path).ok()?;
-    save_file.actors(map_name, game_assets.assets(), state, game_assets.data())
+    let data = active_save.map_delta(map)?;
+    let delta = openmm_data::assets::map_delta::MapDeltaFile::parse(game_assets.assets(), map, &data)
+        .inspect_err(|e| warn!("saved delta for '{}' is unreadable: {e}", map))
+        .ok()?;
+    openmm_data::assets::Actors::from_raw_actors(game_assets.assets(), &delta.delta().actors, state, game_assets.data())
+        .ok()
 }
 
 /// First-frame initialization for PreloadSprites: resolve actors and monsters,
@@ -530,7 +564,7 @@ fn build_preload_queue(
     // Try loading DDM/DLV from save file first (preserves killed monster state),
     // fall back to LOD archives for maps not yet visited.
     let mut loaded_from_save = false;
-    let lod_actors = try_load_actors_from_save(active_save, &map_key, snapshot.as_ref(), game_assets)
+    let lod_actors = try_load_actors_from_save(active_save, &load_request.map_name, snapshot.as_ref(), game_assets)
         .inspect(|actors| {
             loaded_from_save = true;
             let dead = actors.get_actors().iter().filter(|a| a.hp <= 0).count();
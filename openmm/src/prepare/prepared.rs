@@ -49,9 +49,9 @@ pub struct PreparedIndoorWorld {
     pub decorations: openmm_data::assets::Decorations,
     /// Resolved monsters (either from saved DLV or BLV spawn_points).
     pub resolved_actors: Option<openmm_data::assets::Actors>,
-    /// Static point lights from the BLV file (position in Bevy coords, brightness 0–65535).
+    /// Static point lights from the BLV file, keyed by BLV light index.
     /// These are the designer-placed lights that illuminate campfires, cauldrons, etc.
-    pub blv_lights: Vec<(Vec3, u16)>,
+    pub blv_lights: Vec<PreparedLight>,
     /// Per-sector ambient data: (bbox_min, bbox_max in Bevy coords, min_ambient_light 0–255).
     /// Used to set global ambient based on which sector the player currently occupies.
     pub sector_ambients: Vec<SectorAmbient>,
@@ -65,6 +65,23 @@ pub struct SectorAmbient {
     pub bbox_max: Vec3,
     /// 0–255 ambient floor. 0 = pitch black, 255 = fully lit.
     pub min_ambient: u8,
+    /// BLV light indices in this sector; their on/off state raises the ambient.
+    pub light_ids: Vec<usize>,
+}
+
+/// One BLV point light.
+#[derive(Clone)]
+pub struct PreparedLight {
+    /// Index into the BLV light list — the id `ToggleIndoorLight` uses.
+    pub light_id: usize,
+    /// Position in Bevy coords.
+    pub position: Vec3,
+    /// Brightness 0–65535.
+    pub brightness: u16,
+    /// Whether the light is on as the map loads.
+    pub enabled: bool,
+    /// Whether the BLV has it on, before this session's `ToggleIndoorLight`s.
+    pub initially_on: bool,
 }
 
 /// Collision-only geometry for a single door face, including invisible blocking surfaces.
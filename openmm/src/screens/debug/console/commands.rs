@@ -10,7 +10,10 @@ use super::{ConsoleState, HELP_TEXT, NEEDS_RELOAD, parse_coords, parse_toggle, r
 use crate::GameState;
 use crate::game::map::outdoor::{OdmName, PLAY_WIDTH};
 use crate::game::player::SpeedMultiplier;
-use crate::game::save::ActiveSave;
+use crate::game::player::party::Party;
+use crate::game::save::slots::{local_slot_path, slot_path};
+use crate::game::save::store::{LiveMapState, queue_save};
+use crate::game::save::{ActiveSave, QUICKSAVE_SLOT, try_load_save};
 use crate::game::state::{GameTime, WorldState};
 use crate::prepare::loading::LoadRequest;
 use crate::system::config::GameConfig;
@@ -112,6 +115,40 @@ pub(super) fn cmd_load(
     }
 }
 
+/// Write the live game state to a local save slot (default `quiksave`).
+#[allow(clippy::too_many_arguments)]
+pub(super) fn cmd_save(
+    state: &mut ConsoleState,
+    world: &WorldState,
+    active_save: &mut ActiveSave,
+    party: &Party,
+    game_time: &GameTime,
+    live_map: &mut LiveMapState,
+    eye_height: f32,
+    arg: &str,
+) {
+    let slot = if arg.is_empty() { QUICKSAVE_SLOT } else { arg };
+    let dest = local_slot_path(slot);
+    state.push_output(format!("Saving to {}", dest.display()));
+    queue_save(active_save, dest, world, party, game_time, live_map, eye_height);
+}
+
+/// Load a save slot (default `quiksave`), discarding the running game.
+pub(super) fn cmd_loadgame(state: &mut ConsoleState, commands: &mut Commands, arg: &str) {
+    let slot = if arg.is_empty() { QUICKSAVE_SLOT } else { arg };
+    let path = slot_path(slot);
+    if !path.exists() {
+        state.push_output(format!("No save named '{slot}'"));
+        return;
+    }
+    if try_load_save(commands, path.clone()) {
+        state.push_output(format!("Loading {}", path.display()));
+        state.open = false;
+    } else {
+        state.push_output(format!("Failed to load {}", path.display()));
+    }
+}
+
 // --- Graphics ---
 
 pub(super) fn cmd_msaa(state: &mut ConsoleState, cfg: &mut GameConfig, arg: &str) {
@@ -180,6 +180,9 @@ fn console_input(
     game_assets: Res<crate::GameAssets>,
     mut game_time: ResMut<crate::game::state::GameTime>,
     mut speed_mul: ResMut<crate::game::player::SpeedMultiplier>,
+    party: Res<crate::game::player::party::Party>,
+    player_settings: Res<crate::game::player::PlayerSettings>,
+    mut live_map: crate::game::save::store::LiveMapState,
 ) {
     if !state.open {
         return;
@@ -213,6 +216,9 @@ fn console_input(
                         &game_assets,
                         &mut game_time,
                         &mut speed_mul,
+                        &party,
+                        &mut live_map,
+                        player_settings.eye_height,
                     );
                     state.input.clear();
                 }
@@ -315,6 +321,9 @@ fn execute_command(
     game_assets: &crate::GameAssets,
     game_time: &mut crate::game::state::GameTime,
     speed_mul: &mut crate::game::player::SpeedMultiplier,
+    party: &crate::game::player::party::Party,
+    live_map: &mut crate::game::save::store::LiveMapState,
+    eye_height: f32,
 ) {
     let parts: Vec<&str> = cmd.split_whitespace().collect();
     let Some(&command) = parts.first() else { return };
@@ -326,6 +335,8 @@ fn execute_command(
         // Map loading
         "reload" => commands::cmd_reload(state, world, active_save, cmds, game_state),
         "load" | "map" => commands::cmd_load(state, &parts, world, active_save, cmds, game_state, game_assets),
+        "save" => commands::cmd_save(state, world, active_save, party, game_time, live_map, eye_height, arg),
+        "loadgame" | "quickload" => commands::cmd_loadgame(state, cmds, arg),
 
         // Graphics
         "msaa" | "aa" => commands::cmd_msaa(state, cfg, arg),
@@ -414,6 +425,8 @@ pub(super) const HELP_TEXT: &[&str] = &[
     "  load n/s/e/w     - Adjacent map (keeps position)",
     "  reload           - Reload current map",
     "  pos              - Show current position and map",
+    "  save [slot]      - Write game to data/Saves/<slot>.mm6 (default quiksave)",
+    "  loadgame [slot]  - Load a save slot (default quiksave, alias: quickload)",
     "Graphics:",
     "  aa <mode>        - Set AA (msaa2/4/8|fxaa|smaa|taa|off) *",
     "  tonemap <mode>   - Tonemapping *",
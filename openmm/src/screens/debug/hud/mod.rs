@@ -53,7 +53,6 @@ impl Plugin for DebugHudPlugin {
             .add_systems(
                 Update,
                 (
-                    super::quicksave,
                     super::draw_play_area,
                     super::draw_events,
                     super::draw_colliders,
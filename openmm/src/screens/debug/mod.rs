@@ -12,7 +12,6 @@ pub mod hud;
 pub mod perf_log;
 
 use crate::game::player::Player;
-use crate::game::save::ActiveSave;
 use crate::system::config::GameConfig;
 use openmm_data::odm::{ODM_PLAY_SIZE, ODM_TILE_SCALE};
 
@@ -324,20 +323,6 @@ pub fn debug_log(
     }
 }
 
-pub fn quicksave(
-    keys: Res<ButtonInput<KeyCode>>,
-    player_query: Query<&Transform, With<Player>>,
-    mut active_save: ResMut<ActiveSave>,
-) {
-    if keys.just_pressed(KeyCode::F3) {
-        if let Ok(transform) = player_query.single() {
-            active_save.update_from_transform(transform);
-        }
-        // TODO: write ActiveSave back to disk once save writing is implemented
-        info!("Quicksave: position synced to ActiveSave (disk write not yet implemented)");
-    }
-}
-
 pub fn debug_screenshot(mut commands: Commands, keys: Res<ButtonInput<KeyCode>>) {
     if keys.just_pressed(KeyCode::F11) {
         let path = format!(
@@ -8,9 +8,10 @@ use super::runtime::{
     ClickFlash, ClickedAnimation, ClickedTexture, HoverAnimation, HoverOverlay, HoverTexture, RuntimeElement,
     ScreenActionEvent, ScreenActions, ScreenLayer, ScreenLayers, ScreenUiHovered,
 };
-use super::setup::{hide_screen, load_screen_replace_all, show_screen};
+use super::setup::{hide_screen, load_screen_replace_all, show_screen, show_screen_def};
 use super::ui_assets::UiAssets;
 use crate::assets::GameAssets;
+use crate::game::events::scripting::EvtWait;
 use crate::game::optional::OptionalWrite;
 use crate::game::ui::UiMode;
 use crate::system::config::GameConfig;
@@ -578,6 +579,7 @@ pub(super) struct ScreenActionParams<'w, 's> {
     pub sound_manager: Option<Res<'w, crate::game::sound::SoundManager>>,
     pub cursor_query: Query<'w, 's, &'static mut CursorOptions, With<PrimaryWindow>>,
     pub save_manager: ResMut<'w, crate::game::save::slots::SaveManager>,
+    pub chests: Option<Res<'w, crate::game::map::chests::MapChests>>,
 }
 
 /// Process queued actions with full system access (commands, layers, entities, exit).
@@ -667,6 +669,7 @@ pub(super) fn process_pending_actions(
                     info!("action: LoadScreen(\"{}\")", id);
                     if id == "menu_load" {
                         params.save_manager.refresh();
+                        params.ui_assets.forget_screenshots();
                         params.save_manager.absolute_selected = None;
                         params.save_manager.offset = 0;
                     }
@@ -726,6 +729,54 @@ pub(super) fn process_pending_actions(
                         }
                     }
                 }
+                Action::PlayVideo(name, skippable) => {
+                    info!("action: PlayVideo(\"{}\", {})", name, skippable);
+                    if params.game_assets.smk_bytes(&name).is_none()
+                        || params.layers.screens.contains_key(EVT_VIDEO_SCREEN)
+                    {
+                        warn!("PlayVideo: '{}' cannot be shown, skipping", name);
+                        if let Some(ref mut q) = params.event_queue
+                            && q.waiting() == Some(EvtWait::VideoEnded)
+                        {
+                            q.resume();
+                        }
+                        continue;
+                    }
+                    show_screen_def(
+                        evt_video_screen(&name, skippable),
+                        &mut commands,
+                        &mut params.layers,
+                        &mut params.ui_assets,
+                        &params.game_assets,
+                        &mut params.images,
+                        &mut params.audio_sources,
+                        &params.cfg,
+                        &mut params.cursor_query,
+                        &mut actions,
+                    );
+                }
+                Action::ShowChest => {
+                    let Some(screen) = params
+                        .chests
+                        .as_ref()
+                        .and_then(|chests| crate::game::map::chests::chest_screen(chests, &params.game_assets))
+                    else {
+                        warn!("ShowChest: no open chest to show");
+                        continue;
+                    };
+                    show_screen_def(
+                        screen,
+                        &mut commands,
+                        &mut params.layers,
+                        &mut params.ui_assets,
+                        &params.game_assets,
+                        &mut params.images,
+                        &mut params.audio_sources,
+                        &params.cfg,
+                        &mut params.cursor_query,
+                        &mut actions,
+                    );
+                }
                 Action::EvtProxy(raw) => {
                     if let Some(ref mut q) = params.event_queue {
                         info!("action: EvtProxy(\"{}\")", raw);
@@ -863,12 +914,51 @@ fn build_config_flags(cfg: &GameConfig) -> std::collections::HashSet<String> {
     flags
 }
 
+/// Screen id of the fullscreen layer that plays EVT `PlayVideo` clips.
+const EVT_VIDEO_SCREEN: &str = "evt_video";
+
+/// A modal screen holding one fullscreen video that closes itself when done
+/// and resumes the script waiting on it.
+fn evt_video_screen(name: &str, skippable: bool) -> super::Screen {
+    super::Screen {
+        id: EVT_VIDEO_SCREEN.to_string(),
+        kind: super::ScreenKind::Modal,
+        sound: Default::default(),
+        keys: Default::default(),
+        on_load: Vec::new(),
+        on_close: Vec::new(),
+        elements: vec![super::ScreenElement::Video(super::VideoElement {
+            id: "vid_evt".to_string(),
+            position: (0.0, 0.0),
+            size: (super::REF_W, super::REF_H),
+            z: 100,
+            video: name.to_string(),
+            hidden: false,
+            looping: false,
+            skippable,
+            on_end: vec![
+                format!("HideScreen(\"{EVT_VIDEO_SCREEN}\")"),
+                "evt:VideoEnded()".to_string(),
+            ],
+        })],
+        editor: Default::default(),
+    }
+}
+
 /// Proxy an `evt:` action string to the EVT EventQueue.
 fn proxy_evt_action(evt_str: &str, event_queue: &mut crate::game::events::scripting::EventQueue) {
     use openmm_data::evt::GameEvent;
 
     let s = evt_str.trim();
 
+    // VideoEnded() — a PlayVideo clip finished or was skipped.
+    if s == "VideoEnded()" {
+        if event_queue.waiting() == Some(EvtWait::VideoEnded) {
+            event_queue.resume();
+        }
+        return;
+    }
+
     // PlaySound(id)
     if let Some(rest) = s.strip_prefix("PlaySound(").and_then(|r| r.strip_suffix(')'))
         && let Ok(id) = rest.trim().parse::<u32>()
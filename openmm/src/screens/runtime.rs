@@ -190,12 +190,15 @@ pub(super) struct HiddenByDefault;
 pub(super) struct InlineVideo {
     pub(super) decoder: SmkDecoder,
     pub(super) image_handle: Handle<Image>,
-    pub(super) frame_timer: f32,
-    pub(super) spf: f32,
+    /// Playback clock in seconds since the current loop started; the audio runs on
+    /// the same wall clock, so frames are picked by comparing their pts against it.
+    pub(super) clock: f64,
+    /// Presentation time of the frame currently on screen.
+    pub(super) shown_pts: f64,
+    pub(super) spf: f64,
     pub(super) looping: bool,
     pub(super) skippable: bool,
     pub(super) on_end: Vec<String>,
-    pub(super) smk_bytes: Vec<u8>,
     pub(super) finished: bool,
     pub(super) life_timer: f32,
 }
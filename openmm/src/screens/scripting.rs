@@ -37,6 +37,10 @@ pub enum Action {
     GreetingSound,
     /// Enter turn-based combat mode.
     EnterTurnBattle,
+    /// Play a fullscreen SMK video for an EVT `PlayVideo` step (name, skippable).
+    PlayVideo(String, bool),
+    /// Show the chest window for the chest EVT `OpenChest` just opened.
+    ShowChest,
     // EVT proxy — raw action string after stripping "evt:" prefix
     EvtProxy(String),
     // Control flow
@@ -88,6 +92,9 @@ pub fn parse_action(input: &str) -> Action {
     if s == "EnterTurnBattle()" {
         return Action::EnterTurnBattle;
     }
+    if s == "ShowChest()" {
+        return Action::ShowChest;
+    }
     if s == "NewGame()" {
         return Action::NewGame(None);
     }
@@ -111,6 +118,9 @@ pub fn parse_action(input: &str) -> Action {
     if s == "SaveScrollDown()" {
         return Action::SaveScrollDown;
     }
+    if let Some((name, skippable)) = parse_string_int_args(s, "PlayVideo") {
+        return Action::PlayVideo(name.to_string(), skippable != 0);
+    }
     if let Some(id) = parse_string_arg(s, "LoadScreen") {
         return Action::LoadScreen(id.to_string());
     }
@@ -284,6 +294,11 @@ mod tests {
         assert_eq!(parse_action("HideScreen(\"hud\")"), Action::HideScreen("hud".into()));
         assert_eq!(parse_action("ShowSprite(\"icon\")"), Action::ShowSprite("icon".into()));
         assert_eq!(parse_action("HideSprite(\"icon\")"), Action::HideSprite("icon".into()));
+        assert_eq!(
+            parse_action("PlayVideo(\"oracle\", 1)"),
+            Action::PlayVideo("oracle".into(), true)
+        );
+        assert_eq!(parse_action("ShowChest()"), Action::ShowChest);
         // Bare Hint is unknown — use evt:Hint instead
         assert!(matches!(parse_action("Hint(\"Cast Spell\")"), Action::Unknown(_)));
     }
@@ -146,7 +146,35 @@ pub(super) fn show_screen(
             return;
         }
     };
+    show_screen_def(
+        screen,
+        commands,
+        layers,
+        ui_assets,
+        game_assets,
+        images,
+        audio_sources,
+        cfg,
+        cursor_query,
+        actions,
+    );
+}
 
+/// Show an already-built screen definition as a new layer keyed by its id.
+pub(super) fn show_screen_def(
+    screen: Screen,
+    commands: &mut Commands,
+    layers: &mut ScreenLayers,
+    ui_assets: &mut UiAssets,
+    game_assets: &GameAssets,
+    images: &mut Assets<Image>,
+    audio_sources: &mut Assets<AudioSource>,
+    cfg: &GameConfig,
+    cursor_query: &mut Query<&mut CursorOptions, With<PrimaryWindow>>,
+    actions: &mut Option<MessageWriter<ScreenActions>>,
+) {
+    let id = screen.id.clone();
+    let screen_id = id.as_str();
     info!("ShowScreen: '{}' ({} elements)", screen.id, screen.elements.len());
 
     if !screen.sound.is_empty() {
@@ -132,6 +132,11 @@ impl UiAssets {
         Some(handle)
     }
 
+    /// Drop cached save screenshots so previews of rewritten saves reload.
+    pub fn forget_screenshots(&mut self) {
+        self.textures.retain(|key, _| !key.starts_with("saveslot:preview:"));
+    }
+
     /// Get the original pixel dimensions of a loaded asset.
     pub fn dimensions(&self, name: &str) -> Option<(u32, u32)> {
         self.dimensions.get(name).copied()
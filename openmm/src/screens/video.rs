@@ -11,6 +11,9 @@ use super::{REF_H, REF_W, VideoElement};
 use crate::assets::GameAssets;
 use crate::game::optional::OptionalWrite;
 
+/// How far (in seconds) a video may fall behind its clock before it seeks.
+const CATCH_UP_SECS: f64 = 0.5;
+
 // ── Video spawning ──────────────────────────────────────────────────────────
 
 pub(super) fn spawn_video_element(
@@ -29,7 +32,7 @@ pub(super) fn spawn_video_element(
         return;
     };
 
-    let mut decoder = match SmkDecoder::new(bytes.clone()) {
+    let mut decoder = match SmkDecoder::new(bytes) {
         Ok(d) => d,
         Err(e) => {
             warn!("video element '{}': failed to decode '{}': {e}", vid.id, vid.video);
@@ -39,11 +42,7 @@ pub(super) fn spawn_video_element(
 
     let native_w = decoder.width;
     let native_h = decoder.height;
-    let spf = if decoder.fps > 0.0 {
-        1.0 / decoder.fps
-    } else {
-        1.0 / 15.0
-    };
+    let spf = decoder.frame_pts(1);
 
     if decoder.audio.is_some()
         && let Some(wav) = game_assets.smk_audio(&vid.video)
@@ -106,12 +105,12 @@ pub(super) fn spawn_video_element(
         InlineVideo {
             decoder,
             image_handle,
-            frame_timer: 0.0,
+            clock: 0.0,
+            shown_pts: 0.0,
             spf,
             looping: vid.looping,
             skippable: vid.skippable,
             on_end: vid.on_end.clone(),
-            smk_bytes: bytes,
             finished: false,
             life_timer: 0.0,
         },
@@ -156,53 +155,52 @@ pub(super) fn video_tick(
             continue;
         }
 
-        vid.frame_timer += time.delta_secs();
+        vid.clock += time.delta_secs_f64();
+        let clock = vid.clock;
 
-        // Process all frames that should have occurred since the last update.
-        // Using a while loop ensures we catch up if the game frame rate hits a snag.
-        while vid.frame_timer >= vid.spf {
-            vid.frame_timer -= vid.spf;
+        // If a hitch left the video well behind the clock (and its audio), jump
+        // straight to the current time instead of converting every missed frame.
+        if clock - vid.shown_pts > CATCH_UP_SECS
+            && clock < vid.decoder.duration()
+            && let Err(e) = vid.decoder.seek_time(clock)
+        {
+            warn!("video element '{}': seek failed: {e}", vid.id_str());
+        }
 
-            match vid.decoder.next_frame() {
-                Some(rgba) => {
-                    if let Some(img) = images.get_mut(&vid.image_handle) {
-                        img.data = Some(rgba);
-                    }
+        // Decode every frame whose presentation time has come; only the newest
+        // one is uploaded.
+        let mut latest = None;
+        while vid.shown_pts + vid.spf <= vid.clock {
+            match vid.decoder.next_packet() {
+                Some(packet) => {
+                    vid.shown_pts = packet.pts;
+                    latest = Some(packet.rgba);
+                }
+                None if vid.looping && vid.decoder.frame_count > 0 => {
+                    // Restart from the first frame, carrying the overshoot into the new
+                    // loop to preserve timing alignment.
+                    vid.clock = (vid.clock - vid.decoder.duration()).max(0.0);
+                    vid.shown_pts = -vid.spf;
+                    vid.decoder.rewind();
                 }
                 None => {
-                    if vid.looping {
-                        // Restart decoder from beginning.
-                        match SmkDecoder::new(vid.smk_bytes.clone()) {
-                            Ok(mut new_dec) => {
-                                // Immediately fetch the first frame of the new loop to prevent
-                                // a one-frame "blank" or "last-frame" gap.
-                                if let Some(rgba) = new_dec.next_frame()
-                                    && let Some(img) = images.get_mut(&vid.image_handle)
-                                {
-                                    img.data = Some(rgba);
-                                }
-                                vid.decoder = new_dec;
-                                // Note: we keep the remaining frame_timer to preserve timing alignment.
-                            }
-                            Err(e) => {
-                                warn!("video element '{}': loop restart failed: {e}", vid.id_str());
-                                vid.finished = true;
-                                break;
-                            }
-                        }
-                    } else {
-                        vid.finished = true;
-                        if !vid.on_end.is_empty() {
-                            actions.try_write(ScreenActions {
-                                actions: vid.on_end.clone(),
-                            });
-                        }
-                        commands.entity(entity).despawn();
-                        break; // Stop processing frames for this despawned entity.
+                    vid.finished = true;
+                    if !vid.on_end.is_empty() {
+                        actions.try_write(ScreenActions {
+                            actions: vid.on_end.clone(),
+                        });
                     }
+                    commands.entity(entity).despawn();
+                    break;
                 }
             }
         }
+        if let Some(rgba) = latest
+            && !vid.finished
+            && let Some(img) = images.get_mut(&vid.image_handle)
+        {
+            img.data = Some(rgba);
+        }
     }
 }
 
@@ -223,6 +223,7 @@ struct Cli {
 #[derive(Deserialize, Debug, Default)]
 struct ConfigFile {
     path_mm6: Option<String>,
+    overlays: Option<Vec<String>>,
     map: Option<String>,
     log_level: Option<String>,
     skip_intro: Option<bool>,
@@ -240,6 +241,7 @@ struct ConfigFile {
     game_tick_rate: Option<f64>,
     close_actor_range: Option<f32>,
     medium_actor_range: Option<f32>,
+    autosave_slots: Option<u32>,
     render_scale: Option<f32>,
     draw_distance: Option<f32>,
     fog_start: Option<f32>,
@@ -285,6 +287,9 @@ pub struct GameConfig {
     pub config_path: PathBuf,
     /// Path to the MM6 install root. The engine reads data from `path_mm6/data`.
     pub path_mm6: String,
+    /// Override directories and patch LODs searched before the game archives,
+    /// first match wins. Layers from `OPENMM_OVERLAYS` come before these.
+    pub overlays: Vec<String>,
     pub map: Option<String>,
     /// Save file to load (name without .mm6), e.g. "autosave1"
     #[serde(skip)]
@@ -315,6 +320,8 @@ pub struct GameConfig {
     pub close_actor_range: f32,
     /// Distance threshold for "medium" actors that update every 2nd tick (Bevy units).
     pub medium_actor_range: f32,
+    /// Number of rotating autosave slots (autosave1…autosaveN) written on map transitions.
+    pub autosave_slots: u32,
     /// 3D render scale (0.25–1.0). Viewport is scaled down; HUD stays native.
     pub render_scale: f32,
     pub draw_distance: f32,
@@ -390,6 +397,7 @@ impl Default for GameConfig {
         Self {
             config_path: PathBuf::from(CONFIG_PATH),
             path_mm6: "./mm6".into(),
+            overlays: Vec::new(),
             map: None,
             save: None,
             log_level: "info".into(),
@@ -408,6 +416,7 @@ impl Default for GameConfig {
             game_tick_rate: crate::game::state::tick::DEFAULT_GAME_TICK_RATE,
             close_actor_range: crate::game::state::tick::DEFAULT_CLOSE_RANGE,
             medium_actor_range: crate::game::state::tick::DEFAULT_MEDIUM_RANGE,
+            autosave_slots: 6,
             render_scale: 1.0,
             draw_distance: 16000.0,
             fog_start: 12000.0,
@@ -497,6 +506,7 @@ impl GameConfig {
         let resolved = GameConfig {
             config_path: config_path.clone(),
             path_mm6: resolve!(None::<String>, file_cfg.path_mm6, d.path_mm6),
+            overlays: resolve!(None::<Vec<String>>, file_cfg.overlays, d.overlays),
             map: cli.map.or(file_cfg.map).or(d.map),
             save: cli.save,
             log_level: resolve!(cli.log_level, file_cfg.log_level, d.log_level),
@@ -515,6 +525,7 @@ impl GameConfig {
             game_tick_rate: resolve!(None::<f64>, file_cfg.game_tick_rate, d.game_tick_rate),
             close_actor_range: resolve!(None::<f32>, file_cfg.close_actor_range, d.close_actor_range),
             medium_actor_range: resolve!(None::<f32>, file_cfg.medium_actor_range, d.medium_actor_range),
+            autosave_slots: resolve!(None::<u32>, file_cfg.autosave_slots, d.autosave_slots),
             render_scale: resolve!(cli.render_scale, file_cfg.render_scale, d.render_scale),
             draw_distance: resolve!(cli.draw_distance, file_cfg.draw_distance, d.draw_distance),
             fog_start: resolve!(cli.fog_start, file_cfg.fog_start, d.fog_start),
@@ -628,6 +639,9 @@ impl GameConfig {
         ) {
             warn!("Unknown tonemapping '{}' — using 'agx'", self.tonemapping);
         }
+        if self.autosave_slots == 0 {
+            warn!("autosave_slots is 0 — autosaving on map transitions is disabled");
+        }
         // Warn about incompatible combos
         if self.bloom && self.tonemapping == "none" {
             warn!("Bloom requires tonemapping — forcing AgX (set tonemapping to avoid this)");